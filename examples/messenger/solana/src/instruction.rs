@@ -9,43 +9,92 @@ use solana_program::sysvar::rent;
 use solana_program::sysvar::clock;
 
 use wormhole_sdk::{
-    id,
+    claim,
     config,
     fee_collector,
+    registered_emitter,
     sequence,
+    MessageData,
 };
 
 use messenger_common::Message;
 
 use crate::Instruction::{
+    Initialize,
     RecvMessage,
+    RegisterEmitter,
     SendMessage,
 };
 
 
-/// Create a RecvMessage instruction.
-pub fn recv_message(program_id: Pubkey, payer: Pubkey, vaa: Pubkey) -> Instruction {
+/// Create an Initialize instruction, configuring the messenger to use the Wormhole program
+/// `wormhole`. Clients find it in the address book of the network, with `wormhole_sdk::id`.
+pub fn initialize(program_id: Pubkey, payer: Pubkey, wormhole: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+        data: Initialize(wormhole).try_to_vec().unwrap(),
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(crate::config(&program_id), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+
+/// Create a RecvMessage instruction for the VAA posted to `vaa`, whose contents are `message`.
+pub fn recv_message(
+    program_id: Pubkey,
+    payer: Pubkey,
+    vaa: Pubkey,
+    message: &MessageData,
+) -> Instruction {
+    let emitter_chain = message.emitter_chain;
+    let claim = claim(&program_id, emitter_chain, &message.emitter_address, message.sequence);
     Instruction {
         program_id,
         data: RecvMessage.try_to_vec().unwrap(),
         accounts: vec![
-            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(crate::config(&program_id), false),
             AccountMeta::new_readonly(vaa, false),
+            AccountMeta::new_readonly(registered_emitter(&program_id, emitter_chain), false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+/// Create a RegisterEmitter instruction, trusting the messenger at `address` on `chain`.
+pub fn register_emitter(
+    program_id: Pubkey,
+    payer: Pubkey,
+    chain: u16,
+    address: [u8; 32],
+) -> Instruction {
+    Instruction {
+        program_id,
+        data: RegisterEmitter(chain, address).try_to_vec().unwrap(),
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(registered_emitter(&program_id, chain), false),
+            AccountMeta::new_readonly(system_program::id(), false),
         ],
     }
 }
 
 
-/// Create a SendMessage instruction.
+/// Create a SendMessage instruction, for a messenger configured to use the Wormhole program
+/// `wormhole`.
 pub fn send_message(
     program_id: Pubkey,
+    wormhole: Pubkey,
     payer: Pubkey,
     emitter: Pubkey,
     message: Pubkey,
     payload: Message,
     nonce: u32,
 ) -> Instruction {
-    let wormhole = id();
     let config = config(&wormhole);
     let fee_collector = fee_collector(&wormhole);
     let sequence = sequence(&wormhole, &emitter);
@@ -58,6 +107,7 @@ pub fn send_message(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new(message, true),
+            AccountMeta::new_readonly(crate::config(&program_id), false),
             AccountMeta::new(fee_collector, false),
             AccountMeta::new(config, false),
             AccountMeta::new_readonly(emitter, false),
@@ -75,13 +125,13 @@ pub fn send_message(
 /// handler does not use the Wormhole SDK helper API.
 pub fn send_message_raw(
     program_id: Pubkey,
+    wormhole: Pubkey,
     payer: Pubkey,
     emitter: Pubkey,
     message: Pubkey,
     payload: Message,
     nonce: u32,
 ) -> Instruction {
-    let wormhole = id();
     let config = config(&wormhole);
     let fee_collector = fee_collector(&wormhole);
     let sequence = sequence(&wormhole, &emitter);
@@ -94,6 +144,7 @@ pub fn send_message_raw(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new(message, true),
+            AccountMeta::new_readonly(crate::config(&program_id), false),
             AccountMeta::new(fee_collector, false),
             AccountMeta::new(config, false),
             AccountMeta::new_readonly(emitter, false),
@@ -14,7 +14,10 @@ use solana_program::account_info::{
 };
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program::invoke_signed;
+use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
 use solana_program::{
     entrypoint,
     msg,
@@ -24,6 +27,7 @@ use solana_program::{
 use wormhole_sdk::{
     instructions::post_message,
     ConsistencyLevel,
+    VerifiedVAA,
     VAA,
 };
 
@@ -32,33 +36,70 @@ pub use messenger_common::Message;
 
 pub mod instruction;
 
+/// The messenger's settings, stored at `config(program_id)`. The Wormhole program differs between
+/// networks, so it is configured when the messenger is deployed rather than compiled in.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Config {
+    pub wormhole: Pubkey,
+}
+
+impl Config {
+    pub const LEN: usize = 32;
+}
+
+/// Derives the account holding the messenger's `Config`.
+pub fn config(program_id: &Pubkey) -> Pubkey {
+    let (config, _) = Pubkey::find_program_address(&[b"Config"], program_id);
+    config
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub enum Instruction {
+    /// Stores the Wormhole program the messenger sends through and receives from, usually the
+    /// one the address book lists for the network it is deployed to.
+    ///
+    /// 0: Payer    [Signer]
+    /// 1: Config   [PDA]
+    /// 2: System   [Program]
+    Initialize(Pubkey),
+
     /// This instruction is used to send a message to another chain by emitting it as a wormhole
     /// message targetting another users key.
     ///
-    /// 0: Payer         [Signer]
-    /// 1: Message       [Signer]
-    /// 2: Worm Config   [PDA]
-    /// 3: Worm Fee      [PDA]
-    /// 4: Worm Sequence [PDA]
-    /// 5: Emitter       [PDA, Signer]
-    /// 6: Clock         [Program]         -- Needed for wormhole to take block times.
-    /// 7: Rent          [Program]         -- Needed for wormhole fee calculation on the message account.
-    /// 8: System        [Program]         -- Needed for wormhole to take fees.
-    /// 9: Wormhole      [Program]         -- Needed for wormhole invoke_signed.
+    /// 0:  Payer         [Signer]
+    /// 1:  Message       [Signer]
+    /// 2:  Config        [PDA]            -- The messenger config, naming the Wormhole program.
+    /// 3:  Worm Fee      [PDA]
+    /// 4:  Worm Config   [PDA]
+    /// 5:  Worm Sequence [PDA]
+    /// 6:  Emitter       [PDA, Signer]
+    /// 7:  Clock         [Program]        -- Needed for wormhole to take block times.
+    /// 8:  Rent          [Program]        -- Needed for wormhole fee calculation on the message account.
+    /// 9:  System        [Program]        -- Needed for wormhole to take fees.
+    /// 10: Wormhole      [Program]        -- Needed for wormhole invoke_signed.
     SendMessage(Message, u32),
 
     /// This is the same as the above message, but the example handler is more low level.
     SendMessageRaw(Message, u32),
 
     /// This instruction receives a message by processing an incoming VAA containing a message
-    /// intended for a receiver on Solana. Note that the simple existence of the VAA account is
-    /// enough to verify it as the account is only created by the bridge if the guardians had
-    /// successfull signed it.
+    /// intended for a receiver on Solana. The VAA account is only trusted once the SDK has checked
+    /// that the bridge created it, and that it was sent by the messenger registered for its chain.
     ///
-    /// 0: VAA [PDA]
+    /// 0: Payer    [Signer]
+    /// 1: Config   [PDA]              -- The messenger config, naming the Wormhole program.
+    /// 2: VAA      [PDA]
+    /// 3: Emitter  [PDA]              -- The messenger registered for the sending chain.
+    /// 4: Claim    [PDA]              -- Created to mark the VAA as received.
+    /// 5: System   [Program]          -- Needed to create the claim.
     RecvMessage,
+
+    /// Registers the messenger contract on another chain, whose messages RecvMessage accepts.
+    ///
+    /// 0: Payer    [Signer]
+    /// 1: Emitter  [PDA]
+    /// 2: System   [Program]
+    RegisterEmitter(u16, [u8; 32]),
 }
 
 
@@ -68,24 +109,68 @@ entrypoint!(process_instruction);
 /// program handlers.
 pub fn process_instruction(id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
     match BorshDeserialize::try_from_slice(data).unwrap() {
+        Instruction::Initialize(wormhole) => initialize(id, accs, wormhole),
+
         // Send Message Variants. Check the source of each to see various ways to invoke Wormhole.
         Instruction::SendMessage(msg, nonce)    => send_message(id, accs, msg, nonce),
         Instruction::SendMessageRaw(msg, nonce) => send_message_raw(id, accs, msg, nonce),
 
         // RecvMessage shows an example of safely processing a VAA.
         Instruction::RecvMessage         => recv_message(id, accs),
+        Instruction::RegisterEmitter(c, a) => register_emitter(id, accs, c, a),
     }?;
     Ok(())
 }
 
+/// Store the Wormhole program in the messenger config. Like `register_emitter`, a real program
+/// would only let its admin do this.
+fn initialize(id: &Pubkey, accs: &[AccountInfo], wormhole: Pubkey) -> ProgramResult {
+    let accounts = &mut accs.iter();
+    let payer    = next_account_info(accounts)?;
+    let account  = next_account_info(accounts)?;
+
+    let (expected, bump) = Pubkey::find_program_address(&[b"Config"], id);
+    if *account.key != expected {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if account.owner == id {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let seeds: &[&[u8]] = &[b"Config", &[bump]];
+    invoke_signed(
+        &solana_program::system_instruction::create_account(
+            payer.key,
+            account.key,
+            Rent::get()?.minimum_balance(Config::LEN),
+            Config::LEN as u64,
+            id,
+        ),
+        accs,
+        &[seeds],
+    )?;
+
+    Config { wormhole }.serialize(&mut &mut account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Read the Wormhole program from the messenger config, checking `account` is the config.
+fn configured_wormhole(id: &Pubkey, account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    if account.owner != id || *account.key != config(id) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(Config::try_from_slice(&account.data.borrow())?.wormhole)
+}
+
 /// Send a Message from this chain to a user on a remote target chain.
 ///
 /// This method is a reference example of emitting messages via Wormhole using the ergonomic API
 /// methods. This is the easiest way to use Wormhole.
 fn send_message(id: &Pubkey, accounts: &[AccountInfo], payload: Message, nonce: u32) -> ProgramResult {
-    let iter    = &mut accounts.iter();
-    let payer   = next_account_info(iter)?;
-    let message = next_account_info(iter)?;
+    let iter     = &mut accounts.iter();
+    let payer    = next_account_info(iter)?;
+    let message  = next_account_info(iter)?;
+    let wormhole = configured_wormhole(id, next_account_info(iter)?)?;
 
     // This helper method will take care of all of the following for you:
     //
@@ -93,6 +178,7 @@ fn send_message(id: &Pubkey, accounts: &[AccountInfo], payload: Message, nonce:
     // - Pays the Bridge (Payer Key)
     // - Emits a Message
     wormhole_sdk::post_message(
+        wormhole,
         *id,
         *payer.key,
         *message.key,
@@ -114,6 +200,7 @@ fn send_message_raw(id: &Pubkey, accs: &[AccountInfo], payload: Message, nonce:
     let accounts      = &mut accs.iter();
     let payer         = next_account_info(accounts)?;
     let message       = next_account_info(accounts)?;
+    let wormhole      = configured_wormhole(id, next_account_info(accounts)?)?;
     let fee_collector = next_account_info(accounts)?;
     let config        = next_account_info(accounts)?;
 
@@ -137,7 +224,7 @@ fn send_message_raw(id: &Pubkey, accs: &[AccountInfo], payload: Message, nonce:
     // Invoke the Wormhole post_message endpoint to create an on-chain message.
     invoke_signed(
         &post_message(
-            wormhole_sdk::id(),
+            wormhole,
             *payer.key,
             emitter,
             *message.key,
@@ -158,19 +245,37 @@ fn send_message_raw(id: &Pubkey, accs: &[AccountInfo], payload: Message, nonce:
 /// validate the message has been safely attested by the guardian set. Prints the message in
 /// validator logs.
 fn recv_message(id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
-    // We must verify the VAA is legitimately signed by the guardians. We do this by deriving the
-    // expected PDA derived by the bridge, as long as we produce the same account we can trust the
-    // contents of the VAA.
     let accounts = &mut accs.iter();
     let payer    = next_account_info(accounts)?;
+    let wormhole = configured_wormhole(id, next_account_info(accounts)?)?;
     let vaa      = next_account_info(accounts)?;
+    let emitter  = next_account_info(accounts)?;
+    let claim    = next_account_info(accounts)?;
+
+    // Anyone can pass any account as the VAA, so we must check it is the account the bridge
+    // created after verifying the guardian signatures, and that the message came from the
+    // messenger we registered on the sending chain rather than from any contract there.
+    let vaa = VerifiedVAA::load(&wormhole, id, vaa, emitter)?;
+
+    // Claiming fails if the VAA was already received, so each message is only processed once.
+    vaa.claim(id, payer, claim, accs)?;
 
-    // If we want to avoid processing a message twice we need to track whether we have already
-    // processed a VAA manually. There are several ways to do this in Solana but in this example
-    // we will simply reprocess VAA's.
-    let vaa = wormhole_sdk::read_vaa(vaa).unwrap();
     let msg = Message::try_from_slice(&vaa.payload)?;
     msg!("{}: {}", msg.nick, msg.text);
 
     Ok(())
 }
+
+/// Registers the messenger deployed on another chain. A real program would only let its admin do
+/// this, otherwise anyone could register their own contract and send arbitrary messages.
+fn register_emitter(
+    id: &Pubkey,
+    accs: &[AccountInfo],
+    chain: u16,
+    address: [u8; 32],
+) -> ProgramResult {
+    let accounts = &mut accs.iter();
+    let payer    = next_account_info(accounts)?;
+    let emitter  = next_account_info(accounts)?;
+    wormhole_sdk::register_emitter(id, payer, emitter, accs, chain, address)
+}
@@ -34,15 +34,19 @@ use solana_sdk::secp256k1_instruction::new_secp256k1_instruction;
 use messenger::Message;
 use messenger::process_instruction;
 use messenger::instruction::{
+    initialize,
     send_message,
     recv_message,
+    register_emitter,
     send_message_raw,
 };
 
 // We utilise the bridge_endpoint, which is re-exposed by the SDK, to run instructions against
 // within the Solana program test framework.
+use wormhole_sdk::AddressBook;
 use wormhole_sdk::Chain;
 use wormhole_sdk::MessageData;
+use wormhole_sdk::Network;
 use wormhole_sdk::VAA;
 use wormhole_sdk::PostVAAData;
 use wormhole_sdk::VerifySignaturesData;
@@ -59,6 +63,16 @@ use secp256k1::SecretKey;
 /// the tests.
 const ID: Pubkey = Pubkey::new_from_array([2u8; 32]);
 
+/// The Wormhole program to deploy, from the devnet address book. A redeployed bridge can be used
+/// by pointing `ADDRESS_BOOK` at a config file overriding it, see `wormhole_sdk::network`.
+fn wormhole() -> Pubkey {
+    let book = match std::env::var("ADDRESS_BOOK") {
+        Ok(path) => AddressBook::load(Network::Devnet, path).unwrap(),
+        Err(_) => AddressBook::new(Network::Devnet),
+    };
+    wormhole_sdk::id(&book).unwrap()
+}
+
 
 #[tokio::test]
 pub async fn test_publish_message() {
@@ -71,22 +85,25 @@ pub async fn test_publish_message() {
     // expect them to be.
     let mut context = {
         let mut test = ProgramTest::default();
-        test.add_program("bridge", wormhole_sdk::id(), processor!(bridge_entrypoint));
+        test.add_program("bridge", wormhole(), processor!(bridge_entrypoint));
         test.add_program("messenger", ID, processor!(process_instruction));
         test.start_with_context().await
     };
 
-    // Initialize Wormhole
+    // Initialize Wormhole, and the messenger to use it
     context
         .banks_client
         .process_transaction(Transaction::new_signed_with_payer(
-            &[wormhole_sdk::instructions::initialize(
-                wormhole_sdk::id(),
-                context.payer.pubkey(),
-                50,
-                2_000_000_000,
-                &[*guardian],
-            ).unwrap()],
+            &[
+                wormhole_sdk::instructions::initialize(
+                    wormhole(),
+                    context.payer.pubkey(),
+                    50,
+                    2_000_000_000,
+                    &[*guardian],
+                ).unwrap(),
+                initialize(ID, context.payer.pubkey(), wormhole()),
+            ],
             Some(&context.payer.pubkey()),
             &[&context.payer],
             context.last_blockhash,
@@ -104,6 +121,7 @@ pub async fn test_publish_message() {
         .process_transaction(Transaction::new_signed_with_payer(
             &[send_message(
                 ID,
+                wormhole(),
                 context.payer.pubkey(),
                 emitter.0,
                 message.pubkey(),
@@ -149,6 +167,23 @@ pub async fn test_publish_message() {
     // Simulate Guardian behaviour: detecting message, signing, posting VAA.
     let vaa = simulate_guardians(&mut context, &message).await;
 
+    // Messages are only received from registered messengers, here the one that sent it.
+    context
+        .banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[register_emitter(
+                ID,
+                context.payer.pubkey(),
+                message.emitter_chain,
+                message.emitter_address,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        ))
+        .await
+        .unwrap();
+
     // We can now test the recv_message endpoint by submitting the signed VAA.
     context
         .banks_client
@@ -157,6 +192,7 @@ pub async fn test_publish_message() {
                 ID,
                 context.payer.pubkey(),
                 vaa,
+                &message,
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer],
@@ -177,22 +213,25 @@ pub async fn test_publish_message_raw() {
     // expect them to be.
     let mut context = {
         let mut test = ProgramTest::default();
-        test.add_program("bridge", wormhole_sdk::id(), processor!(bridge_entrypoint));
+        test.add_program("bridge", wormhole(), processor!(bridge_entrypoint));
         test.add_program("messenger", ID, processor!(process_instruction));
         test.start_with_context().await
     };
 
-    // Initialize Wormhole
+    // Initialize Wormhole, and the messenger to use it
     context
         .banks_client
         .process_transaction(Transaction::new_signed_with_payer(
-            &[wormhole_sdk::instructions::initialize(
-                wormhole_sdk::id(),
-                context.payer.pubkey(),
-                50,
-                2_000_000_000,
-                &[*guardian],
-            ).unwrap()],
+            &[
+                wormhole_sdk::instructions::initialize(
+                    wormhole(),
+                    context.payer.pubkey(),
+                    50,
+                    2_000_000_000,
+                    &[*guardian],
+                ).unwrap(),
+                initialize(ID, context.payer.pubkey(), wormhole()),
+            ],
             Some(&context.payer.pubkey()),
             &[&context.payer],
             context.last_blockhash,
@@ -210,6 +249,7 @@ pub async fn test_publish_message_raw() {
         .process_transaction(Transaction::new_signed_with_payer(
             &[send_message_raw(
                 ID,
+                wormhole(),
                 context.payer.pubkey(),
                 emitter.0,
                 message.pubkey(),
@@ -289,7 +329,7 @@ pub async fn simulate_guardians(context: &mut ProgramTestContext, message: &Mess
                     &body
                 ),
                 wormhole_sdk::instructions::verify_signatures(
-                    wormhole_sdk::id(),
+                    wormhole(),
                     context.payer.pubkey(),
                     0,
                     signatures.pubkey(),
@@ -308,7 +348,7 @@ pub async fn simulate_guardians(context: &mut ProgramTestContext, message: &Mess
         .process_transaction(Transaction::new_signed_with_payer(
             &[
                 wormhole_sdk::instructions::post_vaa(
-                    wormhole_sdk::id(),
+                    wormhole(),
                     context.payer.pubkey(),
                     signatures.pubkey(),
                     PostVAAData {
@@ -334,6 +374,6 @@ pub async fn simulate_guardians(context: &mut ProgramTestContext, message: &Mess
     // Derive VAA Destination.
     Pubkey::find_program_address(
         &[b"PostedVAA", &body],
-        &wormhole_sdk::id(),
+        &wormhole(),
     ).0
 }
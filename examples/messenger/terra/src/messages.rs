@@ -7,21 +7,22 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use wormhole_sdk::ReceiverConfig;
 
 /// InstantiateMsg is passed into the contract initialiser when the contract is first deployed,
 /// this is a one off message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub version: String,
+    pub version:  String,
+    pub receiver: ReceiverConfig,
 }
 
 /// ExecuteMsg is passed into the execute contract handler whenever a user submits a transaction
 /// targetting our contract, this is our "main" entrypoint.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum ExecuteMsg {
-    RecvMessage {
-        vaa: Binary,
-    },
+    /// Receive a message, or a governance VAA registering the messenger of another chain.
+    RecvMessage { vaa: Binary },
 
     SendMessage {
         nonce: u32,
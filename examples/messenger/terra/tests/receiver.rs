@@ -0,0 +1,268 @@
+//! Receives messages through a mock core bridge, which trusts every VAA it is asked to verify, to
+//! exercise the emitter registry and replay protection of `WormholeReceiver`.
+
+use borsh::BorshSerialize;
+use cosmwasm_std::{
+    to_binary,
+    Addr,
+    Binary,
+    Deps,
+    DepsMut,
+    Empty,
+    Env,
+    MessageInfo,
+    Response,
+    StdResult,
+};
+use cw_multi_test::{
+    App,
+    AppResponse,
+    ContractWrapper,
+    Executor,
+};
+
+use wormhole::msg::QueryMsg;
+use wormhole::state::ParsedVAA;
+use wormhole_sdk::vaa::{
+    governance_module,
+    GovHeader,
+};
+use wormhole_sdk::{
+    Chain,
+    ReceiverConfig,
+    REGISTER_EMITTER,
+    VAA,
+};
+
+use messenger_common::Message;
+use wormhole_messenger_terra::{
+    ExecuteMsg,
+    InstantiateMsg,
+};
+
+const GOVERNANCE: [u8; 32] = [1; 32];
+const EMITTER: [u8; 32] = [2; 32];
+
+fn core_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn core_execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn core_query(_: Deps, _: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VerifyVAA { vaa, .. } => to_binary(&ParsedVAA::deserialize(&vaa)?),
+        _ => unimplemented!(),
+    }
+}
+
+fn messenger_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    unimplemented!()
+}
+
+fn setup() -> (App, Addr) {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+
+    let core = app.store_code(Box::new(ContractWrapper::new(
+        core_execute,
+        core_instantiate,
+        core_query,
+    )));
+    let core = app
+        .instantiate_contract(core, owner.clone(), &Empty {}, &[], "wormhole", None)
+        .unwrap();
+
+    let messenger = app.store_code(Box::new(ContractWrapper::new(
+        wormhole_messenger_terra::execute,
+        wormhole_messenger_terra::instantiate,
+        messenger_query,
+    )));
+    let messenger = app
+        .instantiate_contract(
+            messenger,
+            owner,
+            &InstantiateMsg {
+                version:  "1.0.0".to_string(),
+                receiver: ReceiverConfig {
+                    wormhole:           core,
+                    governance_chain:   1,
+                    governance_address: Binary::from(&GOVERNANCE),
+                    module:             "Messenger".to_string(),
+                },
+            },
+            &[],
+            "messenger",
+            None,
+        )
+        .unwrap();
+
+    (app, messenger)
+}
+
+fn vaa(emitter_chain: Chain, emitter_address: [u8; 32], sequence: u64, payload: Vec<u8>) -> Binary {
+    let vaa = VAA {
+        version: 1,
+        emitter_chain,
+        emitter_address,
+        sequence,
+        payload,
+        ..VAA::default()
+    };
+    Binary::from(vaa.to_bytes())
+}
+
+fn register(module: &[u8], chains: Chain, sequence: u64) -> Binary {
+    let mut payload = GovHeader {
+        module: governance_module(module),
+        action: REGISTER_EMITTER,
+        chains,
+    }
+    .to_bytes();
+    payload.extend_from_slice(&2u16.to_be_bytes());
+    payload.extend_from_slice(&EMITTER);
+    vaa(Chain::Solana, GOVERNANCE, sequence, payload)
+}
+
+fn message(text: &str) -> Vec<u8> {
+    Message {
+        nick: "Alice".to_string(),
+        text: text.to_string(),
+    }
+    .try_to_vec()
+    .unwrap()
+}
+
+fn recv(app: &mut App, messenger: &Addr, vaa: Binary) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked("relayer"),
+        messenger.clone(),
+        &ExecuteMsg::RecvMessage { vaa },
+        &[],
+    )
+}
+
+fn attribute(response: &AppResponse, key: &str) -> Option<String> {
+    response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.clone())
+}
+
+#[test]
+fn test_recv_message() {
+    let (mut app, messenger) = setup();
+
+    // Nothing is accepted before the emitter is registered.
+    assert!(recv(
+        &mut app,
+        &messenger,
+        vaa(Chain::Ethereum, EMITTER, 0, message("Hi"))
+    )
+    .is_err());
+
+    let response = recv(
+        &mut app,
+        &messenger,
+        register(b"Messenger", Chain::Terra, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        attribute(&response, "action").as_deref(),
+        Some("register_emitter")
+    );
+    assert_eq!(attribute(&response, "chain_id").as_deref(), Some("2"));
+
+    let response = recv(
+        &mut app,
+        &messenger,
+        vaa(Chain::Ethereum, EMITTER, 1, message("Hi")),
+    )
+    .unwrap();
+    assert_eq!(attribute(&response, "nick").as_deref(), Some("Alice"));
+    assert_eq!(attribute(&response, "text").as_deref(), Some("Hi"));
+}
+
+#[test]
+fn test_reject_replay() {
+    let (mut app, messenger) = setup();
+    recv(&mut app, &messenger, register(b"Messenger", Chain::All, 0)).unwrap();
+
+    let hello = vaa(Chain::Ethereum, EMITTER, 1, message("Hello"));
+    recv(&mut app, &messenger, hello.clone()).unwrap();
+    assert!(recv(&mut app, &messenger, hello).is_err());
+
+    // Registrations cannot be replayed, or replaced by a later one.
+    assert!(recv(&mut app, &messenger, register(b"Messenger", Chain::All, 0)).is_err());
+    assert!(recv(&mut app, &messenger, register(b"Messenger", Chain::All, 1)).is_err());
+}
+
+#[test]
+fn test_reject_emitter() {
+    let (mut app, messenger) = setup();
+    recv(
+        &mut app,
+        &messenger,
+        register(b"Messenger", Chain::Terra, 0),
+    )
+    .unwrap();
+
+    // Other emitters on the registered chain, and the registered emitter address on another chain.
+    assert!(recv(
+        &mut app,
+        &messenger,
+        vaa(Chain::Ethereum, [3; 32], 1, message("Hi"))
+    )
+    .is_err());
+    assert!(recv(
+        &mut app,
+        &messenger,
+        vaa(Chain::Binance, EMITTER, 1, message("Hi"))
+    )
+    .is_err());
+}
+
+#[test]
+fn test_reject_governance() {
+    let (mut app, messenger) = setup();
+
+    // Registrations for another module or chain, or from an emitter other than governance.
+    assert!(recv(
+        &mut app,
+        &messenger,
+        register(b"TokenBridge", Chain::Terra, 0)
+    )
+    .is_err());
+    assert!(recv(
+        &mut app,
+        &messenger,
+        register(b"Messenger", Chain::Solana, 1)
+    )
+    .is_err());
+
+    let mut payload = GovHeader {
+        module: governance_module(b"Messenger"),
+        action: REGISTER_EMITTER,
+        chains: Chain::Terra,
+    }
+    .to_bytes();
+    payload.extend_from_slice(&2u16.to_be_bytes());
+    payload.extend_from_slice(&EMITTER);
+    assert!(recv(
+        &mut app,
+        &messenger,
+        vaa(Chain::Solana, EMITTER, 2, payload)
+    )
+    .is_err());
+
+    assert!(recv(
+        &mut app,
+        &messenger,
+        vaa(Chain::Ethereum, EMITTER, 3, message("Hi"))
+    )
+    .is_err());
+}
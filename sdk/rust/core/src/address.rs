@@ -0,0 +1,470 @@
+//! Wormhole refers to accounts on every chain with 32 byte addresses. Chains with shorter native
+//! addresses, such as the 20 byte addresses of EVM chains and Terra, are left zero padded. This
+//! module converts between that universal form and the text formats each chain uses natively.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::vaa::ForeignAddress;
+use crate::WormholeError::InvalidAddress;
+use crate::{
+    require,
+    Chain,
+    WormholeError,
+};
+
+/// A 32 byte Wormhole address. Conversions to native formats check that the bytes a shorter
+/// native address does not use are zero, so a corrupted or misencoded address is rejected instead
+/// of being truncated to a different account.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UniversalAddress(pub ForeignAddress);
+
+impl From<ForeignAddress> for UniversalAddress {
+    fn from(bytes: ForeignAddress) -> Self {
+        UniversalAddress(bytes)
+    }
+}
+
+impl From<UniversalAddress> for ForeignAddress {
+    fn from(address: UniversalAddress) -> Self {
+        address.0
+    }
+}
+
+impl UniversalAddress {
+    /// Left zero pad a native address of up to 32 bytes.
+    pub fn from_native(bytes: &[u8]) -> Result<Self, WormholeError> {
+        require!(bytes.len() <= 32, InvalidAddress);
+        let mut address = [0u8; 32];
+        address[32 - bytes.len()..].copy_from_slice(bytes);
+        Ok(UniversalAddress(address))
+    }
+
+    /// The last `len` bytes, failing if any of the padding before them is set.
+    pub fn to_native(&self, len: usize) -> Result<&[u8], WormholeError> {
+        require!(len <= 32, InvalidAddress);
+        let (padding, native) = self.0.split_at(32 - len);
+        require!(padding.iter().all(|&b| b == 0), InvalidAddress);
+        Ok(native)
+    }
+
+    /// Parse an address in the native text format of `chain`.
+    pub fn parse(chain: &Chain, address: &str) -> Result<Self, WormholeError> {
+        match chain {
+            Chain::Solana => Self::from_solana(address),
+            Chain::Terra => Self::from_terra(address),
+            Chain::Alephium => Self::from_alephium(address),
+            Chain::Ethereum | Chain::Binance | Chain::Polygon | Chain::AVAX | Chain::Oasis => {
+                Self::from_evm(address)
+            }
+            _ => Err(InvalidAddress),
+        }
+    }
+
+    /// Render the address in the native text format of `chain`.
+    pub fn format(&self, chain: &Chain) -> Result<String, WormholeError> {
+        match chain {
+            Chain::Solana => Ok(self.to_solana()),
+            Chain::Terra => self.to_terra(),
+            Chain::Alephium => Ok(self.to_alephium()),
+            Chain::Ethereum | Chain::Binance | Chain::Polygon | Chain::AVAX | Chain::Oasis => {
+                self.to_evm()
+            }
+            _ => Err(InvalidAddress),
+        }
+    }
+
+    /// Parse a base58 Solana public key.
+    pub fn from_solana(address: &str) -> Result<Self, WormholeError> {
+        let bytes = base58::decode(address).ok_or(InvalidAddress)?;
+        require!(bytes.len() == 32, InvalidAddress);
+        Self::from_native(&bytes)
+    }
+
+    /// Solana public keys take up all 32 bytes.
+    pub fn to_solana(&self) -> String {
+        base58::encode(&self.0)
+    }
+
+    /// Parse a `terra1...` bech32 account or contract address.
+    pub fn from_terra(address: &str) -> Result<Self, WormholeError> {
+        let bytes = bech32::decode("terra", address).ok_or(InvalidAddress)?;
+        require!(bytes.len() == 20, InvalidAddress);
+        Self::from_native(&bytes)
+    }
+
+    /// Terra addresses are the last 20 bytes.
+    pub fn to_terra(&self) -> Result<String, WormholeError> {
+        Ok(bech32::encode("terra", self.to_native(20)?))
+    }
+
+    /// Parse a `0x` prefixed EVM address. Mixed case addresses must carry a valid EIP-55
+    /// checksum, all lowercase or all uppercase ones are taken as is.
+    pub fn from_evm(address: &str) -> Result<Self, WormholeError> {
+        let hex = address.strip_prefix("0x").ok_or(InvalidAddress)?;
+        require!(hex.len() == 40, InvalidAddress);
+        let bytes = decode_hex(hex).ok_or(InvalidAddress)?;
+        let lower = hex.bytes().all(|c| !c.is_ascii_uppercase());
+        let upper = hex.bytes().all(|c| !c.is_ascii_lowercase());
+        require!(lower || upper || checksum(&bytes) == hex, InvalidAddress);
+        Self::from_native(&bytes)
+    }
+
+    /// EVM addresses are the last 20 bytes, rendered with an EIP-55 checksum.
+    pub fn to_evm(&self) -> Result<String, WormholeError> {
+        let mut address = String::from("0x");
+        address.push_str(&checksum(self.to_native(20)?));
+        Ok(address)
+    }
+
+    /// Parse an Alephium contract ID, 32 bytes of hex.
+    pub fn from_alephium(contract_id: &str) -> Result<Self, WormholeError> {
+        require!(contract_id.len() == 64, InvalidAddress);
+        let bytes = decode_hex(contract_id).ok_or(InvalidAddress)?;
+        Self::from_native(&bytes)
+    }
+
+    /// Alephium contract IDs take up all 32 bytes.
+    pub fn to_alephium(&self) -> String {
+        encode_hex(&self.0)
+    }
+
+    /// Parse a base58 Alephium address. Only the two kinds the token bridge deals in are accepted,
+    /// P2PKH addresses, which hold a 32 byte public key hash, and contract addresses, which hold
+    /// the contract ID.
+    pub fn from_alephium_address(address: &str) -> Result<Self, WormholeError> {
+        let bytes = base58::decode(address).ok_or(InvalidAddress)?;
+        require!(bytes.len() == 33, InvalidAddress);
+        require!(
+            bytes[0] == ALEPHIUM_P2PKH || bytes[0] == ALEPHIUM_CONTRACT,
+            InvalidAddress
+        );
+        Self::from_native(&bytes[1..])
+    }
+
+    /// Render as the base58 address of the Alephium contract with this ID.
+    pub fn to_alephium_contract_address(&self) -> String {
+        alephium_address(ALEPHIUM_CONTRACT, &self.0)
+    }
+
+    /// Render as a base58 Alephium P2PKH address, the kind the token bridge pays transfers out to.
+    pub fn to_alephium_p2pkh_address(&self) -> String {
+        alephium_address(ALEPHIUM_P2PKH, &self.0)
+    }
+}
+
+/// Alephium addresses are a type byte followed by the public key hash or contract ID.
+const ALEPHIUM_P2PKH: u8 = 0x00;
+const ALEPHIUM_CONTRACT: u8 = 0x03;
+
+fn alephium_address(kind: u8, bytes: &[u8]) -> String {
+    let mut address = Vec::with_capacity(33);
+    address.push(kind);
+    address.extend_from_slice(bytes);
+    base58::encode(&address)
+}
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        s.push(DIGITS[(b >> 4) as usize] as char);
+        s.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    s
+}
+
+pub(crate) fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let pairs = s.as_bytes().chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+    pairs
+        .map(|pair| Some(nibble(pair[0])? << 4 | nibble(pair[1])?))
+        .collect()
+}
+
+/// EIP-55: a hex digit is uppercase when the matching nibble of the Keccak hash of the lowercase
+/// address is 8 or more.
+fn checksum(address: &[u8]) -> String {
+    use sha3::Digest;
+
+    let lower = encode_hex(address);
+    let hash = sha3::Keccak256::digest(lower.as_bytes());
+    lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Base58 with the Bitcoin alphabet, as used for Solana public keys and Alephium addresses.
+mod base58 {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    pub fn encode(bytes: &[u8]) -> String {
+        // Little endian base 58 digits.
+        let mut digits: Vec<u8> = Vec::new();
+        for &byte in bytes {
+            let mut carry = byte as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+
+        let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+        let mut s = String::with_capacity(zeros + digits.len());
+        for _ in 0..zeros {
+            s.push('1');
+        }
+        s.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+        s
+    }
+
+    pub fn decode(s: &str) -> Option<Vec<u8>> {
+        // Little endian bytes.
+        let mut bytes: Vec<u8> = Vec::new();
+        for c in s.bytes() {
+            let mut carry = ALPHABET.iter().position(|&a| a == c)? as u32;
+            for byte in bytes.iter_mut() {
+                carry += (*byte as u32) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        let zeros = s.bytes().take_while(|&c| c == b'1').count();
+        bytes.resize(bytes.len() + zeros, 0);
+        bytes.reverse();
+        Some(bytes)
+    }
+}
+
+/// BIP-173 bech32, as used for Cosmos SDK addresses.
+mod bech32 {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+        let mut chk = 1u32;
+        for v in values {
+            let top = chk >> 25;
+            chk = (chk & 0x1ffffff) << 5 ^ v as u32;
+            for (i, g) in GENERATOR.iter().enumerate() {
+                if (top >> i) & 1 == 1 {
+                    chk ^= g;
+                }
+            }
+        }
+        chk
+    }
+
+    fn expand_hrp(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+        let high = hrp.bytes().map(|c| c >> 5);
+        let low = hrp.bytes().map(|c| c & 31);
+        high.chain(core::iter::once(0)).chain(low)
+    }
+
+    /// Regroup bits, `pad` allows a final partial group. Without padding the leftover bits must
+    /// be fewer than `from` and zero.
+    fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+        let mut acc = 0u32;
+        let mut bits = 0u32;
+        let mut out = Vec::new();
+        for &value in data {
+            acc = (acc << from) | value as u32;
+            bits += from;
+            while bits >= to {
+                bits -= to;
+                out.push(((acc >> bits) & ((1 << to) - 1)) as u8);
+            }
+        }
+        if pad {
+            if bits > 0 {
+                out.push(((acc << (to - bits)) & ((1 << to) - 1)) as u8);
+            }
+        } else if bits >= from || (acc << (to - bits)) & ((1 << to) - 1) != 0 {
+            return None;
+        }
+        Some(out)
+    }
+
+    pub fn encode(hrp: &str, bytes: &[u8]) -> String {
+        let data = convert_bits(bytes, 8, 5, true).unwrap_or_default();
+        let values = expand_hrp(hrp).chain(data.iter().copied()).chain([0u8; 6].iter().copied());
+        let checksum = polymod(values) ^ 1;
+
+        let mut s = String::with_capacity(hrp.len() + 1 + data.len() + 6);
+        s.push_str(hrp);
+        s.push('1');
+        s.extend(data.iter().map(|&d| CHARSET[d as usize] as char));
+        s.extend((0..6).map(|i| CHARSET[((checksum >> (5 * (5 - i))) & 31) as usize] as char));
+        s
+    }
+
+    /// Decode an address with the given human readable part.
+    pub fn decode(hrp: &str, s: &str) -> Option<Vec<u8>> {
+        let lower = s.bytes().all(|c| !c.is_ascii_uppercase());
+        let upper = s.bytes().all(|c| !c.is_ascii_lowercase());
+        if !(lower || upper) || s.len() > 90 {
+            return None;
+        }
+
+        let s = s.to_ascii_lowercase();
+        let (prefix, rest) = s.split_at(s.rfind('1')?);
+        if prefix != hrp || rest.len() < 7 {
+            return None;
+        }
+        let data = rest[1..]
+            .bytes()
+            .map(|c| CHARSET.iter().position(|&a| a == c).map(|d| d as u8))
+            .collect::<Option<Vec<u8>>>()?;
+
+        if polymod(expand_hrp(hrp).chain(data.iter().copied())) != 1 {
+            return None;
+        }
+        convert_bits(&data[..data.len() - 6], 5, 8, false)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    fn address(hex: &str) -> UniversalAddress {
+        UniversalAddress::from_native(&decode_hex(hex).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_solana() {
+        let token_program = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+        let expected = address("06ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a9");
+        assert_eq!(UniversalAddress::from_solana(token_program).unwrap(), expected);
+        assert_eq!(expected.to_solana(), token_program);
+
+        let system_program = "11111111111111111111111111111111";
+        let zero = UniversalAddress::default();
+        assert_eq!(UniversalAddress::from_solana(system_program).unwrap(), zero);
+        assert_eq!(zero.to_solana(), system_program);
+
+        // Wrong length and characters outside the alphabet.
+        assert!(UniversalAddress::from_solana("1111111111111111111111111111111").is_err());
+        assert!(UniversalAddress::from_solana("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5D0").is_err());
+    }
+
+    #[test]
+    fn test_evm() {
+        // EIP-55 test vectors.
+        for checksummed in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ]
+        .iter()
+        {
+            let address = UniversalAddress::from_evm(checksummed).unwrap();
+            assert_eq!(&address.0[..12], &[0; 12]);
+            assert_eq!(address.to_evm().unwrap(), *checksummed);
+
+            let lower = checksummed.to_ascii_lowercase();
+            assert_eq!(UniversalAddress::from_evm(&lower).unwrap(), address);
+        }
+
+        // A single flipped case breaks the checksum.
+        assert!(UniversalAddress::from_evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+        assert!(UniversalAddress::from_evm("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+        assert!(UniversalAddress::from_evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA").is_err());
+    }
+
+    #[test]
+    fn test_terra() {
+        let terra = "terra1x46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38v";
+        let expected = address("35743074956c710800e83198011ccbd4ddf1556d");
+        assert_eq!(UniversalAddress::from_terra(terra).unwrap(), expected);
+        assert_eq!(expected.to_terra().unwrap(), terra);
+        assert_eq!(UniversalAddress::from_terra(&terra.to_ascii_uppercase()).unwrap(), expected);
+
+        // Wrong prefix, broken checksum and mixed case.
+        assert!(UniversalAddress::from_terra("cosmos1x46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38v").is_err());
+        assert!(UniversalAddress::from_terra("terra1x46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38w").is_err());
+        assert!(UniversalAddress::from_terra("terra1X46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38v").is_err());
+    }
+
+    #[test]
+    fn test_alephium() {
+        let id = address(&"ab".repeat(32));
+        let contract = "26F5exrtYW913Zj83GcK1LMP1VduPQVcVzvqBYkwkJpti";
+        let p2pkh = "1CZ8YUVdk7znjrUmnb5n7kgySk9yRAsQDYmyCxzfSky9t";
+        assert_eq!(id.to_alephium_contract_address(), contract);
+        assert_eq!(id.to_alephium_p2pkh_address(), p2pkh);
+        assert_eq!(UniversalAddress::from_alephium_address(contract).unwrap(), id);
+        assert_eq!(UniversalAddress::from_alephium_address(p2pkh).unwrap(), id);
+
+        // Other address kinds and lengths are rejected.
+        assert!(UniversalAddress::from_alephium_address(&id.to_solana()).is_err());
+        let mut multisig = [2u8; 33];
+        multisig[1..].copy_from_slice(&id.0);
+        assert!(UniversalAddress::from_alephium_address(&base58::encode(&multisig)).is_err());
+    }
+
+    #[test]
+    fn test_padding() {
+        let mut address = address("35743074956c710800e83198011ccbd4ddf1556d");
+        assert!(address.to_evm().is_ok());
+        assert!(address.to_terra().is_ok());
+
+        address.0[11] = 1;
+        assert!(address.to_evm().is_err());
+        assert!(address.to_terra().is_err());
+        assert_eq!(address.to_native(21).unwrap().len(), 21);
+        assert!(UniversalAddress::from_native(&[1; 33]).is_err());
+    }
+
+    #[test]
+    fn test_chain_dispatch() {
+        let address = address(&"ab".repeat(32));
+        let id = address.to_alephium();
+        assert_eq!(UniversalAddress::from_alephium(&id).unwrap(), address);
+        assert!(UniversalAddress::from_alephium(&id[2..]).is_err());
+
+        for chain in [Chain::Solana, Chain::Ethereum, Chain::Terra, Chain::Alephium].iter() {
+            let native = UniversalAddress::from_native(&[7; 20]).unwrap();
+            let text = native.format(chain).unwrap();
+            assert_eq!(UniversalAddress::parse(chain, &text).unwrap(), native);
+        }
+        assert!(address.format(&Chain::Unknown(1000)).is_err());
+    }
+}
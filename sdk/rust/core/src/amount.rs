@@ -0,0 +1,214 @@
+//! Token bridge amounts travel with at most 8 decimals, whatever the precision of the token on
+//! either side. Outgoing amounts are normalized by dropping the digits past the 8th decimal,
+//! incoming ones are denormalized to the precision of the local token. Both directions use checked
+//! math, so an amount a local token cannot represent is rejected instead of wrapping or panicking.
+
+use primitive_types::U256;
+
+use crate::WormholeError::{
+    AmountTooLarge,
+    DustAmount,
+};
+use crate::{
+    require,
+    WormholeError,
+};
+
+/// Decimals token bridge amounts are carried with.
+pub const MAX_DECIMALS: u8 = 8;
+
+/// Factor between the smallest unit of a token with `decimals` and the smallest unit on the wire,
+/// 1 for tokens with no more than `MAX_DECIMALS`.
+pub fn decimal_factor(decimals: u8) -> Result<u128, WormholeError> {
+    let shift = decimals.saturating_sub(MAX_DECIMALS) as u32;
+    10u128.checked_pow(shift).ok_or(AmountTooLarge)
+}
+
+/// An outgoing amount split into what is sent over the wire and what leaves the sender.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NormalizedAmount {
+    /// The amount with `MAX_DECIMALS`, as carried by the transfer payload.
+    pub normalized: u128,
+
+    /// The amount with the dust past `MAX_DECIMALS` dropped, in the token's own decimals. Only this
+    /// much should be taken from the sender, so the dust is not burned.
+    pub truncated: u128,
+}
+
+/// Normalize an outgoing amount of a token with `decimals`. Fails with `DustAmount` if nothing is
+/// left to send, which also rejects an amount of zero.
+pub fn normalize_amount(amount: u128, decimals: u8) -> Result<NormalizedAmount, WormholeError> {
+    let factor = decimal_factor(decimals)?;
+    let normalized = amount / factor;
+    require!(normalized != 0, DustAmount);
+    Ok(NormalizedAmount {
+        normalized,
+        truncated: normalized * factor,
+    })
+}
+
+/// Normalize an outgoing fee of a token with `decimals`. Unlike the amount a fee may be zero, so
+/// any dust is dropped in the sender's favour.
+pub fn normalize_fee(fee: u128, decimals: u8) -> Result<u128, WormholeError> {
+    Ok(fee / decimal_factor(decimals)?)
+}
+
+/// Denormalize an incoming amount, such as the amount or fee of a transfer payload, to a token with
+/// `decimals`. Fails with `AmountTooLarge` if the result does not fit 128 bits, callers storing
+/// amounts in narrower integers must check the result against their own bound.
+pub fn denormalize_amount(amount: U256, decimals: u8) -> Result<u128, WormholeError> {
+    require!(amount.bits() <= 128, AmountTooLarge);
+    amount
+        .low_u128()
+        .checked_mul(decimal_factor(decimals)?)
+        .ok_or(AmountTooLarge)
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_decimal_factor() {
+        for decimals in 0..=MAX_DECIMALS {
+            assert_eq!(decimal_factor(decimals).unwrap(), 1);
+        }
+        for decimals in MAX_DECIMALS + 1..=46 {
+            let factor = 10u128.pow((decimals - MAX_DECIMALS) as u32);
+            assert_eq!(decimal_factor(decimals).unwrap(), factor);
+        }
+
+        // 10^39 is past u128::MAX.
+        for decimals in 47..=u8::MAX {
+            assert!(matches!(decimal_factor(decimals), Err(AmountTooLarge)));
+        }
+    }
+
+    #[test]
+    fn test_normalize_amount() {
+        // Tokens with up to 8 decimals are sent as is.
+        for decimals in 0..=MAX_DECIMALS {
+            let amount = normalize_amount(12_345, decimals).unwrap();
+            assert_eq!(amount.normalized, 12_345);
+            assert_eq!(amount.truncated, 12_345);
+        }
+
+        let amount = normalize_amount(5_000_000_123, 9).unwrap();
+        assert_eq!(amount.normalized, 500_000_012);
+        assert_eq!(amount.truncated, 5_000_000_120);
+
+        let amount = normalize_amount(1_234_567_890_123_456_789, 18).unwrap();
+        assert_eq!(amount.normalized, 123_456_789);
+        assert_eq!(amount.truncated, 1_234_567_890_000_000_000);
+
+        let amount = normalize_amount(u128::MAX, 18).unwrap();
+        assert_eq!(amount.normalized, u128::MAX / 10_000_000_000);
+        assert_eq!(amount.truncated, u128::MAX - u128::MAX % 10_000_000_000);
+
+        let amount = normalize_amount(u128::MAX, 46).unwrap();
+        assert_eq!(amount.normalized, 3);
+        assert_eq!(amount.truncated, 3 * 10u128.pow(38));
+    }
+
+    #[test]
+    fn test_normalize_dust() {
+        assert!(matches!(normalize_amount(0, 8), Err(DustAmount)));
+        assert!(matches!(normalize_amount(0, 18), Err(DustAmount)));
+        assert!(matches!(normalize_amount(9, 9), Err(DustAmount)));
+        assert!(matches!(
+            normalize_amount(9_999_999_999, 18),
+            Err(DustAmount)
+        ));
+        assert!(normalize_amount(10_000_000_000, 18).is_ok());
+        assert!(matches!(
+            normalize_amount(u128::MAX, 47),
+            Err(AmountTooLarge)
+        ));
+    }
+
+    #[test]
+    fn test_normalize_fee() {
+        assert_eq!(normalize_fee(0, 18).unwrap(), 0);
+        assert_eq!(normalize_fee(9, 9).unwrap(), 0);
+        assert_eq!(normalize_fee(1_000_000_000, 9).unwrap(), 100_000_000);
+        assert_eq!(normalize_fee(123, 8).unwrap(), 123);
+        assert!(matches!(normalize_fee(1, 47), Err(AmountTooLarge)));
+    }
+
+    #[test]
+    fn test_denormalize_amount() {
+        for decimals in 0..=MAX_DECIMALS {
+            assert_eq!(
+                denormalize_amount(U256::from(12_345), decimals).unwrap(),
+                12_345
+            );
+        }
+        assert_eq!(
+            denormalize_amount(U256::from(500_000_012), 9).unwrap(),
+            5_000_000_120
+        );
+        assert_eq!(
+            denormalize_amount(U256::from(123_456_789), 18).unwrap(),
+            1_234_567_890_000_000_000
+        );
+        assert_eq!(
+            denormalize_amount(U256::from(u128::MAX), 8).unwrap(),
+            u128::MAX
+        );
+        assert_eq!(denormalize_amount(U256::zero(), 46).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_denormalize_overflow() {
+        // Amounts past 128 bits, however little of the high half is set.
+        let amount = U256::from(u128::MAX) + 1;
+        assert!(matches!(denormalize_amount(amount, 8), Err(AmountTooLarge)));
+        assert!(matches!(
+            denormalize_amount(U256::max_value(), 0),
+            Err(AmountTooLarge)
+        ));
+
+        // Amounts that fit 128 bits until scaled up.
+        let amount = U256::from(u128::MAX / 10 + 1);
+        assert!(matches!(denormalize_amount(amount, 9), Err(AmountTooLarge)));
+        assert!(denormalize_amount(U256::from(u128::MAX / 10), 9).is_ok());
+        assert!(matches!(
+            denormalize_amount(U256::from(4), 46),
+            Err(AmountTooLarge)
+        ));
+        assert!(denormalize_amount(U256::from(3), 46).is_ok());
+        assert!(matches!(
+            denormalize_amount(U256::from(1), 47),
+            Err(AmountTooLarge)
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // Whatever is normalized denormalizes to exactly what was taken from the sender.
+        let amounts = [
+            1u128,
+            7,
+            99,
+            100_000_001,
+            5_000_000_123,
+            u64::MAX as u128,
+            u128::MAX,
+        ];
+        for decimals in 0..=46 {
+            for &amount in amounts.iter() {
+                let normalized = match normalize_amount(amount, decimals) {
+                    Ok(normalized) => normalized,
+                    Err(DustAmount) => continue,
+                    Err(e) => panic!("{:?}", e),
+                };
+                assert!(normalized.truncated <= amount);
+                assert!(amount - normalized.truncated < decimal_factor(decimals).unwrap());
+                assert_eq!(
+                    denormalize_amount(U256::from(normalized.normalized), decimals).unwrap(),
+                    normalized.truncated
+                );
+            }
+        }
+    }
+}
@@ -1,40 +1,97 @@
 //! Exposes an API implementation depending on which feature flags have been toggled for the
 //! library. Check submodules for chain runtime specific documentation.
-use std::convert::TryFrom; // Remove in 2021
 
 
 /// Chain contains a mapping of Wormhole supported chains to their u16 representation. These are
 /// universally defined among all Wormhole contracts.
-#[repr(u16)]
+///
+/// Payloads refer to tokens and recipients on any chain, including chains added to Wormhole after
+/// this crate was built. IDs without a name here are carried as `Unknown` rather than rejected, so
+/// every parser accepts the same payloads regardless of how current its chain list is.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Chain {
-    All      = 0,
-    Solana   = 1,
-    Ethereum = 2,
-    Terra    = 3,
-    Binance  = 4,
-    Polygon  = 5,
-    AVAX     = 6,
-    Oasis    = 7,
+    All,
+    Solana,
+    Ethereum,
+    Terra,
+    Binance,
+    Polygon,
+    AVAX,
+    Oasis,
+    Alephium,
+    Unknown(u16),
 }
 
-impl TryFrom<u16> for Chain {
-    type Error = ();
-    fn try_from(other: u16) -> Result<Chain, Self::Error> {
+impl From<u16> for Chain {
+    fn from(other: u16) -> Chain {
         match other {
-            0 => Ok(Chain::All),
-            1 => Ok(Chain::Solana),
-            2 => Ok(Chain::Ethereum),
-            3 => Ok(Chain::Terra),
-            4 => Ok(Chain::Binance),
-            5 => Ok(Chain::Polygon),
-            6 => Ok(Chain::AVAX),
-            7 => Ok(Chain::Oasis),
-            _ => Err(()),
+            0 => Chain::All,
+            1 => Chain::Solana,
+            2 => Chain::Ethereum,
+            3 => Chain::Terra,
+            4 => Chain::Binance,
+            5 => Chain::Polygon,
+            6 => Chain::AVAX,
+            7 => Chain::Oasis,
+            13 => Chain::Alephium,
+            c => Chain::Unknown(c),
         }
     }
 }
 
+impl From<Chain> for u16 {
+    fn from(other: Chain) -> u16 {
+        match other {
+            Chain::All => 0,
+            Chain::Solana => 1,
+            Chain::Ethereum => 2,
+            Chain::Terra => 3,
+            Chain::Binance => 4,
+            Chain::Polygon => 5,
+            Chain::AVAX => 6,
+            Chain::Oasis => 7,
+            Chain::Alephium => 13,
+            Chain::Unknown(c) => c,
+        }
+    }
+}
+
+impl Chain {
+    /// A lowercase name for the chain, as used in JSON.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Chain::All => "all",
+            Chain::Solana => "solana",
+            Chain::Ethereum => "ethereum",
+            Chain::Terra => "terra",
+            Chain::Binance => "binance",
+            Chain::Polygon => "polygon",
+            Chain::AVAX => "avalanche",
+            Chain::Oasis => "oasis",
+            Chain::Alephium => "alephium",
+            Chain::Unknown(_) => "unknown",
+        }
+    }
+
+    /// The chain called `name`, the inverse of `name`. Unknown chains have no name of their own
+    /// so are never returned.
+    pub fn from_name(name: &str) -> Option<Chain> {
+        match name {
+            "all" => Some(Chain::All),
+            "solana" => Some(Chain::Solana),
+            "ethereum" => Some(Chain::Ethereum),
+            "terra" => Some(Chain::Terra),
+            "binance" => Some(Chain::Binance),
+            "polygon" => Some(Chain::Polygon),
+            "avalanche" => Some(Chain::AVAX),
+            "oasis" => Some(Chain::Oasis),
+            "alephium" => Some(Chain::Alephium),
+            _ => None,
+        }
+    }
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` on variants needs a newer compiler than BPF's.
 impl Default for Chain {
     fn default() -> Self {
         Self::All
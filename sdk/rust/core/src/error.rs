@@ -17,6 +17,10 @@ pub enum WormholeError {
     InvalidGovernanceAction,
     InvalidGovernanceChain,
     InvalidGovernanceModule,
+    InvalidAddress,
+    InvalidPayload,
+    AmountTooLarge,
+    DustAmount,
     DeserializeFailed,
     ParseError(ErrorCode),
 }
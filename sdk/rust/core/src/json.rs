@@ -0,0 +1,345 @@
+//! Serde support for VAAs and payloads, enabled by the `serde` feature. The representation is
+//! meant for JSON and is stable, services that log or serve Wormhole data should produce the same
+//! documents:
+//!
+//! - Addresses, including `UniversalAddress`, keys, signatures and raw payloads are lowercase hex
+//!   strings without `0x`.
+//! - U256 values are decimal strings, they do not fit in JSON numbers.
+//! - Chains are `{"id": 2, "name": "ethereum"}`. The ID is what counts, the name may be left out
+//!   on input but has to match the ID when given. IDs this crate does not know are `"unknown"`.
+//! - Every other field keeps its Rust name and type.
+//! - Payloads carry a `"type"` tag: `token_transfer`, `asset_meta`, `nft_transfer`,
+//!   `nft_transfer_v2`, `nft_batch_transfer`, `nft_semi_fungible_transfer` and
+//!   `alephium_transfer` hold the payload fields next to the tag, governance types hold a `header`
+//!   and an `action`, and `unknown` holds the raw `payload`.
+//! - A VAA holds its raw `payload` and, on output, the `decoded` result of `Payload::classify`.
+//!   The raw payload is authoritative and `decoded` is ignored on input, so a VAA always goes
+//!   back to the same wire bytes.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "guardian_set_index": 0,
+//!   "signatures": ["00b072...9801"],
+//!   "timestamp": 1000,
+//!   "nonce": 7,
+//!   "emitter_chain": {"id": 2, "name": "ethereum"},
+//!   "emitter_address": "0000...0004",
+//!   "sequence": 5,
+//!   "consistency_level": 15,
+//!   "payload": "01000000...",
+//!   "decoded": {
+//!     "type": "token_transfer",
+//!     "amount": "1000",
+//!     "token_address": "0000...0001",
+//!     "token_chain": {"id": 2, "name": "ethereum"},
+//!     "to": "0000...0002",
+//!     "to_chain": {"id": 1, "name": "solana"},
+//!     "fee": "0"
+//!   }
+//! }
+//! ```
+
+use alloc::string::{
+    String,
+    ToString,
+};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use primitive_types::U256;
+use serde::de::Error;
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+
+use crate::address::{
+    decode_hex,
+    encode_hex,
+    UniversalAddress,
+};
+use crate::vaa::{
+    alephium,
+    core as governance,
+    nft,
+    token,
+    ForeignAddress,
+    GovHeader,
+    Signature,
+};
+use crate::{
+    Chain,
+    Payload,
+    VAA,
+};
+
+/// Byte strings and fixed size byte arrays as hex.
+pub(crate) mod hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: AsRef<[u8]>>(bytes: &T, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&encode_hex(bytes.as_ref()))
+    }
+
+    pub fn deserialize<'de, D, T>(d: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        let s = String::deserialize(d)?;
+        let bytes = decode_hex(&s).ok_or_else(|| D::Error::custom("invalid hex string"))?;
+        T::try_from(bytes).map_err(|_| D::Error::custom("hex string has the wrong length"))
+    }
+}
+
+/// Lists of fixed size byte arrays, such as signatures and guardian keys, as lists of hex.
+pub(crate) mod hex_list {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: AsRef<[u8]>>(list: &[T], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(list.iter().map(|bytes| encode_hex(bytes.as_ref())))
+    }
+
+    pub fn deserialize<'de, D, T>(d: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        Vec::<String>::deserialize(d)?
+            .iter()
+            .map(|s| {
+                let bytes = decode_hex(s).ok_or_else(|| D::Error::custom("invalid hex string"))?;
+                T::try_from(bytes).map_err(|_| D::Error::custom("hex string has the wrong length"))
+            })
+            .collect()
+    }
+}
+
+/// U256 values as decimal strings.
+pub(crate) mod u256 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &U256, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<U256, D::Error> {
+        let s = String::deserialize(d)?;
+        U256::from_dec_str(&s).map_err(|_| D::Error::custom("invalid decimal uint256"))
+    }
+}
+
+impl Serialize for UniversalAddress {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        hex::serialize(&self.0, s)
+    }
+}
+
+impl<'de> Deserialize<'de> for UniversalAddress {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        hex::deserialize(d).map(UniversalAddress)
+    }
+}
+
+#[derive(Serialize)]
+struct ChainRef {
+    id:   u16,
+    name: &'static str,
+}
+
+#[derive(Deserialize)]
+struct ChainJson {
+    id:   u16,
+    name: Option<String>,
+}
+
+impl Serialize for Chain {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        ChainRef {
+            id:   u16::from(self.clone()),
+            name: self.name(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Chain {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let json = ChainJson::deserialize(d)?;
+        let chain = Chain::from(json.id);
+        match json.name {
+            Some(name) if name != chain.name() => Err(D::Error::custom("chain name does not match id")),
+            _ => Ok(chain),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PayloadJson {
+    TokenTransfer(token::Transfer),
+    AssetMeta(token::AssetMeta),
+    NftTransfer(nft::Transfer),
+    NftTransferV2(nft::TransferV2),
+    NftBatchTransfer(nft::BatchTransfer),
+    NftSemiFungibleTransfer(nft::SemiFungibleTransfer),
+    AlephiumTransfer(alephium::Transfer),
+    CoreContractUpgrade {
+        header: GovHeader,
+        action: governance::GovernanceContractUpgrade,
+    },
+    GuardianSetChange {
+        header: GovHeader,
+        action: governance::GovernanceGuardianSetChange,
+    },
+    SetMessageFee {
+        header: GovHeader,
+        action: governance::GovernanceSetMessageFee,
+    },
+    TransferFees {
+        header: GovHeader,
+        action: governance::GovernanceTransferFees,
+    },
+    TokenRegisterChain {
+        header: GovHeader,
+        action: token::GovernanceRegisterChain,
+    },
+    TokenContractUpgrade {
+        header: GovHeader,
+        action: token::GovernanceContractUpgrade,
+    },
+    NftRegisterChain {
+        header: GovHeader,
+        action: nft::GovernanceRegisterChain,
+    },
+    NftContractUpgrade {
+        header: GovHeader,
+        action: nft::GovernanceContractUpgrade,
+    },
+    Unknown {
+        #[serde(with = "hex")]
+        payload: Vec<u8>,
+    },
+}
+
+impl From<Payload> for PayloadJson {
+    fn from(payload: Payload) -> Self {
+        use PayloadJson as J;
+        match payload {
+            Payload::TokenTransfer(transfer) => J::TokenTransfer(transfer),
+            Payload::AssetMeta(meta) => J::AssetMeta(meta),
+            Payload::NftTransfer(transfer) => J::NftTransfer(transfer),
+            Payload::NftTransferV2(transfer) => J::NftTransferV2(transfer),
+            Payload::NftBatchTransfer(transfer) => J::NftBatchTransfer(transfer),
+            Payload::NftSemiFungibleTransfer(transfer) => J::NftSemiFungibleTransfer(transfer),
+            Payload::AlephiumTransfer(transfer) => J::AlephiumTransfer(transfer),
+            Payload::CoreContractUpgrade(header, action) => J::CoreContractUpgrade { header, action },
+            Payload::GuardianSetChange(header, action) => J::GuardianSetChange { header, action },
+            Payload::SetMessageFee(header, action) => J::SetMessageFee { header, action },
+            Payload::TransferFees(header, action) => J::TransferFees { header, action },
+            Payload::TokenRegisterChain(header, action) => J::TokenRegisterChain { header, action },
+            Payload::TokenContractUpgrade(header, action) => {
+                J::TokenContractUpgrade { header, action }
+            }
+            Payload::NftRegisterChain(header, action) => J::NftRegisterChain { header, action },
+            Payload::NftContractUpgrade(header, action) => J::NftContractUpgrade { header, action },
+            Payload::Unknown(payload) => J::Unknown { payload },
+        }
+    }
+}
+
+impl From<PayloadJson> for Payload {
+    fn from(json: PayloadJson) -> Self {
+        use PayloadJson as J;
+        match json {
+            J::TokenTransfer(transfer) => Payload::TokenTransfer(transfer),
+            J::AssetMeta(meta) => Payload::AssetMeta(meta),
+            J::NftTransfer(transfer) => Payload::NftTransfer(transfer),
+            J::NftTransferV2(transfer) => Payload::NftTransferV2(transfer),
+            J::NftBatchTransfer(transfer) => Payload::NftBatchTransfer(transfer),
+            J::NftSemiFungibleTransfer(transfer) => Payload::NftSemiFungibleTransfer(transfer),
+            J::AlephiumTransfer(transfer) => Payload::AlephiumTransfer(transfer),
+            J::CoreContractUpgrade { header, action } => Payload::CoreContractUpgrade(header, action),
+            J::GuardianSetChange { header, action } => Payload::GuardianSetChange(header, action),
+            J::SetMessageFee { header, action } => Payload::SetMessageFee(header, action),
+            J::TransferFees { header, action } => Payload::TransferFees(header, action),
+            J::TokenRegisterChain { header, action } => Payload::TokenRegisterChain(header, action),
+            J::TokenContractUpgrade { header, action } => {
+                Payload::TokenContractUpgrade(header, action)
+            }
+            J::NftRegisterChain { header, action } => Payload::NftRegisterChain(header, action),
+            J::NftContractUpgrade { header, action } => Payload::NftContractUpgrade(header, action),
+            J::Unknown { payload } => Payload::Unknown(payload),
+        }
+    }
+}
+
+impl Serialize for Payload {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        PayloadJson::from(self.clone()).serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Payload {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        PayloadJson::deserialize(d).map(Payload::from)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct VAAJson {
+    version:            u8,
+    guardian_set_index: u32,
+    #[serde(with = "hex_list")]
+    signatures:         Vec<Signature>,
+    timestamp:          u32,
+    nonce:              u32,
+    emitter_chain:      Chain,
+    #[serde(with = "hex")]
+    emitter_address:    ForeignAddress,
+    sequence:           u64,
+    consistency_level:  u8,
+    #[serde(with = "hex")]
+    payload:            Vec<u8>,
+    #[serde(default, skip_deserializing)]
+    decoded:            Option<Payload>,
+}
+
+impl Serialize for VAA {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        VAAJson {
+            version:            self.version,
+            guardian_set_index: self.guardian_set_index,
+            signatures:         self.signatures.clone(),
+            timestamp:          self.timestamp,
+            nonce:              self.nonce,
+            emitter_chain:      self.emitter_chain.clone(),
+            emitter_address:    self.emitter_address,
+            sequence:           self.sequence,
+            consistency_level:  self.consistency_level,
+            payload:            self.payload.clone(),
+            decoded:            Some(Payload::classify(self)),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for VAA {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let json = VAAJson::deserialize(d)?;
+        Ok(VAA {
+            version:            json.version,
+            guardian_set_index: json.guardian_set_index,
+            signatures:         json.signatures,
+            timestamp:          json.timestamp,
+            nonce:              json.nonce,
+            emitter_chain:      json.emitter_chain,
+            emitter_address:    json.emitter_address,
+            sequence:           json.sequence,
+            consistency_level:  json.consistency_level,
+            payload:            json.payload,
+        })
+    }
+}
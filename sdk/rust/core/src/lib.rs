@@ -1,11 +1,33 @@
 #![deny(unused_results)]
+#![no_std]
 
+//! Wormhole wire formats and parsers. The crate is `no_std` and only needs `alloc`, so it can be
+//! used from on-chain programs and other constrained targets as well as from regular clients.
+
+extern crate alloc;
+
+#[cfg(test)]
+extern crate std;
+
+pub use address::*;
+pub use amount::*;
 pub use chain::*;
 pub use error::*;
+pub use payload::*;
 pub use vaa::*;
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 
+pub mod address;
+pub mod amount;
 pub mod chain;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod payload;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod vaa;
 
 #[macro_use]
@@ -19,13 +41,9 @@ pub mod error;
 /// This should be used to parse any Text-over-Wormhole fields that are meant to be human readable.
 pub(crate) fn parse_fixed_utf8<T: AsRef<[u8]>, const N: usize>(s: T) -> Option<String> {
     use bstr::ByteSlice;
-    use std::io::Cursor;
-    use std::io::Read;
 
     // Read Bytes.
-    let mut cursor = Cursor::new(s.as_ref());
-    let mut buffer = vec![0u8; N];
-    cursor.read_exact(&mut buffer).ok()?;
+    let mut buffer = s.as_ref().get(..N)?.to_vec();
     buffer.retain(|&c| c != 0);
 
     // Attempt UTF-8 Decoding. Stripping invalid Unicode characters (0xFFFD).
@@ -34,3 +52,22 @@ pub(crate) fn parse_fixed_utf8<T: AsRef<[u8]>, const N: usize>(s: T) -> Option<S
 
     Some(buffer.iter().collect())
 }
+
+/// Counterpart to `parse_fixed_utf8`, writes `s` into an `N` byte field. Shorter strings are right
+/// padded with zeroes, longer ones are truncated to the last character boundary within `N` bytes.
+pub(crate) fn write_fixed_utf8<const N: usize>(s: &str) -> [u8; N] {
+    let mut buffer = [0u8; N];
+    let len = truncate_utf8(s, N).len();
+    buffer[..len].copy_from_slice(&s.as_bytes()[..len]);
+    buffer
+}
+
+/// Longest prefix of `s` that is at most `max` bytes and ends on a character boundary. Encoders use
+/// this to fit strings into length limited fields without splitting a character.
+pub fn truncate_utf8(s: &str, max: usize) -> &str {
+    let mut len = s.len().min(max);
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    &s[..len]
+}
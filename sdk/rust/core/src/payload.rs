@@ -0,0 +1,388 @@
+//! Generic payload decoding. A VAA does not say which format its payload follows, so consumers that
+//! see arbitrary VAAs, such as explorers and relayers, can use `Payload::classify` to recognize the
+//! standard Wormhole payloads instead of picking a parser up front.
+
+use alloc::vec::Vec;
+
+use crate::vaa::{
+    alephium,
+    core,
+    governance_module,
+    nft,
+    token,
+    ForeignAddress,
+    GovHeader,
+    GovernanceAction,
+    VAA,
+};
+use crate::Chain;
+
+/// The kind of emitter a VAA comes from, which fixes the payload formats it can carry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Emitter {
+    /// The governance emitter, signs Core, TokenBridge and NFTBridge governance actions.
+    Governance,
+    TokenBridge,
+    NftBridge,
+}
+
+/// A set of known emitters. Payloads are only classified by their content, so any emitter can
+/// produce bytes that look like a token transfer. Classifying against a registry only decodes
+/// payloads from registered emitters, and only with the formats their kind emits.
+#[derive(Clone, Debug, Default)]
+pub struct EmitterRegistry {
+    emitters: Vec<(Chain, ForeignAddress, Emitter)>,
+}
+
+impl EmitterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an emitter, replacing any kind it was registered with before.
+    pub fn register(&mut self, chain: Chain, address: ForeignAddress, kind: Emitter) -> &mut Self {
+        self.emitters.retain(|(c, a, _)| !(*c == chain && *a == address));
+        self.emitters.push((chain, address, kind));
+        self
+    }
+
+    pub fn lookup(&self, chain: &Chain, address: &ForeignAddress) -> Option<Emitter> {
+        self.emitters
+            .iter()
+            .find(|(c, a, _)| c == chain && a == address)
+            .map(|(_, _, kind)| *kind)
+    }
+}
+
+/// A decoded VAA payload. Governance actions keep their header, which carries the chain the action
+/// is addressed to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Payload {
+    TokenTransfer(token::Transfer),
+    AssetMeta(token::AssetMeta),
+    NftTransfer(nft::Transfer),
+    NftTransferV2(nft::TransferV2),
+    NftBatchTransfer(nft::BatchTransfer),
+    NftSemiFungibleTransfer(nft::SemiFungibleTransfer),
+    AlephiumTransfer(alephium::Transfer),
+    CoreContractUpgrade(GovHeader, core::GovernanceContractUpgrade),
+    GuardianSetChange(GovHeader, core::GovernanceGuardianSetChange),
+    SetMessageFee(GovHeader, core::GovernanceSetMessageFee),
+    TransferFees(GovHeader, core::GovernanceTransferFees),
+    TokenRegisterChain(GovHeader, token::GovernanceRegisterChain),
+    TokenContractUpgrade(GovHeader, token::GovernanceContractUpgrade),
+    NftRegisterChain(GovHeader, nft::GovernanceRegisterChain),
+    NftContractUpgrade(GovHeader, nft::GovernanceContractUpgrade),
+
+    /// Any payload that none of the standard formats parse.
+    Unknown(Vec<u8>),
+}
+
+impl Payload {
+    /// Decode the payload of a VAA from any emitter. Governance actions are recognized by their
+    /// module and action, other payloads by their payload ID.
+    ///
+    /// Token and NFT transfers share payload ID 1. They are told apart by length, a token transfer
+    /// is always 133 bytes while an NFT transfer is at least 166. Token transfers emitted on
+    /// Alephium are 166 bytes, and are recognized by their emitter chain instead.
+    ///
+    /// Asset metadata and version 2 NFT transfers share payload ID 2. Asset metadata is always 100
+    /// bytes while a version 2 NFT transfer is at least 140. Payload IDs 3 and 4 are only used by
+    /// batched and semi-fungible NFT transfers.
+    pub fn classify(vaa: &VAA) -> Self {
+        Self::decode(vaa, None)
+    }
+
+    /// Decode the payload of a VAA from a registered emitter, using only the formats that emitter
+    /// produces. Payloads from unregistered emitters are `Unknown`.
+    pub fn classify_with(vaa: &VAA, registry: &EmitterRegistry) -> Self {
+        match registry.lookup(&vaa.emitter_chain, &vaa.emitter_address) {
+            Some(kind) => Self::decode(vaa, Some(kind)),
+            None => Payload::Unknown(vaa.payload.clone()),
+        }
+    }
+
+    /// Serialize to Wormhole wire format. Governance actions are addressed to the chain in their
+    /// header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Payload::TokenTransfer(transfer) => transfer.to_bytes(),
+            Payload::AssetMeta(meta) => meta.to_bytes(),
+            Payload::NftTransfer(transfer) => transfer.to_bytes(),
+            Payload::NftTransferV2(transfer) => transfer.to_bytes(),
+            Payload::NftBatchTransfer(transfer) => transfer.to_bytes(),
+            Payload::NftSemiFungibleTransfer(transfer) => transfer.to_bytes(),
+            Payload::AlephiumTransfer(transfer) => transfer.to_bytes(),
+            Payload::CoreContractUpgrade(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::GuardianSetChange(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::SetMessageFee(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::TransferFees(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::TokenRegisterChain(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::TokenContractUpgrade(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::NftRegisterChain(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::NftContractUpgrade(header, action) => action.to_bytes(header.chains.clone()),
+            Payload::Unknown(payload) => payload.clone(),
+        }
+    }
+
+    fn decode(vaa: &VAA, kind: Option<Emitter>) -> Self {
+        let payload = &vaa.payload[..];
+        let from = |emitter| kind.is_none() || kind == Some(emitter);
+        let alephium = vaa.emitter_chain == Chain::Alephium;
+        let decoded = match payload.first() {
+            _ if from(Emitter::Governance) && is_governance(payload) => governance(payload),
+            Some(1) if from(Emitter::TokenBridge) && alephium && payload.len() == 166 => {
+                alephium::Transfer::from_bytes(payload)
+                    .ok()
+                    .map(Payload::AlephiumTransfer)
+            }
+            Some(1) if from(Emitter::TokenBridge) && payload.len() == 133 => {
+                token::Transfer::from_bytes(payload)
+                    .ok()
+                    .map(Payload::TokenTransfer)
+            }
+            Some(1) if from(Emitter::NftBridge) => {
+                nft::Transfer::from_bytes(payload)
+                    .ok()
+                    .map(Payload::NftTransfer)
+            }
+            Some(2) if from(Emitter::TokenBridge) && payload.len() == 100 => {
+                token::AssetMeta::from_bytes(payload)
+                    .ok()
+                    .map(Payload::AssetMeta)
+            }
+            Some(2) if from(Emitter::NftBridge) => {
+                nft::TransferV2::from_bytes(payload)
+                    .ok()
+                    .map(Payload::NftTransferV2)
+            }
+            Some(3) if from(Emitter::NftBridge) => {
+                nft::BatchTransfer::from_bytes(payload)
+                    .ok()
+                    .map(Payload::NftBatchTransfer)
+            }
+            Some(4) if from(Emitter::NftBridge) => {
+                nft::SemiFungibleTransfer::from_bytes(payload)
+                    .ok()
+                    .map(Payload::NftSemiFungibleTransfer)
+            }
+            _ => None,
+        };
+        decoded.unwrap_or_else(|| Payload::Unknown(payload.to_vec()))
+    }
+}
+
+const MODULES: [&[u8]; 3] = [b"Core", b"TokenBridge", b"NFTBridge"];
+
+fn is_governance(payload: &[u8]) -> bool {
+    payload.len() >= 32 && MODULES.iter().any(|m| governance_module(m) == payload[..32])
+}
+
+fn governance(payload: &[u8]) -> Option<Payload> {
+    fn action<A: GovernanceAction>(
+        payload: &[u8],
+        variant: fn(GovHeader, A) -> Payload,
+    ) -> Option<Payload> {
+        let (header, action) = A::from_bytes(payload, None).ok()?;
+        Some(variant(header, action))
+    }
+
+    let (header, _) = GovHeader::from_bytes(payload).ok()?;
+    let module = MODULES.iter().position(|m| governance_module(m) == header.module)?;
+    match (module, header.action) {
+        (0, 1) => action(payload, Payload::CoreContractUpgrade),
+        (0, 2) => action(payload, Payload::GuardianSetChange),
+        (0, 3) => action(payload, Payload::SetMessageFee),
+        (0, 4) => action(payload, Payload::TransferFees),
+        (1, 1) => action(payload, Payload::TokenRegisterChain),
+        (1, 2) => action(payload, Payload::TokenContractUpgrade),
+        (2, 1) => action(payload, Payload::NftRegisterChain),
+        (2, 2) => action(payload, Payload::NftContractUpgrade),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    fn vaa(emitter_chain: Chain, emitter_address: ForeignAddress, payload: Vec<u8>) -> VAA {
+        VAA {
+            emitter_chain,
+            emitter_address,
+            payload,
+            ..VAA::default()
+        }
+    }
+
+    fn transfer() -> token::Transfer {
+        token::Transfer {
+            amount:        1000.into(),
+            token_address: [1; 32],
+            token_chain:   Chain::Ethereum,
+            to:            [2; 32],
+            to_chain:      Chain::Solana,
+            fee:           0.into(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let payload = transfer().to_bytes();
+        let decoded = Payload::classify(&vaa(Chain::Ethereum, [3; 32], payload));
+        assert_eq!(decoded, Payload::TokenTransfer(transfer()));
+
+        let action = token::GovernanceRegisterChain {
+            emitter:          Chain::Terra,
+            endpoint_address: [4; 32],
+        };
+        let payload = action.to_bytes(Chain::All);
+        let decoded = Payload::classify(&vaa(Chain::Solana, [5; 32], payload.clone()));
+        let (header, _) = GovHeader::from_bytes(&payload).unwrap();
+        assert_eq!(decoded, Payload::TokenRegisterChain(header, action));
+
+        // A governance header with an action the module does not define.
+        let mut payload = payload;
+        payload[32] = 9;
+        let decoded = Payload::classify(&vaa(Chain::Solana, [5; 32], payload.clone()));
+        assert_eq!(decoded, Payload::Unknown(payload));
+
+        let decoded = Payload::classify(&vaa(Chain::Solana, [5; 32], Vec::new()));
+        assert_eq!(decoded, Payload::Unknown(Vec::new()));
+    }
+
+    #[test]
+    fn test_classify_alephium() {
+        let sent = alephium::Transfer {
+            amount:           1000.into(),
+            token_address:    [1; 32],
+            token_chain:      Chain::Alephium,
+            to:               [2; 32],
+            to_chain:         Chain::Ethereum,
+            fee:              0.into(),
+            is_local_token:   true,
+            token_wrapper_id: [8; 32],
+        };
+        let payload = sent.to_bytes();
+        let decoded = Payload::classify(&vaa(Chain::Alephium, [3; 32], payload.clone()));
+        assert_eq!(decoded, Payload::AlephiumTransfer(sent));
+        assert_eq!(decoded.to_bytes(), payload);
+
+        // The same bytes from another chain are not an Alephium transfer.
+        let decoded = Payload::classify(&vaa(Chain::Ethereum, [3; 32], payload));
+        assert!(!matches!(decoded, Payload::AlephiumTransfer(_)));
+
+        // 133 byte transfers keep the standard layout whatever chain emitted them.
+        let payload = transfer().to_bytes();
+        let decoded = Payload::classify(&vaa(Chain::Alephium, [3; 32], payload));
+        assert_eq!(decoded, Payload::TokenTransfer(transfer()));
+    }
+
+    #[test]
+    fn test_classify_nft_transfer_v2() {
+        let sent = nft::TransferV2 {
+            nft_address:             [1; 32],
+            nft_chain:               Chain::Solana,
+            symbol:                  "NFT".into(),
+            name:                    "Non Fungible".into(),
+            token_id:                7.into(),
+            uri:                     "https://example.com/7.json".into(),
+            collection:              [0; 32],
+            seller_fee_basis_points: 250,
+            creators:                Vec::new(),
+            to:                      [2; 32],
+            to_chain:                Chain::Ethereum,
+        };
+        let payload = sent.to_bytes();
+        let decoded = Payload::classify(&vaa(Chain::Solana, [3; 32], payload.clone()));
+        assert_eq!(decoded, Payload::NftTransferV2(sent));
+        assert_eq!(decoded.to_bytes(), payload);
+
+        // Asset metadata shares payload ID 2 and still classifies by its length.
+        let meta = token::AssetMeta {
+            token_address: [1; 32],
+            token_chain:   Chain::Ethereum,
+            decimals:      18,
+            symbol:        "WETH".into(),
+            name:          "Wrapped Ether".into(),
+        };
+        let decoded = Payload::classify(&vaa(Chain::Ethereum, [3; 32], meta.to_bytes()));
+        assert_eq!(decoded, Payload::AssetMeta(meta));
+    }
+
+    #[test]
+    fn test_classify_nft_batch_transfer() {
+        let sent = nft::BatchTransfer {
+            nft_address: [1; 32],
+            nft_chain:   Chain::Solana,
+            symbol:      "NFT".into(),
+            name:        "Non Fungible".into(),
+            tokens:      alloc::vec![nft::BatchToken {
+                token_id: 7.into(),
+                uri:      "https://example.com/7.json".into(),
+            }],
+            to:          [2; 32],
+            to_chain:    Chain::Ethereum,
+        };
+        let payload = sent.to_bytes();
+        let decoded = Payload::classify(&vaa(Chain::Solana, [3; 32], payload.clone()));
+        assert_eq!(decoded, Payload::NftBatchTransfer(sent));
+        assert_eq!(decoded.to_bytes(), payload);
+
+        // Token bridges do not emit batches.
+        let mut registry = EmitterRegistry::new();
+        let _ = registry.register(Chain::Solana, [3; 32], Emitter::TokenBridge);
+        let decoded =
+            Payload::classify_with(&vaa(Chain::Solana, [3; 32], payload.clone()), &registry);
+        assert_eq!(decoded, Payload::Unknown(payload));
+    }
+
+    #[test]
+    fn test_classify_nft_semi_fungible_transfer() {
+        let sent = nft::SemiFungibleTransfer {
+            nft_address: [1; 32],
+            nft_chain:   Chain::Ethereum,
+            symbol:      "SFT".into(),
+            name:        "Semi Fungible".into(),
+            token_id:    7.into(),
+            amount:      100.into(),
+            uri:         "https://example.com/7.json".into(),
+            to:          [2; 32],
+            to_chain:    Chain::Solana,
+        };
+        let payload = sent.to_bytes();
+        let decoded = Payload::classify(&vaa(Chain::Ethereum, [3; 32], payload.clone()));
+        assert_eq!(decoded, Payload::NftSemiFungibleTransfer(sent));
+        assert_eq!(decoded.to_bytes(), payload);
+
+        // Token bridges do not emit semi-fungible transfers.
+        let mut registry = EmitterRegistry::new();
+        let _ = registry.register(Chain::Ethereum, [3; 32], Emitter::TokenBridge);
+        let decoded =
+            Payload::classify_with(&vaa(Chain::Ethereum, [3; 32], payload.clone()), &registry);
+        assert_eq!(decoded, Payload::Unknown(payload));
+    }
+
+    #[test]
+    fn test_classify_with_registry() {
+        let mut registry = EmitterRegistry::new();
+        let _ = registry
+            .register(Chain::Ethereum, [3; 32], Emitter::TokenBridge)
+            .register(Chain::Ethereum, [6; 32], Emitter::NftBridge);
+
+        let payload = transfer().to_bytes();
+        let vaa_from_bridge = vaa(Chain::Ethereum, [3; 32], payload.clone());
+        let decoded = Payload::classify_with(&vaa_from_bridge, &registry);
+        assert_eq!(decoded, Payload::TokenTransfer(transfer()));
+
+        // The same bytes from an NFT bridge or an unregistered emitter are not a token transfer.
+        for emitter in [[6; 32], [7; 32]].iter() {
+            let vaa = vaa(Chain::Ethereum, *emitter, payload.clone());
+            let decoded = Payload::classify_with(&vaa, &registry);
+            assert_eq!(decoded, Payload::Unknown(payload.clone()));
+        }
+
+        let _ = registry.register(Chain::Ethereum, [3; 32], Emitter::NftBridge);
+        assert_eq!(registry.lookup(&Chain::Ethereum, &[3; 32]), Some(Emitter::NftBridge));
+    }
+}
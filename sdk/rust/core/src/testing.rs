@@ -0,0 +1,18 @@
+//! Helpers for the test suites of crates that parse Wormhole wire formats alongside this one.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A valid payload followed by its mutations: every truncation, a trailing byte, and every single
+/// byte inverted. Parsers that mirror wormhole-core should accept and reject the same ones.
+pub fn corpus(valid: &[u8]) -> Vec<Vec<u8>> {
+    let mut corpus = vec![valid.to_vec()];
+    corpus.extend((0..valid.len()).map(|n| valid[..n].to_vec()));
+    corpus.push([valid, &[0]].concat());
+    for i in 0..valid.len() {
+        let mut bytes = valid.to_vec();
+        bytes[i] ^= 0xff;
+        corpus.push(bytes);
+    }
+    corpus
+}
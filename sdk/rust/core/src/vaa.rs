@@ -9,10 +9,10 @@
 //! includes parsers for the core VAA type. Programs targetting wormhole can use this module to
 //! parse and verify incoming VAA's securely.
 
-use nom::combinator::rest;
-use nom::error::{
-    Error,
-    ErrorKind,
+use nom::combinator::{
+    all_consuming,
+    map,
+    rest,
 };
 use nom::multi::{
     count,
@@ -26,12 +26,11 @@ use nom::number::complete::{
 };
 use nom::number::Endianness;
 use nom::{
-    Err,
     Finish,
     IResult,
 };
-use std::convert::TryFrom;
-use std::str::FromStr; // Remove in 2021
+use alloc::string::String;
+use alloc::vec::Vec;
 
 use crate::WormholeError::{
     InvalidGovernanceAction,
@@ -46,6 +45,7 @@ use crate::{
 
 // Import Module Specific VAAs.
 
+pub mod alephium;
 pub mod core;
 pub mod nft;
 pub mod token;
@@ -72,7 +72,7 @@ type ShortUTFString = String;
 /// The core VAA itself. This structure is what is received by a contract on the receiving side of
 /// a wormhole message passing flow. The payload of the message must be parsed separately to the
 /// VAA itself as it is completely user defined.
-#[derive(Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct VAA {
     // Header
     pub version:            u8,
@@ -105,40 +105,36 @@ impl VAA {
         }
     }
 
+    /// Serialize to Wormhole wire format, the header and signatures followed by the body.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let body = self.body();
+        let mut v = Vec::with_capacity(6 + 66 * self.signatures.len() + body.len());
+        v.push(self.version);
+        v.extend_from_slice(&self.guardian_set_index.to_be_bytes());
+        v.push(self.signatures.len() as u8);
+        for signature in &self.signatures {
+            v.extend_from_slice(signature);
+        }
+        v.extend(body);
+        v
+    }
+
     /// A VAA is distinguished by the unique hash of its deterministic components. This method
     /// returns a 256 bit Keccak hash of these components. This hash is utilised in all Wormhole
     /// components for identifying unique VAA's, including the bridge, modules, and core guardian
     /// software.
     pub fn digest(&self) -> Option<VAADigest> {
-        use byteorder::{
-            BigEndian,
-            WriteBytesExt,
-        };
         use sha3::Digest;
-        use std::io::{
-            Cursor,
-            Write,
-        };
 
         // Hash Deterministic Pieces
-        let body = {
-            let mut v = Cursor::new(Vec::new());
-            v.write_u32::<BigEndian>(self.timestamp).ok()?;
-            v.write_u32::<BigEndian>(self.nonce).ok()?;
-            v.write_u16::<BigEndian>(self.emitter_chain.clone() as u16).ok()?;
-            let _ = v.write(&self.emitter_address).ok()?;
-            v.write_u64::<BigEndian>(self.sequence).ok()?;
-            v.write_u8(self.consistency_level).ok()?;
-            let _ = v.write(&self.payload).ok()?;
-            v.into_inner()
-        };
+        let body = self.body();
 
         // We hash the body so that secp256k1 signatures are signing the hash instead of the body
         // within our contracts. We do this so we don't have to submit the entire VAA for signature
         // verification, only the hash.
         let hash: [u8; 32] = {
             let mut h = sha3::Keccak256::default();
-            let _ = h.write(body.as_slice()).unwrap();
+            h.update(body.as_slice());
             h.finalize().into()
         };
 
@@ -147,6 +143,19 @@ impl VAA {
             hash,
         })
     }
+
+    /// The deterministic part of the VAA, everything after the signatures.
+    fn body(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(51 + self.payload.len());
+        v.extend_from_slice(&self.timestamp.to_be_bytes());
+        v.extend_from_slice(&self.nonce.to_be_bytes());
+        v.extend_from_slice(&u16::from(self.emitter_chain.clone()).to_be_bytes());
+        v.extend_from_slice(&self.emitter_address);
+        v.extend_from_slice(&self.sequence.to_be_bytes());
+        v.push(self.consistency_level);
+        v.extend_from_slice(&self.payload);
+        v
+    }
 }
 
 /// Using nom, parse a fixed array of bytes without any allocation. Useful for parsing addresses,
@@ -159,12 +168,21 @@ pub fn parse_fixed<const S: usize>(input: &[u8]) -> IResult<&[u8], [u8; S]> {
 }
 
 /// Parse a Chain ID, which is a 16 bit numeric ID. The mapping of network to ID is defined by the
-/// Wormhole standard.
+/// Wormhole standard, IDs this crate does not know are parsed as `Chain::Unknown`.
 #[inline]
 pub fn parse_chain(input: &[u8]) -> IResult<&[u8], Chain> {
-    let (i, chain) = u16(Endianness::Big)(input)?;
-    let chain = Chain::try_from(chain).map_err(|_| Err::Error(Error::new(i, ErrorKind::NoneOf)))?;
-    Ok((i, chain))
+    map(u16(Endianness::Big), Chain::from)(input)
+}
+
+/// Run a payload parser over the whole input, trailing bytes are an error.
+pub(crate) fn parse_exact<'a, T>(
+    parser: impl FnMut(&'a [u8]) -> IResult<&'a [u8], T>,
+    input: &'a [u8],
+) -> Result<T, WormholeError> {
+    match all_consuming(parser)(input).finish() {
+        Ok((_, value)) => Ok(value),
+        Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+    }
 }
 
 /// Parse a VAA from a vector of raw bytes. Nom handles situations where the data is either too
@@ -201,12 +219,42 @@ fn parse_vaa(input: &[u8]) -> IResult<&[u8], VAA> {
 
 /// All current Wormhole programs using Governance are prefixed with a Governance header with a
 /// consistent format.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovHeader {
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub module: [u8; 32],
     pub action: u8,
     pub chains: Chain,
 }
 
+impl GovHeader {
+    /// Parse the header of a governance payload, returning it along with the action specific bytes
+    /// that follow it.
+    pub fn from_bytes(input: &[u8]) -> Result<(Self, &[u8]), WormholeError> {
+        match parse_governance_header(input).finish() {
+            Ok((rest, header)) => Ok((header, rest)),
+            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+        }
+    }
+
+    /// Serialize to Wormhole wire format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(35);
+        v.extend_from_slice(&self.module);
+        v.push(self.action);
+        v.extend_from_slice(&u16::from(self.chains.clone()).to_be_bytes());
+        v
+    }
+}
+
+/// Left 0-pad a governance module name to the 32 bytes it takes on the wire.
+pub fn governance_module(name: &[u8]) -> [u8; 32] {
+    let mut module = [0u8; 32];
+    module[32 - name.len()..].copy_from_slice(name);
+    module
+}
+
 pub trait GovernanceAction: Sized {
     const ACTION: u8;
     const MODULE: &'static [u8];
@@ -214,48 +262,55 @@ pub trait GovernanceAction: Sized {
     /// Implement a nom parser for the Action.
     fn parse(input: &[u8]) -> IResult<&[u8], Self>;
 
-    /// Serialize to Wormhole wire format.
-    /// fn serialize(&self) -> Result<Vec<u8>, WormholeError>;
+    /// Serialize the Action to Wormhole wire format, without the governance header.
+    fn serialize(&self) -> Vec<u8>;
 
-    /// Parses an Action from a governance payload securely.
+    /// Parses an Action from a governance payload securely. The whole payload must be consumed.
     fn from_bytes<T: AsRef<[u8]>>(
         input: T,
         chain: Option<Chain>,
     ) -> Result<(GovHeader, Self), WormholeError> {
-        match parse_action(input.as_ref()).finish() {
-            Ok((_, (header, action))) => {
-                // If no Chain is given, we assume All, which implies always valid.
-                let chain = chain.unwrap_or(Chain::All);
-
-                // Left 0-pad the MODULE in case it is unpadded.
-                let mut module = [0u8; 32];
-                let modlen = Self::MODULE.len();
-                (&mut module[32 - modlen..]).copy_from_slice(&Self::MODULE);
-
-                // Verify Governance Data.
-                let valid_chain = chain == header.chains || chain == Chain::All;
-                let valid_action = header.action == Self::ACTION;
-                let valid_module = module == header.module;
-                require!(valid_action, InvalidGovernanceAction);
-                require!(valid_chain, InvalidGovernanceChain);
-                require!(valid_module, InvalidGovernanceModule);
-
-                Ok((header, action))
-            }
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
-        }
+        let (header, action) = parse_exact(parse_action::<Self>, input.as_ref())?;
+
+        // If no Chain is given, we assume All, which implies always valid. Actions addressed to
+        // All are valid on every chain.
+        let chain = chain.unwrap_or(Chain::All);
+
+        // Verify Governance Data.
+        let valid_module = governance_module(Self::MODULE) == header.module;
+        let valid_action = header.action == Self::ACTION;
+        let valid_chain =
+            chain == Chain::All || header.chains == Chain::All || chain == header.chains;
+        require!(valid_module, InvalidGovernanceModule);
+        require!(valid_action, InvalidGovernanceAction);
+        require!(valid_chain, InvalidGovernanceChain);
+
+        Ok((header, action))
+    }
+
+    /// Serialize the Action to Wormhole wire format, behind a governance header addressed to
+    /// `chain`.
+    fn to_bytes(&self, chain: Chain) -> Vec<u8> {
+        let header = GovHeader {
+            module: governance_module(Self::MODULE),
+            action: Self::ACTION,
+            chains: chain,
+        };
+        let mut v = header.to_bytes();
+        v.extend(self.serialize());
+        v
     }
 }
 
 #[inline]
 pub fn parse_action<A: GovernanceAction>(input: &[u8]) -> IResult<&[u8], (GovHeader, A)> {
-    let (i, header) = parse_governance_header(input.as_ref())?;
+    let (i, header) = parse_governance_header(input)?;
     let (i, action) = A::parse(i)?;
     Ok((i, (header, action)))
 }
 
 #[inline]
-pub fn parse_governance_header<'i, 'a>(input: &'i [u8]) -> IResult<&'i [u8], GovHeader> {
+pub fn parse_governance_header(input: &[u8]) -> IResult<&[u8], GovHeader> {
     let (i, module) = parse_fixed(input)?;
     let (i, action) = u8(i)?;
     let (i, chains) = u16(Endianness::Big)(i)?;
@@ -264,7 +319,7 @@ pub fn parse_governance_header<'i, 'a>(input: &'i [u8]) -> IResult<&'i [u8], Gov
         GovHeader {
             module,
             action,
-            chains: Chain::try_from(chains).unwrap(),
+            chains: Chain::from(chains),
         },
     ))
 }
@@ -276,14 +331,10 @@ mod testing {
         Chain,
         VAA,
     };
+    use std::vec::Vec;
 
     #[test]
     fn test_valid_gov_header() {
-        let signers = hex::decode("00b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e9801").unwrap();
-        let payload = hex::decode("000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
-        let emitter =
-            hex::decode("0000000000000000000000000000000000000000000000000000000000000004")
-                .unwrap();
         let module =
             hex::decode("000000000000000000000000000000000000000000546f6b656e427269646765")
                 .unwrap();
@@ -301,7 +352,26 @@ mod testing {
         assert_eq!(header.chains, Chain::All);
     }
 
+    #[test]
+    fn test_valid_gov_vaa() {
+        let signers = hex::decode("00b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e9801").unwrap();
+        let payload = hex::decode("000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let emitter =
+            hex::decode("0000000000000000000000000000000000000000000000000000000000000004")
+                .unwrap();
+
+        // Decode VAA.
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let vaa = VAA::from_bytes(vaa).unwrap();
+
+        // Confirm the envelope of the governance VAA.
+        assert_eq!(vaa.signatures[0][..], signers);
+        assert_eq!(vaa.emitter_address, emitter[..]);
+        assert_eq!(vaa.payload, payload);
+    }
+
     // Legacy VAA Signature Struct.
+    #[allow(dead_code)]
     #[derive(Default, Clone)]
     pub struct VAASignature {
         pub signature:      Vec<u8>,
@@ -314,7 +384,6 @@ mod testing {
             BigEndian,
             ReadBytesExt,
         };
-        use std::convert::TryFrom;
         use std::io::Read;
 
         let mut rdr = std::io::Cursor::new(data);
@@ -334,7 +403,7 @@ mod testing {
         v.signatures = sigs;
         v.timestamp = rdr.read_u32::<BigEndian>()?;
         v.nonce = rdr.read_u32::<BigEndian>()?;
-        v.emitter_chain = Chain::try_from(rdr.read_u16::<BigEndian>()?).unwrap();
+        v.emitter_chain = Chain::from(rdr.read_u16::<BigEndian>()?);
         let mut emitter_address = [0u8; 32];
         rdr.read_exact(&mut emitter_address)?;
         v.emitter_address = emitter_address;
@@ -353,6 +422,33 @@ mod testing {
         assert_eq!(new, old);
     }
 
+    #[test]
+    fn test_digest_parity() {
+        use byteorder::{
+            BigEndian,
+            WriteBytesExt,
+        };
+        use sha3::Digest;
+        use std::io::Write;
+
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        let vaa = VAA::from_bytes(vaa).unwrap();
+
+        // Original digest body, written with byteorder.
+        let mut body = Vec::new();
+        body.write_u32::<BigEndian>(vaa.timestamp).unwrap();
+        body.write_u32::<BigEndian>(vaa.nonce).unwrap();
+        body.write_u16::<BigEndian>(vaa.emitter_chain.clone().into()).unwrap();
+        body.write_all(&vaa.emitter_address).unwrap();
+        body.write_u64::<BigEndian>(vaa.sequence).unwrap();
+        body.write_u8(vaa.consistency_level).unwrap();
+        body.write_all(&vaa.payload).unwrap();
+
+        let digest = vaa.digest().unwrap();
+        assert_eq!(digest.digest, body);
+        assert_eq!(digest.hash[..], sha3::Keccak256::digest(&body)[..]);
+    }
+
     #[test]
     fn test_valid_parse_vaa() {
         let signers = hex::decode("00b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e9801").unwrap();
@@ -382,4 +478,452 @@ mod testing {
     #[test]
     fn test_invalid_vaa() {
     }
+
+    #[test]
+    fn test_unknown_chain() {
+        use super::token::Transfer;
+        use primitive_types::U256;
+
+        let transfer = Transfer {
+            amount:        U256::from(1000),
+            token_address: [1u8; 32],
+            token_chain:   Chain::Unknown(0x2a),
+            to:            [2u8; 32],
+            to_chain:      Chain::Solana,
+            fee:           U256::from(1),
+        };
+        let bytes = transfer.to_bytes();
+        assert_eq!(&bytes[65..67], &[0x00, 0x2a]);
+        assert_eq!(Transfer::from_bytes(&bytes).unwrap(), transfer);
+        assert_eq!(Chain::from(7), Chain::Oasis);
+        assert_eq!(Chain::from(13), Chain::Alephium);
+        assert_eq!(u16::from(Chain::Unknown(0xffff)), 0xffff);
+    }
+
+    #[test]
+    fn test_payload_length() {
+        use super::token::AssetMeta;
+
+        let meta = AssetMeta {
+            token_address: [1u8; 32],
+            token_chain:   Chain::Ethereum,
+            decimals:      8,
+            symbol:        "WETH".into(),
+            name:          "Wrapped Ether".into(),
+        };
+        let bytes = meta.to_bytes();
+        assert_eq!(bytes.len(), 100);
+        assert_eq!(AssetMeta::from_bytes(&bytes).unwrap(), meta);
+
+        // Short and long payloads are both rejected.
+        assert!(AssetMeta::from_bytes(&bytes[..99]).is_err());
+        let mut long = bytes.clone();
+        long.push(0);
+        assert!(AssetMeta::from_bytes(&long).is_err());
+    }
+
+    #[test]
+    fn test_nft_uri() {
+        use super::nft::Transfer;
+        use primitive_types::U256;
+
+        let transfer = Transfer {
+            nft_address: [1u8; 32],
+            nft_chain:   Chain::Solana,
+            symbol:      "NFT".into(),
+            name:        "Non Fungible".into(),
+            token_id:    U256::from(42),
+            uri:         "https://example.com/42.json".into(),
+            to:          [2u8; 32],
+            to_chain:    Chain::Terra,
+        };
+        let mut bytes = transfer.to_bytes();
+        assert_eq!(Transfer::from_bytes(&bytes).unwrap(), transfer);
+
+        // Invalid UTF-8 in the URI is a parse error rather than a panic.
+        bytes[132] = 0xff;
+        assert!(Transfer::from_bytes(&bytes).is_err());
+
+        // Over long URIs are truncated on a character boundary.
+        let long = Transfer {
+            uri: "é".repeat(200),
+            ..transfer
+        };
+        let parsed = Transfer::from_bytes(long.to_bytes()).unwrap();
+        assert_eq!(parsed.uri, "é".repeat(127));
+    }
+
+    #[test]
+    fn test_nft_transfer_v2() {
+        use super::nft::{
+            Creator,
+            PayloadKind,
+            Transfer,
+            TransferV2,
+        };
+        use primitive_types::U256;
+        use std::string::ToString;
+        use std::vec;
+
+        let transfer = TransferV2 {
+            nft_address:             [1u8; 32],
+            nft_chain:               Chain::Solana,
+            symbol:                  "NFT".into(),
+            name:                    "Non Fungible".into(),
+            token_id:                U256::from(42),
+            uri:                     "https://example.com/".to_string() + &"a".repeat(300),
+            collection:              [3u8; 32],
+            seller_fee_basis_points: 500,
+            creators:                vec![
+                Creator {
+                    address:  [4u8; 32],
+                    verified: true,
+                    share:    60,
+                },
+                Creator {
+                    address:  [5u8; 32],
+                    verified: false,
+                    share:    40,
+                },
+            ],
+            to:                      [2u8; 32],
+            to_chain:                Chain::Terra,
+        };
+        let bytes = transfer.to_bytes();
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 140 + 3 + 12 + 320 + 2 * 34);
+        assert_eq!(TransferV2::from_bytes(&bytes).unwrap(), transfer);
+        assert_eq!(transfer.to_versioned_bytes(), bytes);
+        assert_eq!(TransferV2::from_versioned_bytes(&bytes).unwrap(), transfer);
+
+        // Truncated, long and mislabeled payloads are rejected.
+        for len in 0..bytes.len() {
+            assert!(TransferV2::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut long = bytes.clone();
+        long.push(0);
+        assert!(TransferV2::from_bytes(&long).is_err());
+        assert!(TransferV2::from_versioned_bytes(&bytes[1..]).is_err());
+        assert!(TransferV2::from_versioned_bytes([]).is_err());
+
+        // Invalid UTF-8 in any string, or a creator flag other than 0 or 1, is a parse error
+        // rather than a panic.
+        for index in [36, 40, 90, bytes.len() - 34 - 2 * 34 + 32] {
+            let mut invalid = bytes.clone();
+            invalid[index] = 0xff;
+            assert!(TransferV2::from_bytes(&invalid).is_err());
+        }
+
+        // Strings are truncated on character boundaries, and creators past 255 are dropped.
+        let long = TransferV2 {
+            symbol: "é".repeat(200),
+            name: "€".repeat(100),
+            uri: "é".repeat(40_000),
+            creators: vec![transfer.creators[0].clone(); 300],
+            ..transfer.clone()
+        };
+        let parsed = TransferV2::from_bytes(long.to_bytes()).unwrap();
+        assert_eq!(parsed.symbol, "é".repeat(127));
+        assert_eq!(parsed.name, "€".repeat(85));
+        assert_eq!(parsed.uri, "é".repeat(32_767));
+        assert_eq!(parsed.creators.len(), 255);
+
+        // Transfers version 1 can carry are still sent as version 1.
+        let v1 = Transfer {
+            nft_address: [1u8; 32],
+            nft_chain:   Chain::Solana,
+            symbol:      "NFT".into(),
+            name:        "Non Fungible".into(),
+            token_id:    U256::from(42),
+            uri:         "https://example.com/42.json".into(),
+            to:          [2u8; 32],
+            to_chain:    Chain::Terra,
+        };
+        let converted = TransferV2::from(v1.clone());
+        assert_eq!(converted.to_versioned_bytes(), v1.to_bytes());
+        assert_eq!(
+            TransferV2::from_versioned_bytes(v1.to_bytes()).unwrap(),
+            converted
+        );
+
+        // Any field version 1 would lose switches to version 2.
+        let changes = [
+            TransferV2 {
+                collection: [3u8; 32],
+                ..converted.clone()
+            },
+            TransferV2 {
+                seller_fee_basis_points: 1,
+                ..converted.clone()
+            },
+            TransferV2 {
+                creators: transfer.creators.clone(),
+                ..converted.clone()
+            },
+            TransferV2 {
+                uri: "a".repeat(256),
+                ..converted.clone()
+            },
+            TransferV2 {
+                symbol: "a".repeat(33),
+                ..converted.clone()
+            },
+            TransferV2 {
+                name: "a\0b".into(),
+                ..converted.clone()
+            },
+            TransferV2 {
+                name: "a\u{FFFD}b".into(),
+                ..converted.clone()
+            },
+        ];
+        for changed in changes.iter() {
+            let bytes = changed.to_versioned_bytes();
+            assert_eq!(bytes[0], 2);
+            assert_eq!(&TransferV2::from_versioned_bytes(&bytes).unwrap(), changed);
+        }
+
+        // Chains that only redeem version 1 get version 1 regardless, dropping what it can't carry.
+        let to_ethereum = TransferV2 {
+            to_chain: Chain::Ethereum,
+            ..transfer.clone()
+        };
+        let bytes = to_ethereum.to_versioned_bytes();
+        assert_eq!(bytes[0], 1);
+        let parsed = Transfer::from_bytes(&bytes).unwrap();
+        assert!(transfer.uri.starts_with(&parsed.uri));
+        assert_eq!(parsed.uri.len(), 255);
+        assert_eq!(parsed.to_chain, Chain::Ethereum);
+        assert!(PayloadKind::TransferV2.is_redeemable_on(&Chain::Solana));
+        assert!(!PayloadKind::TransferV2.is_redeemable_on(&Chain::Polygon));
+        assert!(PayloadKind::Transfer.is_redeemable_on(&Chain::Polygon));
+    }
+
+    #[test]
+    fn test_nft_batch_transfer() {
+        use super::nft::{
+            BatchToken,
+            BatchTransfer,
+            PayloadKind,
+        };
+        use primitive_types::U256;
+        use std::vec;
+
+        let transfer = BatchTransfer {
+            nft_address: [1u8; 32],
+            nft_chain:   Chain::Solana,
+            symbol:      "NFT".into(),
+            name:        "Non Fungible".into(),
+            tokens:      vec![
+                BatchToken {
+                    token_id: U256::from(1),
+                    uri:      "https://a/1".into(),
+                },
+                BatchToken {
+                    token_id: U256::MAX,
+                    uri:      "https://a/2".into(),
+                },
+            ],
+            to:          [2u8; 32],
+            to_chain:    Chain::Terra,
+        };
+        let bytes = transfer.to_bytes();
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes.len(), 72 + 3 + 12 + 2 * (34 + 11));
+        assert_eq!(BatchTransfer::from_bytes(&bytes).unwrap(), transfer);
+
+        // Truncated, long and mislabeled payloads are rejected.
+        for len in 0..bytes.len() {
+            assert!(BatchTransfer::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut long = bytes.clone();
+        long.push(0);
+        assert!(BatchTransfer::from_bytes(&long).is_err());
+        let mut mislabeled = bytes.clone();
+        mislabeled[0] = 2;
+        assert!(BatchTransfer::from_bytes(&mislabeled).is_err());
+
+        // Invalid UTF-8 in a token URI is a parse error rather than a panic.
+        let mut invalid = bytes.clone();
+        invalid[38 + 3 + 12 + 34] = 0xff;
+        assert!(BatchTransfer::from_bytes(&invalid).is_err());
+
+        // An empty batch is well formed, the bridges decide whether to accept it.
+        let empty = BatchTransfer {
+            tokens: vec![],
+            ..transfer.clone()
+        };
+        assert_eq!(BatchTransfer::from_bytes(empty.to_bytes()).unwrap(), empty);
+
+        // URIs are truncated on character boundaries, and tokens past 255 are dropped.
+        let long = BatchTransfer {
+            tokens: vec![
+                BatchToken {
+                    token_id: U256::from(7),
+                    uri:      "é".repeat(40_000),
+                };
+                300
+            ],
+            ..transfer.clone()
+        };
+        let parsed = BatchTransfer::from_bytes(long.to_bytes()).unwrap();
+        assert_eq!(parsed.tokens.len(), BatchTransfer::MAX_TOKENS);
+        assert_eq!(parsed.tokens[0].uri, "é".repeat(32_767));
+
+        assert!(PayloadKind::BatchTransfer.is_redeemable_on(&Chain::Terra));
+        assert!(!PayloadKind::BatchTransfer.is_redeemable_on(&Chain::Ethereum));
+    }
+
+    #[test]
+    fn test_nft_semi_fungible_transfer() {
+        use super::nft::{
+            PayloadKind,
+            SemiFungibleTransfer,
+        };
+        use primitive_types::U256;
+
+        let transfer = SemiFungibleTransfer {
+            nft_address: [1u8; 32],
+            nft_chain:   Chain::Ethereum,
+            symbol:      "SFT".into(),
+            name:        "Semi Fungible".into(),
+            token_id:    U256::from(7),
+            amount:      U256::MAX,
+            uri:         "https://a/7".into(),
+            to:          [2u8; 32],
+            to_chain:    Chain::Solana,
+        };
+        let bytes = transfer.to_bytes();
+        assert_eq!(bytes[0], 4);
+        assert_eq!(bytes.len(), 137 + 3 + 13 + 11);
+        assert_eq!(SemiFungibleTransfer::from_bytes(&bytes).unwrap(), transfer);
+
+        // Truncated, long and mislabeled payloads are rejected.
+        for len in 0..bytes.len() {
+            assert!(SemiFungibleTransfer::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut long = bytes.clone();
+        long.push(0);
+        assert!(SemiFungibleTransfer::from_bytes(&long).is_err());
+        let mut mislabeled = bytes.clone();
+        mislabeled[0] = 3;
+        assert!(SemiFungibleTransfer::from_bytes(&mislabeled).is_err());
+
+        // Invalid UTF-8 in the URI is a parse error rather than a panic.
+        let mut invalid = bytes.clone();
+        invalid[37 + 3 + 13 + 66] = 0xff;
+        assert!(SemiFungibleTransfer::from_bytes(&invalid).is_err());
+
+        // Strings are truncated on character boundaries.
+        let long = SemiFungibleTransfer {
+            name: "é".repeat(200),
+            uri: "é".repeat(40_000),
+            ..transfer.clone()
+        };
+        let parsed = SemiFungibleTransfer::from_bytes(long.to_bytes()).unwrap();
+        assert_eq!(parsed.name, "é".repeat(127));
+        assert_eq!(parsed.uri, "é".repeat(32_767));
+
+        assert!(PayloadKind::SemiFungibleTransfer.is_redeemable_on(&Chain::Solana));
+        assert!(!PayloadKind::SemiFungibleTransfer.is_redeemable_on(&Chain::Ethereum));
+    }
+
+    #[test]
+    fn test_alephium_payloads() {
+        use super::alephium;
+        use super::core::{
+            GovernanceGuardianSetChange,
+            GovernanceSetMessageFee,
+        };
+        use super::token::GovernanceRegisterChain;
+        use super::GovernanceAction;
+        use primitive_types::U256;
+
+        let transfer = alephium::Transfer {
+            amount:           U256::from(1000),
+            token_address:    [1u8; 32],
+            token_chain:      Chain::Alephium,
+            to:               [2u8; 32],
+            to_chain:         Chain::Ethereum,
+            fee:              U256::from(1),
+            is_local_token:   true,
+            token_wrapper_id: [3u8; 32],
+        };
+        let mut bytes = transfer.to_bytes();
+        assert_eq!(bytes.len(), 166);
+        assert_eq!(&bytes[..133], &transfer.to_standard().to_bytes()[..]);
+        assert_eq!(alephium::Transfer::from_bytes(&bytes).unwrap(), transfer);
+        bytes[133] = 2;
+        assert!(alephium::Transfer::from_bytes(&bytes).is_err());
+
+        // Incoming transfers must be addressed to Alephium.
+        let standard = transfer.to_standard();
+        assert!(alephium::parse_incoming_transfer(standard.to_bytes()).is_err());
+        let incoming = super::token::Transfer {
+            to_chain: Chain::Alephium,
+            ..standard
+        };
+        assert!(alephium::parse_incoming_transfer(incoming.to_bytes()).is_ok());
+
+        // RegisterChain must be exactly 69 bytes and may not register Alephium.
+        let register = GovernanceRegisterChain {
+            emitter:          Chain::Ethereum,
+            endpoint_address: [4u8; 32],
+        };
+        let bytes = register.to_bytes(Chain::Alephium);
+        assert_eq!(bytes.len(), alephium::REGISTER_CHAIN_SIZE);
+        assert!(alephium::parse_register_chain(&bytes).is_ok());
+        assert!(alephium::parse_register_chain(&bytes[..68]).is_err());
+        assert!(alephium::parse_register_chain(register.to_bytes(Chain::Solana)).is_err());
+        let itself = GovernanceRegisterChain {
+            emitter: Chain::Alephium,
+            ..register
+        };
+        assert!(alephium::parse_register_chain(itself.to_bytes(Chain::All)).is_err());
+
+        // Guardian sets hold at most 19 keys.
+        let guardians = |n| GovernanceGuardianSetChange {
+            new_guardian_set_index: 1,
+            new_guardian_set:       [[5u8; 20]].repeat(n),
+        };
+        assert!(alephium::parse_guardian_set_change(guardians(19).to_bytes(Chain::All)).is_ok());
+        assert!(alephium::parse_guardian_set_change(guardians(20).to_bytes(Chain::All)).is_err());
+        assert!(alephium::parse_guardian_set_change(guardians(0).to_bytes(Chain::All)).is_err());
+
+        // Message fees can not be set for all chains at once.
+        let fee = GovernanceSetMessageFee { fee: U256::from(5) };
+        assert!(alephium::parse_set_message_fee(fee.to_bytes(Chain::Alephium)).is_ok());
+        assert!(alephium::parse_set_message_fee(fee.to_bytes(Chain::All)).is_err());
+    }
+
+    #[test]
+    fn test_governance_chain() {
+        use super::core::GovernanceContractUpgrade;
+        use super::GovernanceAction;
+        use crate::WormholeError;
+
+        let upgrade = GovernanceContractUpgrade {
+            new_contract: [3u8; 32],
+        };
+
+        // Actions for all chains are valid everywhere, others only on their own chain.
+        let all = upgrade.to_bytes(Chain::All);
+        let solana = upgrade.to_bytes(Chain::Solana);
+        assert!(GovernanceContractUpgrade::from_bytes(&all, Some(Chain::Solana)).is_ok());
+        assert!(GovernanceContractUpgrade::from_bytes(&solana, Some(Chain::Solana)).is_ok());
+        assert!(GovernanceContractUpgrade::from_bytes(&solana, None).is_ok());
+        assert!(matches!(
+            GovernanceContractUpgrade::from_bytes(&solana, Some(Chain::Terra)),
+            Err(WormholeError::InvalidGovernanceChain)
+        ));
+
+        // Unknown target chains are parsed rather than crashing the header parser.
+        let unknown = upgrade.to_bytes(Chain::Unknown(0xffff));
+        let (header, _) = GovernanceContractUpgrade::from_bytes(&unknown, None).unwrap();
+        assert_eq!(header.chains, Chain::Unknown(0xffff));
+
+        let mut long = solana.clone();
+        long.push(0);
+        assert!(GovernanceContractUpgrade::from_bytes(&long, None).is_err());
+    }
 }
@@ -0,0 +1,197 @@
+//! This module exposes parsers for the payloads of the Alephium contracts in `alephium/contracts`.
+//! Alephium uses the standard Wormhole layouts with two differences: the token transfers it emits
+//! carry extra fields after the standard 133 bytes, and its contracts check some fields of the
+//! payloads they consume more strictly than other chains do.
+//!
+//! The checks here mirror `governance.ral`, `token_bridge.ral` and `token_bridge_for_chain.ral`,
+//! so a relayer can reject a VAA that Alephium would refuse before paying to submit it.
+
+use alloc::vec::Vec;
+use nom::combinator::{
+    map,
+    verify,
+};
+use nom::number::complete::u8;
+use nom::IResult;
+use primitive_types::U256;
+
+use crate::vaa::core::{
+    GovernanceGuardianSetChange,
+    GovernanceSetMessageFee,
+    GovernanceTransferFees,
+};
+use crate::vaa::token::{
+    self,
+    parse_payload_transfer,
+    u256_to_bytes,
+    GovernanceRegisterChain,
+};
+use crate::vaa::{
+    parse_exact,
+    parse_fixed,
+    GovHeader,
+    GovernanceAction,
+};
+use crate::WormholeError::{
+    InvalidGovernanceChain,
+    InvalidPayload,
+};
+use crate::{
+    require,
+    Chain,
+    WormholeError,
+};
+
+/// Size of a TokenBridge RegisterChain payload, `parseRegisterChain` rejects any other.
+pub const REGISTER_CHAIN_SIZE: usize = 69;
+
+/// Guardian sets, and so the signatures on a VAA, are stored in fixed arrays of this size.
+pub const MAX_GUARDIANS: usize = 19;
+
+/// A token transfer as emitted by an Alephium token wrapper. The first 133 bytes are a standard
+/// token bridge transfer, followed by:
+/// ```markdown
+/// 133 .. 134: Whether the token is native to Alephium (0 or 1)
+/// 134 .. 166: Contract ID of the token wrapper that locked or burned the tokens
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transfer {
+    /// Amount being transferred (big-endian uint256), normalized to at most 8 decimals.
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
+    pub amount: U256,
+
+    /// Address of the token on its origin chain.
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub token_address: [u8; 32],
+
+    /// Chain ID of the token
+    pub token_chain: Chain,
+
+    /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub to: [u8; 32],
+
+    /// Chain ID of the recipient
+    pub to_chain: Chain,
+
+    /// Relayer fee, normalized like the amount.
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
+    pub fee: U256,
+
+    /// True when the token originates on Alephium and is locked rather than burned.
+    pub is_local_token: bool,
+
+    /// Contract ID of the Alephium token wrapper that sent the transfer.
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub token_wrapper_id: [u8; 32],
+}
+
+impl Transfer {
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        parse_exact(parse_payload_transfer_alephium, input.as_ref())
+    }
+
+    /// Serialize to Alephium wire format, including the payload ID.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(166);
+        v.push(1);
+        v.extend_from_slice(&u256_to_bytes(self.amount));
+        v.extend_from_slice(&self.token_address);
+        v.extend_from_slice(&u16::from(self.token_chain.clone()).to_be_bytes());
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain.clone()).to_be_bytes());
+        v.extend_from_slice(&u256_to_bytes(self.fee));
+        v.push(self.is_local_token as u8);
+        v.extend_from_slice(&self.token_wrapper_id);
+        v
+    }
+
+    /// The standard token bridge fields of the transfer.
+    pub fn to_standard(&self) -> token::Transfer {
+        token::Transfer {
+            amount:        self.amount,
+            token_address: self.token_address,
+            token_chain:   self.token_chain.clone(),
+            to:            self.to,
+            to_chain:      self.to_chain.clone(),
+            fee:           self.fee,
+        }
+    }
+}
+
+fn parse_payload_transfer_alephium(input: &[u8]) -> IResult<&[u8], Transfer> {
+    let (i, transfer) = parse_payload_transfer(input)?;
+    let (i, is_local_token) = map(verify(u8, |&b| b <= 1), |b| b == 1)(i)?;
+    let (i, token_wrapper_id) = parse_fixed(i)?;
+    Ok((
+        i,
+        Transfer {
+            amount: transfer.amount,
+            token_address: transfer.token_address,
+            token_chain: transfer.token_chain,
+            to: transfer.to,
+            to_chain: transfer.to_chain,
+            fee: transfer.fee,
+            is_local_token,
+            token_wrapper_id,
+        },
+    ))
+}
+
+/// Parse a standard token transfer for redemption on Alephium, which must be addressed to it.
+pub fn parse_incoming_transfer<T: AsRef<[u8]>>(input: T) -> Result<token::Transfer, WormholeError> {
+    let transfer = token::Transfer::from_bytes(input)?;
+    require!(transfer.to_chain == Chain::Alephium, InvalidPayload);
+    Ok(transfer)
+}
+
+/// Parse an attestation for a token from `remote_chain`, the chain of the token bridge it is
+/// submitted through.
+pub fn parse_incoming_asset_meta<T: AsRef<[u8]>>(
+    input: T,
+    remote_chain: Chain,
+) -> Result<token::AssetMeta, WormholeError> {
+    let meta = token::AssetMeta::from_bytes(input)?;
+    require!(meta.token_chain == remote_chain, InvalidPayload);
+    Ok(meta)
+}
+
+/// Parse a TokenBridge RegisterChain action. It must be exactly `REGISTER_CHAIN_SIZE` bytes,
+/// addressed to Alephium or all chains, and may not register Alephium itself.
+pub fn parse_register_chain<T: AsRef<[u8]>>(
+    input: T,
+) -> Result<(GovHeader, GovernanceRegisterChain), WormholeError> {
+    let input = input.as_ref();
+    require!(input.len() == REGISTER_CHAIN_SIZE, InvalidPayload);
+    let (header, action) = GovernanceRegisterChain::from_bytes(input, Some(Chain::Alephium))?;
+    require!(action.emitter != Chain::Alephium, InvalidPayload);
+    Ok((header, action))
+}
+
+/// Parse a guardian set change for Alephium, which holds between 1 and `MAX_GUARDIANS` keys.
+pub fn parse_guardian_set_change<T: AsRef<[u8]>>(
+    input: T,
+) -> Result<(GovHeader, GovernanceGuardianSetChange), WormholeError> {
+    let (header, action) = GovernanceGuardianSetChange::from_bytes(input, Some(Chain::Alephium))?;
+    let size = action.new_guardian_set.len();
+    require!(size > 0 && size <= MAX_GUARDIANS, InvalidPayload);
+    Ok((header, action))
+}
+
+/// Parse a message fee change. Unlike other actions, Alephium does not accept fee changes
+/// addressed to all chains.
+pub fn parse_set_message_fee<T: AsRef<[u8]>>(
+    input: T,
+) -> Result<(GovHeader, GovernanceSetMessageFee), WormholeError> {
+    let (header, action) = GovernanceSetMessageFee::from_bytes(input, Some(Chain::Alephium))?;
+    require!(header.chains == Chain::Alephium, InvalidGovernanceChain);
+    Ok((header, action))
+}
+
+/// Parse a fee transfer, the recipient is the public key hash of an Alephium P2PKH address.
+pub fn parse_transfer_fees<T: AsRef<[u8]>>(
+    input: T,
+) -> Result<(GovHeader, GovernanceTransferFees), WormholeError> {
+    GovernanceTransferFees::from_bytes(input, Some(Chain::Alephium))
+}
@@ -15,6 +15,7 @@ use nom::number::complete::{
 };
 use nom::number::Endianness;
 use nom::IResult;
+use alloc::vec::Vec;
 use primitive_types::U256;
 
 use crate::vaa::{
@@ -22,7 +23,10 @@ use crate::vaa::{
     GovernanceAction,
 };
 
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceContractUpgrade {
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub new_contract: [u8; 32],
 }
 
@@ -33,10 +37,17 @@ impl GovernanceAction for GovernanceContractUpgrade {
         let (i, new_contract) = parse_fixed(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        self.new_contract.to_vec()
+    }
 }
 
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceGuardianSetChange {
     pub new_guardian_set_index: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex_list"))]
     pub new_guardian_set:       Vec<[u8; 20]>,
 }
 
@@ -55,9 +66,22 @@ impl GovernanceAction for GovernanceGuardianSetChange {
             },
         ))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(5 + 20 * self.new_guardian_set.len());
+        v.extend_from_slice(&self.new_guardian_set_index.to_be_bytes());
+        v.push(self.new_guardian_set.len() as u8);
+        for key in &self.new_guardian_set {
+            v.extend_from_slice(key);
+        }
+        v
+    }
 }
 
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceSetMessageFee {
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
     pub fee: U256,
 }
 
@@ -74,10 +98,20 @@ impl GovernanceAction for GovernanceSetMessageFee {
             },
         ))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut fee = [0u8; 32];
+        self.fee.to_big_endian(&mut fee);
+        fee.to_vec()
+    }
 }
 
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceTransferFees {
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
     pub amount: U256,
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub to:     [u8; 32],
 }
 
@@ -96,4 +130,13 @@ impl GovernanceAction for GovernanceTransferFees {
             },
         ))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(64);
+        let mut amount = [0u8; 32];
+        self.amount.to_big_endian(&mut amount);
+        v.extend_from_slice(&amount);
+        v.extend_from_slice(&self.to);
+        v
+    }
 }
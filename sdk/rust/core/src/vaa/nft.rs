@@ -4,17 +4,29 @@
 //! supports, namely contract upgrades and chain registrations.
 
 use nom::bytes::complete::take;
-use nom::combinator::verify;
-use nom::number::complete::u8;
-use nom::{
-    Finish,
-    IResult,
+use nom::combinator::{
+    flat_map,
+    map,
+    map_res,
+    verify,
 };
+use nom::multi::length_count;
+use nom::number::complete::{
+    be_u16,
+    u8,
+};
+use nom::IResult;
 use primitive_types::U256;
-use std::str::from_utf8;
+use alloc::string::{
+    String,
+    ToString,
+};
+use alloc::vec::Vec;
+use core::str::from_utf8;
 
 use crate::vaa::{
     parse_chain,
+    parse_exact,
     parse_fixed,
     GovernanceAction,
 };
@@ -22,15 +34,41 @@ use crate::vaa::ShortUTFString;
 use crate::{
     Chain,
     parse_fixed_utf8,
+    truncate_utf8,
+    write_fixed_utf8,
     WormholeError,
 };
 
+/// The kinds of NFT bridge payloads, by payload ID.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PayloadKind {
+    Transfer             = 1,
+    TransferV2           = 2,
+    BatchTransfer        = 3,
+    SemiFungibleTransfer = 4,
+}
+
+impl PayloadKind {
+    /// Whether the NFT bridge on `chain` redeems payloads of this kind. Every NFT bridge redeems
+    /// version 1 transfers, the later kinds are only redeemed on Solana and Terra.
+    pub fn is_redeemable_on(self, chain: &Chain) -> bool {
+        match self {
+            PayloadKind::Transfer => true,
+            PayloadKind::TransferV2
+            | PayloadKind::BatchTransfer
+            | PayloadKind::SemiFungibleTransfer => matches!(chain, Chain::Solana | Chain::Terra),
+        }
+    }
+}
+
 /// Transfer is a message containing specifics detailing a token lock up on a sending chain. Chains
 /// that are attempting to initiate a transfer must lock up tokens in some manner, such as in a
 /// custody account or via burning, before emitting this message.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transfer {
     /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub nft_address: [u8; 32],
 
     /// Chain ID of the token
@@ -43,12 +81,14 @@ pub struct Transfer {
     pub name: ShortUTFString,
 
     /// TokenID of the token (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
     pub token_id: U256,
 
     /// URI of the token metadata
     pub uri: ShortUTFString,
 
     /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub to: [u8; 32],
 
     /// Chain ID of the recipient
@@ -57,23 +97,42 @@ pub struct Transfer {
 
 impl Transfer {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
-        match parse_payload_transfer(input.as_ref()).finish() {
-            Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
-        }
+        parse_exact(parse_payload_transfer, input.as_ref())
+    }
+
+    /// Serialize to Wormhole wire format, including the payload ID. Symbol and name are truncated
+    /// to 32 bytes, the URI to the last character boundary within 255 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let uri = truncate_utf8(&self.uri, 255);
+
+        let mut token_id = [0u8; 32];
+        self.token_id.to_big_endian(&mut token_id);
+
+        let mut v = Vec::with_capacity(166 + uri.len());
+        v.push(PayloadKind::Transfer as u8);
+        v.extend_from_slice(&self.nft_address);
+        v.extend_from_slice(&u16::from(self.nft_chain.clone()).to_be_bytes());
+        v.extend_from_slice(&write_fixed_utf8::<32>(&self.symbol));
+        v.extend_from_slice(&write_fixed_utf8::<32>(&self.name));
+        v.extend_from_slice(&token_id);
+        v.push(uri.len() as u8);
+        v.extend_from_slice(uri.as_bytes());
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain.clone()).to_be_bytes());
+        v
     }
 }
 
 fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
     // Parse Payload
-    let (i, _) = verify(u8, |&s| s == 0x1)(input.as_ref())?;
+    let (i, _) = verify(u8, |&s| s == PayloadKind::Transfer as u8)(input)?;
     let (i, nft_address) = parse_fixed(i)?;
     let (i, nft_chain) = parse_chain(i)?;
     let (i, symbol): (_, [u8; 32]) = parse_fixed(i)?;
     let (i, name): (_, [u8; 32]) = parse_fixed(i)?;
     let (i, token_id): (_, [u8; 32]) = parse_fixed(i)?;
     let (i, uri_len) = u8(i)?;
-    let (i, uri) = take(uri_len)(i)?;
+    let (i, uri) = map_res(take(uri_len), from_utf8)(i)?;
     let (i, to) = parse_fixed(i)?;
     let (i, to_chain) = parse_chain(i)?;
 
@@ -81,7 +140,7 @@ fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
     // invalid bytes -- for the latter, assume UTF-8 and fail if unparseable.
     let name = parse_fixed_utf8::<_, 32>(name).unwrap();
     let symbol = parse_fixed_utf8::<_, 32>(symbol).unwrap();
-    let uri = from_utf8(uri).unwrap().to_string();
+    let uri = uri.to_string();
 
     Ok((
         i,
@@ -98,9 +157,446 @@ fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
     ))
 }
 
-#[derive(PartialEq, Debug)]
+/// A creator of an NFT and their share of its royalties, as tracked by Metaplex on Solana.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Creator {
+    /// Address of the creator. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub address: [u8; 32],
+
+    /// Whether the creator signed off on being listed
+    pub verified: bool,
+
+    /// Percentage of the royalties paid to the creator
+    pub share: u8,
+}
+
+/// TransferV2 is the second version of the NFT transfer, with payload ID 2. Compared to `Transfer`
+/// it carries URIs of up to 65535 bytes, length prefixed symbols and names, and the collection,
+/// royalty and creators of the NFT. Every string must be valid UTF-8.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferV2 {
+    /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub nft_address: [u8; 32],
+
+    /// Chain ID of the token
+    pub nft_chain: Chain,
+
+    /// Symbol of the token
+    pub symbol: ShortUTFString,
+
+    /// Name of the token
+    pub name: ShortUTFString,
+
+    /// TokenID of the token (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
+    pub token_id: U256,
+
+    /// URI of the token metadata
+    pub uri: String,
+
+    /// Address of the collection the token belongs to, zero if it belongs to none
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub collection: [u8; 32],
+
+    /// Royalty paid on secondary sales, in basis points
+    pub seller_fee_basis_points: u16,
+
+    /// Creators sharing the royalty
+    pub creators: Vec<Creator>,
+
+    /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub to: [u8; 32],
+
+    /// Chain ID of the recipient
+    pub to_chain: Chain,
+}
+
+impl TransferV2 {
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        parse_exact(parse_payload_transfer_v2, input.as_ref())
+    }
+
+    /// Parse either version of the NFT transfer, telling them apart by payload ID. Version 1
+    /// transfers are converted, with no collection, royalty or creators.
+    pub fn from_versioned_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        let input = input.as_ref();
+        match input.first() {
+            Some(1) => Transfer::from_bytes(input).map(Self::from),
+            Some(2) => Self::from_bytes(input),
+            _ => Err(WormholeError::InvalidPayload),
+        }
+    }
+
+    /// Serialize to Wormhole wire format, including the payload ID. Symbol and name are truncated
+    /// to the last character boundary within 255 bytes, the URI within 65535 bytes, and only the
+    /// first 255 creators are kept.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let symbol = truncate_utf8(&self.symbol, u8::MAX as usize);
+        let name = truncate_utf8(&self.name, u8::MAX as usize);
+        let uri = truncate_utf8(&self.uri, u16::MAX as usize);
+        let creators = &self.creators[..self.creators.len().min(u8::MAX as usize)];
+
+        let mut token_id = [0u8; 32];
+        self.token_id.to_big_endian(&mut token_id);
+
+        let mut v =
+            Vec::with_capacity(140 + symbol.len() + name.len() + uri.len() + 34 * creators.len());
+        v.push(PayloadKind::TransferV2 as u8);
+        v.extend_from_slice(&self.nft_address);
+        v.extend_from_slice(&u16::from(self.nft_chain.clone()).to_be_bytes());
+        v.push(symbol.len() as u8);
+        v.extend_from_slice(symbol.as_bytes());
+        v.push(name.len() as u8);
+        v.extend_from_slice(name.as_bytes());
+        v.extend_from_slice(&token_id);
+        v.extend_from_slice(&(uri.len() as u16).to_be_bytes());
+        v.extend_from_slice(uri.as_bytes());
+        v.extend_from_slice(&self.collection);
+        v.extend_from_slice(&self.seller_fee_basis_points.to_be_bytes());
+        v.push(creators.len() as u8);
+        for creator in creators {
+            v.extend_from_slice(&creator.address);
+            v.push(creator.verified as u8);
+            v.push(creator.share);
+        }
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain.clone()).to_be_bytes());
+        v
+    }
+
+    /// Serialize as a version 1 transfer if that loses nothing, so receivers that only know the
+    /// first version keep working for the transfers they can represent. Anything else is
+    /// serialized as version 2, unless the recipient chain cannot redeem it: those still get
+    /// version 1, without collection, royalty and creators and with strings truncated.
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        // Version 1 drops NUL and U+FFFD from symbol and name when parsing.
+        let fixed = |s: &str| s.len() <= 32 && !s.contains(['\0', '\u{FFFD}']);
+        let lossless = self.creators.is_empty()
+            && self.collection == [0; 32]
+            && self.seller_fee_basis_points == 0
+            && self.uri.len() <= 255
+            && fixed(&self.symbol)
+            && fixed(&self.name);
+        let v1 = lossless || !PayloadKind::TransferV2.is_redeemable_on(&self.to_chain);
+
+        if v1 {
+            Transfer {
+                nft_address: self.nft_address,
+                nft_chain:   self.nft_chain.clone(),
+                symbol:      self.symbol.clone(),
+                name:        self.name.clone(),
+                token_id:    self.token_id,
+                uri:         self.uri.clone(),
+                to:          self.to,
+                to_chain:    self.to_chain.clone(),
+            }
+            .to_bytes()
+        } else {
+            self.to_bytes()
+        }
+    }
+}
+
+impl From<Transfer> for TransferV2 {
+    fn from(transfer: Transfer) -> Self {
+        TransferV2 {
+            nft_address:             transfer.nft_address,
+            nft_chain:               transfer.nft_chain,
+            symbol:                  transfer.symbol,
+            name:                    transfer.name,
+            token_id:                transfer.token_id,
+            uri:                     transfer.uri,
+            collection:              [0; 32],
+            seller_fee_basis_points: 0,
+            creators:                Vec::new(),
+            to:                      transfer.to,
+            to_chain:                transfer.to_chain,
+        }
+    }
+}
+
+/// Parse a length prefixed UTF-8 string. Unlike `length_data`, which reports a short input as
+/// `Incomplete`, running out of input is an error.
+fn parse_utf8<'a>(
+    length: impl FnMut(&'a [u8]) -> IResult<&'a [u8], usize>,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], String> {
+    map(map_res(flat_map(length, take), from_utf8), ToString::to_string)
+}
+
+fn parse_creator(input: &[u8]) -> IResult<&[u8], Creator> {
+    let (i, address) = parse_fixed(input)?;
+    let (i, verified) = verify(u8, |&v| v <= 1)(i)?;
+    let (i, share) = u8(i)?;
+    Ok((
+        i,
+        Creator {
+            address,
+            verified: verified == 1,
+            share,
+        },
+    ))
+}
+
+fn parse_payload_transfer_v2(input: &[u8]) -> IResult<&[u8], TransferV2> {
+    let (i, _) = verify(u8, |&s| s == PayloadKind::TransferV2 as u8)(input)?;
+    let (i, nft_address) = parse_fixed(i)?;
+    let (i, nft_chain) = parse_chain(i)?;
+    let (i, symbol) = parse_utf8(map(u8, usize::from))(i)?;
+    let (i, name) = parse_utf8(map(u8, usize::from))(i)?;
+    let (i, token_id): (_, [u8; 32]) = parse_fixed(i)?;
+    let (i, uri) = parse_utf8(map(be_u16, usize::from))(i)?;
+    let (i, collection) = parse_fixed(i)?;
+    let (i, seller_fee_basis_points) = be_u16(i)?;
+    let (i, creators) = length_count(u8, parse_creator)(i)?;
+    let (i, to) = parse_fixed(i)?;
+    let (i, to_chain) = parse_chain(i)?;
+
+    Ok((
+        i,
+        TransferV2 {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            token_id: U256::from_big_endian(&token_id),
+            uri,
+            collection,
+            seller_fee_basis_points,
+            creators,
+            to,
+            to_chain,
+        },
+    ))
+}
+
+/// A single token of a `BatchTransfer`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchToken {
+    /// TokenID of the token (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
+    pub token_id: U256,
+
+    /// URI of the token metadata
+    pub uri: String,
+}
+
+/// BatchTransfer moves several tokens of one NFT contract to the same recipient in a single
+/// message, with payload ID 3. Symbol and name are shared by every token, each token carries its
+/// own URI. Every string must be valid UTF-8.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchTransfer {
+    /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub nft_address: [u8; 32],
+
+    /// Chain ID of the token
+    pub nft_chain: Chain,
+
+    /// Symbol of the token
+    pub symbol: ShortUTFString,
+
+    /// Name of the token
+    pub name: ShortUTFString,
+
+    /// Tokens being transferred
+    pub tokens: Vec<BatchToken>,
+
+    /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub to: [u8; 32],
+
+    /// Chain ID of the recipient
+    pub to_chain: Chain,
+}
+
+impl BatchTransfer {
+    /// The most tokens a single batch can carry.
+    pub const MAX_TOKENS: usize = u8::MAX as usize;
+
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        parse_exact(parse_payload_batch_transfer, input.as_ref())
+    }
+
+    /// Serialize to Wormhole wire format, including the payload ID. Symbol and name are truncated
+    /// to the last character boundary within 255 bytes, URIs within 65535 bytes, and only the
+    /// first `MAX_TOKENS` tokens are kept.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let symbol = truncate_utf8(&self.symbol, u8::MAX as usize);
+        let name = truncate_utf8(&self.name, u8::MAX as usize);
+        let tokens = &self.tokens[..self.tokens.len().min(Self::MAX_TOKENS)];
+
+        let mut v = Vec::with_capacity(72 + symbol.len() + name.len() + 34 * tokens.len());
+        v.push(PayloadKind::BatchTransfer as u8);
+        v.extend_from_slice(&self.nft_address);
+        v.extend_from_slice(&u16::from(self.nft_chain.clone()).to_be_bytes());
+        v.push(symbol.len() as u8);
+        v.extend_from_slice(symbol.as_bytes());
+        v.push(name.len() as u8);
+        v.extend_from_slice(name.as_bytes());
+        v.push(tokens.len() as u8);
+        for token in tokens {
+            let uri = truncate_utf8(&token.uri, u16::MAX as usize);
+            let mut token_id = [0u8; 32];
+            token.token_id.to_big_endian(&mut token_id);
+            v.extend_from_slice(&token_id);
+            v.extend_from_slice(&(uri.len() as u16).to_be_bytes());
+            v.extend_from_slice(uri.as_bytes());
+        }
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain.clone()).to_be_bytes());
+        v
+    }
+}
+
+fn parse_batch_token(input: &[u8]) -> IResult<&[u8], BatchToken> {
+    let (i, token_id): (_, [u8; 32]) = parse_fixed(input)?;
+    let (i, uri) = parse_utf8(map(be_u16, usize::from))(i)?;
+    Ok((
+        i,
+        BatchToken {
+            token_id: U256::from_big_endian(&token_id),
+            uri,
+        },
+    ))
+}
+
+fn parse_payload_batch_transfer(input: &[u8]) -> IResult<&[u8], BatchTransfer> {
+    let (i, _) = verify(u8, |&s| s == PayloadKind::BatchTransfer as u8)(input)?;
+    let (i, nft_address) = parse_fixed(i)?;
+    let (i, nft_chain) = parse_chain(i)?;
+    let (i, symbol) = parse_utf8(map(u8, usize::from))(i)?;
+    let (i, name) = parse_utf8(map(u8, usize::from))(i)?;
+    let (i, tokens) = length_count(u8, parse_batch_token)(i)?;
+    let (i, to) = parse_fixed(i)?;
+    let (i, to_chain) = parse_chain(i)?;
+
+    Ok((
+        i,
+        BatchTransfer {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            tokens,
+            to,
+            to_chain,
+        },
+    ))
+}
+
+/// SemiFungibleTransfer moves an amount of a token that exists in several copies, such as an
+/// ERC-1155 token or an SPL mint with a supply above one, with payload ID 4. Every string must be
+/// valid UTF-8.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SemiFungibleTransfer {
+    /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub nft_address: [u8; 32],
+
+    /// Chain ID of the token
+    pub nft_chain: Chain,
+
+    /// Symbol of the token
+    pub symbol: ShortUTFString,
+
+    /// Name of the token
+    pub name: ShortUTFString,
+
+    /// TokenID of the token (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
+    pub token_id: U256,
+
+    /// Number of copies transferred (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
+    pub amount: U256,
+
+    /// URI of the token metadata
+    pub uri: String,
+
+    /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
+    pub to: [u8; 32],
+
+    /// Chain ID of the recipient
+    pub to_chain: Chain,
+}
+
+impl SemiFungibleTransfer {
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        parse_exact(parse_payload_semi_fungible_transfer, input.as_ref())
+    }
+
+    /// Serialize to Wormhole wire format, including the payload ID. Symbol and name are truncated
+    /// to the last character boundary within 255 bytes, the URI within 65535 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let symbol = truncate_utf8(&self.symbol, u8::MAX as usize);
+        let name = truncate_utf8(&self.name, u8::MAX as usize);
+        let uri = truncate_utf8(&self.uri, u16::MAX as usize);
+
+        let mut token_id = [0u8; 32];
+        self.token_id.to_big_endian(&mut token_id);
+        let mut amount = [0u8; 32];
+        self.amount.to_big_endian(&mut amount);
+
+        let mut v = Vec::with_capacity(137 + symbol.len() + name.len() + uri.len());
+        v.push(PayloadKind::SemiFungibleTransfer as u8);
+        v.extend_from_slice(&self.nft_address);
+        v.extend_from_slice(&u16::from(self.nft_chain.clone()).to_be_bytes());
+        v.push(symbol.len() as u8);
+        v.extend_from_slice(symbol.as_bytes());
+        v.push(name.len() as u8);
+        v.extend_from_slice(name.as_bytes());
+        v.extend_from_slice(&token_id);
+        v.extend_from_slice(&amount);
+        v.extend_from_slice(&(uri.len() as u16).to_be_bytes());
+        v.extend_from_slice(uri.as_bytes());
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain.clone()).to_be_bytes());
+        v
+    }
+}
+
+fn parse_payload_semi_fungible_transfer(input: &[u8]) -> IResult<&[u8], SemiFungibleTransfer> {
+    let (i, _) = verify(u8, |&s| s == PayloadKind::SemiFungibleTransfer as u8)(input)?;
+    let (i, nft_address) = parse_fixed(i)?;
+    let (i, nft_chain) = parse_chain(i)?;
+    let (i, symbol) = parse_utf8(map(u8, usize::from))(i)?;
+    let (i, name) = parse_utf8(map(u8, usize::from))(i)?;
+    let (i, token_id): (_, [u8; 32]) = parse_fixed(i)?;
+    let (i, amount): (_, [u8; 32]) = parse_fixed(i)?;
+    let (i, uri) = parse_utf8(map(be_u16, usize::from))(i)?;
+    let (i, to) = parse_fixed(i)?;
+    let (i, to_chain) = parse_chain(i)?;
+
+    Ok((
+        i,
+        SemiFungibleTransfer {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            token_id: U256::from_big_endian(&token_id),
+            amount: U256::from_big_endian(&amount),
+            uri,
+            to,
+            to_chain,
+        },
+    ))
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceRegisterChain {
     pub emitter:          Chain,
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub endpoint_address: [u8; 32],
 }
 
@@ -118,10 +614,19 @@ impl GovernanceAction for GovernanceRegisterChain {
             },
         ))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(34);
+        v.extend_from_slice(&u16::from(self.emitter.clone()).to_be_bytes());
+        v.extend_from_slice(&self.endpoint_address);
+        v
+    }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceContractUpgrade {
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub new_contract: [u8; 32],
 }
 
@@ -132,4 +637,8 @@ impl GovernanceAction for GovernanceContractUpgrade {
         let (i, new_contract) = parse_fixed(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        self.new_contract.to_vec()
+    }
 }
@@ -3,23 +3,23 @@
 //! parsers can be used to read these VAAs. It also defines the Governance actions that this module
 //! supports, namely contract upgrades and chain registrations.
 
+use alloc::vec::Vec;
 use nom::combinator::verify;
 use nom::multi::fill;
 use nom::number::complete::u8;
-use nom::{
-    Finish,
-    IResult,
-};
+use nom::IResult;
 use primitive_types::U256;
 
 use crate::vaa::{
     GovernanceAction,
     parse_chain,
+    parse_exact,
     parse_fixed,
     ShortUTFString,
 };
 use crate::{
     parse_fixed_utf8,
+    write_fixed_utf8,
     Chain,
     WormholeError,
 };
@@ -28,36 +28,57 @@ use crate::{
 /// that are attempting to initiate a transfer must lock up tokens in some manner, such as in a
 /// custody account or via burning, before emitting this message.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transfer {
     /// Amount being transferred (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
     pub amount: U256,
 
     /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub token_address: [u8; 32],
 
     /// Chain ID of the token
     pub token_chain: Chain,
 
     /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub to: [u8; 32],
 
     /// Chain ID of the recipient
     pub to_chain: Chain,
 
     /// Amount of tokens (big-endian uint256) that the user is willing to pay as relayer fee. Must be <= Amount.
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::u256"))]
     pub fee: U256,
 }
 
 impl Transfer {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
-        match parse_payload_transfer(input.as_ref()).finish() {
-            Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
-        }
+        parse_exact(parse_payload_transfer, input.as_ref())
+    }
+
+    /// Serialize to Wormhole wire format, including the payload ID.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(133);
+        v.push(1);
+        v.extend_from_slice(&u256_to_bytes(self.amount));
+        v.extend_from_slice(&self.token_address);
+        v.extend_from_slice(&u16::from(self.token_chain.clone()).to_be_bytes());
+        v.extend_from_slice(&self.to);
+        v.extend_from_slice(&u16::from(self.to_chain.clone()).to_be_bytes());
+        v.extend_from_slice(&u256_to_bytes(self.fee));
+        v
     }
 }
 
-fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
+pub(crate) fn u256_to_bytes(value: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+pub(crate) fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
     // Parser Buffers.
     let mut amount = [0u8; 32];
     let mut fee = [0u8; 32];
@@ -84,9 +105,11 @@ fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
     ))
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssetMeta {
     /// Address of the original token on the source chain.
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub token_address: [u8; 32],
 
     /// Source Chain ID.
@@ -104,16 +127,26 @@ pub struct AssetMeta {
 
 impl AssetMeta {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
-        match parse_payload_asset_meta(input.as_ref()).finish() {
-            Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
-        }
+        parse_exact(parse_payload_asset_meta, input.as_ref())
+    }
+
+    /// Serialize to Wormhole wire format, including the payload ID. Symbol and name are truncated
+    /// to the 32 bytes they have on the wire.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(100);
+        v.push(2);
+        v.extend_from_slice(&self.token_address);
+        v.extend_from_slice(&u16::from(self.token_chain.clone()).to_be_bytes());
+        v.push(self.decimals);
+        v.extend_from_slice(&write_fixed_utf8::<32>(&self.symbol));
+        v.extend_from_slice(&write_fixed_utf8::<32>(&self.name));
+        v
     }
 }
 
 fn parse_payload_asset_meta(input: &[u8]) -> IResult<&[u8], AssetMeta> {
     // Parse Payload.
-    let (i, _) = verify(u8, |&s| s == 0x2)(input.as_ref())?;
+    let (i, _) = verify(u8, |&s| s == 0x2)(input)?;
     let (i, token_address) = parse_fixed(i)?;
     let (i, token_chain) = parse_chain(i)?;
     let (i, decimals) = u8(i)?;
@@ -136,9 +169,11 @@ fn parse_payload_asset_meta(input: &[u8]) -> IResult<&[u8], AssetMeta> {
     ))
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceRegisterChain {
     pub emitter:          Chain,
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub endpoint_address: [u8; 32],
 }
 
@@ -156,10 +191,19 @@ impl GovernanceAction for GovernanceRegisterChain {
             },
         ))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(34);
+        v.extend_from_slice(&u16::from(self.emitter.clone()).to_be_bytes());
+        v.extend_from_slice(&self.endpoint_address);
+        v
+    }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GovernanceContractUpgrade {
+    #[cfg_attr(feature = "serde", serde(with = "crate::json::hex"))]
     pub new_contract: [u8; 32],
 }
 
@@ -170,4 +214,8 @@ impl GovernanceAction for GovernanceContractUpgrade {
         let (i, new_contract) = parse_fixed(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Vec<u8> {
+        self.new_contract.to_vec()
+    }
 }
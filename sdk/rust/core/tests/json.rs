@@ -0,0 +1,130 @@
+//! Checks the JSON representation from the `serde` feature, run with `--features serde`.
+#![cfg(feature = "serde")]
+
+use serde_json::{
+    json,
+    Value,
+};
+use wormhole_core::{
+    vaa::token,
+    Chain,
+    Payload,
+    VAA,
+};
+
+const VECTORS: &str = include_str!("../../../vectors/v1.json");
+
+/// The valid vectors of the shared wire format file, as (name, type, canonical bytes).
+fn vectors() -> Vec<(String, String, Vec<u8>)> {
+    let file: Value = serde_json::from_str(VECTORS).unwrap();
+    file["vectors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["valid"].as_bool().unwrap())
+        .map(|v| {
+            let hex = v["canonical"].as_str().or_else(|| v["hex"].as_str()).unwrap();
+            (
+                v["name"].as_str().unwrap().to_string(),
+                v["type"].as_str().unwrap().to_string(),
+                hex::decode(hex).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_vectors_round_trip() {
+    for (name, kind, bytes) in vectors() {
+        if kind == "vaa" {
+            let vaa = VAA::from_bytes(&bytes).unwrap();
+            let json = serde_json::to_string(&vaa).unwrap();
+            let decoded: VAA = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.to_bytes(), bytes, "{}", name);
+        } else {
+            let payload = Payload::classify(&VAA {
+                payload: bytes.clone(),
+                ..VAA::default()
+            });
+            let json = serde_json::to_string(&payload).unwrap();
+            let decoded: Payload = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, payload, "{}", name);
+            assert_eq!(decoded.to_bytes(), bytes, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_schema() {
+    let transfer = token::Transfer {
+        amount:        1000.into(),
+        token_address: [1; 32],
+        token_chain:   Chain::Ethereum,
+        to:            [2; 32],
+        to_chain:      Chain::Unknown(1000),
+        fee:           0.into(),
+    };
+    let vaa = VAA {
+        version: 1,
+        signatures: vec![[3; 66]],
+        emitter_chain: Chain::Ethereum,
+        emitter_address: [4; 32],
+        sequence: 5,
+        payload: transfer.to_bytes(),
+        ..VAA::default()
+    };
+
+    let expected = json!({
+        "version": 1,
+        "guardian_set_index": 0,
+        "signatures": ["03".repeat(66)],
+        "timestamp": 0,
+        "nonce": 0,
+        "emitter_chain": {"id": 2, "name": "ethereum"},
+        "emitter_address": "04".repeat(32),
+        "sequence": 5,
+        "consistency_level": 0,
+        "payload": hex::encode(transfer.to_bytes()),
+        "decoded": {
+            "type": "token_transfer",
+            "amount": "1000",
+            "token_address": "01".repeat(32),
+            "token_chain": {"id": 2, "name": "ethereum"},
+            "to": "02".repeat(32),
+            "to_chain": {"id": 1000, "name": "unknown"},
+            "fee": "0",
+        },
+    });
+    assert_eq!(serde_json::to_value(&vaa).unwrap(), expected);
+
+    // Chain names are optional on input.
+    let chain: Chain = serde_json::from_value(json!({"id": 3})).unwrap();
+    assert_eq!(chain, Chain::Terra);
+}
+
+#[test]
+fn test_invalid_json() {
+    let transfer = json!({
+        "amount": "1000",
+        "token_address": "01".repeat(32),
+        "token_chain": {"id": 2, "name": "ethereum"},
+        "to": "02".repeat(32),
+        "to_chain": {"id": 1, "name": "solana"},
+        "fee": "0",
+    });
+    assert!(serde_json::from_value::<token::Transfer>(transfer.clone()).is_ok());
+
+    let invalid = [
+        ("token_chain", json!({"id": 2, "name": "solana"})),
+        ("token_address", json!("01".repeat(31))),
+        ("token_address", json!("0x".to_string() + &"01".repeat(31))),
+        ("to", json!("0".repeat(63))),
+        ("amount", json!(1000)),
+        ("fee", json!("-1")),
+    ];
+    for (field, value) in invalid.iter() {
+        let mut transfer = transfer.clone();
+        transfer[*field] = value.clone();
+        assert!(serde_json::from_value::<token::Transfer>(transfer).is_err(), "{}", field);
+    }
+}
@@ -0,0 +1,322 @@
+//! Runs the shared wire format vectors in `sdk/vectors` through the wormhole-core parsers and
+//! serializers. The Solana and Terra test suites load the same file, so a change to the wire
+//! format has to be made to every implementation at once.
+
+use primitive_types::U256;
+use serde_json::Value;
+use wormhole_core::{
+    vaa::{
+        core,
+        nft,
+        token,
+    },
+    Chain,
+    GovernanceAction,
+    Payload,
+    VAA,
+};
+
+const VECTORS: &str = include_str!("../../../vectors/v1.json");
+
+struct Vector {
+    name:      String,
+    kind:      String,
+    bytes:     Vec<u8>,
+    valid:     bool,
+    fields:    Value,
+    canonical: Vec<u8>,
+}
+
+fn vectors() -> Vec<Vector> {
+    let file: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(file["version"], 1);
+    file["vectors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| {
+            let bytes = hex::decode(v["hex"].as_str().unwrap()).unwrap();
+            Vector {
+                name: v["name"].as_str().unwrap().to_string(),
+                kind: v["type"].as_str().unwrap().to_string(),
+                valid: v["valid"].as_bool().unwrap(),
+                fields: v["fields"].clone(),
+                canonical: match v["canonical"].as_str() {
+                    Some(canonical) => hex::decode(canonical).unwrap(),
+                    None => bytes.clone(),
+                },
+                bytes,
+            }
+        })
+        .collect()
+}
+
+fn bytes<const N: usize>(fields: &Value, key: &str) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(&hex::decode(fields[key].as_str().unwrap()).unwrap());
+    out
+}
+
+fn u256(fields: &Value, key: &str) -> U256 {
+    U256::from_big_endian(&bytes::<32>(fields, key))
+}
+
+fn int(fields: &Value, key: &str) -> u64 {
+    fields[key].as_u64().unwrap()
+}
+
+fn chain(fields: &Value, key: &str) -> Chain {
+    Chain::from(int(fields, key) as u16)
+}
+
+fn string(fields: &Value, key: &str) -> String {
+    fields[key].as_str().unwrap().to_string()
+}
+
+/// Parse a governance vector, it must decode to `expected` and re-encode to the canonical bytes.
+fn governance<A: GovernanceAction + PartialEq + std::fmt::Debug>(
+    v: &Vector,
+    expected: impl FnOnce(&Value) -> A,
+) -> bool {
+    match A::from_bytes(&v.bytes, None) {
+        Ok((header, action)) => {
+            assert_eq!(header.chains, chain(&v.fields, "chain"), "{}", v.name);
+            assert_eq!(action, expected(&v.fields), "{}", v.name);
+            assert_eq!(action.to_bytes(header.chains), v.canonical, "{}", v.name);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn check(v: &Vector) -> bool {
+    let f = &v.fields;
+    match v.kind.as_str() {
+        "vaa" => match VAA::from_bytes(&v.bytes) {
+            Ok(vaa) => {
+                let signatures: Vec<String> = vaa.signatures.iter().map(hex::encode).collect();
+                assert_eq!(vaa.version as u64, int(f, "version"));
+                assert_eq!(vaa.guardian_set_index as u64, int(f, "guardian_set_index"));
+                assert_eq!(Value::from(signatures), f["signatures"]);
+                assert_eq!(vaa.timestamp as u64, int(f, "timestamp"));
+                assert_eq!(vaa.nonce as u64, int(f, "nonce"));
+                assert_eq!(vaa.emitter_chain, chain(f, "emitter_chain"));
+                assert_eq!(vaa.emitter_address, bytes::<32>(f, "emitter_address"));
+                assert_eq!(vaa.sequence, int(f, "sequence"));
+                assert_eq!(vaa.consistency_level as u64, int(f, "consistency_level"));
+                assert_eq!(hex::encode(&vaa.payload), string(f, "payload"));
+                assert_eq!(vaa.digest().unwrap().hash, bytes::<32>(f, "hash"));
+                assert_eq!(vaa.to_bytes(), v.bytes, "{}", v.name);
+                true
+            }
+            Err(_) => false,
+        },
+
+        "core_contract_upgrade" => governance(v, |f| core::GovernanceContractUpgrade {
+            new_contract: bytes(f, "new_contract"),
+        }),
+        "core_guardian_set_change" => governance(v, |f| core::GovernanceGuardianSetChange {
+            new_guardian_set_index: int(f, "new_guardian_set_index") as u32,
+            new_guardian_set:       f["new_guardian_set"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|key| {
+                    let mut out = [0u8; 20];
+                    out.copy_from_slice(&hex::decode(key.as_str().unwrap()).unwrap());
+                    out
+                })
+                .collect(),
+        }),
+        "core_set_message_fee" => governance(v, |f| core::GovernanceSetMessageFee {
+            fee: u256(f, "fee"),
+        }),
+        "core_transfer_fees" => governance(v, |f| core::GovernanceTransferFees {
+            amount: u256(f, "amount"),
+            to:     bytes(f, "to"),
+        }),
+
+        "token_transfer" => match token::Transfer::from_bytes(&v.bytes) {
+            Ok(transfer) => {
+                let expected = token::Transfer {
+                    amount:        u256(f, "amount"),
+                    token_address: bytes(f, "token_address"),
+                    token_chain:   chain(f, "token_chain"),
+                    to:            bytes(f, "to"),
+                    to_chain:      chain(f, "to_chain"),
+                    fee:           u256(f, "fee"),
+                };
+                assert_eq!(transfer, expected, "{}", v.name);
+                assert_eq!(transfer.to_bytes(), v.canonical, "{}", v.name);
+                true
+            }
+            Err(_) => false,
+        },
+        "token_asset_meta" => match token::AssetMeta::from_bytes(&v.bytes) {
+            Ok(meta) => {
+                let expected = token::AssetMeta {
+                    token_address: bytes(f, "token_address"),
+                    token_chain:   chain(f, "token_chain"),
+                    decimals:      int(f, "decimals") as u8,
+                    symbol:        string(f, "symbol"),
+                    name:          string(f, "name"),
+                };
+                assert_eq!(meta, expected, "{}", v.name);
+                assert_eq!(meta.to_bytes(), v.canonical, "{}", v.name);
+                true
+            }
+            Err(_) => false,
+        },
+        "token_register_chain" => governance(v, |f| token::GovernanceRegisterChain {
+            emitter:          chain(f, "emitter_chain"),
+            endpoint_address: bytes(f, "endpoint_address"),
+        }),
+        "token_contract_upgrade" => governance(v, |f| token::GovernanceContractUpgrade {
+            new_contract: bytes(f, "new_contract"),
+        }),
+
+        "nft_transfer" => match nft::Transfer::from_bytes(&v.bytes) {
+            Ok(transfer) => {
+                let expected = nft::Transfer {
+                    nft_address: bytes(f, "nft_address"),
+                    nft_chain:   chain(f, "nft_chain"),
+                    symbol:      string(f, "symbol"),
+                    name:        string(f, "name"),
+                    token_id:    u256(f, "token_id"),
+                    uri:         string(f, "uri"),
+                    to:          bytes(f, "to"),
+                    to_chain:    chain(f, "to_chain"),
+                };
+                assert_eq!(transfer, expected, "{}", v.name);
+                assert_eq!(transfer.to_bytes(), v.canonical, "{}", v.name);
+                true
+            }
+            Err(_) => false,
+        },
+        "nft_transfer_v2" => match nft::TransferV2::from_bytes(&v.bytes) {
+            Ok(transfer) => {
+                let creators = f["creators"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|c| nft::Creator {
+                        address:  bytes(c, "address"),
+                        verified: c["verified"].as_bool().unwrap(),
+                        share:    int(c, "share") as u8,
+                    })
+                    .collect();
+                let expected = nft::TransferV2 {
+                    nft_address: bytes(f, "nft_address"),
+                    nft_chain: chain(f, "nft_chain"),
+                    symbol: string(f, "symbol"),
+                    name: string(f, "name"),
+                    token_id: u256(f, "token_id"),
+                    uri: string(f, "uri"),
+                    collection: bytes(f, "collection"),
+                    seller_fee_basis_points: int(f, "seller_fee_basis_points") as u16,
+                    creators,
+                    to: bytes(f, "to"),
+                    to_chain: chain(f, "to_chain"),
+                };
+                assert_eq!(transfer, expected, "{}", v.name);
+                assert_eq!(transfer.to_bytes(), v.canonical, "{}", v.name);
+                true
+            }
+            Err(_) => false,
+        },
+        "nft_batch_transfer" => match nft::BatchTransfer::from_bytes(&v.bytes) {
+            Ok(transfer) => {
+                let tokens = f["tokens"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|t| nft::BatchToken {
+                        token_id: u256(t, "token_id"),
+                        uri:      string(t, "uri"),
+                    })
+                    .collect();
+                let expected = nft::BatchTransfer {
+                    nft_address: bytes(f, "nft_address"),
+                    nft_chain: chain(f, "nft_chain"),
+                    symbol: string(f, "symbol"),
+                    name: string(f, "name"),
+                    tokens,
+                    to: bytes(f, "to"),
+                    to_chain: chain(f, "to_chain"),
+                };
+                assert_eq!(transfer, expected, "{}", v.name);
+                assert_eq!(transfer.to_bytes(), v.canonical, "{}", v.name);
+                true
+            }
+            Err(_) => false,
+        },
+        "nft_semi_fungible_transfer" => match nft::SemiFungibleTransfer::from_bytes(&v.bytes) {
+            Ok(transfer) => {
+                let expected = nft::SemiFungibleTransfer {
+                    nft_address: bytes(f, "nft_address"),
+                    nft_chain:   chain(f, "nft_chain"),
+                    symbol:      string(f, "symbol"),
+                    name:        string(f, "name"),
+                    token_id:    u256(f, "token_id"),
+                    amount:      u256(f, "amount"),
+                    uri:         string(f, "uri"),
+                    to:          bytes(f, "to"),
+                    to_chain:    chain(f, "to_chain"),
+                };
+                assert_eq!(transfer, expected, "{}", v.name);
+                assert_eq!(transfer.to_bytes(), v.canonical, "{}", v.name);
+                true
+            }
+            Err(_) => false,
+        },
+        "nft_register_chain" => governance(v, |f| nft::GovernanceRegisterChain {
+            emitter:          chain(f, "emitter_chain"),
+            endpoint_address: bytes(f, "endpoint_address"),
+        }),
+        "nft_contract_upgrade" => governance(v, |f| nft::GovernanceContractUpgrade {
+            new_contract: bytes(f, "new_contract"),
+        }),
+
+        kind => panic!("{}: unknown vector type {}", v.name, kind),
+    }
+}
+
+#[test]
+fn test_vectors() {
+    for v in vectors() {
+        assert_eq!(check(&v), v.valid, "{}", v.name);
+    }
+}
+
+/// The vector type a classified payload belongs to.
+fn payload_kind(payload: &Payload) -> &'static str {
+    match payload {
+        Payload::TokenTransfer(_) => "token_transfer",
+        Payload::AssetMeta(_) => "token_asset_meta",
+        Payload::NftTransfer(_) => "nft_transfer",
+        Payload::NftTransferV2(_) => "nft_transfer_v2",
+        Payload::NftBatchTransfer(_) => "nft_batch_transfer",
+        Payload::NftSemiFungibleTransfer(_) => "nft_semi_fungible_transfer",
+        Payload::AlephiumTransfer(_) => "alephium_transfer",
+        Payload::CoreContractUpgrade(..) => "core_contract_upgrade",
+        Payload::GuardianSetChange(..) => "core_guardian_set_change",
+        Payload::SetMessageFee(..) => "core_set_message_fee",
+        Payload::TransferFees(..) => "core_transfer_fees",
+        Payload::TokenRegisterChain(..) => "token_register_chain",
+        Payload::TokenContractUpgrade(..) => "token_contract_upgrade",
+        Payload::NftRegisterChain(..) => "nft_register_chain",
+        Payload::NftContractUpgrade(..) => "nft_contract_upgrade",
+        Payload::Unknown(_) => "unknown",
+    }
+}
+
+#[test]
+fn test_classify_vectors() {
+    for v in vectors().into_iter().filter(|v| v.valid && v.kind != "vaa") {
+        let vaa = VAA {
+            payload: v.bytes.clone(),
+            ..VAA::default()
+        };
+        assert_eq!(payload_kind(&Payload::classify(&vaa)), v.kind, "{}", v.name);
+    }
+}
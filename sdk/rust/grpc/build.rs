@@ -0,0 +1,23 @@
+//! Generates the gRPC clients and servers from the protobuf definitions shared with the guardian
+//! node. `protoc` comes from `protoc-bin-vendored` so building does not need it installed, and the
+//! googleapis files buf would fetch are vendored in `third_party`.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let protos = "../../../proto";
+    let googleapis = "third_party/googleapis";
+
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    let well_known = protoc_bin_vendored::include_path()?;
+
+    tonic_build::configure().compile_protos(
+        &[
+            format!("{}/spy/v1/spy.proto", protos),
+            format!("{}/publicrpc/v1/publicrpc.proto", protos),
+        ],
+        &[protos, googleapis, &well_known.to_string_lossy()],
+    )?;
+
+    println!("cargo:rerun-if-changed={}", protos);
+    println!("cargo:rerun-if-changed={}", googleapis);
+    Ok(())
+}
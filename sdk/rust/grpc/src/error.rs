@@ -0,0 +1,73 @@
+use std::fmt;
+
+use wormhole_core::WormholeError;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Connecting to the service failed.
+    Transport(tonic::transport::Error),
+
+    /// The service answered with an error status. Boxed, a `Status` is larger than every other
+    /// variant together.
+    Status(Box<tonic::Status>),
+
+    /// The service returned bytes that do not parse as a VAA.
+    InvalidVAA(WormholeError),
+
+    /// The digest guardians sign could not be computed for the VAA.
+    InvalidDigest,
+
+    /// A guardian address from the service is not 20 bytes of `0x` prefixed hex.
+    InvalidGuardianSet,
+
+    /// The VAA was signed by another guardian set than the one it was checked against. Expected
+    /// while the guardian set is being rotated.
+    WrongGuardianSet { expected: u32, found: u32 },
+
+    /// Fewer guardians signed the VAA than the quorum of the guardian set.
+    NoQuorum,
+
+    /// Signatures are not in strictly ascending guardian order.
+    InvalidSignatureOrder,
+
+    /// A signature names a guardian index past the end of the guardian set.
+    UnknownGuardian(u8),
+
+    /// The signature of this guardian does not recover to its address.
+    InvalidSignature(u8),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Transport(e) => write!(f, "transport error: {}", e),
+            Error::Status(status) => write!(f, "rpc error: {}", status),
+            Error::InvalidVAA(e) => write!(f, "invalid VAA: {:?}", e),
+            Error::InvalidDigest => write!(f, "VAA digest could not be computed"),
+            Error::InvalidGuardianSet => write!(f, "invalid guardian address"),
+            Error::WrongGuardianSet { expected, found } => write!(
+                f,
+                "VAA is signed by guardian set {}, expected {}",
+                found, expected
+            ),
+            Error::NoQuorum => write!(f, "VAA does not have a quorum of signatures"),
+            Error::InvalidSignatureOrder => write!(f, "VAA signatures are out of order"),
+            Error::UnknownGuardian(index) => write!(f, "guardian {} is not in the set", index),
+            Error::InvalidSignature(index) => write!(f, "invalid signature from guardian {}", index),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<tonic::transport::Error> for Error {
+    fn from(e: tonic::transport::Error) -> Self {
+        Error::Transport(e)
+    }
+}
+
+impl From<tonic::Status> for Error {
+    fn from(status: tonic::Status) -> Self {
+        Error::Status(Box::new(status))
+    }
+}
@@ -0,0 +1,150 @@
+//! Guardian sets and VAA signature verification, matching `VerifySignatures` in the guardian node.
+
+use std::convert::TryFrom;
+
+use k256::ecdsa::recoverable::{
+    Id as RecoverableId,
+    Signature as RecoverableSignature,
+};
+use k256::ecdsa::Signature;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::FieldBytes;
+use sha3::{
+    Digest,
+    Keccak256,
+};
+use wormhole_core::vaa::Signature as VAASignature;
+use wormhole_core::VAA;
+
+use crate::proto::publicrpc::v1 as proto;
+use crate::Error;
+
+/// A 20 byte guardian address, the last 20 bytes of the Keccak hash of the guardian public key.
+pub type GuardianAddress = [u8; 20];
+
+/// A guardian set as served by the public RPC service, with its addresses decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GuardianSet {
+    pub index:     u32,
+    pub addresses: Vec<GuardianAddress>,
+}
+
+impl GuardianSet {
+    /// The number of signatures a VAA needs, more than two thirds of the guardians.
+    pub fn quorum(&self) -> usize {
+        (self.addresses.len() * 10 / 3) * 2 / 10 + 1
+    }
+
+    /// Check that a quorum of this set signed `vaa`. Signatures must be in strictly ascending
+    /// guardian order, as every Wormhole contract requires.
+    pub fn verify(&self, vaa: &VAA) -> Result<(), Error> {
+        if vaa.guardian_set_index != self.index {
+            return Err(Error::WrongGuardianSet {
+                expected: self.index,
+                found:    vaa.guardian_set_index,
+            });
+        }
+        if vaa.signatures.len() < self.quorum() {
+            return Err(Error::NoQuorum);
+        }
+
+        let hash = signing_hash(vaa)?;
+        let mut last = None;
+        for signature in &vaa.signatures {
+            let index = signature[0];
+            if last.is_some_and(|last| index <= last) {
+                return Err(Error::InvalidSignatureOrder);
+            }
+            last = Some(index);
+
+            let address = self
+                .addresses
+                .get(index as usize)
+                .ok_or(Error::UnknownGuardian(index))?;
+            if recover(&hash, signature).as_ref() != Some(address) {
+                return Err(Error::InvalidSignature(index));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<proto::GuardianSet> for GuardianSet {
+    type Error = Error;
+
+    fn try_from(set: proto::GuardianSet) -> Result<Self, Error> {
+        let addresses = set
+            .addresses
+            .iter()
+            .map(|address| {
+                let bytes = address
+                    .strip_prefix("0x")
+                    .and_then(|address| hex::decode(address).ok())
+                    .ok_or(Error::InvalidGuardianSet)?;
+                GuardianAddress::try_from(bytes).map_err(|_| Error::InvalidGuardianSet)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(GuardianSet {
+            index: set.index,
+            addresses,
+        })
+    }
+}
+
+impl From<&GuardianSet> for proto::GuardianSet {
+    fn from(set: &GuardianSet) -> Self {
+        proto::GuardianSet {
+            index:     set.index,
+            addresses: set
+                .addresses
+                .iter()
+                .map(|address| format!("0x{}", hex::encode(address)))
+                .collect(),
+        }
+    }
+}
+
+/// The hash guardians sign, `keccak256(keccak256(body))`.
+pub fn signing_hash(vaa: &VAA) -> Result<[u8; 32], Error> {
+    let digest = vaa.digest().ok_or(Error::InvalidDigest)?;
+    Ok(Keccak256::digest(&digest.hash).into())
+}
+
+/// The address of the guardian that made `signature` over `hash`.
+pub fn recover(hash: &[u8; 32], signature: &VAASignature) -> Option<GuardianAddress> {
+    let id = RecoverableId::new(signature[65]).ok()?;
+    let signature = Signature::try_from(&signature[1..65]).ok()?;
+    let signature = RecoverableSignature::new(&signature, id).ok()?;
+    let key = signature
+        .recover_verify_key_from_digest_bytes(FieldBytes::from_slice(hash))
+        .ok()?;
+    let point = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+    GuardianAddress::try_from(&hash[12..]).ok()
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_devnet_signature() {
+        // A token bridge registration signed by the single devnet guardian.
+        let vaa = hex::decode("01000000000100c9f4230109e378f7efc0605fb40f0e1869f2d82fda5b1dfad8a5a2dafee85e033d155c18641165a77a2db6a7afbf2745b458616cb59347e89ae0c7aa3e7cc2d400000000010000000100010000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000546f6b656e4272696467650100000001c69a1b1a65dd336bf1df6a77afb501fc25db7fc0938cb08595a9ef473265cb4f").unwrap();
+        let mut vaa = VAA::from_bytes(vaa).unwrap();
+        let devnet = GuardianSet::try_from(proto::GuardianSet {
+            index:     0,
+            addresses: vec!["0xbeFA429d57cD18b7F8A4d91A2da9AB4AF05d0FBe".into()],
+        })
+        .unwrap();
+        assert_eq!(devnet.quorum(), 1);
+        assert!(devnet.verify(&vaa).is_ok());
+
+        vaa.sequence += 1;
+        assert!(matches!(devnet.verify(&vaa), Err(Error::InvalidSignature(0))));
+
+        // The devnet set has a single guardian, index 1 is not part of it.
+        vaa.signatures[0][0] = 1;
+        assert!(matches!(devnet.verify(&vaa), Err(Error::UnknownGuardian(1))));
+    }
+}
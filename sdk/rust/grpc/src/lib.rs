@@ -0,0 +1,66 @@
+//! Clients for the gRPC services of a guardian node: the spy, which streams the signed VAAs the
+//! node sees on gossip, and the public RPC service, which serves stored VAAs, heartbeats and the
+//! current guardian set.
+//!
+//! The clients are generated from `proto/` at the root of the repository and available as is in
+//! `proto`. `SpyClient` and `PublicRpcClient` wrap them to return parsed `wormhole_core` types:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), wormhole_grpc::Error> {
+//! use wormhole_grpc::{PublicRpcClient, SpyClient};
+//!
+//! let guardian_set = PublicRpcClient::connect("http://localhost:7070")
+//!     .await?
+//!     .current_guardian_set()
+//!     .await?;
+//! let mut vaas = SpyClient::connect("http://localhost:7072")
+//!     .await?
+//!     .subscribe(&[])
+//!     .await?
+//!     .verify(guardian_set);
+//! while let Some(vaa) = vaas.message().await {
+//!     println!("{:?}", vaa?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `mock` module serves both services in-process, for tests. It is built with the `mock`
+//! feature.
+
+pub mod proto {
+    pub mod gossip {
+        pub mod v1 {
+            tonic::include_proto!("gossip.v1");
+        }
+    }
+
+    pub mod publicrpc {
+        pub mod v1 {
+            tonic::include_proto!("publicrpc.v1");
+        }
+    }
+
+    pub mod spy {
+        pub mod v1 {
+            tonic::include_proto!("spy.v1");
+        }
+    }
+}
+
+pub mod guardian;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
+mod error;
+mod publicrpc;
+mod spy;
+
+pub use error::Error;
+pub use guardian::GuardianSet;
+pub use publicrpc::PublicRpcClient;
+pub use spy::{
+    EmitterFilter,
+    SpyClient,
+    SpyStream,
+};
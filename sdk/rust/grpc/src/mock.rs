@@ -0,0 +1,267 @@
+//! An in-process guardian that serves the spy and public RPC services, for testing services built
+//! on this crate without a devnet. VAAs are published to it directly and signed by deterministic
+//! test keys.
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
+use k256::ecdsa::recoverable;
+use k256::ecdsa::signature::DigestSigner;
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use sha3::{
+    Digest,
+    Keccak256,
+};
+use tokio::net::TcpListener;
+use tokio::sync::{
+    broadcast,
+    oneshot,
+};
+use tokio_stream::wrappers::{
+    BroadcastStream,
+    TcpListenerStream,
+};
+use tokio_stream::{
+    Stream,
+    StreamExt,
+};
+use tonic::{
+    Request,
+    Response,
+    Status,
+};
+use wormhole_core::VAA;
+
+use crate::guardian::GuardianAddress;
+use crate::proto::publicrpc::v1::get_last_heartbeats_response::Entry;
+use crate::proto::publicrpc::v1::public_rpc_service_server::{
+    PublicRpcService,
+    PublicRpcServiceServer,
+};
+use crate::proto::publicrpc::v1::{
+    GetCurrentGuardianSetRequest,
+    GetCurrentGuardianSetResponse,
+    GetLastHeartbeatsRequest,
+    GetLastHeartbeatsResponse,
+    GetSignedVaaRequest,
+    GetSignedVaaResponse,
+};
+use crate::proto::spy::v1::filter_entry::Filter;
+use crate::proto::spy::v1::spy_rpc_service_server::{
+    SpyRpcService,
+    SpyRpcServiceServer,
+};
+use crate::proto::spy::v1::{
+    EmitterFilter,
+    SubscribeSignedVaaRequest,
+    SubscribeSignedVaaResponse,
+};
+use crate::GuardianSet;
+
+/// A guardian set with known private keys.
+pub struct Guardians {
+    index: u32,
+    keys:  Vec<SigningKey>,
+}
+
+impl Guardians {
+    /// `count` guardians for set `index`. Keys are derived from their position, so every run
+    /// produces the same addresses and signatures.
+    pub fn new(index: u32, count: usize) -> Self {
+        let keys = (0..count)
+            .map(|i| SigningKey::from_bytes(&[i as u8 + 1; 32]).unwrap())
+            .collect();
+        Self { index, keys }
+    }
+
+    pub fn guardian_set(&self) -> GuardianSet {
+        let addresses = self
+            .keys
+            .iter()
+            .map(|key| {
+                let point = key.verifying_key().to_encoded_point(false);
+                let hash = Keccak256::digest(&point.as_bytes()[1..]);
+                let mut address = GuardianAddress::default();
+                address.copy_from_slice(&hash[12..]);
+                address
+            })
+            .collect();
+        GuardianSet {
+            index: self.index,
+            addresses,
+        }
+    }
+
+    /// Sign `vaa` with the guardians at `signers`, which should be in ascending order. Replaces
+    /// any signatures and the guardian set index already on the VAA.
+    pub fn sign(&self, vaa: &mut VAA, signers: &[u8]) {
+        vaa.guardian_set_index = self.index;
+        let hash = vaa.digest().unwrap().hash;
+        vaa.signatures = signers
+            .iter()
+            .map(|&index| {
+                let digest = Keccak256::new().chain(hash);
+                let signature: recoverable::Signature =
+                    self.keys[index as usize].sign_digest(digest);
+                let mut bytes = [0u8; 66];
+                bytes[0] = index;
+                bytes[1..].copy_from_slice(signature.as_ref());
+                bytes
+            })
+            .collect();
+    }
+
+    /// Sign `vaa` with every guardian in the set.
+    pub fn sign_all(&self, vaa: &mut VAA) {
+        let signers: Vec<u8> = (0..self.keys.len() as u8).collect();
+        self.sign(vaa, &signers)
+    }
+}
+
+struct State {
+    guardian_set: Mutex<GuardianSet>,
+    heartbeats:   Mutex<Vec<Entry>>,
+    vaas:         Mutex<Vec<VAA>>,
+    published:    broadcast::Sender<VAA>,
+}
+
+/// Serves both services on a local port until dropped.
+pub struct MockServer {
+    address:   SocketAddr,
+    state:     Arc<State>,
+    _shutdown: oneshot::Sender<()>,
+}
+
+impl MockServer {
+    /// Start serving on a free local port, reporting `guardian_set` as the current set.
+    pub async fn start(guardian_set: GuardianSet) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(State {
+            guardian_set: Mutex::new(guardian_set),
+            heartbeats:   Mutex::new(Vec::new()),
+            vaas:         Mutex::new(Vec::new()),
+            published:    broadcast::channel(1024).0,
+        });
+
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        let server = tonic::transport::Server::builder()
+            .add_service(SpyRpcServiceServer::new(Service(state.clone())))
+            .add_service(PublicRpcServiceServer::new(Service(state.clone())))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                let _ = stopped.await;
+            });
+        tokio::spawn(server);
+
+        Ok(Self {
+            address,
+            state,
+            _shutdown: shutdown,
+        })
+    }
+
+    /// The URL clients connect to.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Make `vaa` available from `GetSignedVAA` and stream it to current spy subscribers.
+    pub fn publish(&self, vaa: VAA) {
+        self.state.vaas.lock().unwrap().push(vaa.clone());
+        let _ = self.state.published.send(vaa);
+    }
+
+    pub fn set_guardian_set(&self, guardian_set: GuardianSet) {
+        *self.state.guardian_set.lock().unwrap() = guardian_set;
+    }
+
+    pub fn set_heartbeats(&self, heartbeats: Vec<Entry>) {
+        *self.state.heartbeats.lock().unwrap() = heartbeats;
+    }
+}
+
+struct Service(Arc<State>);
+
+fn matches(filter: &EmitterFilter, vaa: &VAA) -> bool {
+    filter.chain_id == i32::from(u16::from(vaa.emitter_chain.clone()))
+        && hex::decode(&filter.emitter_address).ok().as_deref() == Some(&vaa.emitter_address[..])
+}
+
+// The service traits fix `Status` as the error type.
+#[allow(clippy::result_large_err)]
+#[tonic::async_trait]
+impl SpyRpcService for Service {
+    type SubscribeSignedVAAStream =
+        Pin<Box<dyn Stream<Item = Result<SubscribeSignedVaaResponse, Status>> + Send>>;
+
+    async fn subscribe_signed_vaa(
+        &self,
+        request: Request<SubscribeSignedVaaRequest>,
+    ) -> Result<Response<Self::SubscribeSignedVAAStream>, Status> {
+        let filters: Vec<EmitterFilter> = request
+            .into_inner()
+            .filters
+            .into_iter()
+            .filter_map(|entry| entry.filter.map(|Filter::EmitterFilter(filter)| filter))
+            .collect();
+
+        let stream = BroadcastStream::new(self.0.published.subscribe()).filter_map(move |vaa| {
+            let vaa = vaa.ok()?;
+            let wanted = filters.is_empty() || filters.iter().any(|f| matches(f, &vaa));
+            wanted.then(|| {
+                Ok(SubscribeSignedVaaResponse {
+                    vaa_bytes: vaa.to_bytes(),
+                })
+            })
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+#[tonic::async_trait]
+impl PublicRpcService for Service {
+    async fn get_last_heartbeats(
+        &self,
+        _: Request<GetLastHeartbeatsRequest>,
+    ) -> Result<Response<GetLastHeartbeatsResponse>, Status> {
+        let entries = self.0.heartbeats.lock().unwrap().clone();
+        Ok(Response::new(GetLastHeartbeatsResponse { entries }))
+    }
+
+    async fn get_signed_vaa(
+        &self,
+        request: Request<GetSignedVaaRequest>,
+    ) -> Result<Response<GetSignedVaaResponse>, Status> {
+        let id = request
+            .into_inner()
+            .message_id
+            .ok_or_else(|| Status::invalid_argument("no message ID given"))?;
+        let filter = EmitterFilter {
+            chain_id:        id.emitter_chain,
+            emitter_address: id.emitter_address,
+        };
+        let vaas = self.0.vaas.lock().unwrap();
+        let vaa = vaas
+            .iter()
+            .find(|vaa| matches(&filter, vaa) && vaa.sequence == id.sequence)
+            .ok_or_else(|| Status::not_found("requested VAA not found in store"))?;
+        Ok(Response::new(GetSignedVaaResponse {
+            vaa_bytes: vaa.to_bytes(),
+        }))
+    }
+
+    async fn get_current_guardian_set(
+        &self,
+        _: Request<GetCurrentGuardianSetRequest>,
+    ) -> Result<Response<GetCurrentGuardianSetResponse>, Status> {
+        let guardian_set = &*self.0.guardian_set.lock().unwrap();
+        Ok(Response::new(GetCurrentGuardianSetResponse {
+            guardian_set: Some(guardian_set.into()),
+        }))
+    }
+}
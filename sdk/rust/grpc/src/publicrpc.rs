@@ -0,0 +1,88 @@
+use std::convert::TryFrom;
+
+use tonic::transport::Channel;
+use wormhole_core::vaa::ForeignAddress;
+use wormhole_core::{
+    Chain,
+    VAA,
+};
+
+use crate::proto::publicrpc::v1::get_last_heartbeats_response::Entry;
+use crate::proto::publicrpc::v1::public_rpc_service_client::PublicRpcServiceClient;
+use crate::proto::publicrpc::v1::{
+    GetCurrentGuardianSetRequest,
+    GetLastHeartbeatsRequest,
+    GetSignedVaaRequest,
+    MessageId,
+};
+use crate::{
+    Error,
+    GuardianSet,
+};
+
+/// Client for the public RPC service of a guardian node.
+#[derive(Clone, Debug)]
+pub struct PublicRpcClient {
+    inner: PublicRpcServiceClient<Channel>,
+}
+
+impl PublicRpcClient {
+    /// Connect to a guardian's public RPC endpoint, such as `http://localhost:7070`.
+    pub async fn connect(endpoint: impl Into<String>) -> Result<Self, Error> {
+        let inner = PublicRpcServiceClient::connect(endpoint.into()).await?;
+        Ok(Self { inner })
+    }
+
+    /// Use an existing channel, for example one shared with a `SpyClient`.
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            inner: PublicRpcServiceClient::new(channel),
+        }
+    }
+
+    /// Fetch the signed VAA for a message. Fails with a `NotFound` status when the guardian has
+    /// not seen it.
+    pub async fn signed_vaa(
+        &mut self,
+        chain: Chain,
+        emitter: &ForeignAddress,
+        sequence: u64,
+    ) -> Result<VAA, Error> {
+        let request = GetSignedVaaRequest {
+            message_id: Some(MessageId {
+                emitter_chain: u16::from(chain).into(),
+                emitter_address: hex::encode(emitter),
+                sequence,
+            }),
+        };
+        let response = self.inner.get_signed_vaa(request).await?.into_inner();
+        VAA::from_bytes(&response.vaa_bytes).map_err(Error::InvalidVAA)
+    }
+
+    /// Fetch the guardian set the node currently considers active.
+    pub async fn current_guardian_set(&mut self) -> Result<GuardianSet, Error> {
+        let response = self
+            .inner
+            .get_current_guardian_set(GetCurrentGuardianSetRequest {})
+            .await?
+            .into_inner();
+        let set = response.guardian_set.ok_or(Error::InvalidGuardianSet)?;
+        GuardianSet::try_from(set)
+    }
+
+    /// The last heartbeat of each guardian in the active set. Heartbeats are passed on as
+    /// received and, apart from the guardian address, are not verified.
+    pub async fn last_heartbeats(&mut self) -> Result<Vec<Entry>, Error> {
+        let response = self
+            .inner
+            .get_last_heartbeats(GetLastHeartbeatsRequest {})
+            .await?
+            .into_inner();
+        Ok(response.entries)
+    }
+
+    /// The generated client, for calls this wrapper does not cover.
+    pub fn inner(&mut self) -> &mut PublicRpcServiceClient<Channel> {
+        &mut self.inner
+    }
+}
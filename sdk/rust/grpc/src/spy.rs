@@ -0,0 +1,124 @@
+use std::pin::Pin;
+use std::task::{
+    Context,
+    Poll,
+};
+
+use tokio_stream::Stream;
+use tonic::transport::Channel;
+use tonic::Streaming;
+use wormhole_core::vaa::ForeignAddress;
+use wormhole_core::{
+    Chain,
+    VAA,
+};
+
+use crate::proto::spy::v1::filter_entry::Filter;
+use crate::proto::spy::v1::spy_rpc_service_client::SpyRpcServiceClient;
+use crate::proto::spy::v1::{
+    self as proto,
+    FilterEntry,
+    SubscribeSignedVaaRequest,
+    SubscribeSignedVaaResponse,
+};
+use crate::{
+    Error,
+    GuardianSet,
+};
+
+/// Selects the VAAs of a single emitter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmitterFilter {
+    pub chain:   Chain,
+    pub address: ForeignAddress,
+}
+
+impl From<&EmitterFilter> for FilterEntry {
+    fn from(filter: &EmitterFilter) -> Self {
+        FilterEntry {
+            filter: Some(Filter::EmitterFilter(proto::EmitterFilter {
+                chain_id:        u16::from(filter.chain.clone()).into(),
+                emitter_address: hex::encode(filter.address),
+            })),
+        }
+    }
+}
+
+/// Client for the spy service, which streams the signed VAAs a guardian node sees on gossip.
+#[derive(Clone, Debug)]
+pub struct SpyClient {
+    inner: SpyRpcServiceClient<Channel>,
+}
+
+impl SpyClient {
+    /// Connect to a spy endpoint, such as `http://localhost:7072`.
+    pub async fn connect(endpoint: impl Into<String>) -> Result<Self, Error> {
+        let inner = SpyRpcServiceClient::connect(endpoint.into()).await?;
+        Ok(Self { inner })
+    }
+
+    /// Use an existing channel, for example one shared with a `PublicRpcClient`.
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            inner: SpyRpcServiceClient::new(channel),
+        }
+    }
+
+    /// Subscribe to the VAAs of any of `filters`, or to all VAAs when there are none.
+    pub async fn subscribe(&mut self, filters: &[EmitterFilter]) -> Result<SpyStream, Error> {
+        let request = SubscribeSignedVaaRequest {
+            filters: filters.iter().map(FilterEntry::from).collect(),
+        };
+        let inner = self.inner.subscribe_signed_vaa(request).await?.into_inner();
+        Ok(SpyStream {
+            inner,
+            guardian_set: None,
+        })
+    }
+
+    /// The generated client, for calls this wrapper does not cover.
+    pub fn inner(&mut self) -> &mut SpyRpcServiceClient<Channel> {
+        &mut self.inner
+    }
+}
+
+/// A stream of parsed VAAs from the spy. VAAs that do not parse, or fail verification when a
+/// guardian set is given, are yielded as errors and the stream carries on.
+#[derive(Debug)]
+pub struct SpyStream {
+    inner:        Streaming<SubscribeSignedVaaResponse>,
+    guardian_set: Option<GuardianSet>,
+}
+
+impl SpyStream {
+    /// Verify the signatures of every VAA against `guardian_set`, usually the one returned by
+    /// `PublicRpcClient::current_guardian_set`.
+    pub fn verify(mut self, guardian_set: GuardianSet) -> Self {
+        self.guardian_set = Some(guardian_set);
+        self
+    }
+
+    /// The next VAA, or `None` once the server closes the stream.
+    pub async fn message(&mut self) -> Option<Result<VAA, Error>> {
+        let response = self.inner.message().await;
+        response.transpose().map(|response| self.parse(response))
+    }
+
+    fn parse(&self, response: Result<SubscribeSignedVaaResponse, tonic::Status>) -> Result<VAA, Error> {
+        let vaa = VAA::from_bytes(&response?.vaa_bytes).map_err(Error::InvalidVAA)?;
+        if let Some(guardian_set) = &self.guardian_set {
+            guardian_set.verify(&vaa)?;
+        }
+        Ok(vaa)
+    }
+}
+
+impl Stream for SpyStream {
+    type Item = Result<VAA, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|response| response.map(|response| self.parse(response)))
+    }
+}
@@ -0,0 +1,131 @@
+//! Runs the clients against the in-process mock guardian.
+
+use wormhole_core::{
+    Chain,
+    VAA,
+};
+use wormhole_grpc::mock::{
+    Guardians,
+    MockServer,
+};
+use wormhole_grpc::proto::publicrpc::v1::get_last_heartbeats_response::Entry;
+use wormhole_grpc::{
+    EmitterFilter,
+    Error,
+    PublicRpcClient,
+    SpyClient,
+};
+
+fn vaa(emitter_address: [u8; 32], sequence: u64, payload: &[u8]) -> VAA {
+    VAA {
+        version: 1,
+        timestamp: 1,
+        emitter_chain: Chain::Ethereum,
+        emitter_address,
+        sequence,
+        payload: payload.to_vec(),
+        ..VAA::default()
+    }
+}
+
+#[tokio::test]
+async fn test_spy_stream() {
+    let guardians = Guardians::new(0, 4);
+    let server = MockServer::start(guardians.guardian_set()).await.unwrap();
+    let guardian_set = PublicRpcClient::connect(server.endpoint())
+        .await
+        .unwrap()
+        .current_guardian_set()
+        .await
+        .unwrap();
+    assert_eq!(guardian_set, guardians.guardian_set());
+
+    let filter = EmitterFilter {
+        chain:   Chain::Ethereum,
+        address: [1; 32],
+    };
+    let mut stream = SpyClient::connect(server.endpoint())
+        .await
+        .unwrap()
+        .subscribe(&[filter])
+        .await
+        .unwrap()
+        .verify(guardian_set);
+
+    // Only VAAs from the filtered emitter come through.
+    let mut other = vaa([2; 32], 0, b"other");
+    guardians.sign_all(&mut other);
+    server.publish(other);
+    let mut signed = vaa([1; 32], 1, b"hello");
+    guardians.sign_all(&mut signed);
+    server.publish(signed.clone());
+    assert_eq!(stream.message().await.unwrap().unwrap(), signed);
+
+    // Two of four guardians are not a quorum.
+    let mut short = vaa([1; 32], 2, b"short");
+    guardians.sign(&mut short, &[0, 1]);
+    server.publish(short);
+    assert!(matches!(stream.message().await.unwrap(), Err(Error::NoQuorum)));
+
+    // Signatures have to cover the body as published.
+    let mut tampered = vaa([1; 32], 3, b"tampered");
+    guardians.sign(&mut tampered, &[0, 1, 3]);
+    tampered.payload = b"changed".to_vec();
+    server.publish(tampered);
+    assert!(matches!(stream.message().await.unwrap(), Err(Error::InvalidSignature(0))));
+
+    // Out of order signers are rejected.
+    let mut unordered = vaa([1; 32], 4, b"unordered");
+    guardians.sign(&mut unordered, &[2, 0, 1]);
+    server.publish(unordered);
+    assert!(matches!(stream.message().await.unwrap(), Err(Error::InvalidSignatureOrder)));
+
+    // VAAs from another guardian set.
+    let mut rotated = vaa([1; 32], 5, b"rotated");
+    Guardians::new(1, 4).sign_all(&mut rotated);
+    server.publish(rotated);
+    assert!(matches!(
+        stream.message().await.unwrap(),
+        Err(Error::WrongGuardianSet {
+            expected: 0,
+            found:    1,
+        })
+    ));
+
+    // The stream survives failed VAAs.
+    let mut last = vaa([1; 32], 6, b"last");
+    guardians.sign(&mut last, &[1, 2, 3]);
+    server.publish(last.clone());
+    assert_eq!(stream.message().await.unwrap().unwrap(), last);
+}
+
+#[tokio::test]
+async fn test_public_rpc() {
+    let guardians = Guardians::new(0, 1);
+    let server = MockServer::start(guardians.guardian_set()).await.unwrap();
+    let mut client = PublicRpcClient::connect(server.endpoint()).await.unwrap();
+
+    let mut signed = vaa([1; 32], 7, b"hello");
+    guardians.sign_all(&mut signed);
+    server.publish(signed.clone());
+
+    let fetched = client.signed_vaa(Chain::Ethereum, &[1; 32], 7).await.unwrap();
+    assert_eq!(fetched, signed);
+    assert!(guardians.guardian_set().verify(&fetched).is_ok());
+
+    let missing = client.signed_vaa(Chain::Ethereum, &[1; 32], 8).await;
+    assert!(matches!(missing, Err(Error::Status(s)) if s.code() == tonic::Code::NotFound));
+
+    assert!(client.last_heartbeats().await.unwrap().is_empty());
+    let entry = Entry {
+        verified_guardian_addr: "0x0000000000000000000000000000000000000001".into(),
+        p2p_node_addr:          "node".into(),
+        raw_heartbeat:          None,
+    };
+    server.set_heartbeats(vec![entry.clone()]);
+    assert_eq!(client.last_heartbeats().await.unwrap(), vec![entry]);
+
+    let rotated = Guardians::new(1, 3).guardian_set();
+    server.set_guardian_set(rotated.clone());
+    assert_eq!(client.current_guardian_set().await.unwrap(), rotated);
+}
@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::token::AssetMeta;
+
+fuzz_target!(|data: &[u8]| {
+    // Invalid UTF-8 and padding are dropped from symbol and name, so only the parsed form has to
+    // survive a round trip.
+    if let Ok(meta) = AssetMeta::from_bytes(data) {
+        assert_eq!(AssetMeta::from_bytes(meta.to_bytes()).unwrap(), meta);
+    }
+});
@@ -0,0 +1,20 @@
+#![no_main]
+use bridge::vaa::DeserializePayload;
+use libfuzzer_sys::fuzz_target;
+use token_bridge::messages::PayloadTransfer;
+use wormhole_sdk::vaa::token::Transfer;
+
+fuzz_target!(|data: &[u8]| {
+    let core = Transfer::from_bytes(data);
+    let solana = PayloadTransfer::deserialize(&mut &data[..]);
+    assert_eq!(core.is_ok(), solana.is_ok());
+
+    if let (Ok(core), Ok(solana)) = (core, solana) {
+        assert_eq!(solana.amount, core.amount);
+        assert_eq!(solana.token_address, core.token_address);
+        assert_eq!(solana.token_chain, u16::from(core.token_chain));
+        assert_eq!(solana.to, core.to);
+        assert_eq!(solana.to_chain, u16::from(core.to_chain));
+        assert_eq!(solana.fee, core.fee);
+    }
+});
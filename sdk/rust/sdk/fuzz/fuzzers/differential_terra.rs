@@ -0,0 +1,31 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use sha3::{
+    Digest,
+    Keccak256,
+};
+use wormhole::state::ParsedVAA;
+use wormhole_sdk::VAA;
+
+fuzz_target!(|data: &[u8]| {
+    let core = VAA::from_bytes(data);
+    let terra = ParsedVAA::deserialize(data);
+    assert_eq!(core.is_ok(), terra.is_ok());
+
+    if let (Ok(core), Ok(terra)) = (core, terra) {
+        assert_eq!(terra.version, core.version);
+        assert_eq!(terra.guardian_set_index, core.guardian_set_index);
+        assert_eq!(terra.len_signers as usize, core.signatures.len());
+        assert_eq!(terra.timestamp, core.timestamp);
+        assert_eq!(terra.nonce, core.nonce);
+        assert_eq!(terra.emitter_chain, u16::from(core.emitter_chain.clone()));
+        assert_eq!(terra.emitter_address, core.emitter_address);
+        assert_eq!(terra.sequence, core.sequence);
+        assert_eq!(terra.consistency_level, core.consistency_level);
+        assert_eq!(terra.payload, core.payload);
+
+        // Terra identifies VAAs by the hash of the body hash.
+        let hash = core.digest().unwrap().hash;
+        assert_eq!(terra.hash, Keccak256::digest(&hash).to_vec());
+    }
+});
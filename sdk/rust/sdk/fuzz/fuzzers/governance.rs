@@ -1,8 +1,32 @@
 #![no_main]
 use libfuzzer_sys::fuzz_target;
-use wormhole_sdk::VAA;
+use wormhole_sdk::vaa::core::{
+    GovernanceContractUpgrade,
+    GovernanceGuardianSetChange,
+    GovernanceSetMessageFee,
+    GovernanceTransferFees,
+};
+use wormhole_sdk::vaa::{
+    nft,
+    token,
+};
+use wormhole_sdk::GovernanceAction;
+
+/// Governance actions are fixed layouts, anything that parses must serialize back to the same
+/// bytes.
+fn check<A: GovernanceAction>(data: &[u8]) {
+    if let Ok((header, action)) = A::from_bytes(data, None) {
+        assert_eq!(action.to_bytes(header.chains), data);
+    }
+}
 
 fuzz_target!(|data: &[u8]| {
-    VAA::from_bytes(data);
+    check::<GovernanceContractUpgrade>(data);
+    check::<GovernanceGuardianSetChange>(data);
+    check::<GovernanceSetMessageFee>(data);
+    check::<GovernanceTransferFees>(data);
+    check::<token::GovernanceRegisterChain>(data);
+    check::<token::GovernanceContractUpgrade>(data);
+    check::<nft::GovernanceRegisterChain>(data);
+    check::<nft::GovernanceContractUpgrade>(data);
 });
-
@@ -0,0 +1,27 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::nft::{
+    BatchTransfer,
+    SemiFungibleTransfer,
+    Transfer,
+    TransferV2,
+};
+
+fuzz_target!(|data: &[u8]| {
+    // Invalid UTF-8 and padding are dropped from symbol and name, so only the parsed form has to
+    // survive a round trip.
+    if let Ok(transfer) = Transfer::from_bytes(data) {
+        assert_eq!(Transfer::from_bytes(transfer.to_bytes()).unwrap(), transfer);
+    }
+
+    // Version 2 strings are parsed as is, so the bytes themselves survive.
+    if let Ok(transfer) = TransferV2::from_bytes(data) {
+        assert_eq!(transfer.to_bytes(), data);
+    }
+    if let Ok(transfer) = BatchTransfer::from_bytes(data) {
+        assert_eq!(transfer.to_bytes(), data);
+    }
+    if let Ok(transfer) = SemiFungibleTransfer::from_bytes(data) {
+        assert_eq!(transfer.to_bytes(), data);
+    }
+});
@@ -0,0 +1,402 @@
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use primitive_types::U256;
+use wormhole_sdk::vaa::core::{
+    GovernanceContractUpgrade,
+    GovernanceGuardianSetChange,
+    GovernanceSetMessageFee,
+    GovernanceTransferFees,
+};
+use wormhole_sdk::vaa::{
+    alephium,
+    nft,
+    token,
+};
+use wormhole_sdk::{
+    Chain,
+    GovernanceAction,
+};
+
+/// Structured inputs for every payload that has a serializer. Each one is built, serialized and
+/// parsed back, which has to give the same value.
+#[derive(Arbitrary, Debug)]
+enum Payload {
+    TokenTransfer {
+        amount:        [u8; 32],
+        token_address: [u8; 32],
+        token_chain:   u16,
+        to:            [u8; 32],
+        to_chain:      u16,
+        fee:           [u8; 32],
+    },
+    AssetMeta {
+        token_address: [u8; 32],
+        token_chain:   u16,
+        decimals:      u8,
+        symbol:        String,
+        name:          String,
+    },
+    NftTransfer {
+        nft_address: [u8; 32],
+        nft_chain:   u16,
+        symbol:      String,
+        name:        String,
+        token_id:    [u8; 32],
+        uri:         String,
+        to:          [u8; 32],
+        to_chain:    u16,
+    },
+    NftTransferV2 {
+        nft_address:             [u8; 32],
+        nft_chain:               u16,
+        symbol:                  String,
+        name:                    String,
+        token_id:                [u8; 32],
+        uri:                     String,
+        collection:              [u8; 32],
+        seller_fee_basis_points: u16,
+        creators:                Vec<([u8; 32], bool, u8)>,
+        to:                      [u8; 32],
+        to_chain:                u16,
+    },
+    NftBatchTransfer {
+        nft_address: [u8; 32],
+        nft_chain:   u16,
+        symbol:      String,
+        name:        String,
+        tokens:      Vec<([u8; 32], String)>,
+        to:          [u8; 32],
+        to_chain:    u16,
+    },
+    NftSemiFungibleTransfer {
+        nft_address: [u8; 32],
+        nft_chain:   u16,
+        symbol:      String,
+        name:        String,
+        token_id:    [u8; 32],
+        amount:      [u8; 32],
+        uri:         String,
+        to:          [u8; 32],
+        to_chain:    u16,
+    },
+    AlephiumTransfer {
+        amount:           [u8; 32],
+        token_address:    [u8; 32],
+        token_chain:      u16,
+        to:               [u8; 32],
+        to_chain:         u16,
+        fee:              [u8; 32],
+        is_local_token:   bool,
+        token_wrapper_id: [u8; 32],
+    },
+    ContractUpgrade {
+        chain:        u16,
+        new_contract: [u8; 32],
+    },
+    GuardianSetChange {
+        chain:                  u16,
+        new_guardian_set_index: u32,
+        new_guardian_set:       Vec<[u8; 20]>,
+    },
+    SetMessageFee {
+        chain: u16,
+        fee:   [u8; 32],
+    },
+    TransferFees {
+        chain:  u16,
+        amount: [u8; 32],
+        to:     [u8; 32],
+    },
+    TokenRegisterChain {
+        chain:            u16,
+        emitter:          u16,
+        endpoint_address: [u8; 32],
+    },
+    NftRegisterChain {
+        chain:            u16,
+        emitter:          u16,
+        endpoint_address: [u8; 32],
+    },
+}
+
+/// Fixed width string fields drop NUL and U+FFFD when parsed and are cut at 32 bytes.
+fn fits_fixed(s: &str) -> bool {
+    s.len() <= 32 && !s.contains(['\0', '\u{FFFD}'])
+}
+
+fn governance<A: GovernanceAction + PartialEq + std::fmt::Debug>(chain: u16, action: A) {
+    let bytes = action.to_bytes(Chain::from(chain));
+    let (header, parsed) = A::from_bytes(&bytes, None).unwrap();
+    assert_eq!(u16::from(header.chains), chain);
+    assert_eq!(parsed, action);
+}
+
+fuzz_target!(|payload: Payload| {
+    match payload {
+        Payload::TokenTransfer {
+            amount,
+            token_address,
+            token_chain,
+            to,
+            to_chain,
+            fee,
+        } => {
+            let transfer = token::Transfer {
+                amount: U256::from_big_endian(&amount),
+                token_address,
+                token_chain: Chain::from(token_chain),
+                to,
+                to_chain: Chain::from(to_chain),
+                fee: U256::from_big_endian(&fee),
+            };
+            assert_eq!(token::Transfer::from_bytes(transfer.to_bytes()).unwrap(), transfer);
+        }
+
+        Payload::AssetMeta {
+            token_address,
+            token_chain,
+            decimals,
+            symbol,
+            name,
+        } => {
+            if !fits_fixed(&symbol) || !fits_fixed(&name) {
+                return;
+            }
+            let meta = token::AssetMeta {
+                token_address,
+                token_chain: Chain::from(token_chain),
+                decimals,
+                symbol,
+                name,
+            };
+            assert_eq!(token::AssetMeta::from_bytes(meta.to_bytes()).unwrap(), meta);
+        }
+
+        Payload::NftTransfer {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            token_id,
+            uri,
+            to,
+            to_chain,
+        } => {
+            if !fits_fixed(&symbol) || !fits_fixed(&name) || uri.len() > 255 {
+                return;
+            }
+            let transfer = nft::Transfer {
+                nft_address,
+                nft_chain: Chain::from(nft_chain),
+                symbol,
+                name,
+                token_id: U256::from_big_endian(&token_id),
+                uri,
+                to,
+                to_chain: Chain::from(to_chain),
+            };
+            assert_eq!(nft::Transfer::from_bytes(transfer.to_bytes()).unwrap(), transfer);
+        }
+
+        Payload::NftTransferV2 {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            token_id,
+            uri,
+            collection,
+            seller_fee_basis_points,
+            creators,
+            to,
+            to_chain,
+        } => {
+            if symbol.len() > 255 || name.len() > 255 || uri.len() > 65535 || creators.len() > 255 {
+                return;
+            }
+            let transfer = nft::TransferV2 {
+                nft_address,
+                nft_chain: Chain::from(nft_chain),
+                symbol,
+                name,
+                token_id: U256::from_big_endian(&token_id),
+                uri,
+                collection,
+                seller_fee_basis_points,
+                creators: creators
+                    .into_iter()
+                    .map(|(address, verified, share)| nft::Creator {
+                        address,
+                        verified,
+                        share,
+                    })
+                    .collect(),
+                to,
+                to_chain: Chain::from(to_chain),
+            };
+            assert_eq!(nft::TransferV2::from_bytes(transfer.to_bytes()).unwrap(), transfer);
+
+            // Recipients that only redeem version 1 get a lossy transfer.
+            if nft::PayloadKind::TransferV2.is_redeemable_on(&transfer.to_chain) {
+                let bytes = transfer.to_versioned_bytes();
+                assert_eq!(nft::TransferV2::from_versioned_bytes(bytes).unwrap(), transfer);
+            }
+        }
+
+        Payload::NftBatchTransfer {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            tokens,
+            to,
+            to_chain,
+        } => {
+            if symbol.len() > 255
+                || name.len() > 255
+                || tokens.len() > nft::BatchTransfer::MAX_TOKENS
+                || tokens.iter().any(|(_, uri)| uri.len() > 65535)
+            {
+                return;
+            }
+            let transfer = nft::BatchTransfer {
+                nft_address,
+                nft_chain: Chain::from(nft_chain),
+                symbol,
+                name,
+                tokens: tokens
+                    .into_iter()
+                    .map(|(token_id, uri)| nft::BatchToken {
+                        token_id: U256::from_big_endian(&token_id),
+                        uri,
+                    })
+                    .collect(),
+                to,
+                to_chain: Chain::from(to_chain),
+            };
+            assert_eq!(nft::BatchTransfer::from_bytes(transfer.to_bytes()).unwrap(), transfer);
+        }
+
+        Payload::NftSemiFungibleTransfer {
+            nft_address,
+            nft_chain,
+            symbol,
+            name,
+            token_id,
+            amount,
+            uri,
+            to,
+            to_chain,
+        } => {
+            if symbol.len() > 255 || name.len() > 255 || uri.len() > 65535 {
+                return;
+            }
+            let transfer = nft::SemiFungibleTransfer {
+                nft_address,
+                nft_chain: Chain::from(nft_chain),
+                symbol,
+                name,
+                token_id: U256::from_big_endian(&token_id),
+                amount: U256::from_big_endian(&amount),
+                uri,
+                to,
+                to_chain: Chain::from(to_chain),
+            };
+            assert_eq!(
+                nft::SemiFungibleTransfer::from_bytes(transfer.to_bytes()).unwrap(),
+                transfer
+            );
+        }
+
+        Payload::AlephiumTransfer {
+            amount,
+            token_address,
+            token_chain,
+            to,
+            to_chain,
+            fee,
+            is_local_token,
+            token_wrapper_id,
+        } => {
+            let transfer = alephium::Transfer {
+                amount: U256::from_big_endian(&amount),
+                token_address,
+                token_chain: Chain::from(token_chain),
+                to,
+                to_chain: Chain::from(to_chain),
+                fee: U256::from_big_endian(&fee),
+                is_local_token,
+                token_wrapper_id,
+            };
+            assert_eq!(alephium::Transfer::from_bytes(transfer.to_bytes()).unwrap(), transfer);
+        }
+
+        Payload::ContractUpgrade {
+            chain,
+            new_contract,
+        } => {
+            governance(chain, GovernanceContractUpgrade { new_contract });
+            governance(chain, token::GovernanceContractUpgrade { new_contract });
+            governance(chain, nft::GovernanceContractUpgrade { new_contract });
+        }
+
+        Payload::GuardianSetChange {
+            chain,
+            new_guardian_set_index,
+            new_guardian_set,
+        } => {
+            // The guardian count is a single byte on the wire.
+            if new_guardian_set.len() > 255 {
+                return;
+            }
+            governance(
+                chain,
+                GovernanceGuardianSetChange {
+                    new_guardian_set_index,
+                    new_guardian_set,
+                },
+            );
+        }
+
+        Payload::SetMessageFee { chain, fee } => {
+            let fee = U256::from_big_endian(&fee);
+            governance(chain, GovernanceSetMessageFee { fee });
+        }
+
+        Payload::TransferFees { chain, amount, to } => {
+            let amount = U256::from_big_endian(&amount);
+            governance(chain, GovernanceTransferFees { amount, to });
+        }
+
+        Payload::TokenRegisterChain {
+            chain,
+            emitter,
+            endpoint_address,
+        } => {
+            let emitter = Chain::from(emitter);
+            governance(
+                chain,
+                token::GovernanceRegisterChain {
+                    emitter,
+                    endpoint_address,
+                },
+            );
+        }
+
+        Payload::NftRegisterChain {
+            chain,
+            emitter,
+            endpoint_address,
+        } => {
+            let emitter = Chain::from(emitter);
+            governance(
+                chain,
+                nft::GovernanceRegisterChain {
+                    emitter,
+                    endpoint_address,
+                },
+            );
+        }
+    }
+});
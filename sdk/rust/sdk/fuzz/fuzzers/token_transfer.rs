@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::token::Transfer;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(transfer) = Transfer::from_bytes(data) {
+        assert_eq!(transfer.to_bytes(), data);
+    }
+});
@@ -1,8 +1,12 @@
 #![no_main]
 use libfuzzer_sys::fuzz_target;
-use wormhole_sdk::vaa::VAA;
+use wormhole_sdk::VAA;
 
 fuzz_target!(|data: &[u8]| {
-    VAA::from_bytes(data);
+    if let Ok(vaa) = VAA::from_bytes(data) {
+        // The body is everything after the signatures, the digest has to reproduce it.
+        let digest = vaa.digest().unwrap();
+        let body = 6 + 66 * vaa.signatures.len();
+        assert_eq!(digest.digest, &data[body..]);
+    }
 });
-
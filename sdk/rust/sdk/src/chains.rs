@@ -12,3 +12,9 @@ pub use solana::*;
 pub mod terra;
 #[cfg(feature = "terra")]
 pub use terra::*;
+
+
+// Alephium is not a Rust runtime, these are client side helpers so their names stay in the module
+// rather than being exported at the crate root.
+#[cfg(feature = "alephium")]
+pub mod alephium;
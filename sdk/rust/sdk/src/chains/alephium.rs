@@ -0,0 +1,95 @@
+//! Helpers for relaying to and from the Alephium contracts in `alephium/contracts`. Unlike the
+//! other chains these run off-chain, in services that watch Alephium for `WormholeMessage` events
+//! and submit VAAs to its contracts.
+//!
+//! Alephium refers to contracts by a 32 byte contract ID. The ID of the contract that publishes a
+//! message is its Wormhole emitter address, and IDs are what the token bridge registers for other
+//! chains. Users see contracts as base58 addresses, `contract_address` and `contract_id` convert
+//! between the two.
+
+use sha3::Digest;
+
+use wormhole_core::vaa::ForeignAddress;
+use wormhole_core::WormholeError::{
+    DeserializeFailed,
+    InvalidAddress,
+    InvalidPayload,
+};
+use wormhole_core::{
+    require,
+    Chain,
+    UniversalAddress,
+    WormholeError,
+    VAA,
+};
+
+// Export the Alephium payload parsers.
+pub use wormhole_core::vaa::alephium::*;
+
+/// A 32 byte Alephium contract ID.
+pub type ContractId = [u8; 32];
+
+/// Parse a base58 Alephium contract address into the contract ID.
+pub fn contract_id(address: &str) -> Result<ContractId, WormholeError> {
+    let id = UniversalAddress::from_alephium_address(address)?;
+
+    // P2PKH addresses decode to 32 bytes as well, only accept the contract form.
+    require!(id.to_alephium_contract_address() == address, InvalidAddress);
+    Ok(id.0)
+}
+
+/// Render a contract ID as a base58 Alephium contract address.
+pub fn contract_address(id: &ContractId) -> String {
+    UniversalAddress(*id).to_alephium_contract_address()
+}
+
+/// The emitter address of messages published by the contract `id`. Governance records the
+/// calling contract as the sender of each message, so this is the contract ID itself.
+pub fn emitter(id: &ContractId) -> ForeignAddress {
+    *id
+}
+
+/// Build the unsigned VAA for a `WormholeMessage` event. The event data holds the 4 byte nonce
+/// followed by the payload.
+pub fn message_vaa(
+    sender: &ContractId,
+    sequence: u64,
+    data: &[u8],
+    consistency_level: u8,
+    timestamp: u32,
+) -> Result<VAA, WormholeError> {
+    require!(data.len() >= 4, DeserializeFailed);
+    let (nonce, payload) = data.split_at(4);
+    Ok(VAA {
+        version: 1,
+        timestamp,
+        nonce: u32::from_be_bytes([nonce[0], nonce[1], nonce[2], nonce[3]]),
+        emitter_chain: Chain::Alephium,
+        emitter_address: emitter(sender),
+        sequence,
+        consistency_level,
+        payload: payload.to_vec(),
+        ..VAA::default()
+    })
+}
+
+/// The hash guardians sign and `governance.ral` recovers signers from, `keccak256(keccak256(body))`.
+pub fn signing_hash(vaa: &VAA) -> [u8; 32] {
+    let digest = vaa.digest().unwrap();
+    sha3::Keccak256::digest(&digest.hash).into()
+}
+
+/// Serialize a signed VAA for submission to Alephium. The contracts only read version 1 VAAs with
+/// at most `MAX_GUARDIANS` signatures, and only in ascending guardian order, so signatures are
+/// sorted here and duplicates are rejected.
+pub fn encode_vaa(vaa: &VAA) -> Result<Vec<u8>, WormholeError> {
+    require!(vaa.version == 1, InvalidPayload);
+    require!(vaa.signatures.len() <= MAX_GUARDIANS, InvalidPayload);
+
+    let mut vaa = vaa.clone();
+    vaa.signatures.sort_by_key(|signature| signature[0]);
+    let ascending = vaa.signatures.windows(2).all(|pair| pair[0][0] < pair[1][0]);
+    let in_range = vaa.signatures.iter().all(|signature| (signature[0] as usize) < MAX_GUARDIANS);
+    require!(ascending && in_range, InvalidPayload);
+    Ok(vaa.to_bytes())
+}
@@ -2,7 +2,9 @@ use borsh::BorshDeserialize;
 use solana_program::pubkey::Pubkey;
 use solana_program::account_info::AccountInfo;
 use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
 use solana_program::program::invoke_signed;
+use solitaire::processors::discriminator::deserialize_account;
 use std::str::FromStr;
 
 // Export Bridge API
@@ -16,25 +18,18 @@ pub use bridge::instructions;
 pub use bridge::solitaire as bridge_entrypoint;
 pub use bridge::types::ConsistencyLevel;
 
+use wormhole_core::Chain;
 use wormhole_core::WormholeError;
 use wormhole_core::VAA;
 
-/// Export Core Mainnet Contract Address
-#[cfg(feature = "mainnet")]
-pub fn id() -> Pubkey {
-    Pubkey::from_str("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth").unwrap()
-}
+use crate::network::AddressBook;
 
-/// Export Core Devnet Contract Address
-#[cfg(feature = "testnet")]
-pub fn id() -> Pubkey {
-    Pubkey::from_str("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5").unwrap()
-}
+mod verify;
+pub use verify::*;
 
-/// Export Local Tilt Devnet Contract Address
-#[cfg(feature = "devnet")]
-pub fn id() -> Pubkey {
-    Pubkey::from_str("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o").unwrap()
+/// The core bridge program in `book`, if it lists one for Solana.
+pub fn id(book: &AddressBook) -> Option<Pubkey> {
+    book.core(&Chain::Solana).and_then(|id| Pubkey::from_str(id).ok())
 }
 
 /// Derives the Wormhole configuration account address.
@@ -64,14 +59,17 @@ pub fn emitter(id: &Pubkey) -> (Pubkey, Vec<&[u8]>, u8) {
     (emitter, seeds.to_vec(), bump)
 }
 
-/// Deserialize helper the BridgeConfig from a Wormhole config account.
+/// Deserialize helper the BridgeConfig from a Wormhole config account, with or without the
+/// discriminator added by the account migration.
 pub fn read_config(config: &AccountInfo) -> Result<BridgeConfig, WormholeError> {
-    let bridge_data = BridgeData::try_from_slice(&config.data.borrow())
+    let bridge_data: BridgeData = deserialize_account(&config.data.borrow())
         .map_err(|_| WormholeError::DeserializeFailed)?;
     Ok(bridge_data.config)
 }
 
-/// Deserialize helper for parsing from Borsh encoded VAA's from Solana accounts.
+/// Deserialize helper for parsing from Borsh encoded VAA's from Solana accounts. This does not
+/// check the account is a VAA posted by the bridge, programs acting on VAAs should use
+/// `VerifiedVAA::load` instead.
 pub fn read_vaa(vaa: &AccountInfo) -> Result<PostedVAAData, WormholeError> {
     Ok(PostedVAAData::try_from_slice(&vaa.data.borrow())
        .map_err(|_| WormholeError::DeserializeFailed)?)
@@ -80,7 +78,10 @@ pub fn read_vaa(vaa: &AccountInfo) -> Result<PostedVAAData, WormholeError> {
 /// This helper method wraps the steps required to invoke Wormhole, it takes care of fee payment,
 /// emitter derivation, and function invocation. This will be the right thing to use if you need to
 /// simply emit a message in the most straight forward way possible.
+///
+/// `id` is the core bridge program to post to, usually `id(&book)`.
 pub fn post_message(
+    id: Pubkey,
     program_id: Pubkey,
     payer: Pubkey,
     message: Pubkey,
@@ -92,7 +93,6 @@ pub fn post_message(
 ) -> ProgramResult {
     // Derive any necessary Pubkeys, derivation makes sure that we match the accounts the are being
     // provided by the user as well.
-    let id = id();
     let fee_collector = fee_collector(&id);
     let (emitter, mut emitter_seeds, bump) = emitter(&program_id);
     let bump = &[bump];
@@ -100,8 +100,11 @@ pub fn post_message(
 
     // Filter for the Config AccountInfo so we can access its data.
     let config = config(&id);
-    let config = accounts.iter().find(|item| *item.key == config).unwrap();
-    let config = read_config(config).unwrap();
+    let config = accounts
+        .iter()
+        .find(|item| *item.key == config)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let config = read_config(config).map_err(|_| ProgramError::InvalidAccountData)?;
 
     let mut seeds = vec![&*emitter_seeds];
     if let Some(v) = pda_seeds {
@@ -137,3 +140,35 @@ pub fn post_message(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    #[test]
+    fn test_read_config() {
+        let key = Pubkey::new_unique();
+        let bridge = BridgeData {
+            guardian_set_index: 1,
+            last_lamports:      2,
+            config:             BridgeConfig {
+                guardian_set_expiration_time: 3,
+                fee:                          4,
+            },
+        };
+        let read = |data: &mut [u8]| {
+            let mut lamports = 0;
+            let info = AccountInfo::new(&key, false, false, &mut lamports, data, &key, false, 0);
+            read_config(&info).map(|config| config.fee)
+        };
+
+        // Config accounts are read both before and after they are migrated.
+        let mut legacy = bridge.try_to_vec().unwrap();
+        let mut migrated = [&b"brg"[..], &legacy].concat();
+        assert_eq!(read(&mut legacy).ok(), Some(4));
+        assert_eq!(read(&mut migrated).ok(), Some(4));
+
+        assert!(read(&mut legacy[..10]).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Safe consumption of VAAs posted to the core bridge. A posted VAA account only proves a quorum
+//! of guardians signed it if it is the account the bridge derived for that VAA, and says nothing
+//! about whether the sender is trusted or the VAA was already processed. `VerifiedVAA::load`
+//! checks the former against an emitter registry kept by the integrating program, and
+//! `VerifiedVAA::claim` marks a VAA as processed so it cannot be replayed.
+
+use std::ops::Deref;
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{
+    invoke,
+    invoke_signed,
+};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
+
+use wormhole_core::{
+    Chain,
+    VAA,
+};
+
+use super::{
+    MessageData,
+    PostedVAAData,
+};
+
+/// The emitter a program accepts VAAs from for one chain, stored at `registered_emitter`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct RegisteredEmitter {
+    pub chain:   u16,
+    pub address: [u8; 32],
+}
+
+impl RegisteredEmitter {
+    pub const LEN: usize = 34;
+}
+
+/// Derives the account the bridge `id` posts a VAA to, from the Keccak hash of the VAA body.
+pub fn posted_vaa(id: &Pubkey, hash: &[u8; 32]) -> Pubkey {
+    let (vaa, _) = Pubkey::find_program_address(&[b"PostedVAA", hash], id);
+    vaa
+}
+
+/// Derives the account holding the emitter that `program_id` trusts on `chain`.
+pub fn registered_emitter(program_id: &Pubkey, chain: u16) -> Pubkey {
+    let (emitter, _) =
+        Pubkey::find_program_address(&[b"Emitter", &chain.to_be_bytes()], program_id);
+    emitter
+}
+
+/// Derives the account that marks a VAA as processed by `program_id`. Seeded like the claims of
+/// the token and NFT bridges.
+pub fn claim(
+    program_id: &Pubkey,
+    emitter_chain: u16,
+    emitter_address: &[u8; 32],
+    sequence: u64,
+) -> Pubkey {
+    let seeds = claim_seeds(emitter_chain, emitter_address, sequence);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    let (claim, _) = Pubkey::find_program_address(&seeds, program_id);
+    claim
+}
+
+/// Whether `account` is a claim of `program_id`, meaning its VAA was already processed.
+pub fn is_claimed(program_id: &Pubkey, account: &AccountInfo) -> bool {
+    account.owner == program_id && account.data.borrow().first() == Some(&1)
+}
+
+fn claim_seeds(emitter_chain: u16, emitter_address: &[u8; 32], sequence: u64) -> Vec<Vec<u8>> {
+    vec![
+        emitter_address.to_vec(),
+        emitter_chain.to_be_bytes().to_vec(),
+        sequence.to_be_bytes().to_vec(),
+    ]
+}
+
+/// Record `address` as the emitter `program_id` trusts on `chain`, creating the registry account
+/// if needed. This does no authorization, programs must check their own admin signed before
+/// calling it.
+pub fn register_emitter(
+    program_id: &Pubkey,
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    accounts: &[AccountInfo],
+    chain: u16,
+    address: [u8; 32],
+) -> ProgramResult {
+    let chain_seed = chain.to_be_bytes();
+    let (expected, bump) = Pubkey::find_program_address(&[b"Emitter", &chain_seed], program_id);
+    if *account.key != expected {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if account.owner != program_id {
+        let seeds: &[&[u8]] = &[b"Emitter", &chain_seed, &[bump]];
+        create_account(
+            program_id,
+            payer,
+            account,
+            accounts,
+            seeds,
+            RegisteredEmitter::LEN,
+        )?;
+    }
+
+    let emitter = RegisteredEmitter { chain, address };
+    emitter.serialize(&mut &mut account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// A VAA posted to the core bridge by a quorum of guardians, from an emitter the loading program
+/// has registered.
+#[derive(Clone)]
+pub struct VerifiedVAA {
+    message: PostedVAAData,
+    hash:    [u8; 32],
+}
+
+impl VerifiedVAA {
+    /// Load the VAA in the `vaa` account, checking that:
+    ///
+    /// - The account is owned by the core bridge `id`.
+    /// - It is the account the bridge derives for the VAA, so the bridge verified its signatures.
+    /// - `emitter` is the registry account of `program_id` for the emitter chain of the VAA, and
+    ///   holds the emitter address of the VAA.
+    pub fn load(
+        id: &Pubkey,
+        program_id: &Pubkey,
+        vaa: &AccountInfo,
+        emitter: &AccountInfo,
+    ) -> Result<Self, ProgramError> {
+        if vaa.owner != id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Messages posted by emitters share the layout under a different tag, and are owned by the
+        // bridge as well.
+        let data = vaa.data.borrow();
+        if !data.starts_with(b"vaa") {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let message = PostedVAAData::deserialize(&mut &data[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        let hash = Self::body(&message).digest().unwrap().hash;
+        if *vaa.key != posted_vaa(id, &hash) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        if emitter.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if *emitter.key != registered_emitter(program_id, message.emitter_chain) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let registered = RegisteredEmitter::deserialize(&mut &emitter.data.borrow()[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if registered.address != message.emitter_address {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(VerifiedVAA { message, hash })
+    }
+
+    /// The Keccak hash of the VAA body, which identifies the VAA.
+    pub fn hash(&self) -> &[u8; 32] {
+        &self.hash
+    }
+
+    /// The VAA without its signatures, which the bridge does not keep.
+    pub fn to_vaa(&self) -> VAA {
+        Self::body(&self.message)
+    }
+
+    /// Mark the VAA as processed by creating its `claim` account. Fails with
+    /// `AccountAlreadyInitialized` if it already was, programs should call this before acting on
+    /// the VAA.
+    pub fn claim(
+        &self,
+        program_id: &Pubkey,
+        payer: &AccountInfo,
+        claim: &AccountInfo,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let seeds = claim_seeds(
+            self.message.emitter_chain,
+            &self.message.emitter_address,
+            self.message.sequence,
+        );
+        let mut seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        let (expected, bump) = Pubkey::find_program_address(&seeds, program_id);
+        if *claim.key != expected {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if claim.owner == program_id {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let bump = &[bump];
+        seeds.push(bump);
+        create_account(program_id, payer, claim, accounts, &seeds, 1)?;
+        claim.data.borrow_mut()[0] = 1;
+        Ok(())
+    }
+
+    fn body(message: &MessageData) -> VAA {
+        VAA {
+            version: message.vaa_version,
+            timestamp: message.vaa_time,
+            nonce: message.nonce,
+            emitter_chain: Chain::from(message.emitter_chain),
+            emitter_address: message.emitter_address,
+            sequence: message.sequence,
+            consistency_level: message.consistency_level,
+            payload: message.payload.clone(),
+            ..VAA::default()
+        }
+    }
+}
+
+impl Deref for VerifiedVAA {
+    type Target = MessageData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+/// Create a rent exempt PDA of `program_id`. Anyone can send lamports to an address before it is
+/// created, which `create_account` refuses, so funded addresses are allocated and assigned instead.
+fn create_account(
+    program_id: &Pubkey,
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    accounts: &[AccountInfo],
+    seeds: &[&[u8]],
+    space: usize,
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            accounts,
+            &[seeds],
+        );
+    }
+
+    let top_up = lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, top_up),
+            accounts,
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        accounts,
+        &[seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, program_id),
+        accounts,
+        &[seeds],
+    )
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    fn account<'a>(
+        key: &'a Pubkey,
+        owner: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+    }
+
+    #[test]
+    fn test_load() {
+        let bridge = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+
+        let message = PostedVAAData(MessageData {
+            vaa_version: 1,
+            vaa_time: 1,
+            nonce: 2,
+            sequence: 3,
+            emitter_chain: 2,
+            emitter_address: [4; 32],
+            payload: b"hello".to_vec(),
+            ..MessageData::default()
+        });
+        let hash = VerifiedVAA::body(&message).digest().unwrap().hash;
+        let vaa_key = posted_vaa(&bridge, &hash);
+        let mut vaa_data = message.try_to_vec().unwrap();
+
+        let emitter_key = registered_emitter(&program, 2);
+        let mut emitter_data = RegisteredEmitter {
+            chain:   2,
+            address: [4; 32],
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let load =
+            |vaa_key: &Pubkey, vaa_owner: &Pubkey, vaa_data: &mut [u8], emitter_data: &mut [u8]| {
+                let (mut a, mut b) = (0, 0);
+                let vaa = account(vaa_key, vaa_owner, &mut a, vaa_data);
+                let emitter = account(&emitter_key, &program, &mut b, emitter_data);
+                VerifiedVAA::load(&bridge, &program, &vaa, &emitter).map(|vaa| *vaa.hash())
+            };
+
+        assert_eq!(
+            load(&vaa_key, &bridge, &mut vaa_data, &mut emitter_data).unwrap(),
+            hash
+        );
+
+        // Accounts not owned by the bridge, or posted for another VAA.
+        assert_eq!(
+            load(&vaa_key, &program, &mut vaa_data, &mut emitter_data),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            load(
+                &Pubkey::new_unique(),
+                &bridge,
+                &mut vaa_data,
+                &mut emitter_data
+            ),
+            Err(ProgramError::InvalidSeeds)
+        );
+
+        // Posted messages carry the same data under another tag.
+        let mut posted_message = vaa_data.clone();
+        posted_message[..3].copy_from_slice(b"msg");
+        assert_eq!(
+            load(&vaa_key, &bridge, &mut posted_message, &mut emitter_data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // Emitters other than the registered one.
+        emitter_data[2] = 5;
+        assert_eq!(
+            load(&vaa_key, &bridge, &mut vaa_data, &mut emitter_data),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -3,7 +3,7 @@ use cosmwasm_std::{
     Addr,
     Binary,
     CosmosMsg,
-    DepsMut,
+    Deps,
     Env,
     QueryRequest,
     StdResult,
@@ -18,31 +18,26 @@ use wormhole::msg::{
 };
 use wormhole::state::ParsedVAA;
 
-/// Export Core Mainnet Contract Address
-#[cfg(feature = "mainnet")]
-pub fn id() -> Addr {
-    Addr::unchecked("terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5")
-}
+use wormhole_core::Chain;
 
-/// Export Core Devnet Contract Address
-#[cfg(feature = "testnet")]
-pub fn id() -> Addr {
-    Addr::unchecked("terra1pd65m0q9tl3v8znnz5f5ltsfegyzah7g42cx5v")
-}
+use crate::network::AddressBook;
+
+mod receiver;
+pub use receiver::*;
 
-/// Export Core Devnet Contract Address
-#[cfg(feature = "devnet")]
-pub fn id() -> Addr {
-    Addr::unchecked("terra1pd65m0q9tl3v8znnz5f5ltsfegyzah7g42cx5v")
+/// The core bridge contract in `book`, if it lists one for Terra.
+pub fn id(book: &AddressBook) -> Option<Addr> {
+    book.core(&Chain::Terra).map(Addr::unchecked)
 }
 
-pub fn post_message<T>(nonce: u32, message: &T) -> StdResult<CosmosMsg>
+/// Post `message` through the core bridge contract `wormhole`, usually `id(&book)`.
+pub fn post_message<T>(wormhole: &Addr, nonce: u32, message: &T) -> StdResult<CosmosMsg>
 where
     T: Serialize,
     T: ?Sized,
 {
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: id().to_string(),
+        contract_addr: wormhole.to_string(),
         funds:         vec![],
         msg:           to_binary(&ExecuteMsg::PostMessage {
             message: to_binary(message)?,
@@ -51,14 +46,16 @@ where
     }))
 }
 
-/// Parse a VAA using the Wormhole contract Query interface.
+/// Parse a VAA using the query interface of the core bridge contract `wormhole`, which fails unless
+/// the VAA is signed by a quorum of the current guardian set.
 pub fn parse_vaa(
-    deps: DepsMut,
-    env: Env,
+    wormhole: &Addr,
+    deps: Deps,
+    env: &Env,
     data: &Binary,
 ) -> StdResult<ParsedVAA> {
     let vaa: ParsedVAA = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: id().to_string(),
+        contract_addr: wormhole.to_string(),
         msg:           to_binary(&QueryMsg::VerifyVAA {
             vaa: data.clone(),
             block_time: env.block.time.seconds(),
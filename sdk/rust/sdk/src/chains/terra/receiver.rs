@@ -0,0 +1,246 @@
+//! Receiving VAAs in a CosmWasm contract. `WormholeReceiver` keeps the state every receiving
+//! contract needs in storage: the emitter it trusts on each chain, and the VAAs it already
+//! received. Emitters are registered through governance VAAs addressed to the contract's own
+//! governance module, in the same format as the token bridge `RegisterChain` action:
+//!
+//! Field      | Size
+//! -----------|-----
+//! module     | 32, the module name left padded with zeroes
+//! action     | 1, `REGISTER_EMITTER`
+//! chain      | 2, Terra or 0 for all chains
+//! emitter    | 2, the chain the emitter is on
+//! address    | 32
+
+use cosmwasm_std::{
+    Addr,
+    Binary,
+    DepsMut,
+    Env,
+    Response,
+    StdError,
+    StdResult,
+    Storage,
+};
+use cosmwasm_storage::{
+    bucket,
+    bucket_read,
+    singleton,
+    singleton_read,
+};
+use schemars::JsonSchema;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use wormhole::state::ParsedVAA;
+
+use wormhole_core::vaa::{
+    governance_module,
+    nft,
+    token,
+    GovHeader,
+};
+use wormhole_core::{
+    Chain,
+    WormholeError,
+};
+
+use super::parse_vaa;
+
+static CONFIG_KEY: &[u8] = b"wormhole_receiver_config";
+static EMITTERS_KEY: &[u8] = b"wormhole_receiver_emitters";
+static ARCHIVE_KEY: &[u8] = b"wormhole_receiver_archive";
+
+/// The governance action that registers an emitter.
+pub const REGISTER_EMITTER: u8 = 1;
+
+/// Where VAAs come from and who governs the receiving contract, usually part of its
+/// `InstantiateMsg`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReceiverConfig {
+    /// The core bridge contract that verifies VAAs.
+    pub wormhole:           Addr,
+    pub governance_chain:   u16,
+    pub governance_address: Binary,
+
+    /// The governance module of the receiving contract, at most 32 bytes, such as `Messenger`.
+    pub module: String,
+}
+
+/// A payload format a contract receives, decoded from the VAA payload.
+pub trait FromPayload: Sized {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError>;
+}
+
+/// The raw payload, for formats wormhole-core does not know.
+impl FromPayload for Vec<u8> {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError> {
+        Ok(payload.to_vec())
+    }
+}
+
+impl FromPayload for token::Transfer {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError> {
+        token::Transfer::from_bytes(payload)
+    }
+}
+
+impl FromPayload for token::AssetMeta {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError> {
+        token::AssetMeta::from_bytes(payload)
+    }
+}
+
+impl FromPayload for nft::Transfer {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError> {
+        nft::Transfer::from_bytes(payload)
+    }
+}
+
+/// Either version of the NFT transfer, version 1 transfers are converted.
+impl FromPayload for nft::TransferV2 {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError> {
+        nft::TransferV2::from_versioned_bytes(payload)
+    }
+}
+
+impl FromPayload for nft::BatchTransfer {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError> {
+        nft::BatchTransfer::from_bytes(payload)
+    }
+}
+
+impl FromPayload for nft::SemiFungibleTransfer {
+    fn from_payload(payload: &[u8]) -> Result<Self, WormholeError> {
+        nft::SemiFungibleTransfer::from_bytes(payload)
+    }
+}
+
+/// A VAA accepted by `WormholeReceiver::receive`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Received<P> {
+    /// A governance VAA, which the receiver has already applied. The response describes the
+    /// change.
+    Governance(Response),
+
+    /// A VAA from the registered emitter of its chain.
+    Message { vaa: ParsedVAA, payload: P },
+}
+
+/// Emitter registry and replay protection for a contract receiving VAAs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WormholeReceiver {
+    config: ReceiverConfig,
+}
+
+impl WormholeReceiver {
+    /// Store `config`, from the `instantiate` entry point of the contract.
+    pub fn instantiate(storage: &mut dyn Storage, config: ReceiverConfig) -> StdResult<Self> {
+        if config.module.is_empty() || config.module.len() > 32 {
+            return Err(StdError::generic_err("governance module must be 1 to 32 bytes"));
+        }
+        singleton(storage, CONFIG_KEY).save(&config)?;
+        Ok(Self { config })
+    }
+
+    /// The receiver stored by `instantiate`.
+    pub fn load(storage: &dyn Storage) -> StdResult<Self> {
+        let config = singleton_read(storage, CONFIG_KEY).load()?;
+        Ok(Self { config })
+    }
+
+    pub fn config(&self) -> &ReceiverConfig {
+        &self.config
+    }
+
+    /// The emitter registered for `chain`.
+    pub fn emitter(&self, storage: &dyn Storage, chain: u16) -> StdResult<Option<Vec<u8>>> {
+        bucket_read(storage, EMITTERS_KEY).may_load(&chain.to_be_bytes())
+    }
+
+    /// Register the emitter of `chain`. Receiving a governance VAA calls this, contracts with
+    /// another form of administration can call it directly. An emitter can only be registered
+    /// once, as messages already received from it could not be told apart from a replacement.
+    pub fn register_emitter(
+        &self,
+        storage: &mut dyn Storage,
+        chain: u16,
+        address: &[u8],
+    ) -> StdResult<()> {
+        if address.len() != 32 {
+            return Err(StdError::generic_err("emitter address must be 32 bytes"));
+        }
+        if self.emitter(storage, chain)?.is_some() {
+            return Err(StdError::generic_err("emitter already registered for this chain"));
+        }
+        bucket(storage, EMITTERS_KEY).save(&chain.to_be_bytes(), &address.to_vec())
+    }
+
+    /// Whether the VAA with `hash` was already received.
+    pub fn is_received(&self, storage: &dyn Storage, hash: &[u8]) -> bool {
+        bucket_read::<bool>(storage, ARCHIVE_KEY)
+            .may_load(hash)
+            .ok()
+            .flatten()
+            .unwrap_or(false)
+    }
+
+    /// Verify `data` with the core bridge and accept it once. Governance VAAs are applied and
+    /// returned as `Received::Governance`, any other VAA must come from the registered emitter of
+    /// its chain and have a payload that decodes as `P`.
+    pub fn receive<P: FromPayload>(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        data: &Binary,
+    ) -> StdResult<Received<P>> {
+        let vaa = parse_vaa(&self.config.wormhole, deps.as_ref(), env, data)?;
+
+        if self.is_received(deps.storage, &vaa.hash) {
+            return Err(StdError::generic_err("VAA already received"));
+        }
+        bucket(deps.storage, ARCHIVE_KEY).save(&vaa.hash, &true)?;
+
+        if vaa.emitter_chain == self.config.governance_chain
+            && vaa.emitter_address == self.config.governance_address.as_slice()
+        {
+            let response = self.handle_governance(deps.storage, &vaa.payload)?;
+            return Ok(Received::Governance(response));
+        }
+
+        let emitter = self.emitter(deps.storage, vaa.emitter_chain)?;
+        if emitter.as_deref() != Some(vaa.emitter_address.as_slice()) {
+            return Err(StdError::generic_err("VAA is not from a registered emitter"));
+        }
+
+        let payload = P::from_payload(&vaa.payload)
+            .map_err(|e| StdError::generic_err(format!("invalid payload: {:?}", e)))?;
+        Ok(Received::Message { vaa, payload })
+    }
+
+    fn handle_governance(&self, storage: &mut dyn Storage, payload: &[u8]) -> StdResult<Response> {
+        let (header, action) = GovHeader::from_bytes(payload)
+            .map_err(|_| StdError::generic_err("invalid governance VAA"))?;
+
+        if header.module != governance_module(self.config.module.as_bytes()) {
+            return Err(StdError::generic_err("this is not a valid module"));
+        }
+        if header.chains != Chain::All && header.chains != Chain::Terra {
+            return Err(StdError::generic_err("the governance VAA is for another chain"));
+        }
+        if header.action != REGISTER_EMITTER || action.len() != 34 {
+            return Err(StdError::generic_err("invalid governance action"));
+        }
+
+        let chain = u16::from_be_bytes([action[0], action[1]]);
+        let address = &action[2..];
+        self.register_emitter(storage, chain, address)?;
+
+        let address: String = address.iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(Response::new()
+            .add_attribute("action", "register_emitter")
+            .add_attribute("chain_id", chain.to_string())
+            .add_attribute("emitter_address", address))
+    }
+}
@@ -11,11 +11,22 @@
 //! ----------|-------------------------|---------------------------------------------------- 
 //! Solana    | --feature=solana        | solana-sdk 1.7.1 
 //! Terra     | --feature=terra         | cosmos-sdk 0.16.0 
+//! Alephium  | --feature=alephium      | Ralph contracts in `alephium/contracts`
 //!
 //! Docs specific to each blockchain's runtime can be found in submodules within the chains module
 //! at the root of this package.
+//!
+//! Contract addresses are selected at runtime through the `AddressBook` of a `Network` rather than by
+//! feature flags, so a single build can serve every network.
 
 pub mod chains;
+pub mod network;
 
+pub use network::*;
 pub use wormhole_core::*;
+#[allow(unused_imports)] // Empty unless a chain feature is enabled.
 pub use chains::*;
+
+// Shadows `wormhole_core::alephium`, whose parsers the chain module re-exports.
+#[cfg(feature = "alephium")]
+pub use chains::alephium;
@@ -0,0 +1,365 @@
+//! Contract addresses for each Wormhole network. The built in address book covers the public
+//! deployments, local Tilt devnets redeploy contracts often enough that their addresses can be
+//! overridden from a config file:
+//!
+//! ```text
+//! # Lines outside a section apply to every network.
+//! polygon.core = 0xC89Ce4735882C9F0f0FE26686c53074E09B0D550
+//!
+//! [devnet]
+//! terra.nft_bridge = terra1plju286nnfj3z54wgcggd4enwaa9fgf5kgrgzl
+//! ```
+//!
+//! Addresses are written in the native format of their chain, Alephium contracts by their hex
+//! contract ID, and are checked when loaded.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use wormhole_core::{
+    Chain,
+    UniversalAddress,
+};
+
+/// A Wormhole deployment, each with its own guardian set and contracts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+impl Network {
+    /// A lowercase name for the network, as used in config files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+        }
+    }
+
+    /// The network called `name`, the inverse of `name`.
+    pub fn from_name(name: &str) -> Option<Network> {
+        match name {
+            "mainnet" => Some(Network::Mainnet),
+            "testnet" => Some(Network::Testnet),
+            "devnet" => Some(Network::Devnet),
+            _ => None,
+        }
+    }
+
+    /// The built in address of `contract` on `chain`, if it is deployed there.
+    pub fn address(&self, chain: &Chain, contract: Contract) -> Option<&'static str> {
+        let id = u16::from(chain.clone());
+        self.addresses()
+            .iter()
+            .find(|(c, k, _)| u16::from(c.clone()) == id && *k == contract)
+            .map(|(_, _, address)| *address)
+    }
+
+    fn addresses(&self) -> &'static [(Chain, Contract, &'static str)] {
+        match self {
+            Network::Mainnet => MAINNET,
+            Network::Testnet => TESTNET,
+            Network::Devnet => DEVNET,
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The Wormhole contracts deployed on each chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Contract {
+    Core,
+    TokenBridge,
+    NftBridge,
+}
+
+impl Contract {
+    /// A lowercase name for the contract, as used in config files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Contract::Core => "core",
+            Contract::TokenBridge => "token_bridge",
+            Contract::NftBridge => "nft_bridge",
+        }
+    }
+
+    /// The contract called `name`, the inverse of `name`.
+    pub fn from_name(name: &str) -> Option<Contract> {
+        match name {
+            "core" => Some(Contract::Core),
+            "token_bridge" => Some(Contract::TokenBridge),
+            "nft_bridge" => Some(Contract::NftBridge),
+            _ => None,
+        }
+    }
+}
+
+/// A failure to load an address book config, with the line it occurred on.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+
+    /// The line is neither a `[network]` section nor a `chain.contract = address` entry.
+    Syntax(usize),
+
+    UnknownNetwork(usize),
+    UnknownChain(usize),
+    UnknownContract(usize),
+
+    /// The address is not in the native format of its chain.
+    InvalidAddress(usize),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "cannot read address book: {}", e),
+            ConfigError::Syntax(line) => write!(f, "line {}: expected `chain.contract = address`", line),
+            ConfigError::UnknownNetwork(line) => write!(f, "line {}: unknown network", line),
+            ConfigError::UnknownChain(line) => write!(f, "line {}: unknown chain", line),
+            ConfigError::UnknownContract(line) => write!(f, "line {}: unknown contract", line),
+            ConfigError::InvalidAddress(line) => write!(f, "line {}: invalid address", line),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+/// The contract addresses of one network, the built in ones with any overrides applied.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddressBook {
+    network:   Network,
+    overrides: BTreeMap<(u16, Contract), String>,
+}
+
+impl AddressBook {
+    /// The built in addresses of `network`.
+    pub fn new(network: Network) -> Self {
+        Self {
+            network,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// The built in addresses of `network` with the overrides from the config file at `path`.
+    pub fn load(network: Network, path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let config = std::fs::read_to_string(path)?;
+        let mut book = Self::new(network);
+        book.apply(&config)?;
+        Ok(book)
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// The address of `contract` on `chain`, in the native format of the chain.
+    pub fn address(&self, chain: &Chain, contract: Contract) -> Option<&str> {
+        match self.overrides.get(&(u16::from(chain.clone()), contract)) {
+            Some(address) => Some(address),
+            None => self.network.address(chain, contract),
+        }
+    }
+
+    pub fn core(&self, chain: &Chain) -> Option<&str> {
+        self.address(chain, Contract::Core)
+    }
+
+    pub fn token_bridge(&self, chain: &Chain) -> Option<&str> {
+        self.address(chain, Contract::TokenBridge)
+    }
+
+    pub fn nft_bridge(&self, chain: &Chain) -> Option<&str> {
+        self.address(chain, Contract::NftBridge)
+    }
+
+    /// Override the address of `contract` on `chain`. Fails if `address` is not in the native
+    /// format of `chain`.
+    pub fn set(
+        &mut self,
+        chain: &Chain,
+        contract: Contract,
+        address: &str,
+    ) -> Result<(), wormhole_core::WormholeError> {
+        let _ = UniversalAddress::parse(chain, address)?;
+        let _ = self
+            .overrides
+            .insert((u16::from(chain.clone()), contract), address.to_string());
+        Ok(())
+    }
+
+    /// Apply the entries of `config` that are outside any section or in the section of this
+    /// network. Entries for other networks are still checked, so a typo fails on every network.
+    pub fn apply(&mut self, config: &str) -> Result<(), ConfigError> {
+        let mut section = None;
+        for (number, line) in config.lines().enumerate() {
+            let number = number + 1;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let network =
+                    Network::from_name(name.trim()).ok_or(ConfigError::UnknownNetwork(number))?;
+                section = Some(network);
+                continue;
+            }
+
+            let (key, address) = line.split_once('=').ok_or(ConfigError::Syntax(number))?;
+            let (chain, contract) = key.trim().split_once('.').ok_or(ConfigError::Syntax(number))?;
+            let chain = Chain::from_name(chain).ok_or(ConfigError::UnknownChain(number))?;
+            let contract = Contract::from_name(contract).ok_or(ConfigError::UnknownContract(number))?;
+            let address = address.trim();
+            UniversalAddress::parse(&chain, address).map_err(|_| ConfigError::InvalidAddress(number))?;
+
+            if section.is_none() || section == Some(self.network) {
+                let _ = self
+                    .overrides
+                    .insert((u16::from(chain), contract), address.to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Network> for AddressBook {
+    fn from(network: Network) -> Self {
+        Self::new(network)
+    }
+}
+
+use Contract::*;
+
+const MAINNET: &[(Chain, Contract, &str)] = &[
+    (Chain::Solana, Core, "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth"),
+    (Chain::Solana, TokenBridge, "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb"),
+    (Chain::Solana, NftBridge, "WnFt12ZrnzZrFZkt2xsNsaNWoQribnuQ5B5FrDbwDhD"),
+    (Chain::Ethereum, Core, "0x98f3c9e6E3fAce36bAAd05FE09d375Ef1464288B"),
+    (Chain::Ethereum, TokenBridge, "0x3ee18B2214AFF97000D974cf647E7C347E8fa585"),
+    (Chain::Ethereum, NftBridge, "0x6FFd7EdE62328b3Af38FCD61461Bbfc52F5651fE"),
+    (Chain::Terra, Core, "terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5"),
+    (Chain::Terra, TokenBridge, "terra10nmmwe8r3g99a9newtqa7a75xfgs2e8z87r2sf"),
+    (Chain::Binance, Core, "0x98f3c9e6E3fAce36bAAd05FE09d375Ef1464288B"),
+    (Chain::Binance, TokenBridge, "0xB6F6D86a8f9879A9c87f643768d9efc38c1Da6E7"),
+    (Chain::Binance, NftBridge, "0x5a58505a96D1dbf8dF91cB21B54419FC36e93fdE"),
+    (Chain::Polygon, Core, "0x7A4B5a56256163F07b2C80A7cA55aBE66c4ec4d7"),
+    (Chain::Polygon, TokenBridge, "0x5a58505a96D1dbf8dF91cB21B54419FC36e93fdE"),
+    (Chain::Polygon, NftBridge, "0x90BBd86a6Fe93D3bc3ed6335935447E75fAb7fCf"),
+    (Chain::AVAX, Core, "0x54a8e5f9c4CbA08F9943965859F6c34eAF03E26c"),
+    (Chain::AVAX, TokenBridge, "0x0e082F06FF657D94310cB8cE8B0D9a04541d8052"),
+    (Chain::AVAX, NftBridge, "0xf7B6737Ca9c4e08aE573F75A97B73D7a813f5De5"),
+    (Chain::Oasis, Core, "0xfE8cD454b4A1CA468B57D79c0cc77Ef5B6f64585"),
+    (Chain::Oasis, TokenBridge, "0x5848C791e09901b40A9Ef749f2a6735b418d7564"),
+    (Chain::Oasis, NftBridge, "0x04952D522Ff217f40B5Ef3cbF659EcA7b952a6c1"),
+];
+
+const TESTNET: &[(Chain, Contract, &str)] = &[
+    (Chain::Solana, Core, "3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5"),
+    (Chain::Solana, TokenBridge, "DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe"),
+    (Chain::Solana, NftBridge, "2rHhojZ7hpu1zA91nvZmT8TqWWvMcKmmNBCr2mKTtMq4"),
+    (Chain::Ethereum, Core, "0x706abc4E45D419950511e474C7B9Ed348A4a716c"),
+    (Chain::Ethereum, TokenBridge, "0xF890982f9310df57d00f659cf4fd87e65adEd8d7"),
+    (Chain::Ethereum, NftBridge, "0xD8E4C2DbDd2e2bd8F1336EA691dBFF6952B1a6eB"),
+    (Chain::Terra, Core, "terra1pd65m0q9tl3v8znnz5f5ltsfegyzah7g42cx5v"),
+    (Chain::Terra, TokenBridge, "terra1pseddrv0yfsn76u4zxrjmtf45kdlmalswdv39a"),
+    (Chain::Binance, Core, "0x68605AD7b15c732a30b1BbC62BE8F2A509D74b4D"),
+    (Chain::Binance, TokenBridge, "0x9dcF9D205C9De35334D646BeE44b2D2859712A09"),
+    (Chain::Binance, NftBridge, "0xcD16E5613EF35599dc82B24Cb45B5A93D779f1EE"),
+    (Chain::Polygon, Core, "0x0CBE91CF822c73C2315FB05100C2F714765d5c20"),
+    (Chain::Polygon, TokenBridge, "0x377D55a7928c046E18eEbb61977e714d2a76472a"),
+    (Chain::Polygon, NftBridge, "0x51a02d0dcb5e52F5b92bdAA38FA013C91c7309A9"),
+    (Chain::AVAX, Core, "0x7bbcE28e64B3F8b84d876Ab298393c38ad7aac4C"),
+    (Chain::AVAX, TokenBridge, "0x61E44E506Ca5659E6c0bba9b678586fA2d729756"),
+    (Chain::AVAX, NftBridge, "0xD601BAf2EEE3C028344471684F6b27E789D9075D"),
+    (Chain::Oasis, Core, "0xc1C338397ffA53a2Eb12A7038b4eeb34791F8aCb"),
+    (Chain::Oasis, TokenBridge, "0x88d8004A9BdbfD9D28090A02010C19897a29605c"),
+    (Chain::Oasis, NftBridge, "0xC5c25B41AB0b797571620F5204Afa116A44c0ebA"),
+];
+
+// Tilt deploys the same EVM contracts to every EVM chain, at addresses fixed by the deployer key.
+const DEVNET: &[(Chain, Contract, &str)] = &[
+    (Chain::Solana, Core, "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o"),
+    (Chain::Solana, TokenBridge, "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE"),
+    (Chain::Solana, NftBridge, "NFTWqJR8YnRVqPDvTJrYuLrQDitTG5AScqbeghi4zSA"),
+    (Chain::Ethereum, Core, "0xC89Ce4735882C9F0f0FE26686c53074E09B0D550"),
+    (Chain::Ethereum, TokenBridge, "0x0290FB167208Af455bB137780163b7B7a9a10C16"),
+    (Chain::Ethereum, NftBridge, "0x26b4afb60d6c903165150c6f0aa14f8016be4aec"),
+    (Chain::Terra, Core, "terra18vd8fpwxzck93qlwghaj6arh4p7c5n896xzem5"),
+    (Chain::Terra, TokenBridge, "terra10pyejy66429refv3g35g2t7am0was7ya7kz2a4"),
+    (Chain::Terra, NftBridge, "terra1plju286nnfj3z54wgcggd4enwaa9fgf5kgrgzl"),
+    (Chain::Binance, Core, "0xC89Ce4735882C9F0f0FE26686c53074E09B0D550"),
+    (Chain::Binance, TokenBridge, "0x0290FB167208Af455bB137780163b7B7a9a10C16"),
+    (Chain::Binance, NftBridge, "0x26b4afb60d6c903165150c6f0aa14f8016be4aec"),
+];
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_builtin_addresses() {
+        for network in &[Network::Mainnet, Network::Testnet, Network::Devnet] {
+            assert_eq!(Network::from_name(network.name()), Some(*network));
+            for (chain, _, address) in network.addresses() {
+                assert!(UniversalAddress::parse(chain, address).is_ok(), "{}", address);
+            }
+        }
+
+        let book = AddressBook::new(Network::Mainnet);
+        assert_eq!(book.core(&Chain::Solana), Some("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth"));
+        assert_eq!(book.core(&Chain::Unknown(99)), None);
+
+        let book = AddressBook::new(Network::Devnet);
+        assert_eq!(book.nft_bridge(&Chain::Terra), Some("terra1plju286nnfj3z54wgcggd4enwaa9fgf5kgrgzl"));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let config = "
+            # Shared by every network.
+            ethereum.core = 0x0000000000000000000000000000000000000001
+
+            [devnet]
+            solana.token_bridge = 11111111111111111111111111111111   # System program
+
+            [mainnet]
+            ethereum.nft_bridge = 0x0000000000000000000000000000000000000002
+        ";
+
+        let mut book = AddressBook::new(Network::Devnet);
+        book.apply(config).unwrap();
+        assert_eq!(book.core(&Chain::Ethereum), Some("0x0000000000000000000000000000000000000001"));
+        assert_eq!(book.token_bridge(&Chain::Solana), Some("11111111111111111111111111111111"));
+        assert_eq!(book.nft_bridge(&Chain::Ethereum), Some("0x26b4afb60d6c903165150c6f0aa14f8016be4aec"));
+        assert_eq!(book.core(&Chain::Solana), Some("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o"));
+
+        let mut book = AddressBook::new(Network::Mainnet);
+        book.apply(config).unwrap();
+        assert_eq!(book.token_bridge(&Chain::Solana), Some("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb"));
+        assert_eq!(book.nft_bridge(&Chain::Ethereum), Some("0x0000000000000000000000000000000000000002"));
+
+        let error = |config| AddressBook::new(Network::Devnet).apply(config).unwrap_err();
+        assert!(matches!(error("\n[localnet]"), ConfigError::UnknownNetwork(2)));
+        assert!(matches!(error("near.core = x"), ConfigError::UnknownChain(1)));
+        assert!(matches!(error("solana.bridge = x"), ConfigError::UnknownContract(1)));
+        assert!(matches!(error("solana.core"), ConfigError::Syntax(1)));
+        assert!(matches!(error("[mainnet]\nterra.core = 0x01"), ConfigError::InvalidAddress(2)));
+        assert!(book.set(&Chain::Solana, Core, "0x01").is_err());
+    }
+}
@@ -8,7 +8,6 @@ use std::{
     process::exit,
 };
 
-use borsh::BorshDeserialize;
 use bridge::{
     accounts::{
         Bridge,
@@ -60,7 +59,10 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        discriminator::deserialize_account,
+        seeded::Seeded,
+    },
     AccountState,
     Info,
 };
@@ -125,7 +127,7 @@ fn command_post_message(
         .get_account(&Bridge::<'_, { AccountState::Initialized }>::key(
             None, bridge,
         ))?;
-    let bridge_config = BridgeData::try_from_slice(bridge_config_account.data.as_slice())?;
+    let bridge_config = deserialize_account::<BridgeData>(bridge_config_account.data.as_slice())?;
     let fee = bridge_config.config.fee;
     println!("Message fee: {} lamports", fee);
 
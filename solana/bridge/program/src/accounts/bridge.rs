@@ -33,6 +33,9 @@ pub struct BridgeData {
 
 #[cfg(not(feature = "cpi"))]
 impl Owned for BridgeData {
+    const DISCRIMINATOR: &'static [u8] = b"brg";
+    const ACCEPT_LEGACY: bool = true;
+
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
     }
@@ -40,9 +43,12 @@ impl Owned for BridgeData {
 
 #[cfg(feature = "cpi")]
 impl Owned for BridgeData {
+    const DISCRIMINATOR: &'static [u8] = b"brg";
+    const ACCEPT_LEGACY: bool = true;
+
     fn owner(&self) -> AccountOwner {
-        use std::str::FromStr;
         use solana_program::pubkey::Pubkey;
+        use std::str::FromStr;
         AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
     }
 }
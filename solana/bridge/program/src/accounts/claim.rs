@@ -9,6 +9,11 @@ use serde::{
     Deserialize,
     Serialize,
 };
+#[cfg(feature = "idl")]
+use solitaire::idl::{
+    IdlSeed,
+    IdlSeeds,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
@@ -45,3 +50,14 @@ impl<'b, const State: AccountState> Seeded<&ClaimDerivationData> for Claim<'b, {
         ];
     }
 }
+
+#[cfg(feature = "idl")]
+impl IdlSeeds for ClaimDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::field::<[u8; 32]>("emitter_address"),
+            IdlSeed::field::<u16>("emitter_chain"),
+            IdlSeed::field::<u64>("sequence"),
+        ]
+    }
+}
@@ -10,6 +10,11 @@ use serde::{
     Deserialize,
     Serialize,
 };
+#[cfg(feature = "idl")]
+use solitaire::idl::{
+    IdlSeed,
+    IdlSeeds,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
@@ -51,6 +56,16 @@ impl<'a, const State: AccountState> Seeded<&GuardianSetDerivationData>
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for GuardianSetDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("GuardianSet"),
+            IdlSeed::field::<u32>("index"),
+        ]
+    }
+}
+
 impl GuardianSetData {
     /// Number of guardians in the set
     pub fn num_guardians(&self) -> u8 {
@@ -59,6 +74,9 @@ impl GuardianSetData {
 }
 
 impl Owned for GuardianSetData {
+    const DISCRIMINATOR: &'static [u8] = b"gst";
+    const ACCEPT_LEGACY: bool = true;
+
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
     }
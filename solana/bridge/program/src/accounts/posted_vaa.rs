@@ -3,6 +3,11 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+#[cfg(feature = "idl")]
+use solitaire::idl::{
+    IdlSeed,
+    IdlSeeds,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
@@ -30,6 +35,16 @@ impl<'a, const State: AccountState> Seeded<&PostedVAADerivationData> for PostedV
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for PostedVAADerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("PostedVAA"),
+            IdlSeed::field::<Vec<u8>>("payload_hash"),
+        ]
+    }
+}
+
 #[repr(transparent)]
 pub struct PostedVAAData(pub MessageData);
 
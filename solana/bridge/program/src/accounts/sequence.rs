@@ -3,6 +3,11 @@ use borsh::{
     BorshSerialize,
 };
 use solana_program::pubkey::Pubkey;
+#[cfg(feature = "idl")]
+use solitaire::idl::{
+    IdlSeed,
+    IdlSeeds,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountState,
@@ -18,11 +23,11 @@ pub struct SequenceTracker {
     pub sequence: u64,
 }
 
-pub struct SequenceDerivationData<'a> {
-    pub emitter_key: &'a Pubkey,
+pub struct SequenceDerivationData {
+    pub emitter_key: Pubkey,
 }
 
-impl<'b> Seeded<&SequenceDerivationData<'b>> for Sequence<'b> {
+impl<'b> Seeded<&SequenceDerivationData> for Sequence<'b> {
     fn seeds(data: &SequenceDerivationData) -> Vec<Vec<u8>> {
         vec![
             "Sequence".as_bytes().to_vec(),
@@ -31,6 +36,16 @@ impl<'b> Seeded<&SequenceDerivationData<'b>> for Sequence<'b> {
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for SequenceDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("Sequence"),
+            IdlSeed::field::<Pubkey>("emitter_key"),
+        ]
+    }
+}
+
 impl Owned for SequenceTracker {
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
@@ -26,6 +26,9 @@ pub struct SignatureSetData {
 }
 
 impl Owned for SignatureSetData {
+    const DISCRIMINATOR: &'static [u8] = b"sig";
+    const ACCEPT_LEGACY: bool = true;
+
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
     }
@@ -1,11 +1,13 @@
 pub mod governance;
 pub mod initialize;
+pub mod migrate;
 pub mod post_message;
 pub mod post_vaa;
 pub mod verify_signature;
 
 pub use governance::*;
 pub use initialize::*;
+pub use migrate::*;
 pub use post_message::*;
 pub use post_vaa::*;
 pub use verify_signature::*;
@@ -94,7 +94,7 @@ pub struct UpgradeContract<'b> {
 impl<'b> InstructionContext<'b> for UpgradeContract<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -135,16 +135,18 @@ pub struct UpgradeGuardianSet<'b> {
     pub vaa: ClaimableVAA<'b, GovernancePayloadGuardianSetChange>,
 
     /// Old guardian set
+    #[seeds(GuardianSetDerivationData)]
     pub guardian_set_old: Mut<GuardianSet<'b, { AccountState::Initialized }>>,
 
     /// New guardian set
+    #[seeds(GuardianSetDerivationData)]
     pub guardian_set_new: Mut<GuardianSet<'b, { AccountState::Uninitialized }>>,
 }
 
 impl<'b> InstructionContext<'b> for UpgradeGuardianSet<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct UpgradeGuardianSetData {}
 
 pub fn upgrade_guardian_set(
@@ -220,7 +222,7 @@ pub struct SetFees<'b> {
 impl<'b> InstructionContext<'b> for SetFees<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct SetFeesData {}
 
 pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData) -> Result<()> {
@@ -256,7 +258,7 @@ pub struct TransferFees<'b> {
 impl<'b> InstructionContext<'b> for TransferFees<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferFeesData {}
 
 pub fn transfer_fees(
@@ -23,6 +23,7 @@ pub struct Initialize<'b> {
     pub bridge: Mut<Bridge<'b, { AccountState::Uninitialized }>>,
 
     /// Location the new guardian set will be allocated at.
+    #[seeds(GuardianSetDerivationData)]
     pub guardian_set: Mut<GuardianSet<'b, { AccountState::Uninitialized }>>,
 
     /// Location of the fee collector that users will need to pay.
@@ -38,7 +39,7 @@ pub struct Initialize<'b> {
 impl<'b> InstructionContext<'b> for Initialize<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct InitializeData {
     /// Period for how long a guardian set is valid after it has been replaced by a new one.  This
     /// guarantees that VAAs issued by that set can still be submitted for a certain period.  In
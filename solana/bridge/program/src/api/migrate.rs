@@ -0,0 +1,82 @@
+use solitaire::*;
+
+use solitaire::processors::{
+    discriminator::migrate_account,
+    seeded::Seeded,
+};
+
+use crate::accounts::{
+    BridgeData,
+    GuardianSet,
+    GuardianSetData,
+    GuardianSetDerivationData,
+};
+
+// Accounts created before their type set a discriminator are migrated in place by the
+// instructions below. Anyone can run them, they only prefix data the bridge already trusts.
+//
+// Signature sets are not migrated: they are not derived, so nothing ties a legacy account to its
+// type. Sets that were still being verified at upgrade time have to be verified again.
+
+#[derive(FromAccounts)]
+pub struct MigrateBridge<'b> {
+    /// Payer for the rent of the grown account
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config, legacy data cannot be peeled as `Bridge` until migrated
+    pub bridge: Mut<Derive<Info<'b>, "Bridge">>,
+
+    /// Required to top up rent
+    pub system: Info<'b>,
+}
+
+impl<'b> InstructionContext<'b> for MigrateBridge<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct MigrateBridgeData {}
+
+pub fn migrate_bridge(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateBridge,
+    _data: MigrateBridgeData,
+) -> Result<()> {
+    // The derivation has no data dependencies and is checked when peeling.
+    migrate_account::<BridgeData, _>(ctx, &accs.bridge, &accs.payer, |_| Ok(()))
+}
+
+#[derive(FromAccounts)]
+pub struct MigrateGuardianSet<'b> {
+    /// Payer for the rent of the grown account
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Guardian set, derived from the index stored in its legacy data
+    pub guardian_set: Mut<Info<'b>>,
+
+    /// Required to top up rent
+    pub system: Info<'b>,
+}
+
+impl<'b> InstructionContext<'b> for MigrateGuardianSet<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct MigrateGuardianSetData {}
+
+pub fn migrate_guardian_set(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateGuardianSet,
+    _data: MigrateGuardianSetData,
+) -> Result<()> {
+    let key = *accs.guardian_set.key;
+    migrate_account::<GuardianSetData, _>(ctx, &accs.guardian_set, &accs.payer, |data| {
+        let expected = GuardianSet::<'_, { AccountState::Initialized }>::key(
+            &GuardianSetDerivationData { index: data.index },
+            ctx.program_id,
+        );
+        if expected != key {
+            return Err(SolitaireError::InvalidDerive(key, expected));
+        }
+        Ok(())
+    })
+}
@@ -26,10 +26,10 @@ use solitaire::{
 
 pub type UninitializedMessage<'b> = PostedMessage<'b, { AccountState::Uninitialized }>;
 
-impl<'a> From<&PostMessage<'a>> for SequenceDerivationData<'a> {
+impl<'a> From<&PostMessage<'a>> for SequenceDerivationData {
     fn from(accs: &PostMessage<'a>) -> Self {
         SequenceDerivationData {
-            emitter_key: accs.emitter.key,
+            emitter_key: *accs.emitter.key,
         }
     }
 }
@@ -46,6 +46,7 @@ pub struct PostMessage<'b> {
     pub emitter: Signer<MaybeMut<Info<'b>>>,
 
     /// Tracker for the emitter sequence
+    #[seeds(SequenceDerivationData)]
     pub sequence: Mut<Sequence<'b>>,
 
     /// Payer for account creation
@@ -60,7 +61,7 @@ pub struct PostMessage<'b> {
 impl<'b> InstructionContext<'b> for PostMessage<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize)]
 pub struct PostMessageData {
     /// Unique nonce for this message
     pub nonce: u32,
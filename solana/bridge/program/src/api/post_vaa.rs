@@ -2,6 +2,7 @@ use solitaire::*;
 
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use solana_program::{
@@ -54,6 +55,7 @@ impl From<&PostVAAData> for GuardianSetDerivationData {
 #[derive(FromAccounts)]
 pub struct PostVAA<'b> {
     /// Information about the current guardian set.
+    #[seeds(GuardianSetDerivationData)]
     pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
 
     /// Bridge Info
@@ -63,6 +65,7 @@ pub struct PostVAA<'b> {
     pub signature_set: SignatureSet<'b, { AccountState::Initialized }>,
 
     /// Message the VAA is associated with.
+    #[seeds(PostedVAADerivationData)]
     pub message: Mut<PostedVAA<'b, { AccountState::MaybeInitialized }>>,
 
     /// Account used to pay for auxillary instructions.
@@ -85,7 +88,7 @@ pub struct Signature {
 
 pub type ForeignAddress = [u8; 32];
 
-#[derive(Default, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(Default, BorshSerialize, BorshDeserialize, BorshSchema, Clone, Serialize, Deserialize)]
 pub struct PostVAAData {
     // Header part
     pub version: u8,
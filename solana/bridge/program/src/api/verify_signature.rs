@@ -25,6 +25,7 @@ pub struct VerifySignatures<'b> {
     pub payer: Mut<Signer<Info<'b>>>,
 
     /// Guardian set of the signatures
+    #[seeds(GuardianSetDerivationData)]
     pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
 
     /// Signature Account
@@ -45,7 +46,7 @@ impl From<&VerifySignatures<'_>> for GuardianSetDerivationData {
     }
 }
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct VerifySignaturesData {
     /// instruction indices of signers (-1 for missing)
     pub signers: [i8; MAX_LEN_GUARDIAN_KEYS],
@@ -1,19 +1,21 @@
 use borsh::BorshSerialize;
 use solana_program::{
-    instruction::{
-        AccountMeta,
-        Instruction,
-    },
+    instruction::Instruction,
     pubkey::Pubkey,
     sysvar,
 };
 
 use byteorder::{
     BigEndian,
+    LittleEndian,
     WriteBytesExt,
 };
 use sha3::Digest;
 use solitaire::{
+    client::{
+        AccEntry,
+        ToInstruction,
+    },
     processors::seeded::Seeded,
     AccountState,
 };
@@ -24,19 +26,32 @@ use std::io::{
 
 use crate::{
     accounts::{
-        Bridge,
-        Claim,
         ClaimDerivationData,
-        FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
-        PostedVAA,
         PostedVAADerivationData,
-        Sequence,
         SequenceDerivationData,
     },
+    api::{
+        InitializeAccounts,
+        MigrateBridgeAccounts,
+        MigrateGuardianSetAccounts,
+        PostMessageAccounts,
+        PostVAAAccounts,
+        SetFeesAccounts,
+        TransferFeesAccounts,
+        UpgradeContractAccounts,
+        UpgradeGuardianSetAccounts,
+        VerifySignaturesAccounts,
+    },
     types::ConsistencyLevel,
+    vaa::{
+        ClaimableVAAAccounts,
+        SignatureItem,
+    },
     InitializeData,
+    MigrateBridgeData,
+    MigrateGuardianSetData,
     PostMessageData,
     PostVAAData,
     SetFeesData,
@@ -54,25 +69,16 @@ pub fn initialize(
     guardian_set_expiration_time: u32,
     initial_guardians: &[[u8; 20]],
 ) -> solitaire::Result<Instruction> {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
-        &GuardianSetDerivationData { index: 0 },
-        &program_id,
-    );
-    let fee_collector = FeeCollector::key(None, &program_id);
-
-    Ok(Instruction {
+    InitializeAccounts {
+        bridge: AccEntry::Derived(program_id),
+        guardian_set: GuardianSetDerivationData { index: 0 },
+        fee_collector: AccEntry::Derived(program_id),
+        payer: AccEntry::Signer(payer),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+    }
+    .to_ix(
         program_id,
-        accounts: vec![
-            AccountMeta::new(bridge, false),
-            AccountMeta::new(guardian_set, false),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-        data: (
+        &(
             crate::instruction::Instruction::Initialize,
             InitializeData {
                 initial_guardians: initial_guardians.to_vec(),
@@ -81,7 +87,7 @@ pub fn initialize(
             },
         )
             .try_to_vec()?,
-    })
+    )
 }
 
 pub fn post_message(
@@ -93,31 +99,20 @@ pub fn post_message(
     payload: Vec<u8>,
     commitment: ConsistencyLevel,
 ) -> solitaire::Result<Instruction> {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
-    let sequence = Sequence::<'_>::key(
-        &SequenceDerivationData {
-            emitter_key: &emitter,
+    PostMessageAccounts {
+        bridge: AccEntry::Derived(program_id),
+        message: AccEntry::Signer(message),
+        emitter: AccEntry::SignerRO(emitter),
+        sequence: SequenceDerivationData {
+            emitter_key: emitter,
         },
-        &program_id,
-    );
-
-    Ok(Instruction {
+        payer: AccEntry::Signer(payer),
+        fee_collector: AccEntry::Derived(program_id),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+    }
+    .to_ix(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(bridge, false),
-            AccountMeta::new(message, true),
-            AccountMeta::new_readonly(emitter, true),
-            AccountMeta::new(sequence, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (
+        &(
             crate::instruction::Instruction::PostMessage,
             PostMessageData {
                 nonce,
@@ -126,7 +121,7 @@ pub fn post_message(
             },
         )
             .try_to_vec()?,
-    })
+    )
 }
 
 pub fn verify_signatures(
@@ -136,27 +131,83 @@ pub fn verify_signatures(
     signature_set: Pubkey,
     data: VerifySignaturesData,
 ) -> solitaire::Result<Instruction> {
-    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
-        &GuardianSetDerivationData {
+    VerifySignaturesAccounts {
+        payer: AccEntry::Signer(payer),
+        guardian_set: GuardianSetDerivationData {
             index: guardian_set_index,
         },
-        &program_id,
-    );
-
-    Ok(Instruction {
+        signature_set: AccEntry::Signer(signature_set),
+        instruction_acc: AccEntry::UnprivilegedRO(sysvar::instructions::id()),
+    }
+    .to_ix(
         program_id,
+        &(crate::instruction::Instruction::VerifySignatures, data).try_to_vec()?,
+    )
+}
 
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(guardian_set, false),
-            AccountMeta::new(signature_set, true),
-            AccountMeta::new_readonly(sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
+/// Instructions verifying the guardian signatures of a VAA, which `post_vaa` requires. The secp256k1
+/// program checks at most 7 signatures per transaction, so these come in pairs of a secp256k1 and a
+/// `verify_signatures` instruction, each pair sent in its own transaction with the same
+/// `signature_set` signer. `body_hash` is the hash of the VAA body.
+pub fn verify_signatures_batches(
+    program_id: Pubkey,
+    payer: Pubkey,
+    guardian_set_index: u32,
+    signature_set: Pubkey,
+    signatures: &[SignatureItem],
+    body_hash: [u8; 32],
+) -> solitaire::Result<Vec<Vec<Instruction>>> {
+    let mut verify_txs: Vec<Vec<Instruction>> = Vec::new();
+    for chunk in signatures.chunks(7) {
+        let mut secp_payload = Vec::new();
+        let mut signature_status = [-1i8; 19];
+
+        let data_offset = 1 + chunk.len() * 11;
+        let message_offset = data_offset + chunk.len() * 85;
+
+        // 1 number of signatures
+        secp_payload.write_u8(chunk.len() as u8)?;
+
+        // Secp signature info description (11 bytes * n)
+        for (i, s) in chunk.iter().enumerate() {
+            secp_payload.write_u16::<LittleEndian>((data_offset + 85 * i) as u16)?;
+            secp_payload.write_u8(0)?;
+            secp_payload.write_u16::<LittleEndian>((data_offset + 85 * i + 65) as u16)?;
+            secp_payload.write_u8(0)?;
+            secp_payload.write_u16::<LittleEndian>(message_offset as u16)?;
+            secp_payload.write_u16::<LittleEndian>(body_hash.len() as u16)?;
+            secp_payload.write_u8(0)?;
+            signature_status[s.index as usize] = i as i8;
+        }
+
+        // Write signatures and addresses
+        for s in chunk.iter() {
+            secp_payload.write_all(&s.signature)?;
+            secp_payload.write_all(&s.key)?;
+        }
+
+        // Write body
+        secp_payload.write_all(&body_hash)?;
+
+        let secp_ix = Instruction {
+            program_id: solana_program::secp256k1_program::id(),
+            data: secp_payload,
+            accounts: vec![],
+        };
+
+        let verify_ix = verify_signatures(
+            program_id,
+            payer,
+            guardian_set_index,
+            signature_set,
+            VerifySignaturesData {
+                signers: signature_status,
+            },
+        )?;
 
-        data: (crate::instruction::Instruction::VerifySignatures, data).try_to_vec()?,
-    })
+        verify_txs.push(vec![secp_ix, verify_ix])
+    }
+    Ok(verify_txs)
 }
 
 pub fn post_vaa(
@@ -165,38 +216,36 @@ pub fn post_vaa(
     signature_set: Pubkey,
     vaa: PostVAAData,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
-        &GuardianSetDerivationData {
+    PostVAAAccounts {
+        guardian_set: GuardianSetDerivationData {
             index: vaa.guardian_set_index,
         },
-        &program_id,
-    );
-
-    let msg_derivation_data = &PostedVAADerivationData {
-        payload_hash: hash_vaa(&vaa).to_vec(),
-    };
-
-    let message =
-        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(&msg_derivation_data, &program_id);
-
-    Instruction {
+        bridge_info: AccEntry::DerivedRO(program_id),
+        signature_set: AccEntry::UnprivilegedRO(signature_set),
+        message: PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        payer: AccEntry::Signer(payer),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+    }
+    .to_ix(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new_readonly(guardian_set, false),
-            AccountMeta::new_readonly(bridge, false),
-            AccountMeta::new_readonly(signature_set, false),
-            AccountMeta::new(message, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (crate::instruction::Instruction::PostVAA, vaa)
+        &(crate::instruction::Instruction::PostVAA, vaa)
             .try_to_vec()
             .unwrap(),
+    )
+    .unwrap()
+}
+
+/// Accounts of a governance VAA emitted by `emitter` on Solana and posted at `payload_message`.
+fn claimable_vaa(payload_message: Pubkey, emitter: Pubkey, sequence: u64) -> ClaimableVAAAccounts {
+    ClaimableVAAAccounts {
+        message: AccEntry::UnprivilegedRO(payload_message),
+        claim: ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_SOLANA,
+            sequence,
+        },
     }
 }
 
@@ -209,49 +258,35 @@ pub fn upgrade_contract(
     spill: Pubkey,
     sequence: u64,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
-    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
-            sequence,
-        },
-        &program_id,
-    );
-
-    let (upgrade_authority, _) = Pubkey::find_program_address(&["upgrade".as_bytes()], &program_id);
-
     let (program_data, _) = Pubkey::find_program_address(
         &[program_id.as_ref()],
         &solana_program::bpf_loader_upgradeable::id(),
     );
 
-    Instruction {
+    UpgradeContractAccounts {
+        payer: AccEntry::Signer(payer),
+        bridge: AccEntry::Derived(program_id),
+        vaa: claimable_vaa(payload_message, emitter, sequence),
+        upgrade_authority: AccEntry::DerivedRO(program_id),
+        spill: AccEntry::Unprivileged(spill),
+        buffer: AccEntry::Unprivileged(new_contract),
+        program_data: AccEntry::Unprivileged(program_data),
+        own_address: AccEntry::Unprivileged(program_id),
+        rent: AccEntry::Sysvar(sysvar::rent::id()),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+        bpf_loader: AccEntry::CPIProgram(solana_program::bpf_loader_upgradeable::id()),
+        system: AccEntry::CPIProgram(solana_program::system_program::id()),
+    }
+    .to_ix(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(payload_message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new_readonly(upgrade_authority, false),
-            AccountMeta::new(spill, false),
-            AccountMeta::new(new_contract, false),
-            AccountMeta::new(program_data, false),
-            AccountMeta::new(program_id, false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (
+        &(
             crate::instruction::Instruction::UpgradeContract,
             UpgradeContractData {},
         )
             .try_to_vec()
             .unwrap(),
-    }
+    )
+    .unwrap()
 }
 
 pub fn upgrade_guardian_set(
@@ -263,46 +298,23 @@ pub fn upgrade_guardian_set(
     new_index: u32,
     sequence: u64,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
-            sequence: sequence,
-        },
-        &program_id,
-    );
-
-    let guardian_set_old = GuardianSet::<'_, { AccountState::Initialized }>::key(
-        &GuardianSetDerivationData { index: old_index },
-        &program_id,
-    );
-
-    let guardian_set_new = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
-        &GuardianSetDerivationData { index: new_index },
-        &program_id,
-    );
-
-    Instruction {
+    UpgradeGuardianSetAccounts {
+        payer: AccEntry::Signer(payer),
+        bridge: AccEntry::Derived(program_id),
+        vaa: claimable_vaa(payload_message, emitter, sequence),
+        guardian_set_old: GuardianSetDerivationData { index: old_index },
+        guardian_set_new: GuardianSetDerivationData { index: new_index },
+    }
+    .to_ix(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(payload_message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new(guardian_set_old, false),
-            AccountMeta::new(guardian_set_new, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (
+        &(
             crate::instruction::Instruction::UpgradeGuardianSet,
             UpgradeGuardianSetData {},
         )
             .try_to_vec()
             .unwrap(),
-    }
+    )
+    .unwrap()
 }
 
 pub fn set_fees(
@@ -312,31 +324,18 @@ pub fn set_fees(
     emitter: Pubkey,
     sequence: u64,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
-            sequence,
-        },
-        &program_id,
-    );
-
-    Instruction {
+    SetFeesAccounts {
+        payer: AccEntry::Signer(payer),
+        bridge: AccEntry::Derived(program_id),
+        vaa: claimable_vaa(message, emitter, sequence),
+    }
+    .to_ix(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (crate::instruction::Instruction::SetFees, SetFeesData {})
+        &(crate::instruction::Instruction::SetFees, SetFeesData {})
             .try_to_vec()
             .unwrap(),
-    }
+    )
+    .unwrap()
 }
 
 pub fn transfer_fees(
@@ -347,39 +346,72 @@ pub fn transfer_fees(
     sequence: u64,
     recipient: Pubkey,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
-            sequence,
-        },
-        &program_id,
-    );
-
-    let fee_collector = FeeCollector::key(None, &program_id);
-
-    Instruction {
+    TransferFeesAccounts {
+        payer: AccEntry::Signer(payer),
+        bridge: AccEntry::DerivedRO(program_id),
+        vaa: claimable_vaa(message, emitter, sequence),
+        fee_collector: AccEntry::Derived(program_id),
+        recipient: AccEntry::Unprivileged(recipient),
+        rent: AccEntry::Sysvar(sysvar::rent::id()),
+    }
+    .to_ix(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(bridge, false),
-            AccountMeta::new_readonly(message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new(recipient, false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (
+        &(
             crate::instruction::Instruction::TransferFees,
             TransferFeesData {},
         )
             .try_to_vec()
             .unwrap(),
+    )
+    .unwrap()
+}
+
+pub fn migrate_bridge(program_id: Pubkey, payer: Pubkey) -> Instruction {
+    MigrateBridgeAccounts {
+        payer: AccEntry::Signer(payer),
+        bridge: AccEntry::Derived(program_id),
+        system: AccEntry::CPIProgram(solana_program::system_program::id()),
+    }
+    .to_ix(
+        program_id,
+        &(
+            crate::instruction::Instruction::MigrateBridge,
+            MigrateBridgeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+pub fn migrate_guardian_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    guardian_set_index: u32,
+) -> Instruction {
+    MigrateGuardianSetAccounts {
+        payer: AccEntry::Signer(payer),
+        // Taken as a plain account on-chain, its legacy data can't be read as a guardian set yet
+        guardian_set: AccEntry::Unprivileged(
+            GuardianSet::<'_, { AccountState::Initialized }>::key(
+                &GuardianSetDerivationData {
+                    index: guardian_set_index,
+                },
+                &program_id,
+            ),
+        ),
+        system: AccEntry::CPIProgram(solana_program::system_program::id()),
     }
+    .to_ix(
+        program_id,
+        &(
+            crate::instruction::Instruction::MigrateGuardianSet,
+            MigrateGuardianSetData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    )
+    .unwrap()
 }
 
 // Convert a full VAA structure into the serialization of its unique components, this structure is
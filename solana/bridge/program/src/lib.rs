@@ -8,7 +8,7 @@ use solitaire::*;
 pub const MAX_LEN_GUARDIAN_KEYS: usize = 19;
 pub const CHAIN_ID_SOLANA: u16 = 1;
 
-#[cfg(feature = "no-entrypoint")]
+#[cfg(feature = "client")]
 pub mod instructions;
 
 #[cfg(feature = "wasm")]
@@ -45,6 +45,8 @@ pub use accounts::{
 pub mod api;
 pub use api::{
     initialize,
+    migrate_bridge,
+    migrate_guardian_set,
     post_message,
     post_vaa,
     set_fees,
@@ -54,6 +56,10 @@ pub use api::{
     verify_signatures,
     Initialize,
     InitializeData,
+    MigrateBridge,
+    MigrateBridgeData,
+    MigrateGuardianSet,
+    MigrateGuardianSetData,
     PostMessage,
     PostMessageData,
     PostVAA,
@@ -93,4 +99,6 @@ solitaire! {
     UpgradeContract(UpgradeContractData)        => upgrade_contract,
     UpgradeGuardianSet(UpgradeGuardianSetData)  => upgrade_guardian_set,
     VerifySignatures(VerifySignaturesData)      => verify_signatures,
+    MigrateBridge(MigrateBridgeData)            => migrate_bridge,
+    MigrateGuardianSet(MigrateGuardianSetData)  => migrate_guardian_set,
 }
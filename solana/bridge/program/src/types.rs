@@ -1,6 +1,7 @@
 use crate::{
     api::ForeignAddress,
     vaa::{
+        parse_governance,
         DeserializeGovernancePayload,
         DeserializePayload,
         SerializeGovernancePayload,
@@ -9,41 +10,41 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-};
 use primitive_types::U256;
 use serde::{
     Deserialize,
     Serialize,
 };
-use solana_program::{
-    program_error::ProgramError::InvalidAccountData,
-    pubkey::Pubkey,
-};
+use solana_program::pubkey::Pubkey;
 use solitaire::SolitaireError;
 use std::{
     self,
-    io::{
-        Cursor,
-        Read,
-        Write,
+    io::Write,
+};
+use wormhole_core::{
+    vaa::core::{
+        GovernanceContractUpgrade,
+        GovernanceGuardianSetChange,
+        GovernanceSetMessageFee,
+        GovernanceTransferFees,
     },
+    GovernanceAction,
 };
 
 /// Type representing an Ethereum style public key for Guardians.
 pub type GuardianPublicKey = [u8; 20];
 
 #[repr(u8)]
-#[derive(BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(BorshSerialize, BorshDeserialize, BorshSchema, Clone, Serialize, Deserialize)]
 pub enum ConsistencyLevel {
     Confirmed,
     Finalized,
 }
 
+#[derive(PartialEq, Debug)]
 pub struct GovernancePayloadUpgrade {
     // Address of the new Implementation
     pub new_contract: Pubkey,
@@ -51,7 +52,10 @@ pub struct GovernancePayloadUpgrade {
 
 impl SerializePayload for GovernancePayloadUpgrade {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        v.write(&self.new_contract.to_bytes())?;
+        let action = GovernanceContractUpgrade {
+            new_contract: self.new_contract.to_bytes(),
+        };
+        v.write_all(&GovernanceAction::serialize(&action))?;
         Ok(())
     }
 }
@@ -61,18 +65,9 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut c = Cursor::new(buf);
-        Self::check_governance_header(&mut c)?;
-
-        let mut addr = [0u8; 32];
-        c.read_exact(&mut addr)?;
-
-        if c.position() != c.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let action: GovernanceContractUpgrade = parse_governance(buf)?;
         Ok(GovernancePayloadUpgrade {
-            new_contract: Pubkey::new(&addr[..]),
+            new_contract: Pubkey::new_from_array(action.new_contract),
         })
     }
 }
@@ -85,6 +80,7 @@ impl SerializeGovernancePayload for GovernancePayloadUpgrade {
 impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
 }
 
+#[derive(PartialEq, Debug)]
 pub struct GovernancePayloadGuardianSetChange {
     // New GuardianSetIndex
     pub new_guardian_set_index: u32,
@@ -95,12 +91,11 @@ pub struct GovernancePayloadGuardianSetChange {
 
 impl SerializePayload for GovernancePayloadGuardianSetChange {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        use byteorder::WriteBytesExt;
-        v.write_u32::<BigEndian>(self.new_guardian_set_index)?;
-        v.write_u8(self.new_guardian_set.len() as u8)?;
-        for key in self.new_guardian_set.iter() {
-            v.write(key)?;
-        }
+        let action = GovernanceGuardianSetChange {
+            new_guardian_set_index: self.new_guardian_set_index,
+            new_guardian_set: self.new_guardian_set.clone(),
+        };
+        v.write_all(&GovernanceAction::serialize(&action))?;
         Ok(())
     }
 }
@@ -110,26 +105,10 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut c = Cursor::new(buf);
-        Self::check_governance_header(&mut c)?;
-
-        let new_index = c.read_u32::<BigEndian>()?;
-
-        let keys_len = c.read_u8()?;
-        let mut keys = Vec::with_capacity(keys_len as usize);
-        for _ in 0..keys_len {
-            let mut key: [u8; 20] = [0; 20];
-            c.read(&mut key)?;
-            keys.push(key);
-        }
-
-        if c.position() != c.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let action: GovernanceGuardianSetChange = parse_governance(buf)?;
         Ok(GovernancePayloadGuardianSetChange {
-            new_guardian_set_index: new_index,
-            new_guardian_set: keys,
+            new_guardian_set_index: action.new_guardian_set_index,
+            new_guardian_set: action.new_guardian_set,
         })
     }
 }
@@ -142,6 +121,7 @@ impl SerializeGovernancePayload for GovernancePayloadGuardianSetChange {
 impl DeserializeGovernancePayload for GovernancePayloadGuardianSetChange {
 }
 
+#[derive(PartialEq, Debug)]
 pub struct GovernancePayloadSetMessageFee {
     // New fee in lamports
     pub fee: U256,
@@ -149,10 +129,8 @@ pub struct GovernancePayloadSetMessageFee {
 
 impl SerializePayload for GovernancePayloadSetMessageFee {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        let mut fee_data = [0u8; 32];
-        self.fee.to_big_endian(&mut fee_data);
-        v.write(&fee_data[..])?;
-
+        let action = GovernanceSetMessageFee { fee: self.fee };
+        v.write_all(&GovernanceAction::serialize(&action))?;
         Ok(())
     }
 }
@@ -162,18 +140,8 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut c = Cursor::new(buf);
-        Self::check_governance_header(&mut c)?;
-
-        let mut fee_data: [u8; 32] = [0; 32];
-        c.read_exact(&mut fee_data)?;
-        let fee = U256::from_big_endian(&fee_data);
-
-        if c.position() != c.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
-        Ok(GovernancePayloadSetMessageFee { fee })
+        let action: GovernanceSetMessageFee = parse_governance(buf)?;
+        Ok(GovernancePayloadSetMessageFee { fee: action.fee })
     }
 }
 
@@ -185,6 +153,7 @@ impl SerializeGovernancePayload for GovernancePayloadSetMessageFee {
 impl DeserializeGovernancePayload for GovernancePayloadSetMessageFee {
 }
 
+#[derive(PartialEq, Debug)]
 pub struct GovernancePayloadTransferFees {
     // Amount to be transferred
     pub amount: U256,
@@ -195,10 +164,11 @@ pub struct GovernancePayloadTransferFees {
 
 impl SerializePayload for GovernancePayloadTransferFees {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        let mut amount_data = [0u8; 32];
-        self.amount.to_big_endian(&mut amount_data);
-        v.write(&amount_data)?;
-        v.write(&self.to)?;
+        let action = GovernanceTransferFees {
+            amount: self.amount,
+            to: self.to,
+        };
+        v.write_all(&GovernanceAction::serialize(&action))?;
         Ok(())
     }
 }
@@ -208,21 +178,11 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut c = Cursor::new(buf);
-        Self::check_governance_header(&mut c)?;
-
-        let mut amount_data: [u8; 32] = [0; 32];
-        c.read_exact(&mut amount_data)?;
-        let amount = U256::from_big_endian(&amount_data);
-
-        let mut to = ForeignAddress::default();
-        c.read_exact(&mut to)?;
-
-        if c.position() != c.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
-        Ok(GovernancePayloadTransferFees { amount, to })
+        let action: GovernanceTransferFees = parse_governance(buf)?;
+        Ok(GovernancePayloadTransferFees {
+            amount: action.amount,
+            to: action.to,
+        })
     }
 }
 
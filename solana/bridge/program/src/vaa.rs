@@ -15,15 +15,14 @@ use crate::{
     Result,
     CHAIN_ID_SOLANA,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-};
 use serde::{
     Deserialize,
     Serialize,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    program_error::ProgramError::InvalidAccountData,
+    pubkey::Pubkey,
+};
 use solitaire::{
     processors::seeded::Seeded,
     trace,
@@ -36,13 +35,16 @@ use solitaire::{
     *,
 };
 use std::{
-    io::{
-        Cursor,
-        Read,
-        Write,
-    },
+    io::Write,
     ops::Deref,
 };
+use wormhole_core::{
+    governance_module,
+    Chain,
+    GovHeader,
+    GovernanceAction,
+    WormholeError,
+};
 
 pub trait SerializePayload: Sized {
     fn serialize<W: Write>(&self, writer: &mut W) -> std::result::Result<(), SolitaireError>;
@@ -73,40 +75,45 @@ pub trait SerializeGovernancePayload: SerializePayload {
         &self,
         c: &mut W,
     ) -> std::result::Result<(), SolitaireError> {
-        use byteorder::WriteBytesExt;
-        let module = format!("{:\0>32}", Self::MODULE);
-        let module = module.as_bytes();
-        c.write(&module)?;
-        c.write_u8(Self::ACTION)?;
-        c.write_u16::<BigEndian>(CHAIN_ID_SOLANA)?;
+        let header = GovHeader {
+            module: governance_module(Self::MODULE.as_bytes()),
+            action: Self::ACTION,
+            chains: Chain::from(CHAIN_ID_SOLANA),
+        };
+        c.write_all(&header.to_bytes())?;
         Ok(())
     }
 }
 
 pub trait DeserializeGovernancePayload: DeserializePayload + SerializeGovernancePayload {
-    fn check_governance_header(
-        c: &mut Cursor<&mut &[u8]>,
-    ) -> std::result::Result<(), SolitaireError> {
-        let mut module = [0u8; 32];
-        c.read_exact(&mut module)?;
-        if module != format!("{:\0>32}", Self::MODULE).as_bytes() {
-            return Err(InvalidGovernanceModule.into());
-        }
-
-        let action = c.read_u8()?;
-        if action != Self::ACTION {
-            return Err(InvalidGovernanceAction.into());
-        }
+}
 
-        let chain = c.read_u16::<BigEndian>()?;
-        if chain != CHAIN_ID_SOLANA && chain != 0 {
-            return Err(InvalidGovernanceChain.into());
+/// Map a wormhole-core parse failure to the errors payloads have always been rejected with:
+/// governance header mismatches keep their own errors, malformed data is `InvalidAccountData`.
+pub fn payload_error(e: WormholeError) -> SolitaireError {
+    match e {
+        WormholeError::InvalidGovernanceAction => InvalidGovernanceAction.into(),
+        WormholeError::InvalidGovernanceChain => InvalidGovernanceChain.into(),
+        WormholeError::InvalidGovernanceModule => InvalidGovernanceModule.into(),
+        WormholeError::DeserializeFailed
+        | WormholeError::InvalidAddress
+        | WormholeError::InvalidPayload
+        | WormholeError::AmountTooLarge
+        | WormholeError::DustAmount
+        | WormholeError::ParseError(_) => {
+            InvalidAccountData.into()
         }
-
-        Ok(())
     }
 }
 
+/// Parse a governance action addressed to Solana, or to all chains, with wormhole-core. The
+/// payload must hold exactly one action.
+pub fn parse_governance<A: GovernanceAction>(buf: &[u8]) -> std::result::Result<A, SolitaireError> {
+    A::from_bytes(buf, Some(Chain::from(CHAIN_ID_SOLANA)))
+        .map(|(_, action)| action)
+        .map_err(payload_error)
+}
+
 pub struct PayloadMessage<'b, T: DeserializePayload>(
     Data<'b, PostedVAAData, { AccountState::Initialized }>,
     T,
@@ -132,6 +139,27 @@ impl<'a, 'b: 'a, 'c, T: DeserializePayload> Peel<'a, 'b, 'c> for PayloadMessage<
     }
 }
 
+#[cfg(feature = "idl")]
+impl<'b, T: DeserializePayload> solitaire::idl::IdlAccounts for PayloadMessage<'b, T> {
+    fn idl_accounts(name: &str) -> Vec<solitaire::idl::IdlAccount> {
+        Data::<'b, PostedVAAData, { AccountState::Initialized }>::idl_accounts(name)
+    }
+}
+
+#[cfg(feature = "client")]
+impl<'b, T: DeserializePayload> solitaire::client::Wrap for PayloadMessage<'b, T> {
+    type Entry = solitaire::client::AccEntry;
+
+    fn wrap(
+        a: &Self::Entry,
+        program_id: &Pubkey,
+    ) -> Result<Vec<solana_program::instruction::AccountMeta>> {
+        <Data<'b, PostedVAAData, { AccountState::Initialized }> as solitaire::client::Wrap>::wrap(
+            a, program_id,
+        )
+    }
+}
+
 impl<'b, T: DeserializePayload> Deref for PayloadMessage<'b, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
@@ -146,14 +174,42 @@ impl<'b, T: DeserializePayload> PayloadMessage<'b, T> {
 }
 
 #[derive(FromAccounts)]
+#[client(manual)]
 pub struct ClaimableVAA<'b, T: DeserializePayload> {
     // Signed message for the transfer
     pub message: PayloadMessage<'b, T>,
 
     // Claim account to prevent double spending
+    #[seeds(ClaimDerivationData)]
     pub claim: Mut<Claim<'b, { AccountState::Uninitialized }>>,
 }
 
+/// Client-side accounts of a `ClaimableVAA`. Written out by hand as the generated builders cannot
+/// be generic over the payload type.
+#[cfg(feature = "client")]
+pub struct ClaimableVAAAccounts {
+    pub message: solitaire::client::AccEntry,
+    pub claim: ClaimDerivationData,
+}
+
+#[cfg(feature = "client")]
+impl<'b, T: DeserializePayload> solitaire::client::Wrap for ClaimableVAA<'b, T> {
+    type Entry = ClaimableVAAAccounts;
+
+    fn wrap(
+        accs: &Self::Entry,
+        program_id: &Pubkey,
+    ) -> Result<Vec<solana_program::instruction::AccountMeta>> {
+        let mut account_metas =
+            <PayloadMessage<'b, T> as solitaire::client::Wrap>::wrap(&accs.message, program_id)?;
+        account_metas.push(solana_program::instruction::AccountMeta::new(
+            Claim::<'b, { AccountState::Uninitialized }>::key(&accs.claim, program_id),
+            false,
+        ));
+        Ok(account_metas)
+    }
+}
+
 impl<'b, T: DeserializePayload> Deref for ClaimableVAA<'b, T> {
     type Target = PayloadMessage<'b, T>;
     fn deref(&self) -> &Self::Target {
@@ -239,40 +295,29 @@ impl VAA {
     pub const SIGNATURE_LEN: usize = 66;
 
     pub fn deserialize(data: &[u8]) -> std::result::Result<VAA, std::io::Error> {
-        let mut rdr = Cursor::new(data);
-        let mut v = VAA::default();
-
-        v.version = rdr.read_u8()?;
-        v.guardian_set_index = rdr.read_u32::<BigEndian>()?;
-
-        let len_sig = rdr.read_u8()?;
-        let mut sigs: Vec<VAASignature> = Vec::with_capacity(len_sig as usize);
-        for _i in 0..len_sig {
-            let mut sig = VAASignature::default();
-
-            sig.guardian_index = rdr.read_u8()?;
-            let mut signature_data = [0u8; 65];
-            rdr.read_exact(&mut signature_data)?;
-            sig.signature = signature_data.to_vec();
-
-            sigs.push(sig);
-        }
-        v.signatures = sigs;
-
-        v.timestamp = rdr.read_u32::<BigEndian>()?;
-        v.nonce = rdr.read_u32::<BigEndian>()?;
-        v.emitter_chain = rdr.read_u16::<BigEndian>()?;
-
-        let mut emitter_address = [0u8; 32];
-        rdr.read_exact(&mut emitter_address)?;
-        v.emitter_address = emitter_address;
+        let vaa = wormhole_core::VAA::from_bytes(data).map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", e))
+        })?;
 
-        v.sequence = rdr.read_u64::<BigEndian>()?;
-        v.consistency_level = rdr.read_u8()?;
-
-        rdr.read_to_end(&mut v.payload)?;
-
-        Ok(v)
+        Ok(VAA {
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            signatures: vaa
+                .signatures
+                .iter()
+                .map(|sig| VAASignature {
+                    guardian_index: sig[0],
+                    signature: sig[1..].to_vec(),
+                })
+                .collect(),
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            emitter_chain: vaa.emitter_chain.into(),
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload,
+        })
     }
 }
 
@@ -1,7 +1,4 @@
-use solana_program::{
-    instruction::Instruction,
-    pubkey::Pubkey,
-};
+use solana_program::pubkey::Pubkey;
 use std::str::FromStr;
 
 use crate::vaa::{
@@ -10,10 +7,12 @@ use crate::vaa::{
     VAA,
 };
 use borsh::BorshDeserialize;
-use byteorder::WriteBytesExt;
 use sha3::Digest;
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        discriminator::deserialize_account,
+        seeded::Seeded,
+    },
     AccountState,
 };
 use std::io::Write;
@@ -38,7 +37,7 @@ use crate::{
         transfer_fees,
         upgrade_contract,
         upgrade_guardian_set,
-        verify_signatures,
+        verify_signatures_batches,
     },
     types::{
         ConsistencyLevel,
@@ -49,9 +48,7 @@ use crate::{
     Claim,
     ClaimDerivationData,
     PostVAAData,
-    VerifySignaturesData,
 };
-use byteorder::LittleEndian;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -241,69 +238,17 @@ pub fn verify_signatures_ix(
         h.finalize().into()
     };
 
-    let mut verify_txs: Vec<Vec<Instruction>> = Vec::new();
-    for (_tx_index, chunk) in signature_items.chunks(7).enumerate() {
-        let mut secp_payload = Vec::new();
-        let mut signature_status = [-1i8; 19];
-
-        let data_offset = 1 + chunk.len() * 11;
-        let message_offset = data_offset + chunk.len() * 85;
-
-        // 1 number of signatures
-        secp_payload.write_u8(chunk.len() as u8).unwrap();
-
-        // Secp signature info description (11 bytes * n)
-        for (i, s) in chunk.iter().enumerate() {
-            secp_payload
-                .write_u16::<LittleEndian>((data_offset + 85 * i) as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>((data_offset + 85 * i + 65) as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>(message_offset as u16)
-                .unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>(body_hash.len() as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            signature_status[s.index as usize] = i as i8;
-        }
-
-        // Write signatures and addresses
-        for s in chunk.iter() {
-            secp_payload.write(&s.signature).unwrap();
-            secp_payload.write(&s.key).unwrap();
-        }
-
-        // Write body
-        secp_payload.write(&body_hash).unwrap();
-
-        let secp_ix = Instruction {
-            program_id: solana_program::secp256k1_program::id(),
-            data: secp_payload,
-            accounts: vec![],
-        };
-
-        let payload = VerifySignaturesData {
-            signers: signature_status,
-        };
-
-        let verify_ix = match verify_signatures(
-            program_id,
-            payer,
-            guardian_set_index,
-            signature_set,
-            payload,
-        ) {
-            Ok(v) => v,
-            Err(e) => panic!("{:?}", e),
-        };
-
-        verify_txs.push(vec![secp_ix, verify_ix])
-    }
+    let verify_txs = match verify_signatures_batches(
+        program_id,
+        payer,
+        guardian_set_index,
+        signature_set,
+        &signature_items,
+        body_hash,
+    ) {
+        Ok(v) => v,
+        Err(e) => panic!("{:?}", e),
+    };
 
     JsValue::from_serde(&verify_txs).unwrap()
 }
@@ -321,7 +266,7 @@ pub fn guardian_set_address(bridge: String, index: u32) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_guardian_set(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&GuardianSetData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&deserialize_account::<GuardianSetData>(data.as_slice()).unwrap()).unwrap()
 }
 
 #[wasm_bindgen]
@@ -334,7 +279,7 @@ pub fn state_address(bridge: String) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_state(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&BridgeData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&deserialize_account::<BridgeData>(data.as_slice()).unwrap()).unwrap()
 }
 
 #[wasm_bindgen]
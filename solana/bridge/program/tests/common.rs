@@ -85,7 +85,13 @@ use bridge::{
 };
 
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        discriminator::deserialize_account,
+        seeded::{
+            Owned,
+            Seeded,
+        },
+    },
     AccountState,
 };
 
@@ -148,9 +154,12 @@ mod helpers {
     }
 
     /// Fetch account data, the loop is there to re-attempt until data is available.
-    pub fn get_account_data<T: BorshDeserialize>(client: &RpcClient, account: &Pubkey) -> T {
+    pub fn get_account_data<T: BorshDeserialize + Owned>(
+        client: &RpcClient,
+        account: &Pubkey,
+    ) -> T {
         let account = client.get_account(account).unwrap();
-        T::try_from_slice(&account.data).unwrap()
+        deserialize_account(&account.data).unwrap()
     }
 
     /// Generate `count` secp256k1 private keys, along with their ethereum-styled public key
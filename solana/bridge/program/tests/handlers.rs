@@ -0,0 +1,357 @@
+#![allow(warnings)]
+
+//! Handler tests on in-memory accounts, see `solitaire::testing`. Each test runs the instruction
+//! built by `bridge::instructions` through the program's dispatcher and checks the accounts it
+//! leaves behind.
+
+use borsh::BorshSerialize;
+use solana_program::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    system_program,
+    sysvar::{
+        self,
+        clock::Clock,
+    },
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    testing::{
+        Invocation,
+        TestAccount,
+        TestRuntime,
+    },
+    AccountState,
+    Result,
+    SolitaireError,
+};
+
+use bridge::{
+    accounts::{
+        Bridge,
+        BridgeConfig,
+        BridgeData,
+        FeeCollector,
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        PostedMessageData,
+        Sequence,
+        SequenceDerivationData,
+        SequenceTracker,
+    },
+    error::Error,
+    instructions,
+    types::ConsistencyLevel,
+    CHAIN_ID_SOLANA,
+};
+
+const FEE: u64 = 500;
+
+fn process(runtime: &mut TestRuntime, ix: &Instruction) -> Result<Vec<Invocation>> {
+    runtime.process(ix, |p, a, d| bridge::instruction::dispatch(p, a, d))
+}
+
+fn bridge_key(program_id: &Pubkey) -> Pubkey {
+    Bridge::<'_, { AccountState::Initialized }>::key(None, program_id)
+}
+
+fn guardian_set_key(program_id: &Pubkey, index: u32) -> Pubkey {
+    GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index },
+        program_id,
+    )
+}
+
+/// A runtime with a funded payer and the clock set, returns the payer.
+fn setup(program_id: &Pubkey) -> (TestRuntime, Pubkey) {
+    let mut runtime = TestRuntime::new();
+    let payer = Pubkey::new_unique();
+    runtime.set_account(payer, TestAccount::wallet(1_000_000_000));
+    runtime.set_account(
+        sysvar::clock::id(),
+        TestAccount::with_sysvar(&Clock {
+            unix_timestamp: 1_000,
+            ..Clock::default()
+        }),
+    );
+    (runtime, payer)
+}
+
+/// A runtime in the state `initialize` leaves behind.
+fn initialized(program_id: &Pubkey) -> (TestRuntime, Pubkey) {
+    let (mut runtime, payer) = setup(program_id);
+    let ix = instructions::initialize(*program_id, payer, FEE, 3600, &[[1u8; 20]]).unwrap();
+    process(&mut runtime, &ix).unwrap();
+    (runtime, payer)
+}
+
+#[test]
+fn initialize() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+
+    let ix =
+        instructions::initialize(program_id, payer, FEE, 3600, &[[1u8; 20], [2u8; 20]]).unwrap();
+    let invocations = process(&mut runtime, &ix).unwrap();
+
+    // Guardian set, bridge and fee collector are all created by the program itself.
+    assert_eq!(invocations.len(), 3);
+    assert!(invocations
+        .iter()
+        .all(|i| i.instruction.program_id == system_program::id()));
+
+    let bridge: BridgeData = runtime.account(&bridge_key(&program_id)).read().unwrap();
+    assert_eq!(bridge.guardian_set_index, 0);
+    assert_eq!(bridge.config.fee, FEE);
+    assert_eq!(bridge.config.guardian_set_expiration_time, 3600);
+
+    let guardian_set = runtime.account(&guardian_set_key(&program_id, 0));
+    assert_eq!(guardian_set.owner, program_id);
+    let guardian_set: GuardianSetData = guardian_set.read().unwrap();
+    assert_eq!(guardian_set.keys, vec![[1u8; 20], [2u8; 20]]);
+    assert_eq!(guardian_set.creation_time, 1_000);
+
+    let fee_collector = runtime.account(&FeeCollector::key(None, &program_id));
+    assert_eq!(fee_collector.owner, system_program::id());
+    assert_eq!(fee_collector.lamports, Rent::default().minimum_balance(0));
+    assert_eq!(bridge.last_lamports, fee_collector.lamports);
+}
+
+#[test]
+fn initialize_twice() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = initialized(&program_id);
+
+    let ix = instructions::initialize(program_id, payer, FEE, 3600, &[[1u8; 20]]).unwrap();
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::AlreadyInitialized(_))
+    ));
+}
+
+#[test]
+fn post_message() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = initialized(&program_id);
+    let (emitter, message) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let fee_collector = FeeCollector::key(None, &program_id);
+
+    let post = instructions::post_message(
+        program_id,
+        payer,
+        emitter,
+        message,
+        7,
+        b"payload".to_vec(),
+        ConsistencyLevel::Finalized,
+    )
+    .unwrap();
+
+    // Without the fee paid nothing is written.
+    assert!(matches!(
+        process(&mut runtime, &post),
+        Err(SolitaireError::Custom(e)) if e == Error::InsufficientFees as u64
+    ));
+    assert_eq!(runtime.account(&message), TestAccount::default());
+
+    let mut collector = runtime.account(&fee_collector);
+    collector.lamports += FEE;
+    runtime.set_account(fee_collector, collector);
+    process(&mut runtime, &post).unwrap();
+
+    let posted: PostedMessageData = runtime.account(&message).read().unwrap();
+    assert_eq!(posted.0.emitter_chain, CHAIN_ID_SOLANA);
+    assert_eq!(posted.0.emitter_address, emitter.to_bytes());
+    assert_eq!(posted.0.nonce, 7);
+    assert_eq!(posted.0.sequence, 0);
+    assert_eq!(posted.0.consistency_level, 32);
+    assert_eq!(posted.0.submission_time, 1_000);
+    assert_eq!(posted.0.payload, b"payload".to_vec());
+
+    let sequence = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: emitter,
+        },
+        &program_id,
+    );
+    let tracker: SequenceTracker = runtime.account(&sequence).read().unwrap();
+    assert_eq!(tracker.sequence, 1);
+
+    let bridge: BridgeData = runtime.account(&bridge_key(&program_id)).read().unwrap();
+    assert_eq!(
+        bridge.last_lamports,
+        runtime.account(&fee_collector).lamports
+    );
+}
+
+#[test]
+fn type_confused_bridge() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = initialized(&program_id);
+
+    // A guardian set decodes as Borsh into a bridge, only the discriminator tells them apart.
+    let guardian_set = runtime.account(&guardian_set_key(&program_id, 0));
+    runtime.set_account(bridge_key(&program_id), guardian_set);
+
+    let ix = instructions::post_message(
+        program_id,
+        payer,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        0,
+        vec![],
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::InvalidDiscriminator(k)) if k == bridge_key(&program_id)
+    ));
+}
+
+/// An account in the layout used before discriminators were introduced.
+fn legacy<T: BorshSerialize>(program_id: &Pubkey, value: &T) -> TestAccount {
+    let data = value.try_to_vec().unwrap();
+    TestAccount {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: *program_id,
+        executable: false,
+    }
+}
+
+#[test]
+fn migrate_bridge() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+    let bridge = BridgeData {
+        guardian_set_index: 2,
+        last_lamports: 10,
+        config: BridgeConfig {
+            guardian_set_expiration_time: 3600,
+            fee: FEE,
+        },
+    };
+    runtime.set_account(bridge_key(&program_id), legacy(&program_id, &bridge));
+
+    let ix = instructions::migrate_bridge(program_id, payer);
+    let invocations = process(&mut runtime, &ix).unwrap();
+
+    // The payer tops up rent for the discriminator.
+    assert_eq!(invocations.len(), 1);
+    let migrated = runtime.account(&bridge_key(&program_id));
+    assert_eq!(&migrated.data[..3], b"brg");
+    assert_eq!(
+        migrated.lamports,
+        Rent::default().minimum_balance(migrated.data.len())
+    );
+    let migrated: BridgeData = migrated.read().unwrap();
+    assert_eq!(migrated.guardian_set_index, 2);
+    assert_eq!(migrated.config.fee, FEE);
+
+    // Running it again is a no-op.
+    assert_eq!(process(&mut runtime, &ix).unwrap(), vec![]);
+}
+
+#[test]
+fn post_message_legacy_bridge() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = initialized(&program_id);
+    let fee_collector = FeeCollector::key(None, &program_id);
+
+    // A bridge written before the upgrade keeps working until it is migrated.
+    let bridge: BridgeData = runtime.account(&bridge_key(&program_id)).read().unwrap();
+    runtime.set_account(bridge_key(&program_id), legacy(&program_id, &bridge));
+
+    let mut collector = runtime.account(&fee_collector);
+    collector.lamports += FEE;
+    runtime.set_account(fee_collector, collector);
+    let ix = instructions::post_message(
+        program_id,
+        payer,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        0,
+        vec![],
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    process(&mut runtime, &ix).unwrap();
+
+    // It is written back without a discriminator, which doesn't fit.
+    let written = runtime.account(&bridge_key(&program_id));
+    assert_eq!(written.data.len(), bridge.try_to_vec().unwrap().len());
+    let written: BridgeData = written.read().unwrap();
+    assert_eq!(
+        written.last_lamports,
+        runtime.account(&fee_collector).lamports
+    );
+}
+
+#[test]
+fn migrate_guardian_set() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+    let guardian_set = GuardianSetData {
+        index: 1,
+        keys: vec![[1u8; 20]],
+        creation_time: 5,
+        expiration_time: 0,
+    };
+    runtime.set_account(
+        guardian_set_key(&program_id, 1),
+        legacy(&program_id, &guardian_set),
+    );
+
+    // The index stored in the account must derive the key it is stored at.
+    runtime.set_account(
+        guardian_set_key(&program_id, 0),
+        legacy(&program_id, &guardian_set),
+    );
+    let ix = instructions::migrate_guardian_set(program_id, payer, 0);
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::InvalidDerive(..))
+    ));
+
+    let ix = instructions::migrate_guardian_set(program_id, payer, 1);
+    process(&mut runtime, &ix).unwrap();
+    let migrated: GuardianSetData = runtime
+        .account(&guardian_set_key(&program_id, 1))
+        .read()
+        .unwrap();
+    assert_eq!(migrated.index, 1);
+    assert_eq!(migrated.keys, vec![[1u8; 20]]);
+}
+
+#[test]
+fn migrate_padded_guardian_set() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+    let guardian_set = GuardianSetData {
+        index: 1,
+        keys: vec![[1u8; 20]],
+        creation_time: 5,
+        expiration_time: 0,
+    };
+
+    // Legacy accounts allocated larger than their data carry zeroes after it.
+    let mut padded = legacy(&program_id, &guardian_set);
+    padded.data.extend_from_slice(&[0u8; 32]);
+    runtime.set_account(guardian_set_key(&program_id, 1), padded);
+
+    let ix = instructions::migrate_guardian_set(program_id, payer, 1);
+    process(&mut runtime, &ix).unwrap();
+    let migrated: GuardianSetData = runtime
+        .account(&guardian_set_key(&program_id, 1))
+        .read()
+        .unwrap();
+    assert_eq!(migrated.index, 1);
+    assert_eq!(migrated.keys, vec![[1u8; 20]]);
+
+    // Running it again is a no-op.
+    assert_eq!(process(&mut runtime, &ix).unwrap(), vec![]);
+}
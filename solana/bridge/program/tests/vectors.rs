@@ -0,0 +1,143 @@
+#![allow(warnings)]
+
+//! Runs the shared wire format vectors in `sdk/vectors` through the bridge's VAA and governance
+//! payload types. wormhole-core and the Terra contracts load the same file.
+
+use primitive_types::U256;
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+
+use bridge::{
+    instructions::hash_vaa,
+    types::{
+        GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetMessageFee,
+        GovernancePayloadTransferFees,
+        GovernancePayloadUpgrade,
+    },
+    vaa::VAA,
+    DeserializePayload,
+    PostVAAData,
+    SerializePayload,
+};
+
+const VECTORS: &str = include_str!("../../../../sdk/vectors/v1.json");
+
+/// The vectors of the given types as (name, bytes, valid, fields, canonical encoding).
+fn vectors(kinds: &[&str]) -> Vec<(String, Vec<u8>, bool, Value, Vec<u8>)> {
+    let file: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(file["version"], 1);
+    file["vectors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| kinds.contains(&v["type"].as_str().unwrap()))
+        .map(|v| {
+            let bytes = hex::decode(v["hex"].as_str().unwrap()).unwrap();
+            let canonical = match v["canonical"].as_str() {
+                Some(canonical) => hex::decode(canonical).unwrap(),
+                None => bytes.clone(),
+            };
+            let name = v["name"].as_str().unwrap().to_string();
+            (name, bytes, v["valid"].as_bool().unwrap(), v["fields"].clone(), canonical)
+        })
+        .collect()
+}
+
+fn bytes(fields: &Value, key: &str) -> Vec<u8> {
+    hex::decode(fields[key].as_str().unwrap()).unwrap()
+}
+
+fn int(fields: &Value, key: &str) -> u64 {
+    fields[key].as_u64().unwrap()
+}
+
+fn u256(fields: &Value, key: &str) -> U256 {
+    U256::from_big_endian(&bytes(fields, key))
+}
+
+#[test]
+fn test_vaa_vectors() {
+    for (name, data, valid, f, _) in vectors(&["vaa"]) {
+        let vaa = match VAA::deserialize(&data) {
+            Ok(vaa) => vaa,
+            Err(_) => {
+                assert!(!valid, "{}", name);
+                continue;
+            }
+        };
+        assert!(valid, "{}", name);
+
+        let signatures: Vec<String> = vaa
+            .signatures
+            .iter()
+            .map(|s| hex::encode([&[s.guardian_index][..], &s.signature].concat()))
+            .collect();
+        assert_eq!(vaa.version as u64, int(&f, "version"), "{}", name);
+        assert_eq!(vaa.guardian_set_index as u64, int(&f, "guardian_set_index"), "{}", name);
+        assert_eq!(Value::from(signatures), f["signatures"], "{}", name);
+        assert_eq!(vaa.timestamp as u64, int(&f, "timestamp"), "{}", name);
+        assert_eq!(vaa.nonce as u64, int(&f, "nonce"), "{}", name);
+        assert_eq!(vaa.emitter_chain as u64, int(&f, "emitter_chain"), "{}", name);
+        assert_eq!(vaa.emitter_address.to_vec(), bytes(&f, "emitter_address"), "{}", name);
+        assert_eq!(vaa.sequence, int(&f, "sequence"), "{}", name);
+        assert_eq!(vaa.consistency_level as u64, int(&f, "consistency_level"), "{}", name);
+        assert_eq!(vaa.payload, bytes(&f, "payload"), "{}", name);
+
+        // The posted VAA is hashed from the program's own encoding of the body.
+        let hash = hash_vaa(&PostVAAData::from(vaa));
+        assert_eq!(hash.to_vec(), bytes(&f, "hash"), "{}", name);
+    }
+}
+
+/// Check a governance payload type against the vectors of `kind`. The bridge serializes
+/// governance payloads without their header, `expected` builds the payload from the fields.
+fn governance<T, F>(kind: &str, expected: F)
+where
+    T: DeserializePayload + SerializePayload + PartialEq + std::fmt::Debug,
+    F: Fn(&Value) -> T,
+{
+    for (name, data, valid, f, canonical) in vectors(&[kind]) {
+        match T::deserialize(&mut data.as_slice()) {
+            Ok(payload) => {
+                assert!(valid, "{}", name);
+                assert_eq!(payload, expected(&f), "{}", name);
+                assert_eq!(payload.try_to_vec().unwrap(), canonical[35..].to_vec(), "{}", name);
+            }
+            Err(_) => assert!(!valid, "{}", name),
+        }
+    }
+}
+
+#[test]
+fn test_governance_vectors() {
+    governance("core_contract_upgrade", |f| GovernancePayloadUpgrade {
+        new_contract: Pubkey::new(&bytes(f, "new_contract")),
+    });
+    governance("core_guardian_set_change", |f| {
+        GovernancePayloadGuardianSetChange {
+            new_guardian_set_index: int(f, "new_guardian_set_index") as u32,
+            new_guardian_set: f["new_guardian_set"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|key| {
+                    let mut out = [0u8; 20];
+                    out.copy_from_slice(&hex::decode(key.as_str().unwrap()).unwrap());
+                    out
+                })
+                .collect(),
+        }
+    });
+    governance("core_set_message_fee", |f| GovernancePayloadSetMessageFee {
+        fee: u256(f, "fee"),
+    });
+    governance("core_transfer_fees", |f| {
+        let mut to = [0u8; 32];
+        to.copy_from_slice(&bytes(f, "to"));
+        GovernancePayloadTransferFees {
+            amount: u256(f, "amount"),
+            to,
+        }
+    });
+}
@@ -0,0 +1,206 @@
+//! Unsigned governance VAAs. Governance actions are ordinary VAAs from a well known emitter, the
+//! payload formats are defined by `wormhole_core::vaa` for every module.
+
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use primitive_types::U256;
+use wormhole_core::{
+    vaa::{
+        core,
+        nft,
+        token,
+        GovernanceAction,
+    },
+    Chain,
+    UniversalAddress,
+    VAA,
+};
+
+use crate::Error;
+
+/// The emitter the guardians sign governance actions as, address 4 on Solana.
+pub const GOVERNANCE_CHAIN: Chain = Chain::Solana;
+pub const GOVERNANCE_ADDRESS: [u8; 32] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4,
+];
+
+/// The fields of a governance VAA around its payload.
+pub struct Envelope {
+    pub guardian_set_index: u32,
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: Chain,
+    pub emitter_address: [u8; 32],
+}
+
+impl Default for Envelope {
+    /// Governance emitted now, under a random sequence so repeated runs are not rejected as
+    /// replays.
+    fn default() -> Self {
+        Envelope {
+            guardian_set_index: 0,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as u32,
+            nonce: 0,
+            sequence: rand::random::<u32>().into(),
+            emitter_chain: GOVERNANCE_CHAIN,
+            emitter_address: GOVERNANCE_ADDRESS,
+        }
+    }
+}
+
+impl Envelope {
+    /// An unsigned VAA carrying `action`, addressed to `chain`.
+    pub fn wrap<A: GovernanceAction>(&self, action: &A, chain: Chain) -> VAA {
+        VAA {
+            version: 1,
+            guardian_set_index: self.guardian_set_index,
+            signatures: vec![],
+            timestamp: self.timestamp,
+            nonce: self.nonce,
+            emitter_chain: self.emitter_chain.clone(),
+            emitter_address: self.emitter_address,
+            sequence: self.sequence,
+            consistency_level: 0,
+            payload: action.to_bytes(chain),
+        }
+    }
+}
+
+/// A chain by name, as in `Chain::name`, or by ID.
+pub fn parse_chain(chain: &str) -> Result<Chain, Error> {
+    Chain::from_name(chain)
+        .or_else(|| chain.parse::<u16>().ok().map(Chain::from))
+        .ok_or_else(|| format!("Unknown chain {}", chain).into())
+}
+
+/// An address on `chain` in its native format, or 32 bytes of hex for chains and contracts that
+/// have no native format, such as Terra code IDs.
+pub fn parse_address(chain: &Chain, address: &str) -> Result<[u8; 32], Error> {
+    let raw = address.strip_prefix("0x").unwrap_or(address);
+    if raw.len() == 64 {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(raw, &mut bytes)?;
+        return Ok(bytes);
+    }
+    UniversalAddress::parse(chain, address)
+        .map(|address| address.0)
+        .map_err(|_| format!("Invalid {} address {}", chain.name(), address).into())
+}
+
+/// A 20 byte guardian address, with or without `0x`.
+pub fn parse_guardian(address: &str) -> Result<[u8; 20], Error> {
+    let mut bytes = [0u8; 20];
+    hex::decode_to_slice(address.strip_prefix("0x").unwrap_or(address), &mut bytes)
+        .map_err(|_| format!("Invalid guardian address {}", address))?;
+    Ok(bytes)
+}
+
+pub fn parse_amount(amount: &str) -> Result<U256, Error> {
+    U256::from_dec_str(amount).map_err(|_| format!("Invalid amount {}", amount).into())
+}
+
+pub fn core_upgrade_contract(envelope: &Envelope, chain: Chain, new_contract: [u8; 32]) -> VAA {
+    envelope.wrap(&core::GovernanceContractUpgrade { new_contract }, chain)
+}
+
+/// Guardian set upgrades are addressed to every chain.
+pub fn guardian_set_upgrade(envelope: &Envelope, index: u32, guardians: Vec<[u8; 20]>) -> VAA {
+    envelope.wrap(
+        &core::GovernanceGuardianSetChange {
+            new_guardian_set_index: index,
+            new_guardian_set: guardians,
+        },
+        Chain::All,
+    )
+}
+
+pub fn set_message_fee(envelope: &Envelope, chain: Chain, fee: U256) -> VAA {
+    envelope.wrap(&core::GovernanceSetMessageFee { fee }, chain)
+}
+
+pub fn transfer_fees(envelope: &Envelope, chain: Chain, amount: U256, to: [u8; 32]) -> VAA {
+    envelope.wrap(&core::GovernanceTransferFees { amount, to }, chain)
+}
+
+/// Registrations of token bridges on other chains are addressed to every chain.
+pub fn token_register_chain(envelope: &Envelope, emitter: Chain, address: [u8; 32]) -> VAA {
+    envelope.wrap(
+        &token::GovernanceRegisterChain {
+            emitter,
+            endpoint_address: address,
+        },
+        Chain::All,
+    )
+}
+
+pub fn token_upgrade_contract(envelope: &Envelope, chain: Chain, new_contract: [u8; 32]) -> VAA {
+    envelope.wrap(&token::GovernanceContractUpgrade { new_contract }, chain)
+}
+
+pub fn nft_register_chain(envelope: &Envelope, emitter: Chain, address: [u8; 32]) -> VAA {
+    envelope.wrap(
+        &nft::GovernanceRegisterChain {
+            emitter,
+            endpoint_address: address,
+        },
+        Chain::All,
+    )
+}
+
+pub fn nft_upgrade_contract(envelope: &Envelope, chain: Chain, new_contract: [u8; 32]) -> VAA {
+    envelope.wrap(&nft::GovernanceContractUpgrade { new_contract }, chain)
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use wormhole_core::Payload;
+
+    #[test]
+    fn test_governance() {
+        let envelope = Envelope::default();
+        let ethereum = parse_address(
+            &Chain::Ethereum,
+            "0x0290FB167208Af455bB137780163b7B7a9a10C16",
+        )
+        .unwrap();
+
+        let vaa = token_register_chain(&envelope, parse_chain("ethereum").unwrap(), ethereum);
+        assert_eq!(vaa.emitter_address, GOVERNANCE_ADDRESS);
+        match Payload::classify(&vaa) {
+            Payload::TokenRegisterChain(header, action) => {
+                assert_eq!(header.chains, Chain::All);
+                assert_eq!(action.emitter, Chain::Ethereum);
+                assert_eq!(action.endpoint_address, ethereum);
+            }
+            payload => panic!("unexpected payload {:?}", payload),
+        }
+
+        let vaa = guardian_set_upgrade(
+            &envelope,
+            1,
+            vec![parse_guardian("0xbeFA429d57cD18b7F8A4d91A2da9AB4AF05d0FBe").unwrap()],
+        );
+        assert!(matches!(
+            Payload::classify(&vaa),
+            Payload::GuardianSetChange(..)
+        ));
+
+        // Chains by ID, and raw addresses for contracts without a native format.
+        assert_eq!(parse_chain("3").unwrap(), Chain::Terra);
+        assert_eq!(parse_chain("13").unwrap(), Chain::Alephium);
+        assert!(parse_chain("moon").is_err());
+        assert_eq!(
+            parse_address(&Chain::Terra, &format!("0x{}", "01".repeat(32))).unwrap(),
+            [1; 32]
+        );
+        assert!(parse_address(&Chain::Ethereum, "0x1234").is_err());
+    }
+}
@@ -0,0 +1,760 @@
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+//! `wormhole`, the operator CLI for Wormhole on Solana: decoding and signing VAAs, building
+//! governance VAAs, deriving program addresses and submitting VAAs.
+
+use std::{
+    fmt::Display,
+    process::exit,
+    str::FromStr,
+};
+
+use clap::{
+    crate_description,
+    crate_name,
+    crate_version,
+    App,
+    AppSettings,
+    Arg,
+    ArgMatches,
+    SubCommand,
+};
+use solana_clap_utils::{
+    input_parsers::{
+        keypair_of,
+        pubkey_of,
+    },
+    input_validators::{
+        is_keypair,
+        is_pubkey,
+        is_url,
+    },
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::read_keypair_file,
+};
+use wormhole_core::{
+    Chain,
+    VAA,
+};
+use wormhole_sdk::{
+    AddressBook,
+    Contract,
+    Network,
+};
+
+use governance::{
+    parse_address,
+    parse_amount,
+    parse_chain,
+    parse_guardian,
+    Envelope,
+};
+
+mod governance;
+mod pda;
+mod submit;
+mod vaa;
+
+type Error = Box<dyn std::error::Error>;
+
+/// The Solana programs commands operate on, from the address book of the selected network unless
+/// overridden on the command line.
+struct Programs {
+    bridge: Pubkey,
+    token_bridge: Pubkey,
+    nft_bridge: Pubkey,
+}
+
+impl Programs {
+    fn from_matches(matches: &ArgMatches) -> Result<Self, Error> {
+        let network = matches.value_of("network").unwrap();
+        let network =
+            Network::from_name(network).ok_or_else(|| format!("Unknown network {}", network))?;
+        let book = match matches.value_of("addresses") {
+            Some(path) => AddressBook::load(network, path)?,
+            None => AddressBook::new(network),
+        };
+
+        let program = |arg: &str, contract: Contract| -> Result<Pubkey, Error> {
+            if let Some(program) = pubkey_of(matches, arg) {
+                return Ok(program);
+            }
+            let address = book.address(&Chain::Solana, contract).ok_or_else(|| {
+                format!(
+                    "No {} address for {}, pass --{}",
+                    contract.name(),
+                    network.name(),
+                    arg.replace('_', "-")
+                )
+            })?;
+            Ok(Pubkey::from_str(address)?)
+        };
+
+        Ok(Programs {
+            bridge: program("bridge", Contract::Core)?,
+            token_bridge: program("token_bridge", Contract::TokenBridge)?,
+            nft_bridge: program("nft_bridge", Contract::NftBridge)?,
+        })
+    }
+}
+
+fn vaa_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("vaa")
+        .value_name("VAA")
+        .takes_value(true)
+        .index(1)
+        .required(true)
+        .help("The VAA as hex or base64, or - to read it from standard input")
+}
+
+fn positional<'a, 'b>(name: &'a str, index: u64, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .value_name(name)
+        .takes_value(true)
+        .index(index)
+        .required(true)
+        .help(help)
+}
+
+/// The envelope options every governance action takes.
+fn governance_command<'a, 'b>(name: &'a str, about: &'a str) -> App<'a, 'b> {
+    SubCommand::with_name(name)
+        .about(about)
+        .arg(
+            Arg::with_name("guardian_set_index")
+                .long("guardian-set-index")
+                .value_name("INDEX")
+                .validator(is_u32)
+                .takes_value(true)
+                .help("Guardian set that will sign the VAA [default: 0]"),
+        )
+        .arg(
+            Arg::with_name("sequence")
+                .long("sequence")
+                .value_name("SEQUENCE")
+                .validator(is_u64)
+                .takes_value(true)
+                .help("Sequence of the VAA [default: random]"),
+        )
+        .arg(
+            Arg::with_name("nonce")
+                .long("nonce")
+                .value_name("NONCE")
+                .validator(is_u32)
+                .takes_value(true)
+                .help("Nonce of the VAA [default: 0]"),
+        )
+        .arg(
+            Arg::with_name("timestamp")
+                .long("timestamp")
+                .value_name("TIMESTAMP")
+                .validator(is_u32)
+                .takes_value(true)
+                .help("Timestamp of the VAA [default: now]"),
+        )
+        .arg(
+            Arg::with_name("emitter_chain")
+                .long("emitter-chain")
+                .value_name("CHAIN")
+                .takes_value(true)
+                .help("Chain of the governance emitter [default: solana]"),
+        )
+        .arg(
+            Arg::with_name("emitter_address")
+                .long("emitter-address")
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .help("Address of the governance emitter, 32 bytes of hex [default: 0x..04]"),
+        )
+}
+
+fn register_chain_command<'a, 'b>() -> App<'a, 'b> {
+    governance_command(
+        "register-chain",
+        "Register the bridge contract of another chain",
+    )
+    .arg(positional("CHAIN", 1, "Chain to register"))
+    .arg(positional(
+        "ADDRESS",
+        2,
+        "Address of the bridge contract on that chain",
+    ))
+}
+
+fn upgrade_contract_command<'a, 'b>() -> App<'a, 'b> {
+    governance_command("upgrade-contract", "Upgrade the contract on one chain")
+        .arg(positional("CHAIN", 1, "Chain of the contract to upgrade"))
+        .arg(positional(
+            "ADDRESS",
+            2,
+            "Address of the new implementation, or code ID on Terra",
+        ))
+}
+
+fn envelope(matches: &ArgMatches) -> Result<Envelope, Error> {
+    let mut envelope = Envelope::default();
+    if let Some(index) = matches.value_of("guardian_set_index") {
+        envelope.guardian_set_index = index.parse()?;
+    }
+    if let Some(sequence) = matches.value_of("sequence") {
+        envelope.sequence = sequence.parse()?;
+    }
+    if let Some(nonce) = matches.value_of("nonce") {
+        envelope.nonce = nonce.parse()?;
+    }
+    if let Some(timestamp) = matches.value_of("timestamp") {
+        envelope.timestamp = timestamp.parse()?;
+    }
+    if let Some(chain) = matches.value_of("emitter_chain") {
+        envelope.emitter_chain = parse_chain(chain)?;
+    }
+    if let Some(address) = matches.value_of("emitter_address") {
+        envelope.emitter_address = parse_address(&envelope.emitter_chain, address)?;
+    }
+    Ok(envelope)
+}
+
+fn chain_and_address(matches: &ArgMatches) -> Result<(Chain, [u8; 32]), Error> {
+    let chain = parse_chain(matches.value_of("CHAIN").unwrap())?;
+    let address = parse_address(&chain, matches.value_of("ADDRESS").unwrap())?;
+    Ok((chain, address))
+}
+
+fn command_governance(matches: &ArgMatches) -> Result<VAA, Error> {
+    let (module, matches) = matches.subcommand();
+    let matches = matches.unwrap();
+    let (action, matches) = matches.subcommand();
+    let matches = matches.unwrap();
+    let envelope = envelope(matches)?;
+
+    Ok(match (module, action) {
+        ("core", "upgrade-contract") => {
+            let (chain, address) = chain_and_address(matches)?;
+            governance::core_upgrade_contract(&envelope, chain, address)
+        }
+        ("core", "guardian-set-upgrade") => {
+            let index = matches.value_of("INDEX").unwrap().parse()?;
+            let guardians = matches
+                .values_of("GUARDIAN")
+                .unwrap()
+                .map(parse_guardian)
+                .collect::<Result<_, _>>()?;
+            governance::guardian_set_upgrade(&envelope, index, guardians)
+        }
+        ("core", "set-fee") => {
+            let chain = parse_chain(matches.value_of("CHAIN").unwrap())?;
+            let fee = parse_amount(matches.value_of("FEE").unwrap())?;
+            governance::set_message_fee(&envelope, chain, fee)
+        }
+        ("core", "transfer-fees") => {
+            let chain = parse_chain(matches.value_of("CHAIN").unwrap())?;
+            let amount = parse_amount(matches.value_of("AMOUNT").unwrap())?;
+            let recipient = parse_address(&chain, matches.value_of("RECIPIENT").unwrap())?;
+            governance::transfer_fees(&envelope, chain, amount, recipient)
+        }
+        ("token-bridge", "register-chain") => {
+            let (chain, address) = chain_and_address(matches)?;
+            governance::token_register_chain(&envelope, chain, address)
+        }
+        ("token-bridge", "upgrade-contract") => {
+            let (chain, address) = chain_and_address(matches)?;
+            governance::token_upgrade_contract(&envelope, chain, address)
+        }
+        ("nft-bridge", "register-chain") => {
+            let (chain, address) = chain_and_address(matches)?;
+            governance::nft_register_chain(&envelope, chain, address)
+        }
+        ("nft-bridge", "upgrade-contract") => {
+            let (chain, address) = chain_and_address(matches)?;
+            governance::nft_upgrade_contract(&envelope, chain, address)
+        }
+        _ => unreachable!(),
+    })
+}
+
+fn pubkey_arg(matches: &ArgMatches, name: &str) -> Result<Pubkey, Error> {
+    let value = matches
+        .value_of(name)
+        .ok_or_else(|| format!("{} is required", name))?;
+    Ok(Pubkey::from_str(value)?)
+}
+
+fn u16_arg(matches: &ArgMatches, name: &str) -> Result<u16, Error> {
+    let value = matches
+        .value_of(name)
+        .ok_or_else(|| format!("{} is required", name))?;
+    Ok(u16::from(parse_chain(value)?))
+}
+
+fn address_arg(matches: &ArgMatches, chain: &str, name: &str) -> Result<[u8; 32], Error> {
+    let chain = parse_chain(matches.value_of(chain).unwrap())?;
+    let value = matches
+        .value_of(name)
+        .ok_or_else(|| format!("{} is required", name))?;
+    parse_address(&chain, value)
+}
+
+fn command_pda(programs: &Programs, matches: &ArgMatches) -> Result<Pubkey, Error> {
+    let (program, matches) = matches.subcommand();
+    let matches = matches.unwrap();
+    let kind = matches.value_of("KIND").unwrap();
+    let id = match program {
+        "bridge" => programs.bridge,
+        "token-bridge" => programs.token_bridge,
+        "nft-bridge" => programs.nft_bridge,
+        _ => unreachable!(),
+    };
+
+    Ok(match (program, kind) {
+        (_, "upgrade-authority") => pda::upgrade_authority(&id),
+        (_, "claim") => pda::claim(
+            &id,
+            &vaa::parse_vaa(matches.value_of("vaa").unwrap_or("-"))?,
+        ),
+        (_, "spl-metadata") => pda::spl_metadata(&pubkey_arg(matches, "mint")?),
+
+        ("bridge", "config") => pda::core::config(&id),
+        ("bridge", "fee-collector") => pda::core::fee_collector(&id),
+        ("bridge", "guardian-set") => {
+            let index = matches.value_of("index").unwrap_or("0").parse()?;
+            pda::core::guardian_set(&id, index)
+        }
+        ("bridge", "sequence") => pda::core::sequence(&id, &pubkey_arg(matches, "emitter")?),
+        ("bridge", "posted-vaa") => pda::core::posted_vaa(
+            &id,
+            &vaa::parse_vaa(matches.value_of("vaa").unwrap_or("-"))?,
+        ),
+
+        ("token-bridge", "config") => pda::token_bridge::config(&id),
+        ("token-bridge", "emitter") => pda::token_bridge::emitter(&id),
+        ("token-bridge", "custody") => {
+            pda::token_bridge::custody(&id, &pubkey_arg(matches, "mint")?)
+        }
+        ("token-bridge", "custody-signer") => pda::token_bridge::custody_signer(&id),
+        ("token-bridge", "authority-signer") => pda::token_bridge::authority_signer(&id),
+        ("token-bridge", "mint-signer") => pda::token_bridge::mint_signer(&id),
+        ("token-bridge", "wrapped-mint") => pda::token_bridge::wrapped_mint(
+            &id,
+            u16_arg(matches, "chain")?,
+            address_arg(matches, "chain", "address")?,
+        ),
+        ("token-bridge", "wrapped-meta") => {
+            pda::token_bridge::wrapped_meta(&id, &pubkey_arg(matches, "mint")?)
+        }
+        ("token-bridge", "endpoint") => pda::token_bridge::endpoint(
+            &id,
+            u16_arg(matches, "chain")?,
+            address_arg(matches, "chain", "address")?,
+        ),
+
+        ("nft-bridge", "config") => pda::nft_bridge::config(&id),
+        ("nft-bridge", "emitter") => pda::nft_bridge::emitter(&id),
+        ("nft-bridge", "custody") => pda::nft_bridge::custody(&id, &pubkey_arg(matches, "mint")?),
+        ("nft-bridge", "custody-signer") => pda::nft_bridge::custody_signer(&id),
+        ("nft-bridge", "authority-signer") => pda::nft_bridge::authority_signer(&id),
+        ("nft-bridge", "mint-signer") => pda::nft_bridge::mint_signer(&id),
+        ("nft-bridge", "wrapped-mint") => pda::nft_bridge::wrapped_mint(
+            &id,
+            u16_arg(matches, "chain")?,
+            address_arg(matches, "chain", "address")?,
+            parse_amount(matches.value_of("token_id").unwrap_or("0"))?,
+        ),
+        ("nft-bridge", "wrapped-meta") => {
+            pda::nft_bridge::wrapped_meta(&id, &pubkey_arg(matches, "mint")?)
+        }
+        ("nft-bridge", "endpoint") => pda::nft_bridge::endpoint(
+            &id,
+            u16_arg(matches, "chain")?,
+            address_arg(matches, "chain", "address")?,
+        ),
+
+        (program, kind) => return Err(format!("{} has no {} account", program, kind).into()),
+    })
+}
+
+/// A `pda` subcommand for one program, with the arguments the accounts of that program derive
+/// from as options.
+fn pda_command<'a, 'b>(name: &'a str, about: &'a str, kinds: &'a [&'a str]) -> App<'a, 'b> {
+    let option = |name: &'a str, value_name: &'a str, help: &'a str| {
+        Arg::with_name(name)
+            .long(name)
+            .value_name(value_name)
+            .takes_value(true)
+            .help(help)
+    };
+
+    SubCommand::with_name(name)
+        .about(about)
+        .arg(
+            Arg::with_name("KIND")
+                .takes_value(true)
+                .index(1)
+                .required(true)
+                .possible_values(kinds)
+                .help("Account to derive"),
+        )
+        .arg(option("vaa", "VAA", "VAA of a posted-vaa or claim account"))
+        .arg(option("index", "INDEX", "Index of a guardian set").validator(is_u32))
+        .arg(option("emitter", "PUBKEY", "Emitter of a sequence").validator(is_pubkey))
+        .arg(option("mint", "PUBKEY", "Mint of a custody or metadata account").validator(is_pubkey))
+        .arg(option(
+            "chain",
+            "CHAIN",
+            "Chain of a wrapped token or endpoint",
+        ))
+        .arg(option(
+            "address",
+            "ADDRESS",
+            "Address of a wrapped token or endpoint on its chain",
+        ))
+        .arg(option("token_id", "TOKEN_ID", "Token ID of a wrapped NFT"))
+}
+
+fn main() {
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .global(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(
+            Arg::with_name("payer")
+                .long("payer")
+                .value_name("KEYPAIR")
+                .validator(is_keypair)
+                .takes_value(true)
+                .global(true)
+                .help("Account paying for transactions. Defaults to the client keypair."),
+        )
+        .arg(
+            Arg::with_name("network")
+                .long("network")
+                .value_name("NETWORK")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["mainnet", "testnet", "devnet"])
+                .default_value("devnet")
+                .help("Network whose program addresses to use"),
+        )
+        .arg(
+            Arg::with_name("addresses")
+                .long("addresses")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Address book overriding the built in program addresses"),
+        )
+        .args(&["bridge", "token_bridge", "nft_bridge"].map(|program| {
+            Arg::with_name(program)
+                .long(match program {
+                    "bridge" => "bridge",
+                    "token_bridge" => "token-bridge",
+                    _ => "nft-bridge",
+                })
+                .value_name("PUBKEY")
+                .validator(is_pubkey)
+                .takes_value(true)
+                .global(true)
+                .help("Program address, overriding the address book")
+        }))
+        .subcommand(
+            SubCommand::with_name("vaa")
+                .about("Decode and sign VAAs")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("decode")
+                        .about("Print a VAA and its payload as JSON")
+                        .arg(vaa_arg()),
+                )
+                .subcommand(
+                    SubCommand::with_name("sign")
+                        .about("Replace the signatures of a VAA, printing it as hex")
+                        .arg(vaa_arg())
+                        .arg(
+                            Arg::with_name("key")
+                                .long("key")
+                                .value_name("HEX")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help(
+                                    "Guardian key, once per guardian in guardian set order. \
+                                     Defaults to the devnet guardian.",
+                                ),
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("governance")
+                .about("Build unsigned governance VAAs, printed as hex")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("core")
+                        .about("Core bridge governance")
+                        .setting(AppSettings::SubcommandRequiredElseHelp)
+                        .subcommand(upgrade_contract_command())
+                        .subcommand(
+                            governance_command(
+                                "guardian-set-upgrade",
+                                "Replace the guardian set on every chain",
+                            )
+                            .arg(
+                                positional("INDEX", 1, "Index of the new guardian set")
+                                    .validator(is_u32),
+                            )
+                            .arg(
+                                positional("GUARDIAN", 2, "Addresses of the new guardians")
+                                    .multiple(true),
+                            ),
+                        )
+                        .subcommand(
+                            governance_command("set-fee", "Set the message fee on one chain")
+                                .arg(positional("CHAIN", 1, "Chain to set the fee on"))
+                                .arg(positional(
+                                    "FEE",
+                                    2,
+                                    "Fee in the smallest unit of the chain",
+                                )),
+                        )
+                        .subcommand(
+                            governance_command(
+                                "transfer-fees",
+                                "Transfer collected message fees on one chain",
+                            )
+                            .arg(positional("CHAIN", 1, "Chain to transfer fees on"))
+                            .arg(positional(
+                                "AMOUNT",
+                                2,
+                                "Amount in the smallest unit of the chain",
+                            ))
+                            .arg(positional(
+                                "RECIPIENT",
+                                3,
+                                "Recipient of the fees",
+                            )),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("token-bridge")
+                        .about("Token bridge governance")
+                        .setting(AppSettings::SubcommandRequiredElseHelp)
+                        .subcommand(register_chain_command())
+                        .subcommand(upgrade_contract_command()),
+                )
+                .subcommand(
+                    SubCommand::with_name("nft-bridge")
+                        .about("NFT bridge governance")
+                        .setting(AppSettings::SubcommandRequiredElseHelp)
+                        .subcommand(register_chain_command())
+                        .subcommand(upgrade_contract_command()),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("pda")
+                .about("Derive the address of a program account")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(pda_command(
+                    "bridge",
+                    "Core bridge accounts",
+                    &[
+                        "config",
+                        "fee-collector",
+                        "guardian-set",
+                        "sequence",
+                        "posted-vaa",
+                        "claim",
+                        "upgrade-authority",
+                    ],
+                ))
+                .subcommand(pda_command(
+                    "token-bridge",
+                    "Token bridge accounts",
+                    &[
+                        "config",
+                        "emitter",
+                        "custody",
+                        "custody-signer",
+                        "authority-signer",
+                        "mint-signer",
+                        "wrapped-mint",
+                        "wrapped-meta",
+                        "spl-metadata",
+                        "endpoint",
+                        "claim",
+                        "upgrade-authority",
+                    ],
+                ))
+                .subcommand(pda_command(
+                    "nft-bridge",
+                    "NFT bridge accounts",
+                    &[
+                        "config",
+                        "emitter",
+                        "custody",
+                        "custody-signer",
+                        "authority-signer",
+                        "mint-signer",
+                        "wrapped-mint",
+                        "wrapped-meta",
+                        "spl-metadata",
+                        "endpoint",
+                        "claim",
+                        "upgrade-authority",
+                    ],
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("solana")
+                .about("Submit VAAs to Solana")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("post-vaa")
+                        .about("Verify the signatures of a VAA and post it to the core bridge")
+                        .arg(vaa_arg()),
+                )
+                .subcommand(
+                    SubCommand::with_name("submit")
+                        .about("Post a governance VAA and apply it")
+                        .arg(vaa_arg()),
+                )
+                .subcommand(
+                    SubCommand::with_name("redeem")
+                        .about("Post a token or NFT bridge VAA and redeem it")
+                        .arg(vaa_arg())
+                        .arg(
+                            Arg::with_name("fee_recipient")
+                                .long("fee-recipient")
+                                .value_name("PUBKEY")
+                                .validator(is_pubkey)
+                                .takes_value(true)
+                                .help("Token account receiving the relayer fee of a transfer"),
+                        ),
+                ),
+        )
+        .get_matches();
+
+    let result = match matches.subcommand() {
+        ("vaa", Some(matches)) => match matches.subcommand() {
+            ("decode", Some(matches)) => vaa::parse_vaa(matches.value_of("vaa").unwrap())
+                .and_then(|vaa| vaa::decode(&vaa))
+                .map(|json| println!("{}", json)),
+            ("sign", Some(matches)) => (|| {
+                let mut vaa = vaa::parse_vaa(matches.value_of("vaa").unwrap())?;
+                let keys = matches
+                    .values_of("key")
+                    .map(|keys| keys.collect())
+                    .unwrap_or_else(|| vec![vaa::DEVNET_GUARDIAN_KEY]);
+                let keys = keys
+                    .into_iter()
+                    .map(vaa::parse_key)
+                    .collect::<Result<Vec<_>, _>>()?;
+                vaa::sign(&mut vaa, &keys);
+                println!("{}", hex::encode(vaa.to_bytes()));
+                Ok(())
+            })(),
+            _ => unreachable!(),
+        },
+        ("governance", Some(matches)) => {
+            command_governance(matches).map(|vaa| println!("{}", hex::encode(vaa.to_bytes())))
+        }
+        ("pda", Some(matches)) => Programs::from_matches(&matches)
+            .and_then(|programs| command_pda(&programs, matches))
+            .map(|address| println!("{}", address)),
+        ("solana", Some(matches)) => (|| {
+            let (command, matches) = matches.subcommand();
+            let matches = matches.unwrap();
+            let client = client(matches)?;
+            let vaa = vaa::parse_vaa(matches.value_of("vaa").unwrap())?;
+            match command {
+                "post-vaa" => client.post_vaa(&vaa).map(|_| ()),
+                "submit" => client.submit_governance(&vaa),
+                "redeem" => client.redeem(&vaa, pubkey_of(matches, "fee_recipient")),
+                _ => unreachable!(),
+            }
+        })(),
+        _ => unreachable!(),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        exit(1);
+    }
+}
+
+fn client(matches: &ArgMatches) -> Result<submit::Client, Error> {
+    let cli_config = match matches.value_of("config_file") {
+        Some(config_file) => solana_cli_config::Config::load(config_file).unwrap_or_default(),
+        None => solana_cli_config::Config::default(),
+    };
+    let json_rpc_url = matches
+        .value_of("json_rpc_url")
+        .map(str::to_string)
+        .unwrap_or_else(|| cli_config.json_rpc_url.clone());
+    let payer = match keypair_of(matches, "payer") {
+        Some(payer) => payer,
+        None => read_keypair_file(&cli_config.keypair_path)
+            .map_err(|err| format!("Unable to read {}: {}", cli_config.keypair_path, err))?,
+    };
+    let programs = Programs::from_matches(matches)?;
+
+    Ok(submit::Client {
+        rpc_client: RpcClient::new(json_rpc_url),
+        payer,
+        bridge: programs.bridge,
+        token_bridge: programs.token_bridge,
+        nft_bridge: programs.nft_bridge,
+    })
+}
+
+pub fn is_u32<T>(amount: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    if amount.as_ref().parse::<u32>().is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unable to parse input amount as integer, provided: {}",
+            amount
+        ))
+    }
+}
+
+pub fn is_u64<T>(amount: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    if amount.as_ref().parse::<u64>().is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unable to parse input amount as integer, provided: {}",
+            amount
+        ))
+    }
+}
@@ -0,0 +1,248 @@
+//! Program derived addresses of the core bridge, token bridge and NFT bridge, derived with the
+//! account types of the programs themselves so they cannot drift from what the programs check.
+
+use primitive_types::U256;
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+    Derive,
+    Info,
+};
+use wormhole_core::VAA;
+
+use bridge::accounts::{
+    Bridge,
+    Claim,
+    ClaimDerivationData,
+    FeeCollector,
+    GuardianSet,
+    GuardianSetDerivationData,
+    PostedVAA,
+    PostedVAADerivationData,
+    Sequence,
+    SequenceDerivationData,
+};
+
+use crate::vaa::hash;
+
+/// The signer every program upgrades itself with, the upgrade authority of its program account.
+pub fn upgrade_authority(program: &Pubkey) -> Pubkey {
+    <Derive<Info<'_>, "upgrade">>::key(None, program)
+}
+
+/// The account that marks `vaa` as processed by `program`, any of the three programs.
+pub fn claim(program: &Pubkey, vaa: &VAA) -> Pubkey {
+    Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain.clone().into(),
+            sequence: vaa.sequence,
+        },
+        program,
+    )
+}
+
+/// The SPL token metadata of `mint`, which is owned by the metadata program.
+pub fn spl_metadata(mint: &Pubkey) -> Pubkey {
+    ::token_bridge::accounts::SplTokenMeta::key(
+        &::token_bridge::accounts::SplTokenMetaDerivationData { mint: *mint },
+        &spl_token_metadata::id(),
+    )
+}
+
+pub mod core {
+    use super::*;
+
+    pub fn config(bridge: &Pubkey) -> Pubkey {
+        Bridge::<'_, { AccountState::Initialized }>::key(None, bridge)
+    }
+
+    pub fn fee_collector(bridge: &Pubkey) -> Pubkey {
+        FeeCollector::key(None, bridge)
+    }
+
+    pub fn guardian_set(bridge: &Pubkey, index: u32) -> Pubkey {
+        GuardianSet::<'_, { AccountState::Initialized }>::key(
+            &GuardianSetDerivationData { index },
+            bridge,
+        )
+    }
+
+    /// The sequence tracker of an emitter posting messages to the bridge.
+    pub fn sequence(bridge: &Pubkey, emitter: &Pubkey) -> Pubkey {
+        Sequence::key(
+            &SequenceDerivationData {
+                emitter_key: *emitter,
+            },
+            bridge,
+        )
+    }
+
+    /// The account `post_vaa` stores `vaa` in.
+    pub fn posted_vaa(bridge: &Pubkey, vaa: &VAA) -> Pubkey {
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: hash(vaa).to_vec(),
+            },
+            bridge,
+        )
+    }
+}
+
+pub mod token_bridge {
+    use super::*;
+    use ::token_bridge::accounts::{
+        AuthoritySigner,
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    };
+
+    pub fn config(program: &Pubkey) -> Pubkey {
+        ConfigAccount::<'_, { AccountState::Initialized }>::key(None, program)
+    }
+
+    pub fn emitter(program: &Pubkey) -> Pubkey {
+        EmitterAccount::key(None, program)
+    }
+
+    /// The account native tokens of `mint` are locked in.
+    pub fn custody(program: &Pubkey, mint: &Pubkey) -> Pubkey {
+        CustodyAccount::<'_, { AccountState::Initialized }>::key(
+            &CustodyAccountDerivationData { mint: *mint },
+            program,
+        )
+    }
+
+    pub fn custody_signer(program: &Pubkey) -> Pubkey {
+        CustodySigner::key(None, program)
+    }
+
+    /// The delegate users approve to transfer tokens out of their accounts.
+    pub fn authority_signer(program: &Pubkey) -> Pubkey {
+        AuthoritySigner::key(None, program)
+    }
+
+    pub fn mint_signer(program: &Pubkey) -> Pubkey {
+        MintSigner::key(None, program)
+    }
+
+    /// The mint of the wrapped version of a token from another chain.
+    pub fn wrapped_mint(program: &Pubkey, token_chain: u16, token_address: [u8; 32]) -> Pubkey {
+        WrappedMint::<'_, { AccountState::Initialized }>::key(
+            &WrappedDerivationData {
+                token_chain,
+                token_address,
+            },
+            program,
+        )
+    }
+
+    pub fn wrapped_meta(program: &Pubkey, mint: &Pubkey) -> Pubkey {
+        WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+            &WrappedMetaDerivationData { mint_key: *mint },
+            program,
+        )
+    }
+
+    /// The registration of the token bridge on another chain.
+    pub fn endpoint(program: &Pubkey, chain: u16, address: [u8; 32]) -> Pubkey {
+        Endpoint::<'_, { AccountState::Initialized }>::key(
+            &EndpointDerivationData {
+                emitter_chain: chain,
+                emitter_address: address,
+            },
+            program,
+        )
+    }
+}
+
+pub mod nft_bridge {
+    use super::*;
+    use ::nft_bridge::accounts::{
+        AuthoritySigner,
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    };
+
+    pub fn config(program: &Pubkey) -> Pubkey {
+        ConfigAccount::<'_, { AccountState::Initialized }>::key(None, program)
+    }
+
+    pub fn emitter(program: &Pubkey) -> Pubkey {
+        EmitterAccount::key(None, program)
+    }
+
+    pub fn custody(program: &Pubkey, mint: &Pubkey) -> Pubkey {
+        CustodyAccount::<'_, { AccountState::Initialized }>::key(
+            &CustodyAccountDerivationData { mint: *mint },
+            program,
+        )
+    }
+
+    pub fn custody_signer(program: &Pubkey) -> Pubkey {
+        CustodySigner::key(None, program)
+    }
+
+    pub fn authority_signer(program: &Pubkey) -> Pubkey {
+        AuthoritySigner::key(None, program)
+    }
+
+    pub fn mint_signer(program: &Pubkey) -> Pubkey {
+        MintSigner::key(None, program)
+    }
+
+    /// Wrapped NFTs have a mint of their own for every token ID.
+    pub fn wrapped_mint(
+        program: &Pubkey,
+        token_chain: u16,
+        token_address: [u8; 32],
+        token_id: U256,
+    ) -> Pubkey {
+        WrappedMint::<'_, { AccountState::Initialized }>::key(
+            &WrappedDerivationData {
+                token_chain,
+                token_address,
+                token_id,
+            },
+            program,
+        )
+    }
+
+    pub fn wrapped_meta(program: &Pubkey, mint: &Pubkey) -> Pubkey {
+        WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+            &WrappedMetaDerivationData { mint_key: *mint },
+            program,
+        )
+    }
+
+    pub fn endpoint(program: &Pubkey, chain: u16, address: [u8; 32]) -> Pubkey {
+        Endpoint::<'_, { AccountState::Initialized }>::key(
+            &EndpointDerivationData {
+                emitter_chain: chain,
+                emitter_address: address,
+            },
+            program,
+        )
+    }
+}
@@ -0,0 +1,371 @@
+//! Submitting VAAs to Solana. Every VAA is first posted to the core bridge, which verifies its
+//! signatures in batches ahead of `post_vaa`, and then redeemed with the program it is addressed
+//! to.
+
+use bridge::{
+    accounts::{
+        BridgeData,
+        GuardianSetData,
+    },
+    vaa::{
+        DeserializePayload,
+        SignatureItem,
+    },
+    PostVAAData,
+};
+use primitive_types::U256;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::RpcSendTransactionConfig,
+};
+use solana_program::instruction::Instruction;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    transaction::Transaction,
+};
+use solitaire::{
+    processors::discriminator::deserialize_account,
+    SolitaireError,
+};
+use wormhole_core::{
+    Chain,
+    Payload,
+    VAA,
+};
+
+use crate::{
+    pda,
+    vaa::hash,
+    Error,
+};
+
+/// The programs VAAs are submitted to, and who pays for it.
+pub struct Client {
+    pub rpc_client: RpcClient,
+    pub payer: Keypair,
+    pub bridge: Pubkey,
+    pub token_bridge: Pubkey,
+    pub nft_bridge: Pubkey,
+}
+
+impl Client {
+    fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), Error> {
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&self.payer.pubkey()));
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        let mut keypairs = vec![&self.payer];
+        keypairs.extend_from_slice(signers);
+        transaction.sign(&keypairs, recent_blockhash);
+
+        let signature = self
+            .rpc_client
+            .send_and_confirm_transaction_with_spinner_and_config(
+                &transaction,
+                CommitmentConfig::processed(),
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    preflight_commitment: None,
+                    encoding: None,
+                    max_retries: None,
+                },
+            )?;
+        println!("Signature: {}", signature);
+        Ok(())
+    }
+
+    fn exists(&self, account: &Pubkey) -> Result<bool, Error> {
+        Ok(self
+            .rpc_client
+            .get_account_with_commitment(account, CommitmentConfig::processed())?
+            .value
+            .is_some())
+    }
+
+    /// Verify the signatures of `vaa` and post it to the core bridge, unless it already is.
+    /// Returns the account the VAA is posted in.
+    pub fn post_vaa(&self, vaa: &VAA) -> Result<Pubkey, Error> {
+        let message = pda::core::posted_vaa(&self.bridge, vaa);
+        if self.exists(&message)? {
+            println!("VAA already posted: {}", message);
+            return Ok(message);
+        }
+
+        let guardian_set = self.rpc_client.get_account(&pda::core::guardian_set(
+            &self.bridge,
+            vaa.guardian_set_index,
+        ))?;
+        let guardian_set = deserialize_account::<GuardianSetData>(&guardian_set.data)?;
+
+        let signatures = vaa
+            .signatures
+            .iter()
+            .map(|signature| {
+                let index = signature[0];
+                let key = guardian_set
+                    .keys
+                    .get(index as usize)
+                    .ok_or_else(|| format!("No guardian {} in guardian set", index))?;
+                Ok(SignatureItem {
+                    signature: signature[1..].to_vec(),
+                    key: *key,
+                    index,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let signature_set = Keypair::new();
+        let batches = bridge::instructions::verify_signatures_batches(
+            self.bridge,
+            self.payer.pubkey(),
+            vaa.guardian_set_index,
+            signature_set.pubkey(),
+            &signatures,
+            hash(vaa),
+        )
+        .map_err(program_error)?;
+        for batch in batches {
+            self.send(&batch, &[&signature_set])?;
+        }
+
+        let ix = bridge::instructions::post_vaa(
+            self.bridge,
+            self.payer.pubkey(),
+            signature_set.pubkey(),
+            post_vaa_data(vaa),
+        );
+        self.send(&[ix], &[])?;
+        println!("VAA posted: {}", message);
+        Ok(message)
+    }
+
+    /// Post a governance VAA and apply it with the program it is addressed to.
+    pub fn submit_governance(&self, vaa: &VAA) -> Result<(), Error> {
+        let message = self.post_vaa(vaa)?;
+        let payer = self.payer.pubkey();
+        let emitter = Pubkey::new(&vaa.emitter_address);
+        let data = post_vaa_data(vaa);
+
+        let ix = match Payload::classify(vaa) {
+            Payload::CoreContractUpgrade(_, action) => bridge::instructions::upgrade_contract(
+                self.bridge,
+                payer,
+                message,
+                emitter,
+                Pubkey::new(&action.new_contract),
+                payer,
+                vaa.sequence,
+            ),
+            Payload::GuardianSetChange(_, action) => {
+                let config = self
+                    .rpc_client
+                    .get_account(&pda::core::config(&self.bridge))?;
+                let config = deserialize_account::<BridgeData>(&config.data)?;
+                bridge::instructions::upgrade_guardian_set(
+                    self.bridge,
+                    payer,
+                    message,
+                    emitter,
+                    config.guardian_set_index,
+                    action.new_guardian_set_index,
+                    vaa.sequence,
+                )
+            }
+            Payload::SetMessageFee(..) => {
+                bridge::instructions::set_fees(self.bridge, payer, message, emitter, vaa.sequence)
+            }
+            Payload::TransferFees(_, action) => bridge::instructions::transfer_fees(
+                self.bridge,
+                payer,
+                message,
+                emitter,
+                vaa.sequence,
+                Pubkey::new(&action.to),
+            ),
+            Payload::TokenRegisterChain(..) => token_bridge::instructions::register_chain(
+                self.token_bridge,
+                self.bridge,
+                payer,
+                message,
+                data,
+                DeserializePayload::deserialize(&mut &vaa.payload[..]).map_err(program_error)?,
+                token_bridge::RegisterChainData {},
+            )
+            .map_err(program_error)?,
+            Payload::TokenContractUpgrade(_, action) => {
+                token_bridge::instructions::upgrade_contract(
+                    self.token_bridge,
+                    payer,
+                    message,
+                    emitter,
+                    Pubkey::new(&action.new_contract),
+                    payer,
+                    vaa.sequence,
+                )
+            }
+            Payload::NftRegisterChain(..) => nft_bridge::instructions::register_chain(
+                self.nft_bridge,
+                self.bridge,
+                payer,
+                message,
+                data,
+                DeserializePayload::deserialize(&mut &vaa.payload[..]).map_err(program_error)?,
+                nft_bridge::RegisterChainData {},
+            )
+            .map_err(program_error)?,
+            Payload::NftContractUpgrade(_, action) => nft_bridge::instructions::upgrade_contract(
+                self.nft_bridge,
+                payer,
+                message,
+                emitter,
+                Pubkey::new(&action.new_contract),
+                payer,
+                vaa.sequence,
+            ),
+            _ => return Err("VAA is not a governance action".into()),
+        };
+        self.send(&[ix], &[])
+    }
+
+    /// Post a token or NFT bridge VAA and redeem it. Token transfers pay the relayer fee to
+    /// `fee_recipient`, or to the recipient if there is none.
+    pub fn redeem(&self, vaa: &VAA, fee_recipient: Option<Pubkey>) -> Result<(), Error> {
+        let payload = Payload::classify(vaa);
+        match &payload {
+            Payload::TokenTransfer(transfer) if transfer.to_chain != Chain::Solana => {
+                return Err("Token transfer is not addressed to Solana".into());
+            }
+            Payload::NftTransfer(transfer) if transfer.to_chain != Chain::Solana => {
+                return Err("NFT transfer is not addressed to Solana".into());
+            }
+            Payload::TokenTransfer(_) | Payload::AssetMeta(_) | Payload::NftTransfer(_) => {}
+            _ => return Err("VAA is not a token or NFT bridge message".into()),
+        }
+
+        let message = self.post_vaa(vaa)?;
+        let payer = self.payer.pubkey();
+        let data = post_vaa_data(vaa);
+
+        match payload {
+            Payload::TokenTransfer(transfer) if transfer.token_chain == Chain::Solana => {
+                let ix = token_bridge::instructions::complete_native(
+                    self.token_bridge,
+                    self.bridge,
+                    payer,
+                    message,
+                    data,
+                    Pubkey::new(&transfer.to),
+                    fee_recipient,
+                    Pubkey::new(&transfer.token_address),
+                    token_bridge::CompleteNativeData {},
+                )
+                .map_err(program_error)?;
+                self.send(&[ix], &[])
+            }
+            Payload::TokenTransfer(transfer) => {
+                let ix = token_bridge::instructions::complete_wrapped(
+                    self.token_bridge,
+                    self.bridge,
+                    payer,
+                    message,
+                    data,
+                    DeserializePayload::deserialize(&mut &vaa.payload[..])
+                        .map_err(program_error)?,
+                    Pubkey::new(&transfer.to),
+                    fee_recipient,
+                    token_bridge::CompleteWrappedData {},
+                )
+                .map_err(program_error)?;
+                self.send(&[ix], &[])
+            }
+            Payload::AssetMeta(_) => {
+                let ix = token_bridge::instructions::create_wrapped(
+                    self.token_bridge,
+                    self.bridge,
+                    payer,
+                    message,
+                    data,
+                    DeserializePayload::deserialize(&mut &vaa.payload[..])
+                        .map_err(program_error)?,
+                    token_bridge::CreateWrappedData {},
+                )
+                .map_err(program_error)?;
+                self.send(&[ix], &[])
+            }
+            // Native NFTs travel with their mint as the token ID.
+            Payload::NftTransfer(transfer) if transfer.nft_chain == Chain::Solana => {
+                let ix = nft_bridge::instructions::complete_native(
+                    self.nft_bridge,
+                    self.bridge,
+                    payer,
+                    message,
+                    data,
+                    Pubkey::new(&transfer.to),
+                    Pubkey::new(&token_id_bytes(transfer.token_id)),
+                    nft_bridge::CompleteNativeData {},
+                )
+                .map_err(program_error)?;
+                self.send(&[ix], &[])
+            }
+            // Wrapped NFTs are minted first and receive their metadata in a second transaction,
+            // both do not fit in one.
+            Payload::NftTransfer(transfer) => {
+                let ix = nft_bridge::instructions::complete_wrapped(
+                    self.nft_bridge,
+                    self.bridge,
+                    payer,
+                    message,
+                    data.clone(),
+                    DeserializePayload::deserialize(&mut &vaa.payload[..])
+                        .map_err(program_error)?,
+                    Pubkey::new(&transfer.to),
+                    nft_bridge::CompleteWrappedData {},
+                )
+                .map_err(program_error)?;
+                self.send(&[ix], &[])?;
+
+                let ix = nft_bridge::instructions::complete_wrapped_meta(
+                    self.nft_bridge,
+                    self.bridge,
+                    payer,
+                    message,
+                    data,
+                    DeserializePayload::deserialize(&mut &vaa.payload[..])
+                        .map_err(program_error)?,
+                    nft_bridge::CompleteWrappedMetaData {},
+                )
+                .map_err(program_error)?;
+                self.send(&[ix], &[])
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+pub fn post_vaa_data(vaa: &VAA) -> PostVAAData {
+    PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain.clone().into(),
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload.clone(),
+    }
+}
+
+/// `SolitaireError` does not implement `std::error::Error`.
+fn program_error(e: SolitaireError) -> Error {
+    format!("{:?}", e).into()
+}
+
+fn token_id_bytes(token_id: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    token_id.to_big_endian(&mut bytes);
+    bytes
+}
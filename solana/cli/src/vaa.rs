@@ -0,0 +1,151 @@
+//! Reading, printing and signing VAAs. VAAs are passed around as hex on the command line, which is
+//! what every command prints, but base64 as found in guardian RPC responses is accepted as well.
+
+use std::io::Read;
+
+use libsecp256k1::{
+    Message,
+    SecretKey,
+};
+use sha3::Digest;
+use wormhole_core::VAA;
+
+use crate::Error;
+
+/// The key of the only guardian of the Tilt devnet.
+pub const DEVNET_GUARDIAN_KEY: &str =
+    "cfb12303a19cde580bb4dd771639b0d26bc68353645571a8cff516ab2ee113a0";
+
+/// Read a VAA given as hex, with or without `0x`, or base64. `-` reads it from standard input so
+/// commands can be piped into each other.
+pub fn read_vaa(input: &str) -> Result<Vec<u8>, Error> {
+    let input = if input == "-" {
+        let mut stdin = String::new();
+        std::io::stdin().read_to_string(&mut stdin)?;
+        stdin
+    } else {
+        input.to_string()
+    };
+
+    let input = input.trim();
+    let hex_input = input.strip_prefix("0x").unwrap_or(input);
+    if let Ok(bytes) = hex::decode(hex_input) {
+        return Ok(bytes);
+    }
+    base64::decode(input).map_err(|_| "VAA is neither hex nor base64".into())
+}
+
+pub fn parse_vaa(input: &str) -> Result<VAA, Error> {
+    let bytes = read_vaa(input)?;
+    VAA::from_bytes(&bytes).map_err(|e| format!("Invalid VAA: {:?}", e).into())
+}
+
+/// The Keccak hash of the VAA body, which identifies the VAA on every chain.
+pub fn hash(vaa: &VAA) -> [u8; 32] {
+    vaa.digest().unwrap().hash
+}
+
+/// The VAA as JSON, in the format of `wormhole_core::json` with its hash added.
+pub fn decode(vaa: &VAA) -> Result<String, Error> {
+    let mut json = serde_json::to_value(vaa)?;
+    json["hash"] = hex::encode(hash(vaa)).into();
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+pub fn parse_key(key: &str) -> Result<SecretKey, Error> {
+    let key = hex::decode(key.strip_prefix("0x").unwrap_or(key))?;
+    SecretKey::parse_slice(&key).map_err(|_| "Invalid guardian key".into())
+}
+
+/// Replace the signatures of `vaa` with ones made by `keys`, which are the guardian set in index
+/// order. Guardians sign the hash of the VAA hash.
+pub fn sign(vaa: &mut VAA, keys: &[SecretKey]) {
+    let digest = sha3::Keccak256::digest(&hash(vaa));
+    let message = Message::parse_slice(&digest).unwrap();
+
+    vaa.signatures = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| {
+            let (signature, recovery_id) = libsecp256k1::sign(&message, key);
+            let mut s = [0u8; 66];
+            s[0] = index as u8;
+            s[1..65].copy_from_slice(&signature.serialize());
+            s[65] = recovery_id.serialize();
+            s
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use libsecp256k1::{
+        recover,
+        PublicKey,
+        RecoveryId,
+        Signature,
+    };
+    use wormhole_core::Chain;
+
+    /// The Ethereum style address guardian sets list a guardian key by.
+    fn guardian_address(key: &PublicKey) -> [u8; 20] {
+        let hash = sha3::Keccak256::digest(&key.serialize()[1..]);
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..]);
+        address
+    }
+
+    #[test]
+    fn test_read_vaa() {
+        let vaa = VAA {
+            version: 1,
+            emitter_chain: Chain::Ethereum,
+            sequence: 7,
+            payload: b"hello".to_vec(),
+            ..VAA::default()
+        };
+        let bytes = vaa.to_bytes();
+
+        assert_eq!(read_vaa(&hex::encode(&bytes)).unwrap(), bytes);
+        assert_eq!(
+            read_vaa(&format!("0x{}\n", hex::encode(&bytes))).unwrap(),
+            bytes
+        );
+        assert_eq!(read_vaa(&base64::encode(&bytes)).unwrap(), bytes);
+        assert_eq!(parse_vaa(&hex::encode(&bytes)).unwrap(), vaa);
+        assert!(read_vaa("not a vaa").is_err());
+    }
+
+    #[test]
+    fn test_sign() {
+        let key = parse_key(DEVNET_GUARDIAN_KEY).unwrap();
+        assert_eq!(
+            hex::encode(guardian_address(&PublicKey::from_secret_key(&key))),
+            "befa429d57cd18b7f8a4d91a2da9ab4af05d0fbe"
+        );
+
+        let mut vaa = VAA {
+            version: 1,
+            payload: b"hello".to_vec(),
+            ..VAA::default()
+        };
+        sign(&mut vaa, &[key.clone(), key]);
+        assert_eq!(vaa.signatures.len(), 2);
+        assert_eq!(vaa.signatures[1][0], 1);
+
+        // The signatures recover to the guardian over the digest the contracts verify.
+        let digest = sha3::Keccak256::digest(&hash(&vaa));
+        let signature = Signature::parse_standard_slice(&vaa.signatures[0][1..65]).unwrap();
+        let recovery_id = RecoveryId::parse(vaa.signatures[0][65]).unwrap();
+        let signer = recover(
+            &Message::parse_slice(&digest).unwrap(),
+            &signature,
+            &recovery_id,
+        );
+        assert_eq!(
+            hex::encode(guardian_address(&signer.unwrap())),
+            "befa429d57cd18b7f8a4d91a2da9ab4af05d0fbe"
+        );
+    }
+}
@@ -4,6 +4,11 @@ use crate::types::{
     SplMint,
 };
 use solana_program::pubkey::Pubkey;
+#[cfg(feature = "idl")]
+use solitaire::idl::{
+    IdlSeed,
+    IdlSeeds,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountState,
@@ -27,6 +32,16 @@ impl<'b, const STATE: AccountState> Seeded<&ShareMintDerivationData> for ShareMi
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for ShareMintDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("share_mint"),
+            IdlSeed::field::<Pubkey>("pool"),
+        ]
+    }
+}
+
 pub type FromCustodyTokenAccount<'a, const STATE: AccountState> = Data<'a, SplAccount, { STATE }>;
 
 pub struct FromCustodyTokenAccountDerivationData {
@@ -44,6 +59,16 @@ impl<'b, const STATE: AccountState> Seeded<&FromCustodyTokenAccountDerivationDat
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for FromCustodyTokenAccountDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("from_custody"),
+            IdlSeed::field::<Pubkey>("pool"),
+        ]
+    }
+}
+
 pub type ToCustodyTokenAccount<'a, const STATE: AccountState> = Data<'a, SplAccount, { STATE }>;
 
 pub struct ToCustodyTokenAccountDerivationData {
@@ -61,6 +86,16 @@ impl<'b, const STATE: AccountState> Seeded<&ToCustodyTokenAccountDerivationData>
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for ToCustodyTokenAccountDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("to_custody"),
+            IdlSeed::field::<Pubkey>("pool"),
+        ]
+    }
+}
+
 pub type MigrationPool<'a, const STATE: AccountState> = Data<'a, PoolData, { STATE }>;
 
 pub struct MigrationPoolDerivationData {
@@ -80,5 +115,16 @@ impl<'b, const STATE: AccountState> Seeded<&MigrationPoolDerivationData>
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for MigrationPoolDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("pool"),
+            IdlSeed::field::<Pubkey>("from"),
+            IdlSeed::field::<Pubkey>("to"),
+        ]
+    }
+}
+
 pub type CustodySigner<'a> = Derive<Info<'a>, "custody_signer">;
 pub type AuthoritySigner<'a> = Derive<Info<'a>, "authority_signer">;
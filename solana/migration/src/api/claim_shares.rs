@@ -13,6 +13,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 
@@ -27,8 +28,11 @@ use solitaire::{
 
 #[derive(FromAccounts)]
 pub struct ClaimShares<'b> {
+    #[seeds(MigrationPoolDerivationData)]
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
+    #[seeds(FromCustodyTokenAccountDerivationData)]
     pub from_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Initialized }>>,
+    #[seeds(ShareMintDerivationData)]
     pub share_mint: Mut<ShareMint<'b, { AccountState::Initialized }>>,
 
     pub from_lp_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
@@ -37,7 +41,7 @@ pub struct ClaimShares<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct ClaimSharesData {
     pub amount: u64,
 }
@@ -14,6 +14,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use solana_program::program::invoke_signed;
@@ -26,17 +27,21 @@ use solitaire::{
 pub struct CreatePool<'b> {
     pub payer: Mut<Signer<Info<'b>>>,
 
+    #[seeds(MigrationPoolDerivationData)]
     pub pool: Mut<MigrationPool<'b, { AccountState::Uninitialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
     pub to_mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    #[seeds(FromCustodyTokenAccountDerivationData)]
     pub from_token_custody: Mut<FromCustodyTokenAccount<'b, { AccountState::Uninitialized }>>,
+    #[seeds(ToCustodyTokenAccountDerivationData)]
     pub to_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Uninitialized }>>,
+    #[seeds(ShareMintDerivationData)]
     pub pool_mint: Mut<ShareMint<'b, { AccountState::Uninitialized }>>,
 
     pub custody_signer: CustodySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CreatePoolData {}
 
 pub fn create_pool(
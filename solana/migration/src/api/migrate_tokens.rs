@@ -16,6 +16,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 
@@ -30,10 +31,13 @@ use solitaire::{
 
 #[derive(FromAccounts)]
 pub struct MigrateTokens<'b> {
+    #[seeds(MigrationPoolDerivationData)]
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
     pub to_mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    #[seeds(ToCustodyTokenAccountDerivationData)]
     pub to_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Initialized }>>,
+    #[seeds(FromCustodyTokenAccountDerivationData)]
     pub from_token_custody: Mut<FromCustodyTokenAccount<'b, { AccountState::Initialized }>>,
 
     pub user_from_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
@@ -42,7 +46,7 @@ pub struct MigrateTokens<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct MigrateTokensData {
     pub amount: u64,
 }
@@ -17,6 +17,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use solitaire::{
@@ -29,10 +30,13 @@ use solitaire::{
 
 #[derive(FromAccounts)]
 pub struct RemoveLiquidity<'b> {
+    #[seeds(MigrationPoolDerivationData)]
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
     pub to_mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    #[seeds(ToCustodyTokenAccountDerivationData)]
     pub to_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Initialized }>>,
+    #[seeds(ShareMintDerivationData)]
     pub share_mint: Mut<ShareMint<'b, { AccountState::Initialized }>>,
 
     pub to_lp_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
@@ -41,7 +45,7 @@ pub struct RemoveLiquidity<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct RemoveLiquidityData {
     pub amount: u64,
 }
@@ -5,6 +5,11 @@ use bridge::{
 };
 use primitive_types::U256;
 use solana_program::pubkey::Pubkey;
+#[cfg(feature = "idl")]
+use solitaire::idl::{
+    IdlSeed,
+    IdlSeeds,
+};
 use solitaire::{
     processors::seeded::Seeded,
     *,
@@ -35,6 +40,13 @@ impl<'b, const STATE: AccountState> Seeded<&CustodyAccountDerivationData>
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for CustodyAccountDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::field::<Pubkey>("mint")]
+    }
+}
+
 pub type WrappedMint<'b, const STATE: AccountState> = Data<'b, SplMint, { STATE }>;
 
 pub struct WrappedDerivationData {
@@ -56,6 +68,53 @@ impl<'b, const STATE: AccountState> Seeded<&WrappedDerivationData> for WrappedMi
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for WrappedDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("wrapped"),
+            IdlSeed::field::<ChainID>("token_chain"),
+            IdlSeed::field::<ForeignAddress>("token_address"),
+            IdlSeed::field::<U256>("token_id"),
+        ]
+    }
+}
+
+/// Wrapped semi-fungible tokens get a mint of their own, apart from the NFTs of the same origin, so
+/// neither can be sent as the other.
+pub struct WrappedSemiFungibleDerivationData {
+    pub token_chain: ChainID,
+    pub token_address: ForeignAddress,
+    pub token_id: U256,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&WrappedSemiFungibleDerivationData>
+    for WrappedMint<'b, { STATE }>
+{
+    fn seeds(data: &WrappedSemiFungibleDerivationData) -> Vec<Vec<u8>> {
+        let mut token_id = vec![0u8; 32];
+        data.token_id.to_big_endian(&mut token_id);
+        vec![
+            String::from("wrapped_semi_fungible").as_bytes().to_vec(),
+            data.token_chain.to_be_bytes().to_vec(),
+            data.token_address.to_vec(),
+            token_id,
+        ]
+    }
+}
+
+#[cfg(feature = "idl")]
+impl IdlSeeds for WrappedSemiFungibleDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("wrapped_semi_fungible"),
+            IdlSeed::field::<ChainID>("token_chain"),
+            IdlSeed::field::<ForeignAddress>("token_address"),
+            IdlSeed::field::<U256>("token_id"),
+        ]
+    }
+}
+
 pub type WrappedTokenMeta<'b, const STATE: AccountState> = Data<'b, WrappedMeta, { STATE }>;
 
 pub struct WrappedMetaDerivationData {
@@ -73,6 +132,16 @@ impl<'b, const STATE: AccountState> Seeded<&WrappedMetaDerivationData>
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for WrappedMetaDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("meta"),
+            IdlSeed::field::<Pubkey>("mint_key"),
+        ]
+    }
+}
+
 /// Registered chain endpoint
 pub type Endpoint<'b, const STATE: AccountState> = Data<'b, EndpointRegistration, { STATE }>;
 
@@ -91,6 +160,54 @@ impl<'b, const STATE: AccountState> Seeded<&EndpointDerivationData> for Endpoint
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for EndpointDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::field::<u16>("emitter_chain"),
+            IdlSeed::field::<ForeignAddress>("emitter_address"),
+        ]
+    }
+}
+
+/// Claim of one token of a batch transfer. Batches are redeemed token by token, possibly over
+/// several transactions, so the VAA itself is never claimed as a whole.
+pub type BatchClaim<'b, const STATE: AccountState> = Data<'b, BatchClaimData, { STATE }>;
+
+pub struct BatchClaimDerivationData {
+    pub emitter_chain: u16,
+    pub emitter_address: ForeignAddress,
+    pub sequence: u64,
+    pub index: u8,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&BatchClaimDerivationData>
+    for BatchClaim<'b, { STATE }>
+{
+    fn seeds(data: &BatchClaimDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("batch_claim").as_bytes().to_vec(),
+            data.emitter_chain.to_be_bytes().to_vec(),
+            data.emitter_address.to_vec(),
+            data.sequence.to_be_bytes().to_vec(),
+            vec![data.index],
+        ]
+    }
+}
+
+#[cfg(feature = "idl")]
+impl IdlSeeds for BatchClaimDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("batch_claim"),
+            IdlSeed::field::<u16>("emitter_chain"),
+            IdlSeed::field::<ForeignAddress>("emitter_address"),
+            IdlSeed::field::<u64>("sequence"),
+            IdlSeed::field::<u8>("index"),
+        ]
+    }
+}
+
 pub type SplTokenMeta<'b> = Info<'b>;
 
 pub struct SplTokenMetaDerivationData {
@@ -1,9 +1,17 @@
+pub mod complete_batch;
+pub mod complete_semi_fungible;
 pub mod complete_transfer;
 pub mod governance;
 pub mod initialize;
 pub mod transfer;
+pub mod transfer_batch;
+pub mod transfer_semi_fungible;
 
+pub use complete_batch::*;
+pub use complete_semi_fungible::*;
 pub use complete_transfer::*;
 pub use governance::*;
 pub use initialize::*;
 pub use transfer::*;
+pub use transfer_batch::*;
+pub use transfer_semi_fungible::*;
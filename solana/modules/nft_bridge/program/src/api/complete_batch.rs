@@ -0,0 +1,402 @@
+use crate::{
+    accounts::{
+        BatchClaim,
+        BatchClaimDerivationData,
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    },
+    api::complete_transfer::create_spl_metadata,
+    messages::{
+        BatchToken,
+        PayloadBatchTransfer,
+    },
+    types::*,
+    TokenBridgeError::*,
+};
+use bridge::{
+    PayloadMessage,
+    CHAIN_ID_SOLANA,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    program::{
+        invoke,
+        invoke_signed,
+    },
+    sysvar::rent::Rent,
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+/// Accounts of one token of a batch. Both the custody account and the wrapped meta derived from
+/// the mint are passed, only the one matching the kind of token is used.
+#[derive(FromAccounts)]
+pub struct CompleteBatchItem<'b> {
+    /// Claim of the token, see `BatchClaim`
+    #[seeds(BatchClaimDerivationData)]
+    pub claim: Mut<BatchClaim<'b, { AccountState::Uninitialized }>>,
+
+    /// Associated token account of the recipient for the mint
+    pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
+
+    pub mint: Mut<Data<'b, SplMint, { AccountState::MaybeInitialized }>>,
+
+    #[seeds(CustodyAccountDerivationData)]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    #[seeds(WrappedMetaDerivationData)]
+    pub meta: Mut<WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteBatch<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    // Signed message for the transfer, its tokens are claimed one by one
+    pub vaa: PayloadMessage<'b, PayloadBatchTransfer>,
+
+    #[seeds(EndpointDerivationData)]
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// Wallet receiving the tokens, the recipient of a batch
+    pub to_authority: MaybeMut<Info<'b>>,
+
+    pub custody_signer: CustodySigner<'b>,
+    pub mint_authority: MintSigner<'b>,
+
+    // Sysvar/program accounts needed for the CPIs, listed before the tokens as those take every
+    // account left.
+    pub rent: Sysvar<'b, Rent>,
+    pub system: Info<'b>,
+    pub bridge_program: Info<'b>,
+    pub token_program: Info<'b>,
+    pub associated_token_program: Info<'b>,
+
+    pub tokens: Remaining<CompleteBatchItem<'b>>,
+}
+
+impl<'a> From<&CompleteBatch<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteBatch<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+impl<'b> InstructionContext<'b> for CompleteBatch<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteBatchData {
+    /// Index in the batch of each token, in the order of the token accounts
+    pub indices: Vec<u8>,
+}
+
+/// Redeem some of the tokens of a batch transfer. Every token is claimed on its own, so a batch
+/// too large for the compute or account limits of one transaction is redeemed over several.
+/// Unlike single transfers, the recipient of a batch is a wallet, and each token goes to its
+/// associated token account.
+pub fn complete_batch(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteBatch,
+    data: CompleteBatchData,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify VAA
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to_authority.key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+
+    if data.indices.is_empty() || data.indices.len() != accs.tokens.len() {
+        return Err(InvalidBatch.into());
+    }
+
+    // The token accounts are written to while the rest of the accounts are read, so they are taken
+    // out for the loop and put back to be persisted.
+    let mut tokens = std::mem::take(&mut *accs.tokens);
+    for (item, &index) in tokens.iter_mut().zip(&data.indices) {
+        let token = accs.vaa.tokens.get(index as usize).ok_or(InvalidBatch)?;
+
+        // Prevent redeeming a token twice
+        let derivation_data = BatchClaimDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+            sequence: accs.vaa.meta().sequence,
+            index,
+        };
+        item.claim
+            .verify_derivation(ctx.program_id, &derivation_data)?;
+        item.claim
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+        item.claim.claimed = true;
+
+        if accs.vaa.token_chain == CHAIN_ID_SOLANA {
+            complete_batch_native(ctx, accs, item, token)?;
+        } else {
+            complete_batch_wrapped(ctx, accs, item, token)?;
+        }
+    }
+    *accs.tokens = tokens;
+
+    Ok(())
+}
+
+/// Release a native token from custody, as `complete_native` does.
+fn complete_batch_native(
+    ctx: &ExecutionContext,
+    accs: &CompleteBatch,
+    item: &CompleteBatchItem,
+    token: &BatchToken,
+) -> Result<()> {
+    // Verify that the custody account is derived correctly
+    item.custody.verify_derivation(
+        ctx.program_id,
+        &CustodyAccountDerivationData {
+            mint: *item.mint.info().key,
+        },
+    )?;
+
+    // Verify mints
+    if !item.custody.is_initialized() || *item.mint.info().key != item.custody.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.custody_signer.key != item.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+
+    // Please refer to transfer.rs for why the token id is used to store the mint
+    if accs.vaa.token_address != [1u8; 32] {
+        return Err(InvalidMint.into());
+    }
+    let mut token_id_bytes = [0u8; 32];
+    token.token_id.to_big_endian(&mut token_id_bytes);
+    if token_id_bytes != item.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+
+    complete_batch_recipient(ctx, accs, item)?;
+
+    // Transfer tokens
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        item.custody.info().key,
+        item.to.info().key,
+        accs.custody_signer.key,
+        &[],
+        1,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    Ok(())
+}
+
+/// Mint a wrapped token, creating its mint on first use, as `complete_wrapped` does.
+fn complete_batch_wrapped(
+    ctx: &ExecutionContext,
+    accs: &CompleteBatch,
+    item: &mut CompleteBatchItem,
+    token: &BatchToken,
+) -> Result<()> {
+    // Verify mint
+    let derivation_data = WrappedDerivationData {
+        token_chain: accs.vaa.token_chain,
+        token_address: accs.vaa.token_address,
+        token_id: token.token_id,
+    };
+    item.mint
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Initialize the NFT if it doesn't already exist
+    if !item.meta.is_initialized() {
+        // Create mint account
+        item.mint
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        // Initialize mint
+        let init_ix = spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            item.mint.info().key,
+            accs.mint_authority.key,
+            None,
+            0,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+
+        // Create meta account
+        let derivation_data = WrappedMetaDerivationData {
+            mint_key: *item.mint.info().key,
+        };
+        item.meta
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        // Populate meta account
+        item.meta.chain = accs.vaa.token_chain;
+        item.meta.token_address = accs.vaa.token_address;
+        item.meta.token_id = token.token_id.0;
+    }
+
+    complete_batch_recipient(ctx, accs, item)?;
+
+    // Mint tokens
+    let mint_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        item.mint.info().key,
+        item.to.info().key,
+        accs.mint_authority.key,
+        &[],
+        1,
+    )?;
+    invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+
+    Ok(())
+}
+
+/// Make sure the recipient's associated token account for the mint exists.
+fn complete_batch_recipient(
+    ctx: &ExecutionContext,
+    accs: &CompleteBatch,
+    item: &CompleteBatchItem,
+) -> Result<()> {
+    let associated_addr = spl_associated_token_account::get_associated_token_address(
+        accs.to_authority.info().key,
+        item.mint.info().key,
+    );
+    if *item.to.info().key != associated_addr {
+        return Err(InvalidAssociatedAccount.into());
+    }
+
+    if !item.to.is_initialized() {
+        // Create associated token account
+        let ix = spl_associated_token_account::create_associated_token_account(
+            accs.payer.info().key,
+            accs.to_authority.info().key,
+            item.mint.info().key,
+        );
+        invoke(&ix, ctx.accounts)?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteBatchMeta<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    // VAA for the transfer; this does not need to get claimed
+    pub vaa: PayloadMessage<'b, PayloadBatchTransfer>,
+
+    #[seeds(EndpointDerivationData)]
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    #[seeds(WrappedDerivationData)]
+    pub mint: WrappedMint<'b, { AccountState::Initialized }>,
+    #[seeds(WrappedMetaDerivationData)]
+    pub meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
+
+    /// SPL Metadata for the associated Mint
+    pub spl_metadata: Mut<SplTokenMeta<'b>>,
+
+    pub mint_authority: MintSigner<'b>,
+}
+
+impl<'a> From<&CompleteBatchMeta<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteBatchMeta<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+impl<'b> InstructionContext<'b> for CompleteBatchMeta<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteBatchMetaData {
+    /// Index in the batch of the token
+    pub index: u8,
+}
+
+/// Create the Metaplex metadata of one wrapped token of a batch, as `complete_wrapped_meta` does.
+pub fn complete_batch_meta(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteBatchMeta,
+    data: CompleteBatchMetaData,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    let token = accs
+        .vaa
+        .tokens
+        .get(data.index as usize)
+        .ok_or(InvalidBatch)?;
+
+    // Verify mint
+    accs.mint.verify_derivation(
+        ctx.program_id,
+        &WrappedDerivationData {
+            token_chain: accs.vaa.token_chain,
+            token_address: accs.vaa.token_address,
+            token_id: token.token_id,
+        },
+    )?;
+
+    // Verify VAA
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    // Make sure the metadata hasn't been initialized yet
+    if !accs.spl_metadata.data_is_empty() {
+        return Err(AlreadyExecuted.into());
+    }
+
+    // Initialize spl meta
+    accs.spl_metadata.verify_derivation(
+        &spl_token_metadata::id(),
+        &SplTokenMetaDerivationData {
+            mint: *accs.mint.info().key,
+        },
+    )?;
+
+    create_spl_metadata(
+        ctx,
+        &accs.payer,
+        &accs.spl_metadata,
+        &accs.mint,
+        &accs.mint_authority,
+        &accs.vaa.name,
+        &accs.vaa.symbol,
+        &token.uri,
+    )
+}
@@ -0,0 +1,352 @@
+use crate::{
+    accounts::{
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedSemiFungibleDerivationData,
+        WrappedTokenMeta,
+    },
+    api::complete_transfer::create_spl_metadata,
+    messages::PayloadSemiFungibleTransfer,
+    types::*,
+    TokenBridgeError::*,
+};
+use bridge::{
+    vaa::ClaimableVAA,
+    PayloadMessage,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    program::{
+        invoke,
+        invoke_signed,
+    },
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+/// Both the custody account and the wrapped meta derived from the mint are passed, only the one
+/// matching the kind of token is used.
+#[derive(FromAccounts)]
+pub struct CompleteSemiFungible<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    // Signed message for the transfer
+    pub vaa: ClaimableVAA<'b, PayloadSemiFungibleTransfer>,
+
+    #[seeds(EndpointDerivationData)]
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
+    pub to_authority: MaybeMut<Info<'b>>,
+
+    pub mint: Mut<Data<'b, SplMint, { AccountState::MaybeInitialized }>>,
+
+    #[seeds(CustodyAccountDerivationData)]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+    #[seeds(WrappedMetaDerivationData)]
+    pub meta: Mut<WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>>,
+
+    pub custody_signer: CustodySigner<'b>,
+    pub mint_authority: MintSigner<'b>,
+}
+
+impl<'a> From<&CompleteSemiFungible<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteSemiFungible<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+impl<'b> InstructionContext<'b> for CompleteSemiFungible<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteSemiFungibleData {}
+
+/// Release or mint the copies of a token sent by a semi-fungible transfer. Wrapped tokens get a
+/// mint apart from regular NFTs of the same origin, see `WrappedSemiFungibleDerivationData`.
+pub fn complete_semi_fungible(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteSemiFungible,
+    _data: CompleteSemiFungibleData,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify VAA
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to.info().key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+
+    // SPL amounts are 64 bit, larger amounts can't be redeemed on Solana
+    if accs.vaa.amount.is_zero() || accs.vaa.amount > U256::from(u64::MAX) {
+        return Err(InvalidAmount.into());
+    }
+    let amount = accs.vaa.amount.as_u64();
+
+    // Prevent vaa double signing
+    accs.vaa.verify(ctx.program_id)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    if accs.vaa.token_chain == CHAIN_ID_SOLANA {
+        complete_semi_fungible_native(ctx, accs, amount)
+    } else {
+        complete_semi_fungible_wrapped(ctx, accs, amount)
+    }
+}
+
+/// Release copies of a native token from custody, as `complete_native` does.
+fn complete_semi_fungible_native(
+    ctx: &ExecutionContext,
+    accs: &CompleteSemiFungible,
+    amount: u64,
+) -> Result<()> {
+    // Verify that the custody account is derived correctly
+    accs.custody.verify_derivation(
+        ctx.program_id,
+        &CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        },
+    )?;
+
+    // Verify mints
+    if !accs.custody.is_initialized() || *accs.mint.info().key != accs.custody.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.custody_signer.key != accs.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+
+    // Please refer to transfer.rs for why the token id is used to store the mint
+    if accs.vaa.token_address != [1u8; 32] {
+        return Err(InvalidMint.into());
+    }
+    let mut token_id_bytes = [0u8; 32];
+    accs.vaa.token_id.to_big_endian(&mut token_id_bytes);
+    if token_id_bytes != accs.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+
+    complete_semi_fungible_recipient(ctx, accs)?;
+
+    // Transfer tokens
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        accs.custody.info().key,
+        accs.to.info().key,
+        accs.custody_signer.key,
+        &[],
+        amount,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    Ok(())
+}
+
+/// Mint copies of a wrapped token, creating its mint on first use, as `complete_wrapped` does.
+fn complete_semi_fungible_wrapped(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteSemiFungible,
+    amount: u64,
+) -> Result<()> {
+    // Verify mint
+    let derivation_data = WrappedSemiFungibleDerivationData {
+        token_chain: accs.vaa.token_chain,
+        token_address: accs.vaa.token_address,
+        token_id: accs.vaa.token_id,
+    };
+    accs.mint
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify that meta is correct
+    accs.meta.verify_derivation(
+        ctx.program_id,
+        &WrappedMetaDerivationData {
+            mint_key: *accs.mint.info().key,
+        },
+    )?;
+
+    // Initialize the token if it doesn't already exist
+    if !accs.meta.is_initialized() {
+        // Create mint account
+        accs.mint
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        // Initialize mint
+        let init_ix = spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            accs.mint.info().key,
+            accs.mint_authority.key,
+            None,
+            0,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+
+        // Create meta account
+        let derivation_data = WrappedMetaDerivationData {
+            mint_key: *accs.mint.info().key,
+        };
+        accs.meta
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        // Populate meta account
+        accs.meta.chain = accs.vaa.token_chain;
+        accs.meta.token_address = accs.vaa.token_address;
+        accs.meta.token_id = accs.vaa.token_id.0;
+    }
+
+    complete_semi_fungible_recipient(ctx, accs)?;
+
+    // Mint tokens
+    let mint_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        accs.mint.info().key,
+        accs.to.info().key,
+        accs.mint_authority.key,
+        &[],
+        amount,
+    )?;
+    invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+
+    Ok(())
+}
+
+/// Create the recipient's associated token account for the mint if it doesn't exist yet.
+fn complete_semi_fungible_recipient(
+    ctx: &ExecutionContext,
+    accs: &CompleteSemiFungible,
+) -> Result<()> {
+    if !accs.to.is_initialized() {
+        let associated_addr = spl_associated_token_account::get_associated_token_address(
+            accs.to_authority.info().key,
+            accs.mint.info().key,
+        );
+        if *accs.to.info().key != associated_addr {
+            return Err(InvalidAssociatedAccount.into());
+        }
+        // Create associated token account
+        let ix = spl_associated_token_account::create_associated_token_account(
+            accs.payer.info().key,
+            accs.to_authority.info().key,
+            accs.mint.info().key,
+        );
+        invoke(&ix, ctx.accounts)?;
+    } else if *accs.mint.info().key != accs.to.mint {
+        return Err(InvalidMint.into());
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteSemiFungibleMeta<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    // VAA for the transfer; this does not need to get claimed
+    pub vaa: PayloadMessage<'b, PayloadSemiFungibleTransfer>,
+
+    #[seeds(EndpointDerivationData)]
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    #[seeds(WrappedSemiFungibleDerivationData)]
+    pub mint: WrappedMint<'b, { AccountState::Initialized }>,
+    #[seeds(WrappedMetaDerivationData)]
+    pub meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
+
+    /// SPL Metadata for the associated Mint
+    pub spl_metadata: Mut<SplTokenMeta<'b>>,
+
+    pub mint_authority: MintSigner<'b>,
+}
+
+impl<'a> From<&CompleteSemiFungibleMeta<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteSemiFungibleMeta<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+impl<'b> InstructionContext<'b> for CompleteSemiFungibleMeta<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteSemiFungibleMetaData {}
+
+/// Create the Metaplex metadata of a wrapped semi-fungible token, as `complete_wrapped_meta` does.
+pub fn complete_semi_fungible_meta(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteSemiFungibleMeta,
+    _data: CompleteSemiFungibleMetaData,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify mint
+    accs.mint.verify_derivation(
+        ctx.program_id,
+        &WrappedSemiFungibleDerivationData {
+            token_chain: accs.vaa.token_chain,
+            token_address: accs.vaa.token_address,
+            token_id: accs.vaa.token_id,
+        },
+    )?;
+
+    // Verify VAA
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    // Make sure the metadata hasn't been initialized yet
+    if !accs.spl_metadata.data_is_empty() {
+        return Err(AlreadyExecuted.into());
+    }
+
+    // Initialize spl meta
+    accs.spl_metadata.verify_derivation(
+        &spl_token_metadata::id(),
+        &SplTokenMetaDerivationData {
+            mint: *accs.mint.info().key,
+        },
+    )?;
+
+    create_spl_metadata(
+        ctx,
+        &accs.payer,
+        &accs.spl_metadata,
+        &accs.mint,
+        &accs.mint_authority,
+        &accs.vaa.name,
+        &accs.vaa.symbol,
+        &accs.vaa.uri,
+    )
+}
@@ -38,6 +38,11 @@ use solitaire::{
     CreationLamports::Exempt,
     *,
 };
+use spl_token_metadata::state::{
+    MAX_NAME_LENGTH,
+    MAX_URI_LENGTH,
+};
+use wormhole_core::truncate_utf8;
 
 #[derive(FromAccounts)]
 pub struct CompleteNative<'b> {
@@ -45,10 +50,12 @@ pub struct CompleteNative<'b> {
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
+    #[seeds(EndpointDerivationData)]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
     pub to_authority: MaybeMut<Info<'b>>,
+    #[seeds(CustodyAccountDerivationData)]
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
 
@@ -75,7 +82,7 @@ impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
 impl<'b> InstructionContext<'b> for CompleteNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteNativeData {}
 
 pub fn complete_native(
@@ -166,11 +173,14 @@ pub struct CompleteWrapped<'b> {
     // Signed message for the transfer
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
 
+    #[seeds(EndpointDerivationData)]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
     pub to_authority: MaybeMut<Info<'b>>,
+    #[seeds(WrappedDerivationData)]
     pub mint: Mut<WrappedMint<'b, { AccountState::MaybeInitialized }>>,
+    #[seeds(WrappedMetaDerivationData)]
     pub meta: Mut<WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>>,
 
     pub mint_authority: MintSigner<'b>,
@@ -206,7 +216,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -241,7 +251,7 @@ pub fn complete_wrapped(
     if !accs.meta.is_initialized() {
         // Create mint account
         accs.mint
-            .create(&((&*accs).into()), ctx, accs.payer.key, Exempt)?;
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
 
         // Initialize mint
         let init_ix = spl_token::instruction::initialize_mint(
@@ -304,9 +314,12 @@ pub struct CompleteWrappedMeta<'b> {
     // VAA for the transfer; this does not need to get claimed
     pub vaa: PayloadMessage<'b, PayloadTransfer>,
 
+    #[seeds(EndpointDerivationData)]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
+    #[seeds(WrappedDerivationData)]
     pub mint: WrappedMint<'b, { AccountState::Initialized }>,
+    #[seeds(WrappedMetaDerivationData)]
     pub meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
     /// SPL Metadata for the associated Mint
@@ -345,7 +358,7 @@ impl<'a> From<&CompleteWrappedMeta<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrappedMeta<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteWrappedMetaData {}
 
 pub fn complete_wrapped_meta(
@@ -353,8 +366,6 @@ pub fn complete_wrapped_meta(
     accs: &mut CompleteWrappedMeta,
     _data: CompleteWrappedMetaData,
 ) -> Result<()> {
-    use bstr::ByteSlice;
-
     // Verify the chain registration
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
@@ -383,8 +394,37 @@ pub fn complete_wrapped_meta(
         },
     )?;
 
-    let name = accs.vaa.name.clone();
-    let mut symbol: Vec<u8> = accs.vaa.symbol.clone().as_bytes().to_vec();
+    create_spl_metadata(
+        ctx,
+        &accs.payer,
+        &accs.spl_metadata,
+        &accs.mint,
+        &accs.mint_authority,
+        &accs.vaa.name,
+        &accs.vaa.symbol,
+        &accs.vaa.uri,
+    )
+}
+
+/// Create the Metaplex metadata of a wrapped mint, with the mint signer as its update authority.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_spl_metadata(
+    ctx: &ExecutionContext,
+    payer: &AccountInfo,
+    spl_metadata: &SplTokenMeta,
+    mint: &WrappedMint<'_, { AccountState::Initialized }>,
+    mint_authority: &MintSigner,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Result<()> {
+    use bstr::ByteSlice;
+
+    // Version 2 transfers carry longer names and URIs than the metadata program stores. Creators are
+    // not carried over, their addresses on other chains are not Solana keys.
+    let name = truncate_utf8(name, MAX_NAME_LENGTH).to_string();
+    let uri = truncate_utf8(uri, MAX_URI_LENGTH).to_string();
+    let mut symbol: Vec<u8> = symbol.as_bytes().to_vec();
     symbol.truncate(10);
     let mut symbol: Vec<char> = symbol.chars().collect();
     symbol.retain(|&c| c != '\u{FFFD}');
@@ -392,20 +432,20 @@ pub fn complete_wrapped_meta(
 
     let spl_token_metadata_ix = spl_token_metadata::instruction::create_metadata_accounts(
         spl_token_metadata::id(),
-        *accs.spl_metadata.key,
-        *accs.mint.info().key,
-        *accs.mint_authority.info().key,
-        *accs.payer.info().key,
-        *accs.mint_authority.info().key,
+        *spl_metadata.key,
+        *mint.info().key,
+        *mint_authority.info().key,
+        *payer.key,
+        *mint_authority.info().key,
         name,
         symbol,
-        accs.vaa.uri.clone(),
+        uri,
         None,
         0,
         false,
         true,
     );
-    invoke_seeded(&spl_token_metadata_ix, ctx, &accs.mint_authority, None)?;
+    invoke_seeded(&spl_token_metadata_ix, ctx, mint_authority, None)?;
 
     Ok(())
 }
@@ -86,7 +86,7 @@ pub struct UpgradeContract<'b> {
 impl<'b> InstructionContext<'b> for UpgradeContract<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -121,6 +121,7 @@ pub struct RegisterChain<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
+    #[seeds(EndpointDerivationData)]
     pub endpoint: Mut<Endpoint<'b, { AccountState::Uninitialized }>>,
 
     pub vaa: ClaimableVAA<'b, PayloadGovernanceRegisterChain>,
@@ -138,7 +139,7 @@ impl<'a> From<&RegisterChain<'a>> for EndpointDerivationData {
 impl<'b> InstructionContext<'b> for RegisterChain<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct RegisterChainData {}
 
 pub fn register_chain(
@@ -10,11 +10,15 @@ use crate::{
         MintSigner,
         SplTokenMeta,
         SplTokenMetaDerivationData,
+        WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
-    messages::PayloadTransfer,
+    messages::{
+        Creator,
+        PayloadTransfer,
+    },
     types::*,
     TokenBridgeError,
     TokenBridgeError::{
@@ -40,6 +44,7 @@ use solana_program::{
         invoke_signed,
     },
     program_option::COption,
+    pubkey::Pubkey,
     sysvar::clock::Clock,
 };
 use solitaire::{
@@ -52,6 +57,30 @@ use solitaire::{
 };
 use spl_token_metadata::state::Metadata;
 
+/// Metaplex pads name, symbol and URI with zeroes to their maximum length, none of it is sent.
+pub(crate) fn trim_padding(s: &str) -> String {
+    s.trim_end_matches('\0').to_string()
+}
+
+/// Read the Metaplex metadata of a mint, every token the bridge sends has some.
+pub(crate) fn token_metadata(spl_metadata: &SplTokenMeta, mint: &Pubkey) -> Result<Metadata> {
+    spl_metadata.verify_derivation(
+        &spl_token_metadata::id(),
+        &SplTokenMetaDerivationData { mint: *mint },
+    )?;
+
+    // Token must have metadata
+    if spl_metadata.data_is_empty() {
+        return Err(TokenNotNFT.into());
+    }
+
+    if *spl_metadata.owner != spl_token_metadata::id() {
+        return Err(WrongAccountOwner.into());
+    }
+
+    Ok(Metadata::from_account_info(spl_metadata.info()).ok_or(InvalidMetadata)?)
+}
+
 #[derive(FromAccounts)]
 pub struct TransferNative<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
@@ -64,6 +93,7 @@ pub struct TransferNative<'b> {
     /// SPL Metadata for the associated Mint
     pub spl_metadata: SplTokenMeta<'b>,
 
+    #[seeds(CustodyAccountDerivationData)]
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -109,7 +139,7 @@ impl<'a> From<&TransferNative<'a>> for SplTokenMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub target_address: Address,
@@ -195,10 +225,24 @@ pub fn transfer_native(
         token_chain: 1,
         to: data.target_address,
         to_chain: data.target_chain,
-        symbol: metadata.data.symbol,
-        name: metadata.data.name,
-        uri: metadata.data.uri,
+        symbol: trim_padding(&metadata.data.symbol),
+        name: trim_padding(&metadata.data.name),
+        uri: trim_padding(&metadata.data.uri),
         token_id: U256::from_big_endian(&accs.mint.info().key.to_bytes()),
+        // This version of the metadata program does not track collections.
+        collection: [0u8; 32],
+        seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        creators: metadata
+            .data
+            .creators
+            .unwrap_or_default()
+            .iter()
+            .map(|c| Creator {
+                address: c.address.to_bytes(),
+                verified: c.verified,
+                share: c.share,
+            })
+            .collect(),
     };
     let params = (
         bridge::instruction::Instruction::PostMessage,
@@ -237,6 +281,7 @@ pub struct TransferWrapped<'b> {
     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub from_owner: MaybeMut<Signer<Info<'b>>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    #[seeds(WrappedMetaDerivationData)]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
     /// SPL Metadata for the associated Mint
     pub spl_metadata: SplTokenMeta<'b>,
@@ -280,7 +325,7 @@ impl<'a> From<&TransferWrapped<'a>> for SplTokenMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub target_address: Address,
@@ -307,6 +352,16 @@ pub fn transfer_wrapped(
     accs.wrapped_meta
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
+    // Wrapped semi-fungible tokens have a meta too, but are minted at another address.
+    accs.mint.verify_derivation(
+        ctx.program_id,
+        &WrappedDerivationData {
+            token_chain: accs.wrapped_meta.chain,
+            token_address: accs.wrapped_meta.token_address,
+            token_id: U256(accs.wrapped_meta.token_id),
+        },
+    )?;
+
     // Burn tokens
     let burn_ix = spl_token::instruction::burn(
         &spl_token::id(),
@@ -355,9 +410,13 @@ pub fn transfer_wrapped(
         token_id: U256(accs.wrapped_meta.token_id),
         to: data.target_address,
         to_chain: data.target_chain,
-        symbol: metadata.data.symbol,
-        name: metadata.data.name,
-        uri: metadata.data.uri,
+        symbol: trim_padding(&metadata.data.symbol),
+        name: trim_padding(&metadata.data.name),
+        uri: trim_padding(&metadata.data.uri),
+        // Creators of wrapped tokens are this program's keys rather than the original creators.
+        collection: [0u8; 32],
+        seller_fee_basis_points: 0,
+        creators: vec![],
     };
     let params = (
         bridge::instruction::Instruction::PostMessage,
@@ -0,0 +1,324 @@
+use crate::{
+    accounts::{
+        AuthoritySigner,
+        ConfigAccount,
+        CoreBridge,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        MintSigner,
+        SplTokenMeta,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedTokenMeta,
+    },
+    api::transfer::{
+        token_metadata,
+        trim_padding,
+    },
+    messages::{
+        BatchToken,
+        PayloadBatchTransfer,
+    },
+    types::*,
+    TokenBridgeError::{
+        InvalidBatch,
+        InvalidChain,
+        InvalidMint,
+        WrongAccountOwner,
+    },
+};
+use bridge::{
+    api::PostMessageData,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program::{
+        invoke,
+        invoke_signed,
+    },
+    program_option::COption,
+    sysvar::{
+        clock::Clock,
+        rent::Rent,
+    },
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+use wormhole_core::{
+    vaa::nft::{
+        BatchTransfer,
+        PayloadKind,
+    },
+    Chain,
+};
+
+/// Accounts of one token of a batch. Both the custody account and the wrapped meta derived from
+/// the mint are passed, only the one matching the kind of token is used.
+#[derive(FromAccounts)]
+pub struct TransferBatchItem<'b> {
+    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+
+    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+
+    /// SPL Metadata for the associated Mint
+    pub spl_metadata: SplTokenMeta<'b>,
+
+    #[seeds(CustodyAccountDerivationData)]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    #[seeds(WrappedMetaDerivationData)]
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>,
+}
+
+#[derive(FromAccounts)]
+pub struct TransferBatch<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Owner of every token account the batch transfers from
+    pub from_owner: MaybeMut<Signer<Info<'b>>>,
+
+    pub authority_signer: AuthoritySigner<'b>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+
+    // Sysvar/program accounts needed for the CPIs, listed before the tokens as those take every
+    // account left.
+    pub rent: Sysvar<'b, Rent>,
+    pub system: Info<'b>,
+    pub bridge_program: Info<'b>,
+    pub token_program: Info<'b>,
+
+    pub tokens: Remaining<TransferBatchItem<'b>>,
+}
+
+impl<'b> InstructionContext<'b> for TransferBatch<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferBatchData {
+    pub nonce: u32,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+}
+
+/// Lock or burn several NFTs of one contract and post a single batch transfer for all of them.
+/// Native Solana NFTs all share the static token address, so any set of them forms a batch, while
+/// wrapped NFTs must come from the same contract on the same chain. The symbol and name of the
+/// batch are taken from the metadata of the first token.
+pub fn transfer_batch(
+    ctx: &ExecutionContext,
+    accs: &mut TransferBatch,
+    data: TransferBatchData,
+) -> Result<()> {
+    // Prevent transferring to the same chain, or to one that can't redeem batches.
+    if data.target_chain == CHAIN_ID_SOLANA
+        || !PayloadKind::BatchTransfer.is_redeemable_on(&Chain::from(data.target_chain))
+    {
+        return Err(InvalidChain.into());
+    }
+
+    if accs.tokens.is_empty() || accs.tokens.len() > BatchTransfer::MAX_TOKENS {
+        return Err(InvalidBatch.into());
+    }
+
+    let mint_signer = MintSigner::key(None, ctx.program_id);
+    let mut payload: Option<PayloadBatchTransfer> = None;
+    for item in accs.tokens.iter() {
+        // Verify that the from account is owned by the from_owner
+        if &item.from.owner != accs.from_owner.key {
+            return Err(WrongAccountOwner.into());
+        }
+
+        // Verify mints
+        if item.from.mint != *item.mint.info().key {
+            return Err(InvalidMint.into());
+        }
+
+        let metadata = token_metadata(&item.spl_metadata, item.mint.info().key)?;
+        let (token_address, token_chain, token_id) =
+            if item.mint.mint_authority == COption::Some(mint_signer) {
+                transfer_batch_wrapped(ctx, accs, item)?
+            } else {
+                transfer_batch_native(ctx, accs, item)?
+            };
+
+        let payload = payload.get_or_insert_with(|| PayloadBatchTransfer {
+            token_address,
+            token_chain,
+            symbol: trim_padding(&metadata.data.symbol),
+            name: trim_padding(&metadata.data.name),
+            tokens: vec![],
+            to: data.target_address,
+            to_chain: data.target_chain,
+        });
+        if payload.token_address != token_address || payload.token_chain != token_chain {
+            return Err(InvalidBatch.into());
+        }
+        payload.tokens.push(BatchToken {
+            token_id,
+            uri: trim_padding(&metadata.data.uri),
+        });
+    }
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post message
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.ok_or(InvalidBatch)?.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+/// Burn a wrapped token, as `transfer_wrapped` does.
+fn transfer_batch_wrapped(
+    ctx: &ExecutionContext,
+    accs: &TransferBatch,
+    item: &TransferBatchItem,
+) -> Result<(Address, ChainID, U256)> {
+    // Verify that meta is correct
+    item.wrapped_meta.verify_derivation(
+        ctx.program_id,
+        &WrappedMetaDerivationData {
+            mint_key: *item.mint.info().key,
+        },
+    )?;
+    if !item.wrapped_meta.is_initialized() {
+        return Err(InvalidMint.into());
+    }
+
+    // Copies of a semi-fungible token are not batched, their wrapped mints are derived apart.
+    item.mint.verify_derivation(
+        ctx.program_id,
+        &WrappedDerivationData {
+            token_chain: item.wrapped_meta.chain,
+            token_address: item.wrapped_meta.token_address,
+            token_id: U256(item.wrapped_meta.token_id),
+        },
+    )?;
+
+    // Burn tokens
+    let burn_ix = spl_token::instruction::burn(
+        &spl_token::id(),
+        item.from.info().key,
+        item.mint.info().key,
+        accs.authority_signer.key,
+        &[],
+        1,
+    )?;
+    invoke_seeded(&burn_ix, ctx, &accs.authority_signer, None)?;
+
+    Ok((
+        item.wrapped_meta.token_address,
+        item.wrapped_meta.chain,
+        U256(item.wrapped_meta.token_id),
+    ))
+}
+
+/// Lock a native token in custody, as `transfer_native` does.
+fn transfer_batch_native(
+    ctx: &ExecutionContext,
+    accs: &TransferBatch,
+    item: &TransferBatchItem,
+) -> Result<(Address, ChainID, U256)> {
+    // Verify that the custody account is derived correctly
+    let derivation_data = CustodyAccountDerivationData {
+        mint: *item.mint.info().key,
+    };
+    item.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if !item.custody.is_initialized() {
+        item.custody
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        let init_ix = spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            item.custody.info().key,
+            item.mint.info().key,
+            accs.custody_signer.key,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+    }
+
+    // Transfer tokens
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        item.from.info().key,
+        item.custody.info().key,
+        accs.authority_signer.key,
+        &[],
+        1,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.authority_signer, None)?;
+
+    // Please refer to transfer.rs for why the token id is used to store the mint
+    Ok((
+        [1u8; 32],
+        CHAIN_ID_SOLANA,
+        U256::from_big_endian(&item.mint.info().key.to_bytes()),
+    ))
+}
@@ -0,0 +1,297 @@
+use crate::{
+    accounts::{
+        AuthoritySigner,
+        ConfigAccount,
+        CoreBridge,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        MintSigner,
+        SplTokenMeta,
+        WrappedMetaDerivationData,
+        WrappedSemiFungibleDerivationData,
+        WrappedTokenMeta,
+    },
+    api::transfer::{
+        token_metadata,
+        trim_padding,
+    },
+    messages::PayloadSemiFungibleTransfer,
+    types::*,
+    TokenBridgeError::{
+        InvalidAmount,
+        InvalidChain,
+        InvalidMint,
+        TokenNotNFT,
+        WrongAccountOwner,
+    },
+};
+use bridge::{
+    api::PostMessageData,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program::{
+        invoke,
+        invoke_signed,
+    },
+    program_option::COption,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+use wormhole_core::{
+    vaa::nft::PayloadKind,
+    Chain,
+};
+
+/// Both the custody account and the wrapped meta derived from the mint are passed, only the one
+/// matching the kind of token is used.
+#[derive(FromAccounts)]
+pub struct TransferSemiFungible<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub from_owner: MaybeMut<Signer<Info<'b>>>,
+
+    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+    /// SPL Metadata for the associated Mint
+    pub spl_metadata: SplTokenMeta<'b>,
+
+    #[seeds(CustodyAccountDerivationData)]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+    #[seeds(WrappedMetaDerivationData)]
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>,
+
+    pub authority_signer: AuthoritySigner<'b>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'b> InstructionContext<'b> for TransferSemiFungible<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferSemiFungibleData {
+    pub nonce: u32,
+    /// Number of copies of the token to send
+    pub amount: u64,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+}
+
+/// Lock or burn some copies of a token with a supply above one and post a semi-fungible transfer
+/// for them. Such tokens are SPL mints without decimals, each unit of the mint is one copy. Prints
+/// of a master edition are mints of their own with a supply of one and are sent as regular NFTs.
+pub fn transfer_semi_fungible(
+    ctx: &ExecutionContext,
+    accs: &mut TransferSemiFungible,
+    data: TransferSemiFungibleData,
+) -> Result<()> {
+    // Prevent transferring to the same chain, or to one that can't redeem semi-fungible transfers.
+    if data.target_chain == CHAIN_ID_SOLANA
+        || !PayloadKind::SemiFungibleTransfer.is_redeemable_on(&Chain::from(data.target_chain))
+    {
+        return Err(InvalidChain.into());
+    }
+
+    if data.amount == 0 {
+        return Err(InvalidAmount.into());
+    }
+
+    // Verify that the from account is owned by the from_owner
+    if &accs.from.owner != accs.from_owner.key {
+        return Err(WrongAccountOwner.into());
+    }
+
+    // Verify mints
+    if accs.from.mint != *accs.mint.info().key {
+        return Err(InvalidMint.into());
+    }
+
+    // Copies of a token are whole units
+    if accs.mint.decimals != 0 {
+        return Err(TokenNotNFT.into());
+    }
+
+    let metadata = token_metadata(&accs.spl_metadata, accs.mint.info().key)?;
+    let mint_signer = MintSigner::key(None, ctx.program_id);
+    let (token_address, token_chain, token_id) =
+        if accs.mint.mint_authority == COption::Some(mint_signer) {
+            transfer_semi_fungible_wrapped(ctx, accs, data.amount)?
+        } else {
+            transfer_semi_fungible_native(ctx, accs, data.amount)?
+        };
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post message
+    let payload = PayloadSemiFungibleTransfer {
+        token_address,
+        token_chain,
+        symbol: trim_padding(&metadata.data.symbol),
+        name: trim_padding(&metadata.data.name),
+        token_id,
+        amount: U256::from(data.amount),
+        uri: trim_padding(&metadata.data.uri),
+        to: data.target_address,
+        to_chain: data.target_chain,
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+/// Burn copies of a wrapped token, as `transfer_wrapped` does.
+fn transfer_semi_fungible_wrapped(
+    ctx: &ExecutionContext,
+    accs: &TransferSemiFungible,
+    amount: u64,
+) -> Result<(Address, ChainID, U256)> {
+    // Verify that meta is correct
+    accs.wrapped_meta.verify_derivation(
+        ctx.program_id,
+        &WrappedMetaDerivationData {
+            mint_key: *accs.mint.info().key,
+        },
+    )?;
+    if !accs.wrapped_meta.is_initialized() {
+        return Err(InvalidMint.into());
+    }
+
+    // Wrapped NFTs have a meta too, but are minted at another address.
+    accs.mint.verify_derivation(
+        ctx.program_id,
+        &WrappedSemiFungibleDerivationData {
+            token_chain: accs.wrapped_meta.chain,
+            token_address: accs.wrapped_meta.token_address,
+            token_id: U256(accs.wrapped_meta.token_id),
+        },
+    )?;
+
+    // Burn tokens
+    let burn_ix = spl_token::instruction::burn(
+        &spl_token::id(),
+        accs.from.info().key,
+        accs.mint.info().key,
+        accs.authority_signer.key,
+        &[],
+        amount,
+    )?;
+    invoke_seeded(&burn_ix, ctx, &accs.authority_signer, None)?;
+
+    Ok((
+        accs.wrapped_meta.token_address,
+        accs.wrapped_meta.chain,
+        U256(accs.wrapped_meta.token_id),
+    ))
+}
+
+/// Lock copies of a native token in custody, as `transfer_native` does.
+fn transfer_semi_fungible_native(
+    ctx: &ExecutionContext,
+    accs: &TransferSemiFungible,
+    amount: u64,
+) -> Result<(Address, ChainID, U256)> {
+    // Verify that the custody account is derived correctly
+    let derivation_data = CustodyAccountDerivationData {
+        mint: *accs.mint.info().key,
+    };
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if !accs.custody.is_initialized() {
+        accs.custody
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        let init_ix = spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            accs.custody.info().key,
+            accs.mint.info().key,
+            accs.custody_signer.key,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+    }
+
+    // Transfer tokens
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        accs.from.info().key,
+        accs.custody.info().key,
+        accs.authority_signer.key,
+        &[],
+        amount,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.authority_signer, None)?;
+
+    // Please refer to transfer.rs for why the token id is used to store the mint
+    Ok((
+        [1u8; 32],
+        CHAIN_ID_SOLANA,
+        U256::from_big_endian(&accs.mint.info().key.to_bytes()),
+    ))
+}
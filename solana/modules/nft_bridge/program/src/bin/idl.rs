@@ -0,0 +1,12 @@
+//! Prints the program IDL as JSON, generated from the `solitaire!` instruction table and the
+//! `FromAccounts` structs of each instruction.
+//!
+//! Usage: cargo run --features idl --bin nft-bridge-idl [output.json]
+
+fn main() {
+    let idl = serde_json::to_string_pretty(&nft_bridge::instruction::idl()).unwrap();
+    match std::env::args().nth(1) {
+        Some(path) => std::fs::write(path, idl).unwrap(),
+        None => println!("{}", idl),
+    }
+}
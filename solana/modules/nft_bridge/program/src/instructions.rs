@@ -1,6 +1,8 @@
 use crate::{
     accounts::{
         AuthoritySigner,
+        BatchClaim,
+        BatchClaimDerivationData,
         ConfigAccount,
         CustodyAccount,
         CustodyAccountDerivationData,
@@ -14,6 +16,7 @@ use crate::{
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
+        WrappedSemiFungibleDerivationData,
         WrappedTokenMeta,
     },
     api::{
@@ -21,16 +24,25 @@ use crate::{
             CompleteNativeData,
             CompleteWrappedData,
         },
+        CompleteBatchData,
+        CompleteBatchMetaData,
+        CompleteSemiFungibleData,
+        CompleteSemiFungibleMetaData,
         RegisterChainData,
+        TransferBatchData,
         TransferNativeData,
+        TransferSemiFungibleData,
         TransferWrappedData,
         UpgradeContractData,
     },
     messages::{
+        PayloadBatchTransfer,
         PayloadGovernanceRegisterChain,
+        PayloadSemiFungibleTransfer,
         PayloadTransfer,
     },
     CompleteWrappedMetaData,
+    TokenBridgeError,
 };
 use borsh::BorshSerialize;
 use bridge::{
@@ -267,6 +279,299 @@ pub fn complete_wrapped_meta(
     })
 }
 
+/// Mint of a token of a batch, see `transfer.rs` for how native mints are encoded in the token id.
+fn batch_mint(program_id: &Pubkey, payload: &PayloadBatchTransfer, token_id: U256) -> Pubkey {
+    if payload.token_chain == CHAIN_ID_SOLANA {
+        let mut mint = [0u8; 32];
+        token_id.to_big_endian(&mut mint);
+        Pubkey::new_from_array(mint)
+    } else {
+        WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+            &WrappedDerivationData {
+                token_chain: payload.token_chain,
+                token_address: payload.token_address,
+                token_id,
+            },
+            program_id,
+        )
+    }
+}
+
+/// The token of a batch at the given index.
+fn batch_token_id(payload: &PayloadBatchTransfer, index: u8) -> solitaire::Result<U256> {
+    payload
+        .tokens
+        .get(index as usize)
+        .map(|token| token.token_id)
+        .ok_or_else(|| TokenBridgeError::InvalidBatch.into())
+}
+
+pub fn complete_batch(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: PayloadBatchTransfer,
+    to_authority: Pubkey,
+    data: CompleteBatchData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let mint_authority_key = MintSigner::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new_readonly(message_key, false),
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new_readonly(to_authority, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        AccountMeta::new_readonly(mint_authority_key, false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+    ];
+    // Tokens go last, they take every account left.
+    for &index in data.indices.iter() {
+        let mint = batch_mint(&program_id, &payload, batch_token_id(&payload, index)?);
+        let claim_key = BatchClaim::<'_, { AccountState::Uninitialized }>::key(
+            &BatchClaimDerivationData {
+                emitter_chain: vaa.emitter_chain,
+                emitter_address: vaa.emitter_address,
+                sequence: vaa.sequence,
+                index,
+            },
+            &program_id,
+        );
+        let custody_key = CustodyAccount::<'_, { AccountState::MaybeInitialized }>::key(
+            &CustodyAccountDerivationData { mint },
+            &program_id,
+        );
+        let meta_key = WrappedTokenMeta::<'_, { AccountState::MaybeInitialized }>::key(
+            &WrappedMetaDerivationData { mint_key: mint },
+            &program_id,
+        );
+        let associated_addr =
+            spl_associated_token_account::get_associated_token_address(&to_authority, &mint);
+
+        accounts.extend_from_slice(&[
+            AccountMeta::new(claim_key, false),
+            AccountMeta::new(associated_addr, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new(meta_key, false),
+        ]);
+    }
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (crate::instruction::Instruction::CompleteBatch, data).try_to_vec()?,
+    })
+}
+
+pub fn complete_batch_meta(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: PayloadBatchTransfer,
+    data: CompleteBatchMetaData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let mint_key = batch_mint(&program_id, &payload, batch_token_id(&payload, data.index)?);
+    let mint_authority_key = MintSigner::key(None, &program_id);
+
+    let mint_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key },
+        &program_id,
+    );
+    // SPL Metadata
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint: mint_key },
+        &spl_token_metadata::id(),
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(message_key, false),
+            AccountMeta::new_readonly(endpoint, false),
+            AccountMeta::new_readonly(mint_key, false),
+            AccountMeta::new_readonly(mint_meta_key, false),
+            AccountMeta::new(spl_metadata, false),
+            AccountMeta::new_readonly(mint_authority_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        ],
+        data: (crate::instruction::Instruction::CompleteBatchMeta, data).try_to_vec()?,
+    })
+}
+
+/// Mint of a semi-fungible token, see `transfer.rs` for how native mints are encoded in the token id.
+fn semi_fungible_mint(program_id: &Pubkey, payload: &PayloadSemiFungibleTransfer) -> Pubkey {
+    if payload.token_chain == CHAIN_ID_SOLANA {
+        let mut mint = [0u8; 32];
+        payload.token_id.to_big_endian(&mut mint);
+        Pubkey::new_from_array(mint)
+    } else {
+        WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+            &WrappedSemiFungibleDerivationData {
+                token_chain: payload.token_chain,
+                token_address: payload.token_address,
+                token_id: payload.token_id,
+            },
+            program_id,
+        )
+    }
+}
+
+pub fn complete_semi_fungible(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: PayloadSemiFungibleTransfer,
+    to_authority: Pubkey,
+    data: CompleteSemiFungibleData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let mint = semi_fungible_mint(&program_id, &payload);
+    let custody_key = CustodyAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let meta_key = WrappedTokenMeta::<'_, { AccountState::MaybeInitialized }>::key(
+        &WrappedMetaDerivationData { mint_key: mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let mint_authority_key = MintSigner::key(None, &program_id);
+    let associated_addr =
+        spl_associated_token_account::get_associated_token_address(&to_authority, &mint);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new_readonly(endpoint, false),
+            AccountMeta::new(associated_addr, false),
+            AccountMeta::new_readonly(to_authority, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new(meta_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new_readonly(mint_authority_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+        data: (crate::instruction::Instruction::CompleteSemiFungible, data).try_to_vec()?,
+    })
+}
+
+pub fn complete_semi_fungible_meta(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: PayloadSemiFungibleTransfer,
+    data: CompleteSemiFungibleMetaData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let mint_key = semi_fungible_mint(&program_id, &payload);
+    let mint_authority_key = MintSigner::key(None, &program_id);
+
+    let mint_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key },
+        &program_id,
+    );
+    // SPL Metadata
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint: mint_key },
+        &spl_token_metadata::id(),
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(message_key, false),
+            AccountMeta::new_readonly(endpoint, false),
+            AccountMeta::new_readonly(mint_key, false),
+            AccountMeta::new_readonly(mint_meta_key, false),
+            AccountMeta::new(spl_metadata, false),
+            AccountMeta::new_readonly(mint_authority_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::CompleteSemiFungibleMeta,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
 pub fn register_chain(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -351,12 +656,7 @@ pub fn transfer_native(
 
     // Bridge keys
     let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
-    let sequence_key = Sequence::key(
-        &SequenceDerivationData {
-            emitter_key: &emitter_key,
-        },
-        &bridge_id,
-    );
+    let sequence_key = Sequence::key(&SequenceDerivationData { emitter_key }, &bridge_id);
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
 
     Ok(Instruction {
@@ -429,12 +729,7 @@ pub fn transfer_wrapped(
 
     // Bridge keys
     let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
-    let sequence_key = Sequence::key(
-        &SequenceDerivationData {
-            emitter_key: &emitter_key,
-        },
-        &bridge_id,
-    );
+    let sequence_key = Sequence::key(&SequenceDerivationData { emitter_key }, &bridge_id);
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
 
     Ok(Instruction {
@@ -465,6 +760,149 @@ pub fn transfer_wrapped(
     })
 }
 
+/// Token account and mint of one token of a batch transfer.
+pub struct TransferBatchToken {
+    pub from: Pubkey,
+    pub mint: Pubkey,
+}
+
+pub fn transfer_batch(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    from_owner: Pubkey,
+    tokens: &[TransferBatchToken],
+    data: TransferBatchData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let authority_signer_key = AuthoritySigner::key(None, &program_id);
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(&SequenceDerivationData { emitter_key }, &bridge_id);
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new_readonly(from_owner, true),
+        AccountMeta::new_readonly(authority_signer_key, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        AccountMeta::new(bridge_config, false),
+        AccountMeta::new(message_key, true),
+        AccountMeta::new_readonly(emitter_key, false),
+        AccountMeta::new(sequence_key, false),
+        AccountMeta::new(fee_collector_key, false),
+        AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    // Tokens go last, they take every account left.
+    for token in tokens {
+        let custody_key = CustodyAccount::<'_, { AccountState::MaybeInitialized }>::key(
+            &CustodyAccountDerivationData { mint: token.mint },
+            &program_id,
+        );
+        let wrapped_meta_key = WrappedTokenMeta::<'_, { AccountState::MaybeInitialized }>::key(
+            &WrappedMetaDerivationData {
+                mint_key: token.mint,
+            },
+            &program_id,
+        );
+        // SPL Metadata
+        let spl_metadata = SplTokenMeta::key(
+            &SplTokenMetaDerivationData { mint: token.mint },
+            &spl_token_metadata::id(),
+        );
+
+        accounts.extend_from_slice(&[
+            AccountMeta::new(token.from, false),
+            AccountMeta::new(token.mint, false),
+            AccountMeta::new_readonly(spl_metadata, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(wrapped_meta_key, false),
+        ]);
+    }
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (crate::instruction::Instruction::TransferBatch, data).try_to_vec()?,
+    })
+}
+
+pub fn transfer_semi_fungible(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    from: Pubkey,
+    from_owner: Pubkey,
+    mint: Pubkey,
+    data: TransferSemiFungibleData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let custody_key = CustodyAccount::<'_, { AccountState::MaybeInitialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let wrapped_meta_key = WrappedTokenMeta::<'_, { AccountState::MaybeInitialized }>::key(
+        &WrappedMetaDerivationData { mint_key: mint },
+        &program_id,
+    );
+
+    let authority_signer_key = AuthoritySigner::key(None, &program_id);
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // SPL Metadata
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint },
+        &spl_token_metadata::id(),
+    );
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(&SequenceDerivationData { emitter_key }, &bridge_id);
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new(from, false),
+            AccountMeta::new_readonly(from_owner, true),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(spl_metadata, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(wrapped_meta_key, false),
+            AccountMeta::new_readonly(authority_signer_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message_key, true),
+            AccountMeta::new_readonly(emitter_key, false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: (crate::instruction::Instruction::TransferSemiFungible, data).try_to_vec()?,
+    })
+}
+
 pub fn upgrade_contract(
     program_id: Pubkey,
     payer: Pubkey,
@@ -4,7 +4,7 @@
 #![deny(unused_must_use)]
 // #![cfg(all(target_arch = "bpf", not(feature = "no-entrypoint")))]
 
-#[cfg(feature = "no-entrypoint")]
+#[cfg(feature = "client")]
 pub mod instructions;
 
 #[cfg(feature = "wasm")]
@@ -21,16 +21,30 @@ pub mod messages;
 pub mod types;
 
 pub use api::{
+    complete_batch,
+    complete_batch_meta,
     complete_native,
+    complete_semi_fungible,
+    complete_semi_fungible_meta,
     complete_wrapped,
     complete_wrapped_meta,
     initialize,
     register_chain,
+    transfer_batch,
     transfer_native,
+    transfer_semi_fungible,
     transfer_wrapped,
     upgrade_contract,
+    CompleteBatch,
+    CompleteBatchData,
+    CompleteBatchMeta,
+    CompleteBatchMetaData,
     CompleteNative,
     CompleteNativeData,
+    CompleteSemiFungible,
+    CompleteSemiFungibleData,
+    CompleteSemiFungibleMeta,
+    CompleteSemiFungibleMetaData,
     CompleteWrapped,
     CompleteWrappedData,
     CompleteWrappedMeta,
@@ -39,8 +53,12 @@ pub use api::{
     InitializeData,
     RegisterChain,
     RegisterChainData,
+    TransferBatch,
+    TransferBatchData,
     TransferNative,
     TransferNativeData,
+    TransferSemiFungible,
+    TransferSemiFungibleData,
     TransferWrapped,
     TransferWrappedData,
     UpgradeContract,
@@ -63,6 +81,8 @@ pub enum TokenBridgeError {
     TokenNotNFT,
     InvalidAssociatedAccount,
     InvalidRecipient,
+    InvalidBatch,
+    InvalidAmount,
 }
 
 impl From<TokenBridgeError> for SolitaireError {
@@ -80,4 +100,10 @@ solitaire! {
     TransferNative(TransferNativeData) => transfer_native,
     RegisterChain(RegisterChainData) => register_chain,
     UpgradeContract(UpgradeContractData) => upgrade_contract,
+    TransferBatch(TransferBatchData) => transfer_batch,
+    CompleteBatch(CompleteBatchData) => complete_batch,
+    CompleteBatchMeta(CompleteBatchMetaData) => complete_batch_meta,
+    TransferSemiFungible(TransferSemiFungibleData) => transfer_semi_fungible,
+    CompleteSemiFungible(CompleteSemiFungibleData) => complete_semi_fungible,
+    CompleteSemiFungibleMeta(CompleteSemiFungibleMetaData) => complete_semi_fungible_meta,
 }
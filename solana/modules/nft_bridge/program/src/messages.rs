@@ -4,31 +4,40 @@ use crate::types::{
 };
 use bridge::{
     vaa::{
+        parse_governance,
+        payload_error,
         DeserializePayload,
         SerializePayload,
     },
     DeserializeGovernancePayload,
     SerializeGovernancePayload,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-    WriteBytesExt,
-};
 use primitive_types::U256;
-use solana_program::{
-    program_error::ProgramError::InvalidAccountData,
-    pubkey::Pubkey,
-};
+use solana_program::pubkey::Pubkey;
 use solitaire::SolitaireError;
-use std::io::{
-    Cursor,
-    Read,
-    Write,
+use std::io::Write;
+use wormhole_core::{
+    vaa::nft::{
+        BatchTransfer,
+        GovernanceContractUpgrade,
+        GovernanceRegisterChain,
+        SemiFungibleTransfer,
+        TransferV2,
+    },
+    Chain,
+    GovernanceAction,
+};
+
+pub use wormhole_core::vaa::nft::{
+    BatchToken,
+    Creator,
 };
 
 pub const MODULE: &str = "NFTBridge";
 
+// The wire formats are defined by wormhole-core, the types below only map them to the fields the
+// program works with.
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct PayloadTransfer {
     // Address of the token. Left-zero-padded if shorter than 32 bytes
@@ -43,6 +52,12 @@ pub struct PayloadTransfer {
     pub token_id: U256,
     // URI of the token metadata
     pub uri: String,
+    // Address of the collection the token belongs to, zero if it belongs to none
+    pub collection: Address,
+    // Royalty paid on secondary sales, in basis points
+    pub seller_fee_basis_points: u16,
+    // Creators sharing the royalty
+    pub creators: Vec<Creator>,
     // Address of the recipient. Left-zero-padded if shorter than 32 bytes
     pub to: Address,
     // Chain ID of the recipient
@@ -51,97 +66,151 @@ pub struct PayloadTransfer {
 
 impl DeserializePayload for PayloadTransfer {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        use bstr::ByteSlice;
-        let mut v = Cursor::new(buf);
-
-        if v.read_u8()? != 1 {
-            return Err(SolitaireError::Custom(0));
-        };
-
-        let mut token_address = Address::default();
-        v.read_exact(&mut token_address)?;
-
-        let token_chain = v.read_u16::<BigEndian>()?;
-
-        // We may receive invalid UTF-8 over the bridge, especially if truncated. To compensate for
-        // this we rely on the bstr libraries ability to parse invalid UTF-8, and strip out the
-        // "Invalid Unicode Codepoint" (FFFD) characters. This becomes the canonical representation
-        // on Solana.
-        let mut symbol_data = vec![0u8; 32];
-        v.read_exact(&mut symbol_data)?;
-        symbol_data.retain(|&c| c != 0);
-        let mut symbol: Vec<char> = symbol_data.chars().collect();
-        symbol.retain(|&c| c != '\u{FFFD}');
-        let symbol: String = symbol.iter().collect();
-
-        let mut name_data = vec![0u8; 32];
-        v.read_exact(&mut name_data)?;
-        name_data.retain(|&c| c != 0);
-        let mut name: Vec<char> = name_data.chars().collect();
-        name.retain(|&c| c != '\u{FFFD}');
-        let name: String = name.iter().collect();
-
-        let mut id_data: [u8; 32] = [0; 32];
-        v.read_exact(&mut id_data)?;
-        let token_id = U256::from_big_endian(&id_data);
-
-        let uri_len = v.read_u8()?;
-        let mut uri_bytes = vec![0u8; uri_len as usize];
-        v.read_exact(uri_bytes.as_mut_slice())?;
-        let uri = String::from_utf8(uri_bytes).unwrap();
-
-        let mut to = Address::default();
-        v.read_exact(&mut to)?;
-
-        let to_chain = v.read_u16::<BigEndian>()?;
-
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        // Both payload versions are accepted. Version 1 symbol and name may arrive as invalid UTF-8,
+        // especially if truncated. wormhole-core strips the invalid characters, which makes its
+        // result the canonical representation on Solana.
+        let transfer = TransferV2::from_versioned_bytes(buf).map_err(payload_error)?;
         Ok(PayloadTransfer {
-            token_address,
-            token_chain,
-            to,
-            to_chain,
-            symbol,
-            name,
-            token_id,
-            uri,
+            token_address: transfer.nft_address,
+            token_chain: transfer.nft_chain.into(),
+            symbol: transfer.symbol,
+            name: transfer.name,
+            token_id: transfer.token_id,
+            uri: transfer.uri,
+            collection: transfer.collection,
+            seller_fee_basis_points: transfer.seller_fee_basis_points,
+            creators: transfer.creators,
+            to: transfer.to,
+            to_chain: transfer.to_chain.into(),
         })
     }
 }
 
 impl SerializePayload for PayloadTransfer {
+    /// Transfers version 1 can carry are still sent as version 1, so receivers that have not been
+    /// upgraded keep accepting them. So are transfers to chains that only redeem version 1, see
+    /// `TransferV2::to_versioned_bytes`.
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
-        // Payload ID
-        writer.write_u8(1)?;
-
-        writer.write(&self.token_address)?;
-        writer.write_u16::<BigEndian>(self.token_chain)?;
+        let transfer = TransferV2 {
+            nft_address: self.token_address,
+            nft_chain: Chain::from(self.token_chain),
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            token_id: self.token_id,
+            uri: self.uri.clone(),
+            collection: self.collection,
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            creators: self.creators.clone(),
+            to: self.to,
+            to_chain: Chain::from(self.to_chain),
+        };
+        writer.write_all(&transfer.to_versioned_bytes())?;
+        Ok(())
+    }
+}
 
-        let mut symbol: [u8; 32] = [0; 32];
-        for i in 0..self.symbol.len() {
-            symbol[i] = self.symbol.as_bytes()[i];
-        }
-        writer.write(&symbol)?;
+#[derive(PartialEq, Debug, Clone)]
+pub struct PayloadBatchTransfer {
+    // Address of the token. Left-zero-padded if shorter than 32 bytes
+    pub token_address: Address,
+    // Chain ID of the token
+    pub token_chain: ChainID,
+    // Symbol of the token
+    pub symbol: String,
+    // Name of the token
+    pub name: String,
+    // TokenIDs and metadata URIs of the tokens
+    pub tokens: Vec<BatchToken>,
+    // Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    pub to: Address,
+    // Chain ID of the recipient
+    pub to_chain: ChainID,
+}
 
-        let mut name: [u8; 32] = [0; 32];
-        for i in 0..self.name.len() {
-            name[i] = self.name.as_bytes()[i];
-        }
-        writer.write(&name)?;
+impl DeserializePayload for PayloadBatchTransfer {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let transfer = BatchTransfer::from_bytes(buf).map_err(payload_error)?;
+        Ok(PayloadBatchTransfer {
+            token_address: transfer.nft_address,
+            token_chain: transfer.nft_chain.into(),
+            symbol: transfer.symbol,
+            name: transfer.name,
+            tokens: transfer.tokens,
+            to: transfer.to,
+            to_chain: transfer.to_chain.into(),
+        })
+    }
+}
 
-        let mut id_data: [u8; 32] = [0; 32];
-        self.token_id.to_big_endian(&mut id_data);
-        writer.write(&id_data)?;
+impl SerializePayload for PayloadBatchTransfer {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
+        let transfer = BatchTransfer {
+            nft_address: self.token_address,
+            nft_chain: Chain::from(self.token_chain),
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            tokens: self.tokens.clone(),
+            to: self.to,
+            to_chain: Chain::from(self.to_chain),
+        };
+        writer.write_all(&transfer.to_bytes())?;
+        Ok(())
+    }
+}
 
-        writer.write_u8(self.uri.len() as u8)?;
-        writer.write(self.uri.as_bytes())?;
+#[derive(PartialEq, Debug, Clone)]
+pub struct PayloadSemiFungibleTransfer {
+    // Address of the token. Left-zero-padded if shorter than 32 bytes
+    pub token_address: Address,
+    // Chain ID of the token
+    pub token_chain: ChainID,
+    // Symbol of the token
+    pub symbol: String,
+    // Name of the token
+    pub name: String,
+    // TokenID of the token
+    pub token_id: U256,
+    // Number of copies of the token transferred
+    pub amount: U256,
+    // URI of the token metadata (UTF-8)
+    pub uri: String,
+    // Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    pub to: Address,
+    // Chain ID of the recipient
+    pub to_chain: ChainID,
+}
 
-        writer.write(&self.to)?;
-        writer.write_u16::<BigEndian>(self.to_chain)?;
+impl DeserializePayload for PayloadSemiFungibleTransfer {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let transfer = SemiFungibleTransfer::from_bytes(buf).map_err(payload_error)?;
+        Ok(PayloadSemiFungibleTransfer {
+            token_address: transfer.nft_address,
+            token_chain: transfer.nft_chain.into(),
+            symbol: transfer.symbol,
+            name: transfer.name,
+            token_id: transfer.token_id,
+            amount: transfer.amount,
+            uri: transfer.uri,
+            to: transfer.to,
+            to_chain: transfer.to_chain.into(),
+        })
+    }
+}
 
+impl SerializePayload for PayloadSemiFungibleTransfer {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
+        let transfer = SemiFungibleTransfer {
+            nft_address: self.token_address,
+            nft_chain: Chain::from(self.token_chain),
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            token_id: self.token_id,
+            amount: self.amount,
+            uri: self.uri.clone(),
+            to: self.to,
+            to_chain: Chain::from(self.to_chain),
+        };
+        writer.write_all(&transfer.to_bytes())?;
         Ok(())
     }
 }
@@ -167,20 +236,10 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut v = Cursor::new(buf);
-        Self::check_governance_header(&mut v)?;
-
-        let chain = v.read_u16::<BigEndian>()?;
-        let mut endpoint_address = [0u8; 32];
-        v.read_exact(&mut endpoint_address)?;
-
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let action: GovernanceRegisterChain = parse_governance(buf)?;
         Ok(PayloadGovernanceRegisterChain {
-            chain,
-            endpoint_address,
+            chain: action.emitter.into(),
+            endpoint_address: action.endpoint_address,
         })
     }
 }
@@ -190,11 +249,11 @@ where
     Self: SerializeGovernancePayload,
 {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
-        self.write_governance_header(writer)?;
-        // Payload ID
-        writer.write_u16::<BigEndian>(self.chain)?;
-        writer.write(&self.endpoint_address[..])?;
-
+        let action = GovernanceRegisterChain {
+            emitter: Chain::from(self.chain),
+            endpoint_address: self.endpoint_address,
+        };
+        writer.write_all(&action.to_bytes(Chain::Solana))?;
         Ok(())
     }
 }
@@ -207,8 +266,10 @@ pub struct GovernancePayloadUpgrade {
 
 impl SerializePayload for GovernancePayloadUpgrade {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        self.write_governance_header(v)?;
-        v.write(&self.new_contract.to_bytes())?;
+        let action = GovernanceContractUpgrade {
+            new_contract: self.new_contract.to_bytes(),
+        };
+        v.write_all(&action.to_bytes(Chain::Solana))?;
         Ok(())
     }
 }
@@ -218,18 +279,9 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut c = Cursor::new(buf);
-        Self::check_governance_header(&mut c)?;
-
-        let mut addr = [0u8; 32];
-        c.read_exact(&mut addr)?;
-
-        if c.position() != c.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let action: GovernanceContractUpgrade = parse_governance(buf)?;
         Ok(GovernancePayloadUpgrade {
-            new_contract: Pubkey::new(&addr[..]),
+            new_contract: Pubkey::new_from_array(action.new_contract),
         })
     }
 }
@@ -245,8 +297,12 @@ impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
 #[cfg(feature = "no-entrypoint")]
 mod tests {
     use crate::messages::{
+        BatchToken,
+        Creator,
         GovernancePayloadUpgrade,
+        PayloadBatchTransfer,
         PayloadGovernanceRegisterChain,
+        PayloadSemiFungibleTransfer,
         PayloadTransfer,
     };
     use bridge::{
@@ -256,6 +312,19 @@ mod tests {
     use primitive_types::U256;
     use rand::RngCore;
     use solana_program::pubkey::Pubkey;
+    use wormhole_core::{
+        testing::corpus,
+        vaa::nft::{
+            BatchTransfer,
+            GovernanceContractUpgrade,
+            GovernanceRegisterChain,
+            SemiFungibleTransfer,
+            Transfer,
+            TransferV2,
+        },
+        Chain,
+        GovernanceAction,
+    };
 
     #[test]
     pub fn test_serde_transfer() {
@@ -273,12 +342,89 @@ mod tests {
             symbol: String::from("TEST"),
             uri: String::from("https://abc.abc.abc.com"),
             token_id: U256::from(1234),
+            collection: [0u8; 32],
+            seller_fee_basis_points: 0,
+            creators: vec![],
         };
 
         let mut data = transfer_original.try_to_vec().unwrap();
         let transfer_deser = PayloadTransfer::deserialize(&mut data.as_slice()).unwrap();
 
         assert_eq!(transfer_original, transfer_deser);
+        assert_eq!(data[0], 1);
+    }
+
+    #[test]
+    pub fn test_serde_transfer_v2() {
+        let transfer_original = PayloadTransfer {
+            token_address: [1u8; 32],
+            token_chain: 1,
+            to: [2u8; 32],
+            to_chain: 3,
+            name: "€".repeat(20),
+            symbol: String::from("TEST"),
+            uri: format!("https://abc.abc.abc.com/{}", "a".repeat(400)),
+            token_id: U256::from(1234),
+            collection: [3u8; 32],
+            seller_fee_basis_points: 250,
+            creators: vec![Creator {
+                address: [4u8; 32],
+                verified: true,
+                share: 100,
+            }],
+        };
+
+        let mut data = transfer_original.try_to_vec().unwrap();
+        let transfer_deser = PayloadTransfer::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(transfer_original, transfer_deser);
+        assert_eq!(data[0], 2);
+    }
+
+    #[test]
+    pub fn test_serde_batch_transfer() {
+        let transfer_original = PayloadBatchTransfer {
+            token_address: [1u8; 32],
+            token_chain: 2,
+            to: [2u8; 32],
+            to_chain: 1,
+            name: String::from("Token Token"),
+            symbol: String::from("TEST"),
+            tokens: (0..3)
+                .map(|i| BatchToken {
+                    token_id: U256::from(i),
+                    uri: format!("https://abc.abc.abc.com/{}", i),
+                })
+                .collect(),
+        };
+
+        let mut data = transfer_original.try_to_vec().unwrap();
+        let transfer_deser = PayloadBatchTransfer::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(transfer_original, transfer_deser);
+        assert_eq!(data[0], 3);
+    }
+
+    #[test]
+    pub fn test_serde_semi_fungible_transfer() {
+        let transfer_original = PayloadSemiFungibleTransfer {
+            token_address: [1u8; 32],
+            token_chain: 2,
+            to: [2u8; 32],
+            to_chain: 1,
+            name: String::from("Token Token"),
+            symbol: String::from("TEST"),
+            uri: String::from("https://abc.abc.abc.com"),
+            token_id: U256::from(1234u64),
+            amount: U256::from(1000u64),
+        };
+
+        let mut data = transfer_original.try_to_vec().unwrap();
+        let transfer_deser =
+            PayloadSemiFungibleTransfer::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(transfer_original, transfer_deser);
+        assert_eq!(data[0], 4);
     }
 
     #[test]
@@ -308,4 +454,121 @@ mod tests {
 
         assert_eq!(original, deser);
     }
+
+    /// Check that `T` accepts exactly the byte strings wormhole-core accepts, and decodes them to
+    /// the same values. `core` parses with wormhole-core and re-serializes what it accepts.
+    fn differential<T, F>(valid: &[u8], core: F)
+    where
+        T: DeserializePayload + SerializePayload,
+        F: Fn(&[u8]) -> Option<Vec<u8>>,
+    {
+        for bytes in corpus(valid) {
+            let solana = T::deserialize(&mut bytes.as_slice()).ok();
+            let solana = solana.map(|payload| payload.try_to_vec().unwrap());
+            assert_eq!(solana, core(&bytes), "payload {:?}", bytes);
+        }
+    }
+
+    #[test]
+    pub fn test_differential_transfer() {
+        let valid = PayloadTransfer {
+            token_address: [1u8; 32],
+            token_chain: 2,
+            to: [2u8; 32],
+            to_chain: 1,
+            name: String::from("Token Token"),
+            symbol: String::from("TEST"),
+            uri: String::from("https://abc.abc.abc.com/é"),
+            token_id: U256::from(1234u64),
+            collection: [0u8; 32],
+            seller_fee_basis_points: 0,
+            creators: vec![],
+        };
+        differential::<PayloadTransfer, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            Transfer::from_bytes(bytes).ok().map(|t| t.to_bytes())
+        });
+
+        let valid = PayloadTransfer {
+            uri: String::from("https://abc.abc.abc.com/é"),
+            seller_fee_basis_points: 500,
+            creators: vec![Creator {
+                address: [3u8; 32],
+                verified: false,
+                share: 100,
+            }],
+            ..valid
+        };
+        differential::<PayloadTransfer, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            TransferV2::from_versioned_bytes(bytes)
+                .ok()
+                .map(|t| t.to_versioned_bytes())
+        });
+    }
+
+    #[test]
+    pub fn test_differential_batch_transfer() {
+        let valid = PayloadBatchTransfer {
+            token_address: [1u8; 32],
+            token_chain: 2,
+            to: [2u8; 32],
+            to_chain: 1,
+            name: String::from("Token Token"),
+            symbol: String::from("TEST"),
+            tokens: vec![
+                BatchToken {
+                    token_id: U256::from(1u64),
+                    uri: String::from("https://abc.abc.abc.com/é"),
+                },
+                BatchToken {
+                    token_id: U256::from(2u64),
+                    uri: String::new(),
+                },
+            ],
+        };
+        differential::<PayloadBatchTransfer, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            BatchTransfer::from_bytes(bytes).ok().map(|t| t.to_bytes())
+        });
+    }
+
+    #[test]
+    pub fn test_differential_semi_fungible_transfer() {
+        let valid = PayloadSemiFungibleTransfer {
+            token_address: [1u8; 32],
+            token_chain: 2,
+            to: [2u8; 32],
+            to_chain: 1,
+            name: String::from("Token Token"),
+            symbol: String::from("TEST"),
+            uri: String::from("https://abc.abc.abc.com/é"),
+            token_id: U256::from(1234u64),
+            amount: U256::from(1000u64),
+        };
+        differential::<PayloadSemiFungibleTransfer, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            SemiFungibleTransfer::from_bytes(bytes)
+                .ok()
+                .map(|t| t.to_bytes())
+        });
+    }
+
+    #[test]
+    pub fn test_differential_governance() {
+        let valid = PayloadGovernanceRegisterChain {
+            chain: 2,
+            endpoint_address: [1u8; 32],
+        };
+        differential::<PayloadGovernanceRegisterChain, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            GovernanceRegisterChain::from_bytes(bytes, Some(Chain::Solana))
+                .ok()
+                .map(|(_, a)| a.to_bytes(Chain::Solana))
+        });
+
+        let valid = GovernancePayloadUpgrade {
+            new_contract: Pubkey::new_unique(),
+        };
+        differential::<GovernancePayloadUpgrade, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            GovernanceContractUpgrade::from_bytes(bytes, Some(Chain::Solana))
+                .ok()
+                .map(|(_, a)| a.to_bytes(Chain::Solana))
+        });
+    }
 }
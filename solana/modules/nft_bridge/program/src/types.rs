@@ -58,5 +58,17 @@ impl Owned for WrappedMeta {
     }
 }
 
+/// Marks a single token of a batch transfer as redeemed.
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct BatchClaimData {
+    pub claimed: bool,
+}
+
+impl Owned for BatchClaimData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
 pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
 pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
@@ -7,6 +7,7 @@ use crate::{
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
+        WrappedSemiFungibleDerivationData,
         WrappedTokenMeta,
     },
     instructions::{
@@ -393,6 +394,30 @@ pub fn wrapped_address(
     wrapped_addr.to_bytes().to_vec()
 }
 
+#[wasm_bindgen]
+pub fn wrapped_semi_fungible_address(
+    program_id: String,
+    token_address: Vec<u8>,
+    token_chain: u16,
+    token_id: Vec<u8>,
+) -> Vec<u8> {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let mut t_addr = [0u8; 32];
+    t_addr.copy_from_slice(&token_address);
+    let token_id = U256::from_big_endian(token_id.as_slice());
+
+    let wrapped_addr = WrappedMint::<'_, { AccountState::Initialized }>::key(
+        &WrappedSemiFungibleDerivationData {
+            token_address: t_addr,
+            token_chain,
+            token_id,
+        },
+        &program_id,
+    );
+
+    wrapped_addr.to_bytes().to_vec()
+}
+
 #[wasm_bindgen]
 pub fn wrapped_meta_address(program_id: String, mint_address: Vec<u8>) -> Vec<u8> {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
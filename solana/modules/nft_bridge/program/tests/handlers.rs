@@ -0,0 +1,526 @@
+#![allow(warnings)]
+
+//! Handler tests on in-memory accounts, see `solitaire::testing`. The SPL token program and the
+//! core bridge run in-process, so a transfer can be followed from the user's token account to the
+//! message the core bridge posts.
+
+use borsh::BorshSerialize;
+use bridge::{
+    accounts::{
+        Bridge,
+        BridgeConfig,
+        BridgeData,
+        FeeCollector,
+        PostedMessageData,
+    },
+    vaa::DeserializePayload,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    instruction::Instruction,
+    program_option::COption,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{
+        self,
+        clock::Clock,
+    },
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    testing::{
+        Invocation,
+        TestAccount,
+        TestRuntime,
+    },
+    AccountState,
+    Result,
+    SolitaireError,
+};
+use spl_token::state::{
+    Account,
+    AccountState as SplAccountState,
+    Mint,
+};
+use spl_token_metadata::state::{
+    Creator,
+    Data,
+    Key,
+    Metadata,
+    MAX_METADATA_LEN,
+};
+use std::str::FromStr;
+
+use nft_bridge::{
+    accounts::{
+        AuthoritySigner,
+        MintSigner,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedSemiFungibleDerivationData,
+        WrappedTokenMeta,
+    },
+    instructions::{
+        self,
+        TransferBatchToken,
+    },
+    messages::{
+        PayloadBatchTransfer,
+        PayloadSemiFungibleTransfer,
+        PayloadTransfer,
+    },
+    types::WrappedMeta,
+    TokenBridgeError,
+    TransferBatchData,
+    TransferNativeData,
+    TransferSemiFungibleData,
+    TransferWrappedData,
+};
+
+const FEE: u64 = 500;
+
+fn process(runtime: &mut TestRuntime, ix: &Instruction) -> Result<Vec<Invocation>> {
+    runtime.process(ix, |p, a, d| nft_bridge::instruction::dispatch(p, a, d))
+}
+
+/// The core bridge this program was built against.
+fn bridge_id() -> Pubkey {
+    Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap()
+}
+
+/// A runtime with a funded payer, the sysvars, SPL token, an initialized core bridge and an
+/// initialized NFT bridge.
+fn setup(program_id: &Pubkey) -> (TestRuntime, Pubkey) {
+    let mut runtime = TestRuntime::new();
+    runtime.add_program(spl_token::id(), spl_token::processor::Processor::process);
+    runtime.add_program(bridge_id(), bridge::instruction::solitaire);
+
+    let payer = Pubkey::new_unique();
+    runtime.set_account(payer, TestAccount::wallet(1_000_000_000));
+    runtime.set_account(
+        sysvar::clock::id(),
+        TestAccount::with_sysvar(&Clock {
+            unix_timestamp: 1_000,
+            ..Clock::default()
+        }),
+    );
+    runtime.set_account(
+        sysvar::rent::id(),
+        TestAccount::with_sysvar(&Rent::default()),
+    );
+
+    let fee_collector = TestAccount::wallet(Rent::default().minimum_balance(0));
+    runtime.set_account(
+        Bridge::<'_, { AccountState::Initialized }>::key(None, &bridge_id()),
+        TestAccount::with_data(
+            &bridge_id(),
+            &BridgeData {
+                guardian_set_index: 0,
+                last_lamports: fee_collector.lamports,
+                config: BridgeConfig {
+                    guardian_set_expiration_time: 3600,
+                    fee: FEE,
+                },
+            },
+        ),
+    );
+    runtime.set_account(FeeCollector::key(None, &bridge_id()), fee_collector);
+
+    process(
+        &mut runtime,
+        &instructions::initialize(*program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    (runtime, payer)
+}
+
+/// A mint without decimals with Metaplex metadata, and a token account of `owner` holding `amount`
+/// of it delegated to the NFT bridge. Returns the token account.
+fn token(
+    runtime: &mut TestRuntime,
+    program_id: &Pubkey,
+    mint: Pubkey,
+    mint_authority: Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    creators: Option<Vec<Creator>>,
+) -> Pubkey {
+    let from = Pubkey::new_unique();
+    runtime.set_account(
+        mint,
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Mint {
+                mint_authority: COption::Some(mint_authority),
+                supply: amount,
+                decimals: 0,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+        ),
+    );
+    runtime.set_account(
+        from,
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Account {
+                mint,
+                owner: *owner,
+                amount,
+                delegate: COption::Some(AuthoritySigner::key(None, program_id)),
+                state: SplAccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: amount,
+                close_authority: COption::None,
+            },
+        ),
+    );
+
+    // Metaplex allocates metadata accounts at their maximum size.
+    let metadata = Metadata {
+        key: Key::MetadataV1,
+        update_authority: Pubkey::new_unique(),
+        mint,
+        data: Data {
+            name: "Non Fungible".to_string(),
+            symbol: "NFT".to_string(),
+            uri: "https://example.com/1.json".to_string(),
+            seller_fee_basis_points: 500,
+            creators,
+        },
+        primary_sale_happened: false,
+        is_mutable: true,
+    };
+    let mut data = metadata.try_to_vec().unwrap();
+    data.resize(MAX_METADATA_LEN, 0);
+    runtime.set_account(
+        SplTokenMeta::key(
+            &SplTokenMetaDerivationData { mint },
+            &spl_token_metadata::id(),
+        ),
+        TestAccount {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: spl_token_metadata::id(),
+            executable: false,
+        },
+    );
+    from
+}
+
+/// An NFT minted outside the bridge, held by `owner`. Returns the mint and the token account.
+fn native_nft(
+    runtime: &mut TestRuntime,
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    creators: Option<Vec<Creator>>,
+) -> (Pubkey, Pubkey) {
+    let mint = Pubkey::new_unique();
+    let from = token(
+        runtime,
+        program_id,
+        mint,
+        Pubkey::new_unique(),
+        owner,
+        1,
+        creators,
+    );
+    (mint, from)
+}
+
+/// A token of another chain wrapped at `mint`, `amount` of which are held by `owner`. Returns the
+/// token account.
+fn wrapped(
+    runtime: &mut TestRuntime,
+    program_id: &Pubkey,
+    mint: Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let from = token(
+        runtime,
+        program_id,
+        mint,
+        MintSigner::key(None, program_id),
+        owner,
+        amount,
+        None,
+    );
+    runtime.set_account(
+        WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+            &WrappedMetaDerivationData { mint_key: mint },
+            program_id,
+        ),
+        TestAccount::with_data(
+            program_id,
+            &WrappedMeta {
+                chain: 2,
+                token_address: [2u8; 32],
+                token_id: U256::from(7).0,
+            },
+        ),
+    );
+    from
+}
+
+/// The transfer posted to the core bridge in `message`, and its payload ID.
+fn posted_transfer(runtime: &TestRuntime, message: &Pubkey) -> (u8, PayloadTransfer) {
+    let posted: PostedMessageData = runtime.account(message).read().unwrap();
+    let payload = &posted.0.payload;
+    (
+        payload[0],
+        PayloadTransfer::deserialize(&mut payload.as_slice()).unwrap(),
+    )
+}
+
+#[test]
+fn transfer_native_with_creators() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+    let creators = vec![Creator {
+        address: Pubkey::new_unique(),
+        verified: true,
+        share: 100,
+    }];
+    let transfer = |runtime: &mut TestRuntime, target_chain| {
+        let (mint, from) = native_nft(
+            runtime,
+            &program_id,
+            &Pubkey::new_unique(),
+            Some(creators.clone()),
+        );
+        let message = Pubkey::new_unique();
+        let ix = instructions::transfer_native(
+            program_id,
+            bridge_id(),
+            payer,
+            message,
+            from,
+            mint,
+            TransferNativeData {
+                nonce: 1,
+                target_address: [9u8; 32],
+                target_chain,
+            },
+        )
+        .unwrap();
+        process(runtime, &ix).unwrap();
+        posted_transfer(runtime, &message)
+    };
+
+    // Terra redeems version 2, which carries the royalty and creators.
+    let (version, payload) = transfer(&mut runtime, 3);
+    assert_eq!(version, 2);
+    assert_eq!(payload.seller_fee_basis_points, 500);
+    assert_eq!(payload.creators.len(), 1);
+    assert_eq!(payload.creators[0].address, creators[0].address.to_bytes());
+
+    // The EVM bridges only redeem version 1, so they are left out.
+    let (version, payload) = transfer(&mut runtime, 2);
+    assert_eq!(version, 1);
+    assert_eq!(payload.token_address, [1u8; 32]);
+    assert_eq!(payload.token_chain, CHAIN_ID_SOLANA);
+    assert_eq!(payload.uri, "https://example.com/1.json");
+    assert_eq!(payload.seller_fee_basis_points, 0);
+    assert!(payload.creators.is_empty());
+    assert_eq!(payload.to_chain, 2);
+}
+
+#[test]
+fn transfer_batch_target_chain() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+    let owner = Pubkey::new_unique();
+    let tokens: Vec<TransferBatchToken> = (0..2)
+        .map(|_| {
+            let (mint, from) = native_nft(&mut runtime, &program_id, &owner, None);
+            TransferBatchToken { from, mint }
+        })
+        .collect();
+    let transfer = |target_chain| {
+        let message = Pubkey::new_unique();
+        let ix = instructions::transfer_batch(
+            program_id,
+            bridge_id(),
+            payer,
+            message,
+            owner,
+            &tokens,
+            TransferBatchData {
+                nonce: 1,
+                target_address: [9u8; 32],
+                target_chain,
+            },
+        )
+        .unwrap();
+        (ix, message)
+    };
+
+    // Only Terra redeems batches, the EVM bridges would reject them.
+    for target_chain in [1, 2, 4] {
+        let (ix, _) = transfer(target_chain);
+        assert!(matches!(
+            process(&mut runtime, &ix),
+            Err(SolitaireError::Custom(e)) if e == TokenBridgeError::InvalidChain as u64
+        ));
+    }
+
+    let (ix, message) = transfer(3);
+    process(&mut runtime, &ix).unwrap();
+    let posted: PostedMessageData = runtime.account(&message).read().unwrap();
+    let payload = PayloadBatchTransfer::deserialize(&mut posted.0.payload.as_slice()).unwrap();
+    assert_eq!(payload.tokens.len(), 2);
+    assert_eq!(payload.to_chain, 3);
+}
+
+/// The mints of the wrapped NFT and semi-fungible token of the same origin.
+fn wrapped_mints(program_id: &Pubkey) -> (Pubkey, Pubkey) {
+    let nft = WrappedMint::<'_, { AccountState::Initialized }>::key(
+        &WrappedDerivationData {
+            token_chain: 2,
+            token_address: [2u8; 32],
+            token_id: U256::from(7),
+        },
+        program_id,
+    );
+    let sft = WrappedMint::<'_, { AccountState::Initialized }>::key(
+        &WrappedSemiFungibleDerivationData {
+            token_chain: 2,
+            token_address: [2u8; 32],
+            token_id: U256::from(7),
+        },
+        program_id,
+    );
+    (nft, sft)
+}
+
+#[test]
+fn transfer_semi_fungible_wrapped() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+    let owner = Pubkey::new_unique();
+    let (nft, sft) = wrapped_mints(&program_id);
+    assert_ne!(nft, sft);
+    let nft_from = wrapped(&mut runtime, &program_id, nft, &owner, 1);
+    let sft_from = wrapped(&mut runtime, &program_id, sft, &owner, 10);
+    let transfer = |from, mint, target_chain| {
+        let message = Pubkey::new_unique();
+        let ix = instructions::transfer_semi_fungible(
+            program_id,
+            bridge_id(),
+            payer,
+            message,
+            from,
+            owner,
+            mint,
+            TransferSemiFungibleData {
+                nonce: 1,
+                amount: 4,
+                target_address: [9u8; 32],
+                target_chain,
+            },
+        )
+        .unwrap();
+        (ix, message)
+    };
+
+    // Only Terra redeems semi-fungible transfers, the EVM bridges would reject them.
+    let (ix, _) = transfer(sft_from, sft, 2);
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::InvalidChain as u64
+    ));
+
+    // A wrapped NFT is not sent as copies of a semi-fungible token.
+    let (ix, _) = transfer(nft_from, nft, 3);
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::InvalidDerive(..))
+    ));
+
+    let (ix, message) = transfer(sft_from, sft, 3);
+    process(&mut runtime, &ix).unwrap();
+    let posted: PostedMessageData = runtime.account(&message).read().unwrap();
+    let payload =
+        PayloadSemiFungibleTransfer::deserialize(&mut posted.0.payload.as_slice()).unwrap();
+    assert_eq!(payload.token_chain, 2);
+    assert_eq!(payload.token_address, [2u8; 32]);
+    assert_eq!(payload.token_id, U256::from(7));
+    assert_eq!(payload.amount, U256::from(4));
+    assert_eq!(payload.to_chain, 3);
+    let from: Account = runtime.account(&sft_from).unpack().unwrap();
+    assert_eq!(from.amount, 6);
+}
+
+#[test]
+fn transfer_wrapped_semi_fungible() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup(&program_id);
+    let owner = Pubkey::new_unique();
+    let (nft, sft) = wrapped_mints(&program_id);
+    let nft_from = wrapped(&mut runtime, &program_id, nft, &owner, 1);
+    let sft_from = wrapped(&mut runtime, &program_id, sft, &owner, 1);
+    let message = Pubkey::new_unique();
+    let ix = instructions::transfer_wrapped(
+        program_id,
+        bridge_id(),
+        payer,
+        message,
+        nft_from,
+        owner,
+        2,
+        [2u8; 32],
+        U256::from(7),
+        TransferWrappedData {
+            nonce: 1,
+            target_address: [9u8; 32],
+            target_chain: 3,
+        },
+    )
+    .unwrap();
+
+    // The same instruction with the accounts of the semi-fungible token, which has a meta too.
+    let mut swapped = ix.clone();
+    let keys = [
+        (nft_from, sft_from),
+        (nft, sft),
+        (
+            WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+                &WrappedMetaDerivationData { mint_key: nft },
+                &program_id,
+            ),
+            WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+                &WrappedMetaDerivationData { mint_key: sft },
+                &program_id,
+            ),
+        ),
+        (
+            SplTokenMeta::key(
+                &SplTokenMetaDerivationData { mint: nft },
+                &spl_token_metadata::id(),
+            ),
+            SplTokenMeta::key(
+                &SplTokenMetaDerivationData { mint: sft },
+                &spl_token_metadata::id(),
+            ),
+        ),
+    ];
+    for account in swapped.accounts.iter_mut() {
+        if let Some((_, to)) = keys.iter().find(|(from, _)| *from == account.pubkey) {
+            account.pubkey = *to;
+        }
+    }
+    assert!(matches!(
+        process(&mut runtime, &swapped),
+        Err(SolitaireError::InvalidDerive(..))
+    ));
+
+    process(&mut runtime, &ix).unwrap();
+    let (_, payload) = posted_transfer(&runtime, &message);
+    assert_eq!(payload.token_chain, 2);
+    assert_eq!(payload.token_id, U256::from(7));
+}
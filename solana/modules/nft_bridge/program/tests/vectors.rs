@@ -0,0 +1,196 @@
+#![allow(warnings)]
+
+//! Runs the shared wire format vectors in `sdk/vectors` through the NFT bridge payload types.
+//! wormhole-core and the Terra contracts load the same file.
+
+use primitive_types::U256;
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+
+use bridge::{
+    DeserializePayload,
+    SerializePayload,
+};
+use nft_bridge::messages::{
+    BatchToken,
+    Creator,
+    GovernancePayloadUpgrade,
+    PayloadBatchTransfer,
+    PayloadGovernanceRegisterChain,
+    PayloadSemiFungibleTransfer,
+    PayloadTransfer,
+};
+
+const VECTORS: &str = include_str!("../../../../../sdk/vectors/v1.json");
+
+/// The vectors of the given type as (name, bytes, valid, fields, canonical encoding).
+fn vectors(kind: &str) -> Vec<(String, Vec<u8>, bool, Value, Vec<u8>)> {
+    let file: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(file["version"], 1);
+    file["vectors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["type"] == kind)
+        .map(|v| {
+            let bytes = hex::decode(v["hex"].as_str().unwrap()).unwrap();
+            let canonical = match v["canonical"].as_str() {
+                Some(canonical) => hex::decode(canonical).unwrap(),
+                None => bytes.clone(),
+            };
+            let name = v["name"].as_str().unwrap().to_string();
+            (name, bytes, v["valid"].as_bool().unwrap(), v["fields"].clone(), canonical)
+        })
+        .collect()
+}
+
+fn bytes32(fields: &Value, key: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hex::decode(fields[key].as_str().unwrap()).unwrap());
+    out
+}
+
+fn int(fields: &Value, key: &str) -> u64 {
+    fields[key].as_u64().unwrap()
+}
+
+fn u256(fields: &Value, key: &str) -> U256 {
+    U256::from_big_endian(&bytes32(fields, key))
+}
+
+fn string(fields: &Value, key: &str) -> String {
+    fields[key].as_str().unwrap().to_string()
+}
+
+/// Check a payload type against the vectors of `kind`, `expected` builds the payload from the
+/// fields of valid vectors.
+fn check<T, F>(kind: &str, expected: F)
+where
+    T: DeserializePayload + SerializePayload + PartialEq + std::fmt::Debug,
+    F: Fn(&Value) -> T,
+{
+    check_encoding(kind, expected, |encoded, canonical| encoded == canonical);
+}
+
+/// Like `check`, for governance payloads. These are always serialized addressed to Solana, so the
+/// target chain in the header is not compared.
+fn check_governance<T, F>(kind: &str, expected: F)
+where
+    T: DeserializePayload + SerializePayload + PartialEq + std::fmt::Debug,
+    F: Fn(&Value) -> T,
+{
+    check_encoding(kind, expected, |encoded, canonical| {
+        encoded[..33] == canonical[..33] && encoded[35..] == canonical[35..]
+    });
+}
+
+fn check_encoding<T, F, E>(kind: &str, expected: F, same_encoding: E)
+where
+    T: DeserializePayload + SerializePayload + PartialEq + std::fmt::Debug,
+    F: Fn(&Value) -> T,
+    E: Fn(&[u8], &[u8]) -> bool,
+{
+    let vectors = vectors(kind);
+    assert!(!vectors.is_empty(), "no vectors for {}", kind);
+    for (name, data, valid, f, canonical) in vectors {
+        match T::deserialize(&mut data.as_slice()) {
+            Ok(payload) => {
+                assert!(valid, "{}", name);
+                assert_eq!(payload, expected(&f), "{}", name);
+                let encoded = payload.try_to_vec().unwrap();
+                assert!(same_encoding(&encoded, &canonical), "{}", name);
+            }
+            Err(_) => assert!(!valid, "{}", name),
+        }
+    }
+}
+
+#[test]
+fn test_transfer_vectors() {
+    check("nft_transfer", |f| PayloadTransfer {
+        token_address: bytes32(f, "nft_address"),
+        token_chain: int(f, "nft_chain") as u16,
+        symbol: string(f, "symbol"),
+        name: string(f, "name"),
+        token_id: u256(f, "token_id"),
+        uri: string(f, "uri"),
+        collection: [0u8; 32],
+        seller_fee_basis_points: 0,
+        creators: vec![],
+        to: bytes32(f, "to"),
+        to_chain: int(f, "to_chain") as u16,
+    });
+}
+
+#[test]
+fn test_transfer_v2_vectors() {
+    check("nft_transfer_v2", |f| PayloadTransfer {
+        token_address: bytes32(f, "nft_address"),
+        token_chain: int(f, "nft_chain") as u16,
+        symbol: string(f, "symbol"),
+        name: string(f, "name"),
+        token_id: u256(f, "token_id"),
+        uri: string(f, "uri"),
+        collection: bytes32(f, "collection"),
+        seller_fee_basis_points: int(f, "seller_fee_basis_points") as u16,
+        creators: f["creators"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| Creator {
+                address: bytes32(c, "address"),
+                verified: c["verified"].as_bool().unwrap(),
+                share: int(c, "share") as u8,
+            })
+            .collect(),
+        to: bytes32(f, "to"),
+        to_chain: int(f, "to_chain") as u16,
+    });
+}
+
+#[test]
+fn test_batch_transfer_vectors() {
+    check("nft_batch_transfer", |f| PayloadBatchTransfer {
+        token_address: bytes32(f, "nft_address"),
+        token_chain: int(f, "nft_chain") as u16,
+        symbol: string(f, "symbol"),
+        name: string(f, "name"),
+        tokens: f["tokens"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| BatchToken {
+                token_id: u256(t, "token_id"),
+                uri: string(t, "uri"),
+            })
+            .collect(),
+        to: bytes32(f, "to"),
+        to_chain: int(f, "to_chain") as u16,
+    });
+}
+
+#[test]
+fn test_semi_fungible_transfer_vectors() {
+    check("nft_semi_fungible_transfer", |f| PayloadSemiFungibleTransfer {
+        token_address: bytes32(f, "nft_address"),
+        token_chain: int(f, "nft_chain") as u16,
+        symbol: string(f, "symbol"),
+        name: string(f, "name"),
+        token_id: u256(f, "token_id"),
+        amount: u256(f, "amount"),
+        uri: string(f, "uri"),
+        to: bytes32(f, "to"),
+        to_chain: int(f, "to_chain") as u16,
+    });
+}
+
+#[test]
+fn test_governance_vectors() {
+    check_governance("nft_register_chain", |f| PayloadGovernanceRegisterChain {
+        chain: int(f, "emitter_chain") as u16,
+        endpoint_address: bytes32(f, "endpoint_address"),
+    });
+    check_governance("nft_contract_upgrade", |f| GovernancePayloadUpgrade {
+        new_contract: Pubkey::new_from_array(bytes32(f, "new_contract")),
+    });
+}
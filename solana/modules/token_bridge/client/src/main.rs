@@ -1,4 +1,3 @@
-
 #![feature(adt_const_params)]
 #![allow(warnings)]
 
@@ -9,6 +8,10 @@ use std::{
 };
 
 use borsh::BorshDeserialize;
+use bridge::{
+    vaa::DeserializePayload,
+    CHAIN_ID_SOLANA,
+};
 use clap::{
     crate_description,
     crate_name,
@@ -17,6 +20,7 @@ use clap::{
     App,
     AppSettings,
     Arg,
+    ArgMatches,
     SubCommand,
 };
 use hex;
@@ -36,7 +40,10 @@ use solana_client::{
     rpc_client::RpcClient,
     rpc_config::RpcSendTransactionConfig,
 };
-use solana_program::account_info::AccountInfo;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::Instruction,
+};
 use solana_sdk::{
     commitment_config::{
         CommitmentConfig,
@@ -59,6 +66,23 @@ use solitaire::{
     Info,
 };
 use solitaire_client::Derive;
+use token_bridge::{
+    accounts::{
+        AuthoritySigner,
+        EmitterAccount,
+    },
+    messages::{
+        PayloadAssetMeta,
+        PayloadTransfer,
+    },
+    CompleteNativeData,
+    CompleteWrappedData,
+    CreateWrappedData,
+    TransferNativeData,
+    TransferWrappedData,
+};
+
+mod vaa;
 
 struct Config {
     rpc_client: RpcClient,
@@ -132,6 +156,257 @@ fn command_create_meta(
     Ok(Some(transaction))
 }
 
+fn command_attest(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    mint: &Pubkey,
+    nonce: u32,
+) -> CommmandResult {
+    println!("Attesting mint {}", mint);
+
+    let message = Keypair::new();
+    let ix = token_bridge::instructions::attest(
+        *bridge,
+        *core_bridge,
+        config.owner.pubkey(),
+        message.pubkey(),
+        *mint,
+        nonce,
+    )
+    .map_err(|e| format!("{:?}", e))?;
+    print_message(bridge, core_bridge, &message);
+
+    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
+    let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+    transaction.sign(
+        &[&config.fee_payer, &config.owner, &message],
+        recent_blockhash,
+    );
+    Ok(Some(transaction))
+}
+
+fn command_transfer_native(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    from: &Pubkey,
+    mint: &Pubkey,
+    data: TransferNativeData,
+) -> CommmandResult {
+    println!(
+        "Transferring {} of mint {} from {}",
+        data.amount, mint, from
+    );
+
+    let message = Keypair::new();
+    let approve_ix = approve_authority_signer(config, bridge, from, data.amount)?;
+    let ix = token_bridge::instructions::transfer_native(
+        *bridge,
+        *core_bridge,
+        config.owner.pubkey(),
+        message.pubkey(),
+        *from,
+        *mint,
+        data,
+    )
+    .map_err(|e| format!("{:?}", e))?;
+    print_message(bridge, core_bridge, &message);
+
+    let mut transaction =
+        Transaction::new_with_payer(&[approve_ix, ix], Some(&config.fee_payer.pubkey()));
+    let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+    transaction.sign(
+        &[&config.fee_payer, &config.owner, &message],
+        recent_blockhash,
+    );
+    Ok(Some(transaction))
+}
+
+fn command_transfer_wrapped(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    from: &Pubkey,
+    token_chain: u16,
+    token_address: [u8; 32],
+    data: TransferWrappedData,
+) -> CommmandResult {
+    println!(
+        "Transferring {} of wrapped token {}:{} from {}",
+        data.amount,
+        token_chain,
+        hex::encode(token_address),
+        from
+    );
+
+    let message = Keypair::new();
+    let approve_ix = approve_authority_signer(config, bridge, from, data.amount)?;
+    let ix = token_bridge::instructions::transfer_wrapped(
+        *bridge,
+        *core_bridge,
+        config.owner.pubkey(),
+        message.pubkey(),
+        *from,
+        config.owner.pubkey(),
+        token_chain,
+        token_address,
+        data,
+    )
+    .map_err(|e| format!("{:?}", e))?;
+    print_message(bridge, core_bridge, &message);
+
+    let mut transaction =
+        Transaction::new_with_payer(&[approve_ix, ix], Some(&config.fee_payer.pubkey()));
+    let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+    transaction.sign(
+        &[&config.fee_payer, &config.owner, &message],
+        recent_blockhash,
+    );
+    Ok(Some(transaction))
+}
+
+/// Post a transfer VAA and complete it, as a native transfer if the token is from Solana and as a
+/// wrapped one otherwise.
+fn command_redeem(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    vaa: &[u8],
+    fee_recipient: Option<Pubkey>,
+) -> CommmandResult {
+    let (message_key, vaa) = vaa::post_vaa(config, core_bridge, vaa)?;
+    let payload = <PayloadTransfer as DeserializePayload>::deserialize(&mut vaa.payload.as_slice())
+        .map_err(|e| format!("VAA is not a token transfer: {:?}", e))?;
+    if payload.to_chain != CHAIN_ID_SOLANA {
+        return Err(format!("Transfer is addressed to chain {}", payload.to_chain).into());
+    }
+
+    let to = Pubkey::new(&payload.to);
+    let ix = if payload.token_chain == CHAIN_ID_SOLANA {
+        let mint = Pubkey::new(&payload.token_address);
+        println!("Completing native transfer of mint {} to {}", mint, to);
+        token_bridge::instructions::complete_native(
+            *bridge,
+            *core_bridge,
+            config.owner.pubkey(),
+            message_key,
+            vaa,
+            to,
+            fee_recipient,
+            mint,
+            CompleteNativeData {},
+        )
+    } else {
+        println!(
+            "Completing wrapped transfer of token {}:{} to {}",
+            payload.token_chain,
+            hex::encode(payload.token_address),
+            to
+        );
+        token_bridge::instructions::complete_wrapped(
+            *bridge,
+            *core_bridge,
+            config.owner.pubkey(),
+            message_key,
+            vaa,
+            payload,
+            to,
+            fee_recipient,
+            CompleteWrappedData {},
+        )
+    }
+    .map_err(|e| format!("{:?}", e))?;
+
+    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
+    let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+    transaction.sign(&[&config.fee_payer, &config.owner], recent_blockhash);
+    Ok(Some(transaction))
+}
+
+fn command_create_wrapped(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    vaa: &[u8],
+) -> CommmandResult {
+    let (message_key, vaa) = vaa::post_vaa(config, core_bridge, vaa)?;
+    let payload =
+        <PayloadAssetMeta as DeserializePayload>::deserialize(&mut vaa.payload.as_slice())
+            .map_err(|e| format!("VAA is not an attestation: {:?}", e))?;
+    println!(
+        "Creating wrapped {} ({}) for token {}:{}",
+        payload.name,
+        payload.symbol,
+        payload.token_chain,
+        hex::encode(payload.token_address)
+    );
+
+    let ix = token_bridge::instructions::create_wrapped(
+        *bridge,
+        *core_bridge,
+        config.owner.pubkey(),
+        message_key,
+        vaa,
+        payload,
+        CreateWrappedData {},
+    )
+    .map_err(|e| format!("{:?}", e))?;
+
+    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
+    let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+    transaction.sign(&[&config.fee_payer, &config.owner], recent_blockhash);
+    Ok(Some(transaction))
+}
+
+/// Transfers take tokens out of `from` through the authority signer, which the owner has to
+/// approve as a delegate first.
+fn approve_authority_signer(
+    config: &Config,
+    bridge: &Pubkey,
+    from: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, Error> {
+    Ok(spl_token::instruction::approve(
+        &spl_token::id(),
+        from,
+        &AuthoritySigner::key(None, bridge),
+        &config.owner.pubkey(),
+        &[],
+        amount,
+    )?)
+}
+
+/// Print the accounts to look the resulting VAA up by.
+fn print_message(bridge: &Pubkey, core_bridge: &Pubkey, message: &Keypair) {
+    let emitter = EmitterAccount::key(None, bridge);
+    let sequence = bridge::accounts::Sequence::key(
+        &bridge::accounts::SequenceDerivationData {
+            emitter_key: emitter,
+        },
+        core_bridge,
+    );
+    println!("Message account: {}", message.pubkey());
+    println!("Emitter: {} (sequence account {})", emitter, sequence);
+}
+
+fn send_transaction(config: &Config, transaction: &Transaction) -> Result<(), Error> {
+    let signature = config
+        .rpc_client
+        .send_and_confirm_transaction_with_spinner_and_config(
+            transaction,
+            config.commitment_config,
+            RpcSendTransactionConfig {
+                skip_preflight: true,
+                preflight_commitment: None,
+                encoding: None,
+                max_retries: None,
+            },
+        )?;
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
 fn main() {
     let matches = App::new(crate_name!())
         .about(crate_description!())
@@ -275,6 +550,107 @@ fn main() {
                         .help("URI of the token metadata"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("attest")
+                .about("Attest a token so it can be wrapped on other chains")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(
+                    Arg::with_name("mint")
+                        .value_name("MINT")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Mint of the token to attest"),
+                )
+                .arg(nonce_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer-native")
+                .about("Transfer a token native to Solana to another chain")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(
+                    Arg::with_name("from")
+                        .value_name("FROM")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Token account to transfer from, owned by the owner"),
+                )
+                .arg(
+                    Arg::with_name("mint")
+                        .value_name("MINT")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(4)
+                        .required(true)
+                        .help("Mint of the token"),
+                )
+                .args(&transfer_args(5))
+                .arg(nonce_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer-wrapped")
+                .about("Transfer a wrapped token back to another chain")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(
+                    Arg::with_name("from")
+                        .value_name("FROM")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Token account to transfer from, owned by the owner"),
+                )
+                .arg(
+                    Arg::with_name("token_chain")
+                        .value_name("TOKEN_CHAIN")
+                        .validator(is_u16)
+                        .takes_value(true)
+                        .index(4)
+                        .required(true)
+                        .help("Chain ID the token is native to"),
+                )
+                .arg(
+                    Arg::with_name("token_address")
+                        .value_name("TOKEN_ADDRESS")
+                        .validator(is_address)
+                        .takes_value(true)
+                        .index(5)
+                        .required(true)
+                        .help("Address of the token on its native chain, as hex"),
+                )
+                .args(&transfer_args(6))
+                .arg(nonce_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("redeem")
+                .about("Post a transfer VAA and complete the transfer")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(vaa_arg())
+                .arg(
+                    Arg::with_name("fee_recipient")
+                        .long("fee-recipient")
+                        .value_name("FEE_RECIPIENT")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .help(
+                            "Token account receiving the relayer fee. Defaults to the recipient.",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("create-wrapped")
+                .about("Post an attestation VAA and create the wrapped token")
+                .arg(bridge_arg())
+                .arg(core_bridge_arg())
+                .arg(vaa_arg()),
+        )
         .get_matches();
 
     let config = {
@@ -338,7 +714,8 @@ fn main() {
             )
             .0;
             let meta_info = config.rpc_client.get_account(&meta_acc).unwrap();
-            let meta_info = spl_token_metadata::state::Metadata::from_bytes(&meta_info.data).unwrap();
+            let meta_info =
+                spl_token_metadata::state::Metadata::from_bytes(&meta_info.data).unwrap();
             println!("Key: {:?}", meta_info.key);
             println!("Mint: {}", meta_info.mint);
             println!("Metadata Key: {}", meta_acc);
@@ -350,24 +727,74 @@ fn main() {
 
             Ok(None)
         }
+        ("attest", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+            let nonce: u32 = value_of(arg_matches, "nonce").unwrap();
+
+            command_attest(&config, &bridge, &core_bridge, &mint, nonce)
+        }
+        ("transfer-native", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let from = pubkey_of(arg_matches, "from").unwrap();
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+            let data = TransferNativeData {
+                nonce: value_of(arg_matches, "nonce").unwrap(),
+                amount: value_of(arg_matches, "amount").unwrap(),
+                fee: value_of(arg_matches, "fee").unwrap(),
+                target_address: address_of(arg_matches, "target_address"),
+                target_chain: value_of(arg_matches, "target_chain").unwrap(),
+            };
+
+            command_transfer_native(&config, &bridge, &core_bridge, &from, &mint, data)
+        }
+        ("transfer-wrapped", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let from = pubkey_of(arg_matches, "from").unwrap();
+            let token_chain: u16 = value_of(arg_matches, "token_chain").unwrap();
+            let token_address = address_of(arg_matches, "token_address");
+            let data = TransferWrappedData {
+                nonce: value_of(arg_matches, "nonce").unwrap(),
+                amount: value_of(arg_matches, "amount").unwrap(),
+                fee: value_of(arg_matches, "fee").unwrap(),
+                target_address: address_of(arg_matches, "target_address"),
+                target_chain: value_of(arg_matches, "target_chain").unwrap(),
+            };
+
+            command_transfer_wrapped(
+                &config,
+                &bridge,
+                &core_bridge,
+                &from,
+                token_chain,
+                token_address,
+                data,
+            )
+        }
+        ("redeem", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let fee_recipient = pubkey_of(arg_matches, "fee_recipient");
+
+            vaa::read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_redeem(&config, &bridge, &core_bridge, &vaa, fee_recipient))
+        }
+        ("create-wrapped", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+
+            vaa::read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_create_wrapped(&config, &bridge, &core_bridge, &vaa))
+        }
 
         _ => unreachable!(),
     }
     .and_then(|transaction| {
         if let Some(transaction) = transaction {
-            let signature = config
-                .rpc_client
-                .send_and_confirm_transaction_with_spinner_and_config(
-                    &transaction,
-                    config.commitment_config,
-                    RpcSendTransactionConfig {
-                        skip_preflight: true,
-                        preflight_commitment: None,
-                        encoding: None,
-                        max_retries: None,
-                    },
-                )?;
-            println!("Signature: {}", signature);
+            send_transaction(&config, &transaction)?;
         }
         Ok(())
     })
@@ -377,6 +804,101 @@ fn main() {
     });
 }
 
+fn bridge_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("bridge")
+        .long("bridge")
+        .value_name("BRIDGE_KEY")
+        .validator(is_pubkey_or_keypair)
+        .takes_value(true)
+        .index(1)
+        .required(true)
+        .help("Specify the token bridge program address")
+}
+
+fn core_bridge_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("core-bridge")
+        .validator(is_pubkey_or_keypair)
+        .value_name("CORE_BRIDGE_KEY")
+        .takes_value(true)
+        .index(2)
+        .required(true)
+        .help("Address of the Wormhole core bridge program")
+}
+
+fn nonce_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("nonce")
+        .long("nonce")
+        .value_name("NONCE")
+        .validator(is_u32)
+        .takes_value(true)
+        .default_value("0")
+        .help("Nonce of the message")
+}
+
+fn vaa_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("vaa")
+        .value_name("VAA_FILE")
+        .takes_value(true)
+        .index(3)
+        .default_value("-")
+        .help("File with the VAA as hex, base64 or bytes. Reads standard input by default.")
+}
+
+/// The amount and recipient of a transfer, as positional arguments starting at `index`.
+fn transfer_args<'a, 'b>(index: u64) -> [Arg<'a, 'b>; 4] {
+    [
+        Arg::with_name("amount")
+            .value_name("AMOUNT")
+            .validator(is_u64)
+            .takes_value(true)
+            .index(index)
+            .required(true)
+            .help("Amount to transfer, in the smallest unit of the token"),
+        Arg::with_name("target_chain")
+            .value_name("TARGET_CHAIN")
+            .validator(is_u16)
+            .takes_value(true)
+            .index(index + 1)
+            .required(true)
+            .help("Chain ID to transfer to"),
+        Arg::with_name("target_address")
+            .value_name("TARGET_ADDRESS")
+            .validator(is_address)
+            .takes_value(true)
+            .index(index + 2)
+            .required(true)
+            .help("Recipient on the target chain, as hex"),
+        Arg::with_name("fee")
+            .long("fee")
+            .value_name("FEE")
+            .validator(is_u64)
+            .takes_value(true)
+            .default_value("0")
+            .help("Part of the amount paid to the relayer redeeming the transfer"),
+    ]
+}
+
+/// A hex address of up to 32 bytes, left padded with zeroes.
+fn address_of(matches: &ArgMatches, name: &str) -> [u8; 32] {
+    let value = matches.value_of(name).unwrap();
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value)).unwrap();
+    let mut address = [0u8; 32];
+    address[32 - bytes.len()..].copy_from_slice(&bytes);
+    address
+}
+
+pub fn is_address<T>(value: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    let value = value.as_ref();
+    match hex::decode(value.strip_prefix("0x").unwrap_or(value)) {
+        Ok(bytes) if bytes.len() <= 32 => Ok(()),
+        Ok(_) => Err("Address is longer than 32 bytes".to_string()),
+        Err(e) => Err(format!("{}", e)),
+    }
+}
+
 pub fn is_u8<T>(amount: T) -> Result<(), String>
 where
     T: AsRef<str> + Display,
@@ -391,6 +913,20 @@ where
     }
 }
 
+pub fn is_u16<T>(amount: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    if amount.as_ref().parse::<u16>().is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unable to parse input amount as integer, provided: {}",
+            amount
+        ))
+    }
+}
+
 pub fn is_u32<T>(amount: T) -> Result<(), String>
 where
     T: AsRef<str> + Display,
@@ -0,0 +1,206 @@
+//! Reading VAAs, checking their signatures against the guardian set on chain, and posting them to
+//! the core bridge ahead of the token bridge instructions that consume them.
+
+use std::{
+    io::Read,
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use bridge::{
+    accounts::{
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        PostedVAA,
+        PostedVAADerivationData,
+    },
+    instructions::hash_vaa,
+    vaa::{
+        SignatureItem,
+        VAA,
+    },
+    PostVAAData,
+};
+use libsecp256k1::{
+    recover,
+    Message,
+    RecoveryId,
+    Signature,
+};
+use sha3::Digest;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{
+        Keypair,
+        Signer,
+    },
+    transaction::Transaction,
+};
+use solitaire::{
+    processors::{
+        discriminator::deserialize_account,
+        seeded::Seeded,
+    },
+    AccountState,
+};
+
+use crate::{
+    send_transaction,
+    Config,
+    Error,
+};
+
+/// Read a VAA from `path`, or from standard input if it is `-`. The VAA can be hex, base64 or
+/// raw bytes.
+pub(crate) fn read_vaa(path: &str) -> Result<Vec<u8>, Error> {
+    let mut data = Vec::new();
+    if path == "-" {
+        std::io::stdin().read_to_end(&mut data)?;
+    } else {
+        data = std::fs::read(path)?;
+    }
+
+    if let Ok(text) = std::str::from_utf8(&data) {
+        let text = text.trim();
+        if let Ok(bytes) = hex::decode(text.strip_prefix("0x").unwrap_or(text)) {
+            return Ok(bytes);
+        }
+        if let Ok(bytes) = base64::decode(text) {
+            return Ok(bytes);
+        }
+    }
+    Ok(data)
+}
+
+/// Check that `vaa` is signed by a quorum of an active guardian set, the same checks `post_vaa`
+/// makes on chain, and map the signatures to the guardian keys `verify_signatures` expects.
+pub(crate) fn verify_vaa(
+    guardian_set: &GuardianSetData,
+    vaa: &VAA,
+    hash: &[u8; 32],
+) -> Result<Vec<SignatureItem>, Error> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    if guardian_set.expiration_time != 0 && (guardian_set.expiration_time as u64) < now {
+        return Err(format!("Guardian set {} has expired", guardian_set.index).into());
+    }
+
+    // Guardians sign the hash of the body hash.
+    let digest = sha3::Keccak256::digest(hash);
+    let message = Message::parse_slice(&digest)?;
+
+    let mut signatures = Vec::new();
+    for s in vaa.signatures.iter() {
+        let key = guardian_set
+            .keys
+            .get(s.guardian_index as usize)
+            .ok_or_else(|| format!("No guardian {} in guardian set", s.guardian_index))?;
+        if signatures
+            .iter()
+            .any(|item: &SignatureItem| item.index == s.guardian_index)
+        {
+            return Err(format!("Guardian {} signed twice", s.guardian_index).into());
+        }
+
+        let signature = Signature::parse_standard_slice(&s.signature[..64])?;
+        let recovery_id = RecoveryId::parse(s.signature[64])?;
+        let signer = recover(&message, &signature, &recovery_id)?;
+        let address = sha3::Keccak256::digest(&signer.serialize()[1..]);
+        if address[12..] != key[..] {
+            return Err(format!("Invalid signature by guardian {}", s.guardian_index).into());
+        }
+
+        signatures.push(SignatureItem {
+            signature: s.signature.clone(),
+            key: *key,
+            index: s.guardian_index,
+        });
+    }
+
+    // Fixed point two thirds plus one, as computed by `post_vaa`.
+    let quorum = (guardian_set.keys.len() * 10 / 3) * 2 / 10 + 1;
+    if signatures.len() < quorum {
+        return Err(format!(
+            "VAA has {} signatures, {} required",
+            signatures.len(),
+            quorum
+        )
+        .into());
+    }
+    Ok(signatures)
+}
+
+/// Verify `vaa` and post it to `core_bridge`, unless it already is. Returns the posted VAA account
+/// along with the VAA, which the token bridge instructions take as `message_key` and `vaa`.
+pub(crate) fn post_vaa(
+    config: &Config,
+    core_bridge: &Pubkey,
+    data: &[u8],
+) -> Result<(Pubkey, PostVAAData), Error> {
+    let vaa = VAA::deserialize(data)?;
+    let post_data: PostVAAData = vaa.clone().into();
+    let hash = hash_vaa(&post_data);
+
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+        core_bridge,
+    );
+    if config
+        .rpc_client
+        .get_account_with_commitment(&message_key, config.commitment_config)?
+        .value
+        .is_some()
+    {
+        println!("VAA already posted: {}", message_key);
+        return Ok((message_key, post_data));
+    }
+
+    let guardian_set = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        core_bridge,
+    );
+    let guardian_set = config.rpc_client.get_account(&guardian_set)?;
+    let guardian_set = deserialize_account::<GuardianSetData>(&guardian_set.data)?;
+    let signatures = verify_vaa(&guardian_set, &vaa, &hash)?;
+
+    println!("Posting VAA {}", hex::encode(hash));
+    let signature_set = Keypair::new();
+    let batches = bridge::instructions::verify_signatures_batches(
+        *core_bridge,
+        config.owner.pubkey(),
+        vaa.guardian_set_index,
+        signature_set.pubkey(),
+        &signatures,
+        hash,
+    )
+    .map_err(|e| format!("{:?}", e))?;
+    for batch in batches {
+        let mut transaction = Transaction::new_with_payer(&batch, Some(&config.fee_payer.pubkey()));
+        let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+        transaction.sign(
+            &[&config.fee_payer, &config.owner, &signature_set],
+            recent_blockhash,
+        );
+        send_transaction(config, &transaction)?;
+    }
+
+    let ix = bridge::instructions::post_vaa(
+        *core_bridge,
+        config.owner.pubkey(),
+        signature_set.pubkey(),
+        post_data.clone(),
+    );
+    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
+    let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+    transaction.sign(&[&config.fee_payer, &config.owner], recent_blockhash);
+    send_transaction(config, &transaction)?;
+
+    println!("VAA account: {}", message_key);
+    Ok((message_key, post_data))
+}
@@ -8,6 +8,11 @@ use bridge::{
     },
 };
 use solana_program::pubkey::Pubkey;
+#[cfg(feature = "idl")]
+use solitaire::idl::{
+    IdlSeed,
+    IdlSeeds,
+};
 use solitaire::{
     processors::seeded::Seeded,
     *,
@@ -38,6 +43,48 @@ impl<'b, const State: AccountState> Seeded<&CustodyAccountDerivationData>
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for CustodyAccountDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![IdlSeed::field::<Pubkey>("mint")]
+    }
+}
+
+/// Temporary wSOL account native SOL is wrapped into and unwrapped from. It is created and closed
+/// within a single instruction, so it is never left initialized. Anyone can send lamports to the
+/// address in between, so it is peeled as a plain account, see `create_native_sol`.
+pub type NativeSolAccount<'b> = Derive<Info<'b>, "native_sol">;
+
+/// Message of one transfer of a batch. Messages are derived from the key signing for the batch, so
+/// a batch needs a single keypair however many assets it moves.
+pub type BatchMessage<'b> = Info<'b>;
+
+pub struct BatchMessageDerivationData {
+    pub batch: Pubkey,
+    pub index: u8,
+}
+
+impl<'b> Seeded<&BatchMessageDerivationData> for BatchMessage<'b> {
+    fn seeds(data: &BatchMessageDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("batch_message").as_bytes().to_vec(),
+            data.batch.to_bytes().to_vec(),
+            vec![data.index],
+        ]
+    }
+}
+
+#[cfg(feature = "idl")]
+impl IdlSeeds for BatchMessageDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("batch_message"),
+            IdlSeed::field::<Pubkey>("batch"),
+            IdlSeed::field::<u8>("index"),
+        ]
+    }
+}
+
 pub type WrappedMint<'b, const State: AccountState> = Data<'b, SplMint, { State }>;
 
 pub struct WrappedDerivationData {
@@ -55,6 +102,17 @@ impl<'b, const State: AccountState> Seeded<&WrappedDerivationData> for WrappedMi
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for WrappedDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("wrapped"),
+            IdlSeed::field::<ChainID>("token_chain"),
+            IdlSeed::field::<ForeignAddress>("token_address"),
+        ]
+    }
+}
+
 pub type WrappedTokenMeta<'b, const State: AccountState> = Data<'b, WrappedMeta, { State }>;
 
 pub struct WrappedMetaDerivationData {
@@ -72,6 +130,16 @@ impl<'b, const State: AccountState> Seeded<&WrappedMetaDerivationData>
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for WrappedMetaDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::constant("meta"),
+            IdlSeed::field::<Pubkey>("mint_key"),
+        ]
+    }
+}
+
 /// Registered chain endpoint
 pub type Endpoint<'b, const State: AccountState> = Data<'b, EndpointRegistration, { State }>;
 
@@ -90,6 +158,16 @@ impl<'b, const State: AccountState> Seeded<&EndpointDerivationData> for Endpoint
     }
 }
 
+#[cfg(feature = "idl")]
+impl IdlSeeds for EndpointDerivationData {
+    fn idl_seeds() -> Vec<IdlSeed> {
+        vec![
+            IdlSeed::field::<u16>("emitter_chain"),
+            IdlSeed::field::<ForeignAddress>("emitter_address"),
+        ]
+    }
+}
+
 pub type SplTokenMeta<'b> = Info<'b>;
 
 pub struct SplTokenMetaDerivationData {
@@ -0,0 +1,76 @@
+//! SPL token amounts converted to and from the 8 decimal amounts of transfer payloads, see
+//! `wormhole_core::amount`. Amounts that do not fit a `u64` once converted are rejected with
+//! `AmountTooLarge` rather than truncated.
+
+use crate::TokenBridgeError::{
+    AmountTooLarge,
+    DustAmount,
+    InvalidFee,
+};
+use primitive_types::U256;
+use solitaire::{
+    Result,
+    SolitaireError,
+};
+use std::convert::TryFrom;
+use wormhole_core::WormholeError;
+
+/// The amounts of an outgoing transfer.
+pub struct NormalizedTransfer {
+    /// Amount for the payload
+    pub amount: u64,
+
+    /// Fee for the payload
+    pub fee: u64,
+
+    /// Amount to take from the sender, in the mint's decimals
+    pub truncated: u64,
+}
+
+/// The amounts of an incoming transfer, in the mint's decimals.
+pub struct DenormalizedTransfer {
+    /// Amount for the recipient, the fee already deducted
+    pub amount: u64,
+
+    /// Amount for the relayer
+    pub fee: u64,
+}
+
+fn amount_error(e: WormholeError) -> SolitaireError {
+    match e {
+        WormholeError::DustAmount => DustAmount.into(),
+        _ => AmountTooLarge.into(),
+    }
+}
+
+fn to_u64(amount: u128) -> Result<u64> {
+    u64::try_from(amount).map_err(|_| AmountTooLarge.into())
+}
+
+/// Normalize the amount and fee of an outgoing transfer of a mint with `decimals`.
+pub fn normalize_transfer(amount: u64, fee: u64, decimals: u8) -> Result<NormalizedTransfer> {
+    if fee > amount {
+        return Err(InvalidFee.into());
+    }
+
+    let normalized =
+        wormhole_core::normalize_amount(amount as u128, decimals).map_err(amount_error)?;
+    let fee = wormhole_core::normalize_fee(fee as u128, decimals).map_err(amount_error)?;
+    Ok(NormalizedTransfer {
+        amount: to_u64(normalized.normalized)?,
+        fee: to_u64(fee)?,
+        truncated: to_u64(normalized.truncated)?,
+    })
+}
+
+/// Denormalize the amount and fee of an incoming transfer to a mint with `decimals`.
+pub fn denormalize_transfer(amount: U256, fee: U256, decimals: u8) -> Result<DenormalizedTransfer> {
+    let amount = wormhole_core::denormalize_amount(amount, decimals).map_err(amount_error)?;
+    let fee = wormhole_core::denormalize_amount(fee, decimals).map_err(amount_error)?;
+    let amount = to_u64(amount)?;
+    let fee = to_u64(fee)?;
+    Ok(DenormalizedTransfer {
+        amount: amount.checked_sub(fee).ok_or(InvalidFee)?,
+        fee,
+    })
+}
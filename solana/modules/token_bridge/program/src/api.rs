@@ -3,11 +3,15 @@ pub mod complete_transfer;
 pub mod create_wrapped;
 pub mod governance;
 pub mod initialize;
+pub mod migrate;
 pub mod transfer;
+pub mod transfer_batch;
 
 pub use attest::*;
 pub use complete_transfer::*;
 pub use create_wrapped::*;
 pub use governance::*;
 pub use initialize::*;
+pub use migrate::*;
 pub use transfer::*;
+pub use transfer_batch::*;
@@ -73,6 +73,7 @@ pub struct AttestToken<'b> {
 
     /// Mint to attest
     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    #[seeds(WrappedMetaDerivationData)]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Uninitialized }>,
 
     /// SPL Metadata for the associated Mint
@@ -115,7 +116,7 @@ impl<'a> From<&AttestToken<'a>> for SplTokenMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct AttestTokenData {
     pub nonce: u32,
 }
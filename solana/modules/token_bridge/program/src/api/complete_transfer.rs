@@ -7,11 +7,14 @@ use crate::{
         Endpoint,
         EndpointDerivationData,
         MintSigner,
+        NativeSolAccount,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
+    amount::denormalize_transfer,
+    api::transfer::create_native_sol,
     messages::PayloadTransfer,
     types::*,
     TokenBridgeError::*,
@@ -22,9 +25,14 @@ use bridge::{
 };
 use solana_program::{
     account_info::AccountInfo,
-    program::invoke_signed,
+    program::{
+        invoke,
+        invoke_signed,
+    },
     program_error::ProgramError,
+    program_pack::Pack,
     pubkey::Pubkey,
+    rent::Rent,
 };
 use solitaire::{
     processors::seeded::{
@@ -49,10 +57,12 @@ pub struct CompleteNative<'b> {
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
+    #[seeds(EndpointDerivationData)]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[seeds(CustodyAccountDerivationData)]
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
 
@@ -79,7 +89,7 @@ impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
 impl<'b> InstructionContext<'b> for CompleteNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteNativeData {}
 
 pub fn complete_native(
@@ -129,14 +139,8 @@ pub fn complete_native(
     accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
-    let mut amount = accs.vaa.amount.as_u64();
-    let mut fee = accs.vaa.fee.as_u64();
-
     // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
-    if accs.mint.decimals > 8 {
-        amount *= 10u64.pow((accs.mint.decimals - 8) as u32);
-        fee *= 10u64.pow((accs.mint.decimals - 8) as u32);
-    }
+    let transfer = denormalize_transfer(accs.vaa.amount, accs.vaa.fee, accs.mint.decimals)?;
 
     // Transfer tokens
     let transfer_ix = spl_token::instruction::transfer(
@@ -145,7 +149,7 @@ pub fn complete_native(
         accs.to.info().key,
         accs.custody_signer.key,
         &[],
-        amount.checked_sub(fee).unwrap(),
+        transfer.amount,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
@@ -156,10 +160,168 @@ pub fn complete_native(
         accs.to_fees.info().key,
         accs.custody_signer.key,
         &[],
-        fee,
+        transfer.fee,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteNativeSol<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: ClaimableVAA<'b, PayloadTransfer>,
+    #[seeds(EndpointDerivationData)]
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// Wallets receiving the unwrapped SOL
+    pub to: Mut<Info<'b>>,
+    pub to_fees: Mut<Info<'b>>,
+    #[seeds(CustodyAccountDerivationData)]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// Temporary account SOL is unwrapped through on its way out of custody
+    pub native_sol: Mut<NativeSolAccount<'b>>,
+}
+
+impl<'a> From<&CompleteNativeSol<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteNativeSol<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+impl<'a> From<&CompleteNativeSol<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &CompleteNativeSol<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+impl<'b> InstructionContext<'b> for CompleteNativeSol<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteNativeSolData {}
+
+pub fn complete_native_sol(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteNativeSol,
+    data: CompleteNativeSolData,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify that the custody account is derived correctly
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify mints
+    if *accs.mint.info().key != spl_token::native_mint::id() {
+        return Err(InvalidMint.into());
+    }
+    if *accs.mint.info().key != accs.custody.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.custody_signer.key != accs.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+
+    // Verify VAA
+    if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+    if accs.vaa.token_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to.key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+
+    // Prevent vaa double signing
+    accs.vaa.verify(ctx.program_id)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
+    let transfer = denormalize_transfer(accs.vaa.amount, accs.vaa.fee, accs.mint.decimals)?;
+
+    // The runtime refuses to leave a wallet funded below the rent exempt minimum, so a transfer
+    // too small to fund an empty wallet fails here. The claim is rolled back with it, and the VAA
+    // can be redeemed once the wallet holds enough SOL.
+    let to_credit = match accs.to.key == accs.to_fees.key {
+        true => transfer.amount.saturating_add(transfer.fee),
+        false => transfer.amount,
+    };
+    for (wallet, credit) in [(&accs.to, to_credit), (&accs.to_fees, transfer.fee)] {
+        let lamports = wallet.lamports().saturating_add(credit);
+        if credit > 0 && !Rent::default().is_exempt(lamports, wallet.data_len()) {
+            return Err(RecipientNotRentExempt.into());
+        }
+    }
+
+    // Create the temporary wSOL account, the payer fronts its rent
+    create_native_sol(
+        ctx,
+        &accs.payer,
+        &accs.native_sol,
+        Exempt.amount(Account::LEN),
+    )?;
+
+    let init_ix = spl_token::instruction::initialize_account(
+        &spl_token::id(),
+        accs.native_sol.key,
+        accs.mint.info().key,
+        accs.custody_signer.key,
+    )?;
+    invoke_signed(&init_ix, ctx.accounts, &[])?;
+
+    // Transfer tokens
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        accs.custody.info().key,
+        accs.native_sol.key,
+        accs.custody_signer.key,
+        &[],
+        transfer.amount + transfer.fee,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
+    // Unwrap into the payer, which gets its rent back along with the SOL
+    let close_ix = spl_token::instruction::close_account(
+        &spl_token::id(),
+        accs.native_sol.key,
+        accs.payer.key,
+        accs.custody_signer.key,
+        &[],
+    )?;
+    invoke_seeded(&close_ix, ctx, &accs.custody_signer, None)?;
+
+    // Pass the SOL on to the recipient and the fee recipient
+    let transfer_ix =
+        solana_program::system_instruction::transfer(accs.payer.key, accs.to.key, transfer.amount);
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.to_fees.key,
+        transfer.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
     Ok(())
 }
 
@@ -171,11 +333,14 @@ pub struct CompleteWrapped<'b> {
     // Signed message for the transfer
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
 
+    #[seeds(EndpointDerivationData)]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[seeds(WrappedDerivationData)]
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    #[seeds(WrappedMetaDerivationData)]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
     pub mint_authority: MintSigner<'b>,
@@ -202,7 +367,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -247,6 +412,9 @@ pub fn complete_wrapped(
     accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
+    // Wrapped mints have at most 8 decimals, this only rejects amounts the mint cannot hold.
+    let transfer = denormalize_transfer(accs.vaa.amount, accs.vaa.fee, accs.mint.decimals)?;
+
     // Mint tokens
     let mint_ix = spl_token::instruction::mint_to(
         &spl_token::id(),
@@ -254,11 +422,7 @@ pub fn complete_wrapped(
         accs.to.info().key,
         accs.mint_authority.key,
         &[],
-        accs.vaa
-            .amount
-            .as_u64()
-            .checked_sub(accs.vaa.fee.as_u64())
-            .unwrap(),
+        transfer.amount,
     )?;
     invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
 
@@ -269,7 +433,7 @@ pub fn complete_wrapped(
         accs.to_fees.info().key,
         accs.mint_authority.key,
         &[],
-        accs.vaa.fee.as_u64(),
+        transfer.fee,
     )?;
     invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
 
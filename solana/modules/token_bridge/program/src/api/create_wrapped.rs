@@ -60,11 +60,14 @@ pub struct CreateWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
+    #[seeds(EndpointDerivationData)]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
     pub vaa: ClaimableVAA<'b, PayloadAssetMeta>,
 
     // New Wrapped
+    #[seeds(WrappedDerivationData)]
     pub mint: Mut<WrappedMint<'b, { AccountState::MaybeInitialized }>>,
+    #[seeds(WrappedMetaDerivationData)]
     pub meta: Mut<WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>>,
 
     /// SPL Metadata for the associated Mint
@@ -102,7 +105,7 @@ impl<'a> From<&CreateWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CreateWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CreateWrappedData {}
 
 pub fn create_wrapped(
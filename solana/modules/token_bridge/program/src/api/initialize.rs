@@ -24,7 +24,7 @@ pub struct Initialize<'b> {
     pub config: Mut<ConfigAccount<'b, { AccountState::Uninitialized }>>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct InitializeData {
     pub bridge: Pubkey,
 }
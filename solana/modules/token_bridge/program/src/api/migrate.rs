@@ -0,0 +1,134 @@
+use crate::{
+    accounts::{
+        Endpoint,
+        EndpointDerivationData,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    },
+    types::*,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::{
+        discriminator::migrate_account,
+        seeded::Seeded,
+    },
+    *,
+};
+
+// Accounts created before their type set a discriminator are migrated in place by the
+// instructions below. Anyone can run them, each checks that the account is derived from the data
+// it holds before prefixing it.
+
+#[derive(FromAccounts)]
+pub struct MigrateConfig<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    /// Legacy data cannot be peeled as `ConfigAccount` until migrated.
+    pub config: Mut<Derive<Info<'b>, "config">>,
+
+    pub system: Info<'b>,
+}
+
+impl<'b> InstructionContext<'b> for MigrateConfig<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct MigrateConfigData {}
+
+pub fn migrate_config(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateConfig,
+    _data: MigrateConfigData,
+) -> Result<()> {
+    // The derivation has no data dependencies and is checked when peeling.
+    migrate_account::<Config, _>(ctx, &accs.config, &accs.payer, |_| Ok(()))
+}
+
+#[derive(FromAccounts)]
+pub struct MigrateWrappedMeta<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    /// Derived from the wrapped mint of the token it describes.
+    pub meta: Mut<Info<'b>>,
+
+    pub system: Info<'b>,
+}
+
+impl<'b> InstructionContext<'b> for MigrateWrappedMeta<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct MigrateWrappedMetaData {}
+
+pub fn migrate_wrapped_meta(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateWrappedMeta,
+    _data: MigrateWrappedMetaData,
+) -> Result<()> {
+    let key = *accs.meta.key;
+    migrate_account::<WrappedMeta, _>(ctx, &accs.meta, &accs.payer, |data| {
+        let mint_key = WrappedMint::<'_, { AccountState::Initialized }>::key(
+            &WrappedDerivationData {
+                token_chain: data.chain,
+                token_address: data.token_address,
+            },
+            ctx.program_id,
+        );
+        verify_key(
+            key,
+            WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+                &WrappedMetaDerivationData { mint_key },
+                ctx.program_id,
+            ),
+        )
+    })
+}
+
+#[derive(FromAccounts)]
+pub struct MigrateEndpoint<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    /// Derived from the chain and contract it registers.
+    pub endpoint: Mut<Info<'b>>,
+
+    pub system: Info<'b>,
+}
+
+impl<'b> InstructionContext<'b> for MigrateEndpoint<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct MigrateEndpointData {}
+
+pub fn migrate_endpoint(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateEndpoint,
+    _data: MigrateEndpointData,
+) -> Result<()> {
+    let key = *accs.endpoint.key;
+    migrate_account::<EndpointRegistration, _>(ctx, &accs.endpoint, &accs.payer, |data| {
+        verify_key(
+            key,
+            Endpoint::<'_, { AccountState::Initialized }>::key(
+                &EndpointDerivationData {
+                    emitter_chain: data.chain,
+                    emitter_address: data.contract,
+                },
+                ctx.program_id,
+            ),
+        )
+    })
+}
+
+fn verify_key(key: Pubkey, expected: Pubkey) -> Result<()> {
+    if key != expected {
+        return Err(SolitaireError::InvalidDerive(key, expected));
+    }
+    Ok(())
+}
@@ -8,11 +8,13 @@ use crate::{
         CustodySigner,
         EmitterAccount,
         MintSigner,
+        NativeSolAccount,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
+    amount::normalize_transfer,
     messages::PayloadTransfer,
     types::*,
     TokenBridgeError,
@@ -45,6 +47,7 @@ use solana_program::{
     },
     program_error::ProgramError,
     program_option::COption,
+    program_pack::Pack,
     pubkey::Pubkey,
     sysvar::clock::Clock,
 };
@@ -78,6 +81,7 @@ pub struct TransferNative<'b> {
 
     pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
 
+    #[seeds(CustodyAccountDerivationData)]
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -115,7 +119,7 @@ impl<'a> From<&TransferNative<'a>> for CustodyAccountDerivationData {
 impl<'b> InstructionContext<'b> for TransferNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub amount: u64,
@@ -169,12 +173,8 @@ pub fn transfer_native(
         invoke_signed(&init_ix, ctx.accounts, &[])?;
     }
 
-    let trunc_divisor = 10u64.pow(8.max(accs.mint.decimals as u32) - 8);
-    // Truncate to 8 decimals
-    let amount: u64 = data.amount / trunc_divisor;
-    let fee: u64 = data.fee / trunc_divisor;
-    // Untruncate the amount to drop the remainder so we don't  "burn" user's funds.
-    let amount_trunc: u64 = amount * trunc_divisor;
+    // Truncate to 8 decimals, only the truncated amount is taken so we don't "burn" user's funds.
+    let normalized = normalize_transfer(data.amount, data.fee, accs.mint.decimals)?;
 
     // Transfer tokens
     let transfer_ix = spl_token::instruction::transfer(
@@ -183,7 +183,7 @@ pub fn transfer_native(
         accs.custody.info().key,
         accs.authority_signer.key,
         &[],
-        amount_trunc,
+        normalized.truncated,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.authority_signer, None)?;
 
@@ -197,12 +197,12 @@ pub fn transfer_native(
 
     // Post message
     let payload = PayloadTransfer {
-        amount: U256::from(amount),
+        amount: U256::from(normalized.amount),
         token_address: accs.mint.info().key.to_bytes(),
         token_chain: CHAIN_ID_SOLANA,
         to: data.target_address,
         to_chain: data.target_chain,
-        fee: U256::from(fee),
+        fee: U256::from(normalized.fee),
     };
     let params = (
         bridge::instruction::Instruction::PostMessage,
@@ -233,6 +233,220 @@ pub fn transfer_native(
     Ok(())
 }
 
+#[derive(FromAccounts)]
+pub struct TransferNativeSol<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Temporary account the payer's SOL is wrapped in before it is moved into custody
+    pub native_sol: Mut<NativeSolAccount<'b>>,
+
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+
+    #[seeds(CustodyAccountDerivationData)]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'a> From<&TransferNativeSol<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &TransferNativeSol<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+impl<'b> InstructionContext<'b> for TransferNativeSol<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferNativeSolData {
+    pub nonce: u32,
+    pub amount: u64,
+    pub fee: u64,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+}
+
+pub fn transfer_native_sol(
+    ctx: &ExecutionContext,
+    accs: &mut TransferNativeSol,
+    data: TransferNativeSolData,
+) -> Result<()> {
+    // Prevent transferring to the same chain.
+    if data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    // Verify that the custody account is derived correctly
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // SOL is bridged as the native mint
+    if *accs.mint.info().key != spl_token::native_mint::id() {
+        return Err(TokenBridgeError::InvalidMint.into());
+    }
+
+    // Fee must be less than amount
+    if data.fee > data.amount {
+        return Err(InvalidFee.into());
+    }
+
+    if !accs.custody.is_initialized() {
+        accs.custody
+            .create(&(&*accs).into(), ctx, accs.payer.key, Exempt)?;
+
+        let init_ix = spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            accs.custody.info().key,
+            accs.mint.info().key,
+            accs.custody_signer.key,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+    }
+
+    // Truncate to 8 decimals, only the truncated amount is taken so we don't "burn" user's funds.
+    let normalized = normalize_transfer(data.amount, data.fee, accs.mint.decimals)?;
+
+    // Wrap the SOL: a rent exempt token account funded with the amount on top
+    let lamports = Exempt
+        .amount(Account::LEN)
+        .checked_add(normalized.truncated)
+        .ok_or(TokenBridgeError::AmountTooLarge)?;
+    create_native_sol(ctx, &accs.payer, &accs.native_sol, lamports)?;
+
+    let init_ix = spl_token::instruction::initialize_account(
+        &spl_token::id(),
+        accs.native_sol.key,
+        accs.mint.info().key,
+        accs.custody_signer.key,
+    )?;
+    invoke_signed(&init_ix, ctx.accounts, &[])?;
+
+    // Transfer tokens
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        accs.native_sol.key,
+        accs.custody.info().key,
+        accs.custody_signer.key,
+        &[],
+        normalized.truncated,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    // Close the temporary account, returning its rent to the payer
+    let close_ix = spl_token::instruction::close_account(
+        &spl_token::id(),
+        accs.native_sol.key,
+        accs.payer.key,
+        accs.custody_signer.key,
+        &[],
+    )?;
+    invoke_seeded(&close_ix, ctx, &accs.custody_signer, None)?;
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    // Post message
+    let payload = PayloadTransfer {
+        amount: U256::from(normalized.amount),
+        token_address: accs.mint.info().key.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(normalized.fee),
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+/// Create the temporary wSOL account with `lamports`, paid by the payer. Lamports sent to the
+/// address before it is created would make `create_account` fail, so a funded address is only
+/// topped up, then allocated and assigned to the token program. Whatever it held is returned to
+/// the payer when it is closed.
+pub fn create_native_sol(
+    ctx: &ExecutionContext,
+    payer: &AccountInfo,
+    native_sol: &NativeSolAccount,
+    lamports: u64,
+) -> Result<()> {
+    let size = Account::LEN as u64;
+    if native_sol.lamports() == 0 {
+        let create_ix = solana_program::system_instruction::create_account(
+            payer.key,
+            native_sol.key,
+            lamports,
+            size,
+            &spl_token::id(),
+        );
+        invoke_seeded(&create_ix, ctx, native_sol, None)?;
+        return Ok(());
+    }
+
+    let top_up = lamports.saturating_sub(native_sol.lamports());
+    if top_up > 0 {
+        let transfer_ix =
+            solana_program::system_instruction::transfer(payer.key, native_sol.key, top_up);
+        invoke(&transfer_ix, ctx.accounts)?;
+    }
+    let allocate_ix = solana_program::system_instruction::allocate(native_sol.key, size);
+    invoke_seeded(&allocate_ix, ctx, native_sol, None)?;
+    let assign_ix = solana_program::system_instruction::assign(native_sol.key, &spl_token::id());
+    invoke_seeded(&assign_ix, ctx, native_sol, None)?;
+
+    Ok(())
+}
+
 #[derive(FromAccounts)]
 pub struct TransferWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
@@ -241,6 +455,7 @@ pub struct TransferWrapped<'b> {
     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub from_owner: MaybeMut<Signer<Info<'b>>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    #[seeds(WrappedMetaDerivationData)]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
     pub authority_signer: AuthoritySigner<'b>,
@@ -283,7 +498,7 @@ impl<'a> From<&TransferWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub amount: u64,
@@ -322,6 +537,9 @@ pub fn transfer_wrapped(
     accs.wrapped_meta
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
+    // Wrapped mints have at most 8 decimals, this only rejects amounts that cannot be sent.
+    let normalized = normalize_transfer(data.amount, data.fee, accs.mint.decimals)?;
+
     // Burn tokens
     let burn_ix = spl_token::instruction::burn(
         &spl_token::id(),
@@ -329,7 +547,7 @@ pub fn transfer_wrapped(
         accs.mint.info().key,
         accs.authority_signer.key,
         &[],
-        data.amount,
+        normalized.truncated,
     )?;
     invoke_seeded(&burn_ix, ctx, &accs.authority_signer, None)?;
 
@@ -344,12 +562,12 @@ pub fn transfer_wrapped(
 
     // Post message
     let payload = PayloadTransfer {
-        amount: U256::from(data.amount),
+        amount: U256::from(normalized.amount),
         token_address: accs.wrapped_meta.token_address,
         token_chain: accs.wrapped_meta.chain,
         to: data.target_address,
         to_chain: data.target_chain,
-        fee: U256::from(data.fee),
+        fee: U256::from(normalized.fee),
     };
     let params = (
         bridge::instruction::Instruction::PostMessage,
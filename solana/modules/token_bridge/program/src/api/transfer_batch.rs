@@ -0,0 +1,355 @@
+use crate::{
+    accounts::{
+        AuthoritySigner,
+        BatchMessage,
+        BatchMessageDerivationData,
+        ConfigAccount,
+        CoreBridge,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        MintSigner,
+        WrappedMetaDerivationData,
+        WrappedTokenMeta,
+    },
+    amount::normalize_transfer,
+    messages::PayloadTransfer,
+    types::*,
+    TokenBridgeError::{
+        AmountTooLarge,
+        InsufficientFees,
+        InvalidBatch,
+        InvalidChain,
+        InvalidFee,
+        InvalidMint,
+        WrongAccountOwner,
+    },
+};
+use bridge::{
+    api::PostMessageData,
+    types::ConsistencyLevel,
+    vaa::SerializePayload,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program::{
+        invoke,
+        invoke_signed,
+    },
+    program_option::COption,
+    sysvar::{
+        clock::Clock,
+        rent::Rent,
+    },
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    CreationLamports::Exempt,
+    *,
+};
+
+/// Accounts of one asset of a batch. Both the custody account and the wrapped meta derived from
+/// the mint are passed, only the one matching the kind of token is used.
+#[derive(FromAccounts)]
+pub struct TransferBatchItem<'b> {
+    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+
+    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+
+    #[seeds(CustodyAccountDerivationData)]
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    #[seeds(WrappedMetaDerivationData)]
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>,
+
+    /// Account to store the posted message, see `BatchMessage`
+    #[seeds(BatchMessageDerivationData)]
+    pub message: Mut<Info<'b>>,
+}
+
+#[derive(FromAccounts)]
+pub struct TransferBatch<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Owner of every token account the batch transfers from
+    pub from_owner: MaybeMut<Signer<Info<'b>>>,
+
+    pub authority_signer: AuthoritySigner<'b>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Key the messages of the batch are derived from
+    pub batch: Signer<Info<'b>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+
+    // Sysvar/program accounts needed for the CPIs, listed before the transfers as those take
+    // every account left.
+    pub rent: Sysvar<'b, Rent>,
+    pub system: Info<'b>,
+    pub bridge_program: Info<'b>,
+    pub token_program: Info<'b>,
+
+    pub transfers: Remaining<TransferBatchItem<'b>>,
+}
+
+impl<'b> InstructionContext<'b> for TransferBatch<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferBatchAmount {
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferBatchData {
+    pub nonce: u32,
+    /// One entry per asset, in the order of the transfer accounts
+    pub transfers: Vec<TransferBatchAmount>,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+}
+
+pub fn transfer_batch(
+    ctx: &ExecutionContext,
+    accs: &mut TransferBatch,
+    data: TransferBatchData,
+) -> Result<()> {
+    // Prevent transferring to the same chain.
+    if data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    if data.transfers.is_empty()
+        || data.transfers.len() != accs.transfers.len()
+        || data.transfers.len() > u8::MAX as usize
+    {
+        return Err(InvalidBatch.into());
+    }
+
+    // The core bridge checks the fee of every message it posts on its own, so the payer pays one
+    // fee per message. Make sure it can cover all of them before any tokens move.
+    let fees = accs
+        .bridge
+        .config
+        .fee
+        .checked_mul(data.transfers.len() as u64)
+        .ok_or(AmountTooLarge)?;
+    if accs.payer.lamports() < fees {
+        return Err(InsufficientFees.into());
+    }
+
+    let mint_signer = MintSigner::key(None, ctx.program_id);
+    for (index, (item, transfer)) in accs.transfers.iter().zip(&data.transfers).enumerate() {
+        // Verify that the from account is owned by the from_owner
+        if &item.from.owner != accs.from_owner.key {
+            return Err(WrongAccountOwner.into());
+        }
+
+        // Verify mints
+        if item.from.mint != *item.mint.info().key {
+            return Err(InvalidMint.into());
+        }
+
+        // Fee must be less than amount
+        if transfer.fee > transfer.amount {
+            return Err(InvalidFee.into());
+        }
+
+        let message = BatchMessageDerivationData {
+            batch: *accs.batch.key,
+            index: index as u8,
+        };
+        if *item.message.key != BatchMessage::key(&message, ctx.program_id) {
+            return Err(InvalidBatch.into());
+        }
+
+        let payload = if item.mint.mint_authority == COption::Some(mint_signer) {
+            transfer_batch_wrapped(ctx, accs, item, transfer)?
+        } else {
+            transfer_batch_native(ctx, accs, item, transfer)?
+        };
+        transfer_batch_post(ctx, accs, item, &message, &data, payload)?;
+    }
+
+    Ok(())
+}
+
+/// Burn a wrapped token, as `transfer_wrapped` does.
+fn transfer_batch_wrapped(
+    ctx: &ExecutionContext,
+    accs: &TransferBatch,
+    item: &TransferBatchItem,
+    transfer: &TransferBatchAmount,
+) -> Result<(u64, u64, Address, ChainID)> {
+    // Verify that meta is correct
+    item.wrapped_meta.verify_derivation(
+        ctx.program_id,
+        &WrappedMetaDerivationData {
+            mint_key: *item.mint.info().key,
+        },
+    )?;
+    if !item.wrapped_meta.is_initialized() {
+        return Err(InvalidMint.into());
+    }
+
+    // Wrapped mints have at most 8 decimals, this only rejects amounts that cannot be sent.
+    let normalized = normalize_transfer(transfer.amount, transfer.fee, item.mint.decimals)?;
+
+    // Burn tokens
+    let burn_ix = spl_token::instruction::burn(
+        &spl_token::id(),
+        item.from.info().key,
+        item.mint.info().key,
+        accs.authority_signer.key,
+        &[],
+        normalized.truncated,
+    )?;
+    invoke_seeded(&burn_ix, ctx, &accs.authority_signer, None)?;
+
+    Ok((
+        normalized.amount,
+        normalized.fee,
+        item.wrapped_meta.token_address,
+        item.wrapped_meta.chain,
+    ))
+}
+
+/// Lock a native token in custody, as `transfer_native` does.
+fn transfer_batch_native(
+    ctx: &ExecutionContext,
+    accs: &TransferBatch,
+    item: &TransferBatchItem,
+    transfer: &TransferBatchAmount,
+) -> Result<(u64, u64, Address, ChainID)> {
+    // Verify that the custody account is derived correctly
+    let derivation_data = CustodyAccountDerivationData {
+        mint: *item.mint.info().key,
+    };
+    item.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if !item.custody.is_initialized() {
+        item.custody
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        let init_ix = spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            item.custody.info().key,
+            item.mint.info().key,
+            accs.custody_signer.key,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+    }
+
+    // Truncate to 8 decimals, only the truncated amount is taken so we don't "burn" user's funds.
+    let normalized = normalize_transfer(transfer.amount, transfer.fee, item.mint.decimals)?;
+
+    // Transfer tokens
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        item.from.info().key,
+        item.custody.info().key,
+        accs.authority_signer.key,
+        &[],
+        normalized.truncated,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.authority_signer, None)?;
+
+    Ok((
+        normalized.amount,
+        normalized.fee,
+        item.mint.info().key.to_bytes(),
+        CHAIN_ID_SOLANA,
+    ))
+}
+
+/// Post the message of one transfer of the batch, signed for by the emitter and the message
+/// account derived from the batch key.
+fn transfer_batch_post(
+    ctx: &ExecutionContext,
+    accs: &TransferBatch,
+    item: &TransferBatchItem,
+    message: &BatchMessageDerivationData,
+    data: &TransferBatchData,
+    (amount, fee, token_address, token_chain): (u64, u64, Address, ChainID),
+) -> Result<()> {
+    // Pay the fee of this message. The core bridge records the collector's balance after each
+    // message and expects a full fee on top of it for the next one, so paying for the whole batch
+    // up front would only cover the first message.
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.fee_collector.key,
+        accs.bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    let payload = PayloadTransfer {
+        amount: U256::from(amount),
+        token_address,
+        token_chain,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(fee),
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*item.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    let emitter_seeds = accs.emitter.self_bumped_seeds(None, ctx.program_id);
+    let message_seeds = BatchMessage::bumped_seeds(message, ctx.program_id);
+    let emitter_seeds: Vec<&[u8]> = emitter_seeds.iter().map(|s| s.as_slice()).collect();
+    let message_seeds: Vec<&[u8]> = message_seeds.iter().map(|s| s.as_slice()).collect();
+    invoke_signed(
+        &ix,
+        ctx.accounts,
+        &[emitter_seeds.as_slice(), message_seeds.as_slice()],
+    )?;
+
+    Ok(())
+}
@@ -1,14 +1,10 @@
 use crate::{
     accounts::{
-        AuthoritySigner,
-        ConfigAccount,
-        CustodyAccount,
+        BatchMessageDerivationData,
         CustodyAccountDerivationData,
-        CustodySigner,
         EmitterAccount,
         Endpoint,
         EndpointDerivationData,
-        MintSigner,
         SplTokenMeta,
         SplTokenMetaDerivationData,
         WrappedDerivationData,
@@ -19,13 +15,35 @@ use crate::{
     api::{
         complete_transfer::{
             CompleteNativeData,
+            CompleteNativeSolData,
             CompleteWrappedData,
         },
+        AttestTokenAccounts,
         AttestTokenData,
+        CompleteNativeAccounts,
+        CompleteNativeSolAccounts,
+        CompleteWrappedAccounts,
+        CreateWrappedAccounts,
         CreateWrappedData,
+        InitializeAccounts,
+        MigrateConfigAccounts,
+        MigrateConfigData,
+        MigrateEndpointAccounts,
+        MigrateEndpointData,
+        MigrateWrappedMetaAccounts,
+        MigrateWrappedMetaData,
+        RegisterChainAccounts,
         RegisterChainData,
+        TransferBatchAccounts,
+        TransferBatchData,
+        TransferBatchItemAccounts,
+        TransferNativeAccounts,
         TransferNativeData,
+        TransferNativeSolAccounts,
+        TransferNativeSolData,
+        TransferWrappedAccounts,
         TransferWrappedData,
+        UpgradeContractAccounts,
         UpgradeContractData,
     },
     messages::{
@@ -38,59 +56,48 @@ use borsh::BorshSerialize;
 use bridge::{
     accounts::{
         Bridge,
-        BridgeConfig,
-        Claim,
         ClaimDerivationData,
         FeeCollector,
-        PostedVAA,
-        PostedVAAData,
-        PostedVAADerivationData,
         Sequence,
         SequenceDerivationData,
     },
     api::ForeignAddress,
-    instructions::hash_vaa,
-    vaa::{
-        ClaimableVAA,
-        PayloadMessage,
-        SerializePayload,
-    },
-    PostVAA,
+    vaa::ClaimableVAAAccounts,
     PostVAAData,
     CHAIN_ID_SOLANA,
 };
-use primitive_types::U256;
 use solana_program::{
     instruction::{
         AccountMeta,
         Instruction,
     },
     pubkey::Pubkey,
+    system_program,
+    sysvar,
 };
 use solitaire::{
+    client::{
+        append_deps,
+        AccEntry,
+        ToInstruction,
+    },
     processors::seeded::Seeded,
     AccountState,
 };
-use spl_token::state::Mint;
-use std::str::FromStr;
 
 pub fn initialize(
     program_id: Pubkey,
     payer: Pubkey,
     bridge: Pubkey,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    Ok(Instruction {
+    InitializeAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::Derived(program_id),
+    }
+    .to_ix(
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(config_key, false),
-            // Dependencies
-            AccountMeta::new(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new(solana_program::system_program::id(), false),
-        ],
-        data: (crate::instruction::Instruction::Initialize, bridge).try_to_vec()?,
-    })
+        &(crate::instruction::Instruction::Initialize, bridge).try_to_vec()?,
+    )
 }
 
 pub fn complete_native(
@@ -104,49 +111,64 @@ pub fn complete_native(
     mint: Pubkey,
     data: CompleteNativeData,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
-    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
-        &EndpointDerivationData {
-            emitter_chain: vaa.emitter_chain,
-            emitter_address: vaa.emitter_address,
-        },
-        &program_id,
-    );
-    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
-        &CustodyAccountDerivationData { mint },
-        &program_id,
-    );
-    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let mut accounts = CompleteNativeAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        vaa: claimable_vaa(message_key, &vaa),
+        chain_registration: chain_registration(&vaa),
+        to: AccEntry::Unprivileged(to),
+        to_fees: AccEntry::Unprivileged(fee_recipient.unwrap_or(to)),
+        custody: CustodyAccountDerivationData { mint },
+        mint: AccEntry::UnprivilegedRO(mint),
+        custody_signer: AccEntry::DerivedRO(program_id),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
 
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            message_acc,
-            claim_acc,
-            AccountMeta::new_readonly(endpoint, false),
-            AccountMeta::new(to, false),
-            if let Some(fee_r) = fee_recipient {
-                AccountMeta::new(fee_r, false)
-            } else {
-                AccountMeta::new(to, false)
-            },
-            AccountMeta::new(custody_key, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(custody_signer_key, false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::CompleteNative, data).try_to_vec()?,
     })
 }
 
+pub fn complete_native_sol(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    to: Pubkey,
+    fee_recipient: Option<Pubkey>,
+    data: CompleteNativeSolData,
+) -> solitaire::Result<Instruction> {
+    let mint = spl_token::native_mint::id();
+    let mut accounts = CompleteNativeSolAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        vaa: claimable_vaa(message_key, &vaa),
+        chain_registration: chain_registration(&vaa),
+        to: AccEntry::Unprivileged(to),
+        to_fees: AccEntry::Unprivileged(fee_recipient.unwrap_or(to)),
+        custody: CustodyAccountDerivationData { mint },
+        mint: AccEntry::UnprivilegedRO(mint),
+        custody_signer: AccEntry::DerivedRO(program_id),
+        native_sol: AccEntry::Derived(program_id),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (crate::instruction::Instruction::CompleteNativeSol, data).try_to_vec()?,
+    })
+}
+
 pub fn complete_wrapped(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -158,52 +180,31 @@ pub fn complete_wrapped(
     fee_recipient: Option<Pubkey>,
     data: CompleteWrappedData,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
-    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
-        &EndpointDerivationData {
-            emitter_chain: vaa.emitter_chain,
-            emitter_address: vaa.emitter_address,
-        },
-        &program_id,
-    );
-    let mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
-        &WrappedDerivationData {
-            token_chain: payload.token_chain,
-            token_address: payload.token_address,
-        },
-        &program_id,
-    );
-    let meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
-        &WrappedMetaDerivationData { mint_key },
-        &program_id,
-    );
-    let mint_authority_key = MintSigner::key(None, &program_id);
+    let mint = WrappedDerivationData {
+        token_chain: payload.token_chain,
+        token_address: payload.token_address,
+    };
+    let mint_key = WrappedMint::<'_, { AccountState::Initialized }>::key(&mint, &program_id);
+
+    let mut accounts = CompleteWrappedAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        vaa: claimable_vaa(message_key, &vaa),
+        chain_registration: chain_registration(&vaa),
+        to: AccEntry::Unprivileged(to),
+        to_fees: AccEntry::Unprivileged(fee_recipient.unwrap_or(to)),
+        mint,
+        wrapped_meta: WrappedMetaDerivationData { mint_key },
+        mint_authority: AccEntry::DerivedRO(program_id),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
 
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            message_acc,
-            claim_acc,
-            AccountMeta::new_readonly(endpoint, false),
-            AccountMeta::new(to, false),
-            if let Some(fee_r) = fee_recipient {
-                AccountMeta::new(fee_r, false)
-            } else {
-                AccountMeta::new(to, false)
-            },
-            AccountMeta::new(mint_key, false),
-            AccountMeta::new_readonly(meta_key, false),
-            AccountMeta::new_readonly(mint_authority_key, false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::CompleteWrapped, data).try_to_vec()?,
     })
 }
@@ -217,53 +218,34 @@ pub fn create_wrapped(
     payload: PayloadAssetMeta,
     data: CreateWrappedData,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
-    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
-        &EndpointDerivationData {
-            emitter_chain: vaa.emitter_chain,
-            emitter_address: vaa.emitter_address,
-        },
-        &program_id,
-    );
-    let mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
-        &WrappedDerivationData {
-            token_chain: payload.token_chain,
-            token_address: payload.token_address,
-        },
-        &program_id,
-    );
-    let mint_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
-        &WrappedMetaDerivationData { mint_key },
-        &program_id,
-    );
-    let mint_authority_key = MintSigner::key(None, &program_id);
-    // SPL Metadata
-    let spl_metadata = SplTokenMeta::key(
-        &SplTokenMetaDerivationData { mint: mint_key },
-        &spl_token_metadata::id(),
-    );
+    let mint = WrappedDerivationData {
+        token_chain: payload.token_chain,
+        token_address: payload.token_address,
+    };
+    let mint_key = WrappedMint::<'_, { AccountState::MaybeInitialized }>::key(&mint, &program_id);
+
+    let mut accounts = CreateWrappedAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        chain_registration: chain_registration(&vaa),
+        vaa: claimable_vaa(message_key, &vaa),
+        mint,
+        meta: WrappedMetaDerivationData { mint_key },
+        spl_metadata: AccEntry::seeded::<SplTokenMeta<'_>, _>(
+            &SplTokenMetaDerivationData { mint: mint_key },
+            &spl_token_metadata::id(),
+        ),
+        mint_authority: AccEntry::DerivedRO(program_id),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+    accounts.push(AccountMeta::new_readonly(spl_token_metadata::id(), false));
 
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            AccountMeta::new_readonly(endpoint, false),
-            message_acc,
-            claim_acc,
-            AccountMeta::new(mint_key, false),
-            AccountMeta::new(mint_meta_key, false),
-            AccountMeta::new(spl_metadata, false),
-            AccountMeta::new_readonly(mint_authority_key, false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(spl_token_metadata::id(), false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::CreateWrapped, data).try_to_vec()?,
     })
 }
@@ -277,52 +259,43 @@ pub fn register_chain(
     payload: PayloadGovernanceRegisterChain,
     data: RegisterChainData,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
-    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
-        &EndpointDerivationData {
+    let mut accounts = RegisterChainAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        endpoint: EndpointDerivationData {
             emitter_chain: payload.chain,
             emitter_address: payload.endpoint_address,
         },
-        &program_id,
-    );
+        vaa: claimable_vaa(message_key, &vaa),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
 
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            AccountMeta::new(endpoint, false),
-            message_acc,
-            claim_acc,
-            // Dependencies
-            AccountMeta::new(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::RegisterChain, data).try_to_vec()?,
     })
 }
 
-fn claimable_vaa(
-    bridge_id: Pubkey,
-    message_key: Pubkey,
-    vaa: PostVAAData,
-) -> (AccountMeta, AccountMeta) {
-    let claim_key = Claim::<'_, { AccountState::Initialized }>::key(
-        &ClaimDerivationData {
+fn claimable_vaa(message_key: Pubkey, vaa: &PostVAAData) -> ClaimableVAAAccounts {
+    ClaimableVAAAccounts {
+        message: AccEntry::UnprivilegedRO(message_key),
+        claim: ClaimDerivationData {
             emitter_address: vaa.emitter_address,
             emitter_chain: vaa.emitter_chain,
             sequence: vaa.sequence,
         },
-        &bridge_id,
-    );
+    }
+}
 
-    (
-        AccountMeta::new_readonly(message_key, false),
-        AccountMeta::new(claim_key, false),
-    )
+/// Registration of the token bridge that emitted `vaa`.
+fn chain_registration(vaa: &PostVAAData) -> EndpointDerivationData {
+    EndpointDerivationData {
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+    }
 }
 
 pub fn transfer_native(
@@ -334,61 +307,141 @@ pub fn transfer_native(
     mint: Pubkey,
     data: TransferNativeData,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
-        &CustodyAccountDerivationData { mint },
-        &program_id,
-    );
-
-    let authority_signer_key = AuthoritySigner::key(None, &program_id);
-    let custody_signer_key = CustodySigner::key(None, &program_id);
     let emitter_key = EmitterAccount::key(None, &program_id);
 
-    // Bridge keys
-    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
-    let payload = PayloadTransfer {
-        amount: U256::from(data.amount),
-        token_address: mint.to_bytes(),
-        token_chain: 1,
-        to: data.target_address,
-        to_chain: data.target_chain,
-        fee: U256::from(data.fee),
-    };
-    let sequence_key = Sequence::key(
-        &SequenceDerivationData {
-            emitter_key: &emitter_key,
-        },
-        &bridge_id,
-    );
-    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let mut accounts = TransferNativeAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        from: AccEntry::Unprivileged(from),
+        mint: AccEntry::Unprivileged(mint),
+        custody: CustodyAccountDerivationData { mint },
+        authority_signer: AccEntry::DerivedRO(program_id),
+        custody_signer: AccEntry::DerivedRO(program_id),
+        bridge: AccEntry::seeded::<Bridge<'_, { AccountState::Initialized }>, _>(None, &bridge_id),
+        message: AccEntry::Signer(message_key),
+        emitter: AccEntry::DerivedRO(program_id),
+        sequence: AccEntry::seeded::<Sequence<'_>, _>(
+            &SequenceDerivationData { emitter_key },
+            &bridge_id,
+        ),
+        fee_collector: AccEntry::seeded::<FeeCollector<'_>, _>(None, &bridge_id),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
 
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            AccountMeta::new(from, false),
-            AccountMeta::new(mint, false),
-            AccountMeta::new(custody_key, false),
-            AccountMeta::new_readonly(authority_signer_key, false),
-            AccountMeta::new_readonly(custody_signer_key, false),
-            AccountMeta::new(bridge_config, false),
-            AccountMeta::new(message_key, true),
-            AccountMeta::new_readonly(emitter_key, false),
-            AccountMeta::new(sequence_key, false),
-            AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::TransferNative, data).try_to_vec()?,
     })
 }
 
+pub fn transfer_native_sol(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    data: TransferNativeSolData,
+) -> solitaire::Result<Instruction> {
+    let emitter_key = EmitterAccount::key(None, &program_id);
+    let mint = spl_token::native_mint::id();
+
+    let mut accounts = TransferNativeSolAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        native_sol: AccEntry::Derived(program_id),
+        mint: AccEntry::UnprivilegedRO(mint),
+        custody: CustodyAccountDerivationData { mint },
+        custody_signer: AccEntry::DerivedRO(program_id),
+        bridge: AccEntry::seeded::<Bridge<'_, { AccountState::Initialized }>, _>(None, &bridge_id),
+        message: AccEntry::Signer(message_key),
+        emitter: AccEntry::DerivedRO(program_id),
+        sequence: AccEntry::seeded::<Sequence<'_>, _>(
+            &SequenceDerivationData { emitter_key },
+            &bridge_id,
+        ),
+        fee_collector: AccEntry::seeded::<FeeCollector<'_>, _>(None, &bridge_id),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (crate::instruction::Instruction::TransferNativeSol, data).try_to_vec()?,
+    })
+}
+
+/// Token account and mint of one asset of a batch transfer.
+pub struct TransferBatchAsset {
+    pub from: Pubkey,
+    pub mint: Pubkey,
+}
+
+pub fn transfer_batch(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    from_owner: Pubkey,
+    batch: Pubkey,
+    assets: &[TransferBatchAsset],
+    data: TransferBatchData,
+) -> solitaire::Result<Instruction> {
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    let transfers = assets
+        .iter()
+        .enumerate()
+        .map(|(index, asset)| TransferBatchItemAccounts {
+            from: AccEntry::Unprivileged(asset.from),
+            mint: AccEntry::Unprivileged(asset.mint),
+            custody: CustodyAccountDerivationData { mint: asset.mint },
+            wrapped_meta: WrappedMetaDerivationData {
+                mint_key: asset.mint,
+            },
+            message: BatchMessageDerivationData {
+                batch,
+                index: index as u8,
+            },
+        })
+        .collect();
+
+    let accounts = TransferBatchAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        from_owner: AccEntry::SignerRO(from_owner),
+        authority_signer: AccEntry::DerivedRO(program_id),
+        custody_signer: AccEntry::DerivedRO(program_id),
+        bridge: AccEntry::seeded::<Bridge<'_, { AccountState::Initialized }>, _>(None, &bridge_id),
+        batch: AccEntry::SignerRO(batch),
+        emitter: AccEntry::DerivedRO(program_id),
+        sequence: AccEntry::seeded::<Sequence<'_>, _>(
+            &SequenceDerivationData { emitter_key },
+            &bridge_id,
+        ),
+        fee_collector: AccEntry::seeded::<FeeCollector<'_>, _>(None, &bridge_id),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+        rent: AccEntry::Sysvar(sysvar::rent::id()),
+        system: AccEntry::CPIProgram(system_program::id()),
+        bridge_program: AccEntry::CPIProgram(bridge_id),
+        token_program: AccEntry::CPIProgram(spl_token::id()),
+        transfers,
+    }
+    .to_account_metas(&program_id)?;
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (crate::instruction::Instruction::TransferBatch, data).try_to_vec()?,
+    })
+}
+
 pub fn transfer_wrapped(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -400,66 +453,46 @@ pub fn transfer_wrapped(
     token_address: ForeignAddress,
     data: TransferWrappedData,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-
-    let wrapped_mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+    let emitter_key = EmitterAccount::key(None, &program_id);
+    let mint_key = WrappedMint::<'_, { AccountState::Initialized }>::key(
         &WrappedDerivationData {
             token_chain,
             token_address,
         },
         &program_id,
     );
-    let wrapped_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
-        &WrappedMetaDerivationData {
-            mint_key: wrapped_mint_key,
-        },
-        &program_id,
-    );
 
-    let authority_signer = AuthoritySigner::key(None, &program_id);
-    let emitter_key = EmitterAccount::key(None, &program_id);
-
-    // Bridge keys
-    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
-    let payload = PayloadTransfer {
-        amount: U256::from(data.amount),
-        token_address,
-        token_chain,
-        to: data.target_address,
-        to_chain: data.target_chain,
-        fee: U256::from(data.fee),
-    };
-    let sequence_key = Sequence::key(
-        &SequenceDerivationData {
-            emitter_key: &emitter_key,
-        },
-        &bridge_id,
+    let mut accounts = TransferWrappedAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::DerivedRO(program_id),
+        from: AccEntry::Unprivileged(from),
+        from_owner: AccEntry::SignerRO(from_owner),
+        mint: AccEntry::Unprivileged(mint_key),
+        wrapped_meta: WrappedMetaDerivationData { mint_key },
+        authority_signer: AccEntry::DerivedRO(program_id),
+        bridge: AccEntry::seeded::<Bridge<'_, { AccountState::Initialized }>, _>(None, &bridge_id),
+        message: AccEntry::Signer(message_key),
+        emitter: AccEntry::DerivedRO(program_id),
+        sequence: AccEntry::seeded::<Sequence<'_>, _>(
+            &SequenceDerivationData { emitter_key },
+            &bridge_id,
+        ),
+        fee_collector: AccEntry::seeded::<FeeCollector<'_>, _>(None, &bridge_id),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+    }
+    .to_account_metas(&program_id)?;
+    // None of the accounts above are created here, but the core bridge creates the message.
+    append_deps(
+        &mut accounts,
+        vec![sysvar::rent::id(), system_program::id()],
     );
-    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
 
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(config_key, false),
-            AccountMeta::new(from, false),
-            AccountMeta::new_readonly(from_owner, true),
-            AccountMeta::new(wrapped_mint_key, false),
-            AccountMeta::new_readonly(wrapped_meta_key, false),
-            AccountMeta::new_readonly(authority_signer, false),
-            AccountMeta::new(bridge_config, false),
-            AccountMeta::new(message_key, true),
-            AccountMeta::new_readonly(emitter_key, false),
-            AccountMeta::new(sequence_key, false),
-            AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
-            // Dependencies
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: (crate::instruction::Instruction::TransferWrapped, data).try_to_vec()?,
     })
 }
@@ -472,52 +505,35 @@ pub fn attest(
     mint: Pubkey,
     nonce: u32,
 ) -> solitaire::Result<Instruction> {
-    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     let emitter_key = EmitterAccount::key(None, &program_id);
 
-    // SPL Metadata
-    let spl_metadata = SplTokenMeta::key(
-        &SplTokenMetaDerivationData { mint },
-        &spl_token_metadata::id(),
-    );
-
-    // Mint Metadata
-    let mint_meta = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
-        &WrappedMetaDerivationData { mint_key: mint },
-        &program_id,
-    );
-
-    // Bridge Keys
-    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
-    let sequence_key = Sequence::key(
-        &SequenceDerivationData {
-            emitter_key: &emitter_key,
-        },
-        &bridge_id,
-    );
-    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let mut accounts = AttestTokenAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::Derived(program_id),
+        mint: AccEntry::UnprivilegedRO(mint),
+        wrapped_meta: WrappedMetaDerivationData { mint_key: mint },
+        spl_metadata: AccEntry::seeded_ro::<SplTokenMeta<'_>, _>(
+            &SplTokenMetaDerivationData { mint },
+            &spl_token_metadata::id(),
+        ),
+        // Bridge accounts
+        bridge: AccEntry::seeded::<Bridge<'_, { AccountState::Initialized }>, _>(None, &bridge_id),
+        message: AccEntry::Signer(message_key),
+        emitter: AccEntry::DerivedRO(program_id),
+        sequence: AccEntry::seeded::<Sequence<'_>, _>(
+            &SequenceDerivationData { emitter_key },
+            &bridge_id,
+        ),
+        fee_collector: AccEntry::seeded::<FeeCollector<'_>, _>(None, &bridge_id),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+    }
+    .to_account_metas(&program_id)?;
+    // Program
+    accounts.push(AccountMeta::new_readonly(bridge_id, false));
 
     Ok(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(config_key, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(mint_meta, false),
-            AccountMeta::new_readonly(spl_metadata, false),
-            // Bridge accounts
-            AccountMeta::new(bridge_config, false),
-            AccountMeta::new(message_key, true),
-            AccountMeta::new_readonly(emitter_key, false),
-            AccountMeta::new(sequence_key, false),
-            AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
-            // Dependencies
-            AccountMeta::new(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new(solana_program::system_program::id(), false),
-            // Program
-            AccountMeta::new_readonly(bridge_id, false),
-        ],
+        accounts,
         data: (
             crate::instruction::Instruction::AttestToken,
             AttestTokenData { nonce },
@@ -535,45 +551,122 @@ pub fn upgrade_contract(
     spill: Pubkey,
     sequence: u64,
 ) -> Instruction {
-    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
-            sequence,
-        },
-        &program_id,
-    );
-
-    let (upgrade_authority, _) = Pubkey::find_program_address(&["upgrade".as_bytes()], &program_id);
-
     let (program_data, _) = Pubkey::find_program_address(
         &[program_id.as_ref()],
         &solana_program::bpf_loader_upgradeable::id(),
     );
 
-    Instruction {
+    UpgradeContractAccounts {
+        payer: AccEntry::Signer(payer),
+        vaa: ClaimableVAAAccounts {
+            message: AccEntry::UnprivilegedRO(payload_message),
+            claim: ClaimDerivationData {
+                emitter_address: emitter.to_bytes(),
+                emitter_chain: CHAIN_ID_SOLANA,
+                sequence,
+            },
+        },
+        upgrade_authority: AccEntry::DerivedRO(program_id),
+        spill: AccEntry::Unprivileged(spill),
+        buffer: AccEntry::Unprivileged(new_contract),
+        program_data: AccEntry::Unprivileged(program_data),
+        own_address: AccEntry::Unprivileged(program_id),
+        rent: AccEntry::Sysvar(sysvar::rent::id()),
+        clock: AccEntry::Sysvar(sysvar::clock::id()),
+        bpf_loader: AccEntry::CPIProgram(solana_program::bpf_loader_upgradeable::id()),
+        system: AccEntry::CPIProgram(system_program::id()),
+    }
+    .to_ix(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(payload_message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new_readonly(upgrade_authority, false),
-            AccountMeta::new(spill, false),
-            AccountMeta::new(new_contract, false),
-            AccountMeta::new(program_data, false),
-            AccountMeta::new(program_id, false),
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
-            AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (
+        &(
             crate::instruction::Instruction::UpgradeContract,
             UpgradeContractData {},
         )
             .try_to_vec()
             .unwrap(),
+    )
+    .unwrap()
+}
+
+pub fn migrate_config(program_id: Pubkey, payer: Pubkey) -> Instruction {
+    MigrateConfigAccounts {
+        payer: AccEntry::Signer(payer),
+        config: AccEntry::Derived(program_id),
+        system: AccEntry::CPIProgram(system_program::id()),
     }
+    .to_ix(
+        program_id,
+        &(
+            crate::instruction::Instruction::MigrateConfig,
+            MigrateConfigData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+pub fn migrate_wrapped_meta(
+    program_id: Pubkey,
+    payer: Pubkey,
+    token_chain: u16,
+    token_address: ForeignAddress,
+) -> Instruction {
+    let mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedDerivationData {
+            token_chain,
+            token_address,
+        },
+        &program_id,
+    );
+
+    MigrateWrappedMetaAccounts {
+        payer: AccEntry::Signer(payer),
+        // Taken as a plain account on-chain, its legacy data can't be read as a wrapped meta yet
+        meta: AccEntry::Unprivileged(WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+            &WrappedMetaDerivationData { mint_key },
+            &program_id,
+        )),
+        system: AccEntry::CPIProgram(system_program::id()),
+    }
+    .to_ix(
+        program_id,
+        &(
+            crate::instruction::Instruction::MigrateWrappedMeta,
+            MigrateWrappedMetaData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+pub fn migrate_endpoint(
+    program_id: Pubkey,
+    payer: Pubkey,
+    chain: u16,
+    contract: ForeignAddress,
+) -> Instruction {
+    MigrateEndpointAccounts {
+        payer: AccEntry::Signer(payer),
+        // Taken as a plain account on-chain, its legacy data can't be read as a registration yet
+        endpoint: AccEntry::Unprivileged(Endpoint::<'_, { AccountState::Initialized }>::key(
+            &EndpointDerivationData {
+                emitter_chain: chain,
+                emitter_address: contract,
+            },
+            &program_id,
+        )),
+        system: AccEntry::CPIProgram(system_program::id()),
+    }
+    .to_ix(
+        program_id,
+        &(
+            crate::instruction::Instruction::MigrateEndpoint,
+            MigrateEndpointData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    )
+    .unwrap()
 }
@@ -4,7 +4,7 @@
 
 // #![cfg(all(target_arch = "bpf", not(feature = "no-entrypoint")))]
 
-#[cfg(feature = "no-entrypoint")]
+#[cfg(feature = "client")]
 pub mod instructions;
 
 #[cfg(feature = "wasm")]
@@ -16,6 +16,7 @@ extern crate wasm_bindgen;
 pub mod wasm;
 
 pub mod accounts;
+pub mod amount;
 pub mod api;
 pub mod messages;
 pub mod types;
@@ -23,27 +24,46 @@ pub mod types;
 pub use api::{
     attest_token,
     complete_native,
+    complete_native_sol,
     complete_wrapped,
     create_wrapped,
     initialize,
+    migrate_config,
+    migrate_endpoint,
+    migrate_wrapped_meta,
     register_chain,
+    transfer_batch,
     transfer_native,
+    transfer_native_sol,
     transfer_wrapped,
     upgrade_contract,
     AttestToken,
     AttestTokenData,
     CompleteNative,
     CompleteNativeData,
+    CompleteNativeSol,
+    CompleteNativeSolData,
     CompleteWrapped,
     CompleteWrappedData,
     CreateWrapped,
     CreateWrappedData,
     Initialize,
     InitializeData,
+    MigrateConfig,
+    MigrateConfigData,
+    MigrateEndpoint,
+    MigrateEndpointData,
+    MigrateWrappedMeta,
+    MigrateWrappedMetaData,
     RegisterChain,
     RegisterChainData,
+    TransferBatch,
+    TransferBatchAmount,
+    TransferBatchData,
     TransferNative,
     TransferNativeData,
+    TransferNativeSol,
+    TransferNativeSolData,
     TransferWrapped,
     TransferWrappedData,
     UpgradeContract,
@@ -66,6 +86,11 @@ pub enum TokenBridgeError {
     WrongAccountOwner,
     InvalidFee,
     InvalidRecipient,
+    InvalidBatch,
+    AmountTooLarge,
+    DustAmount,
+    RecipientNotRentExempt,
+    InsufficientFees,
 }
 
 impl From<TokenBridgeError> for SolitaireError {
@@ -84,4 +109,10 @@ solitaire! {
     RegisterChain(RegisterChainData) => register_chain,
     CreateWrapped(CreateWrappedData) => create_wrapped,
     UpgradeContract(UpgradeContractData) => upgrade_contract,
+    MigrateConfig(MigrateConfigData) => migrate_config,
+    MigrateWrappedMeta(MigrateWrappedMetaData) => migrate_wrapped_meta,
+    MigrateEndpoint(MigrateEndpointData) => migrate_endpoint,
+    TransferNativeSol(TransferNativeSolData) => transfer_native_sol,
+    CompleteNativeSol(CompleteNativeSolData) => complete_native_sol,
+    TransferBatch(TransferBatchData) => transfer_batch,
 }
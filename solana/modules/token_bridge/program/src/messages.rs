@@ -1,48 +1,35 @@
-use crate::{
-    types::{
-        Address,
-        ChainID,
-    },
-    TokenBridgeError,
-};
-use borsh::{
-    BorshDeserialize,
-    BorshSerialize,
+use crate::types::{
+    Address,
+    ChainID,
 };
 use bridge::{
     vaa::{
+        parse_governance,
+        payload_error,
         DeserializePayload,
         SerializePayload,
     },
     DeserializeGovernancePayload,
     SerializeGovernancePayload,
 };
-use byteorder::{
-    BigEndian,
-    ReadBytesExt,
-    WriteBytesExt,
-};
 use primitive_types::U256;
-use solana_program::{
-    native_token::Sol,
-    program_error::{
-        ProgramError,
-        ProgramError::InvalidAccountData,
-    },
-    pubkey::Pubkey,
-};
+use solana_program::pubkey::Pubkey;
 use solitaire::SolitaireError;
-use std::{
-    error::Error,
-    io::{
-        Cursor,
-        Read,
-        Write,
+use std::io::Write;
+use wormhole_core::{
+    vaa::token::{
+        AssetMeta,
+        GovernanceContractUpgrade,
+        GovernanceRegisterChain,
+        Transfer,
     },
-    str::Utf8Error,
-    string::FromUtf8Error,
+    Chain,
+    GovernanceAction,
 };
 
+// The wire formats are defined by wormhole-core, the types below only map them to the fields the
+// program works with.
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct PayloadTransfer {
     // Amount being transferred (big-endian uint256)
@@ -61,63 +48,29 @@ pub struct PayloadTransfer {
 
 impl DeserializePayload for PayloadTransfer {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut v = Cursor::new(buf);
-
-        if v.read_u8()? != 1 {
-            return Err(SolitaireError::Custom(0));
-        };
-
-        let mut am_data: [u8; 32] = [0; 32];
-        v.read_exact(&mut am_data)?;
-        let amount = U256::from_big_endian(&am_data);
-
-        let mut token_address = Address::default();
-        v.read_exact(&mut token_address)?;
-
-        let token_chain = v.read_u16::<BigEndian>()?;
-
-        let mut to = Address::default();
-        v.read_exact(&mut to)?;
-
-        let to_chain = v.read_u16::<BigEndian>()?;
-
-        let mut fee_data: [u8; 32] = [0; 32];
-        v.read_exact(&mut fee_data)?;
-        let fee = U256::from_big_endian(&fee_data);
-
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let transfer = Transfer::from_bytes(buf).map_err(payload_error)?;
         Ok(PayloadTransfer {
-            amount,
-            token_address,
-            token_chain,
-            to,
-            to_chain,
-            fee,
+            amount: transfer.amount,
+            token_address: transfer.token_address,
+            token_chain: transfer.token_chain.into(),
+            to: transfer.to,
+            to_chain: transfer.to_chain.into(),
+            fee: transfer.fee,
         })
     }
 }
 
 impl SerializePayload for PayloadTransfer {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
-        // Payload ID
-        writer.write_u8(1)?;
-
-        let mut am_data: [u8; 32] = [0; 32];
-        self.amount.to_big_endian(&mut am_data);
-        writer.write(&am_data)?;
-
-        writer.write(&self.token_address)?;
-        writer.write_u16::<BigEndian>(self.token_chain)?;
-        writer.write(&self.to)?;
-        writer.write_u16::<BigEndian>(self.to_chain)?;
-
-        let mut fee_data: [u8; 32] = [0; 32];
-        self.fee.to_big_endian(&mut fee_data);
-        writer.write(&fee_data)?;
-
+        let transfer = Transfer {
+            amount: self.amount,
+            token_address: self.token_address,
+            token_chain: Chain::from(self.token_chain),
+            to: self.to,
+            to_chain: Chain::from(self.to_chain),
+            fee: self.fee,
+        };
+        writer.write_all(&transfer.to_bytes())?;
         Ok(())
     }
 }
@@ -138,70 +91,27 @@ pub struct PayloadAssetMeta {
 
 impl DeserializePayload for PayloadAssetMeta {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        use bstr::ByteSlice;
-
-        let mut v = Cursor::new(buf);
-
-        if v.read_u8()? != 2 {
-            return Err(SolitaireError::Custom(0));
-        };
-
-        let mut token_address = Address::default();
-        v.read_exact(&mut token_address)?;
-
-        let token_chain = v.read_u16::<BigEndian>()?;
-        let decimals = v.read_u8()?;
-
-        let mut symbol_data = vec![0u8; 32];
-        v.read_exact(&mut symbol_data)?;
-        symbol_data.retain(|&c| c != 0);
-        let mut symbol: Vec<char> = symbol_data.chars().collect();
-        symbol.retain(|&c| c != '\u{FFFD}');
-        let symbol: String = symbol.iter().collect();
-
-        let mut name_data = vec![0u8; 32];
-        v.read_exact(&mut name_data)?;
-        name_data.retain(|&c| c != 0);
-        let mut name: Vec<char> = name_data.chars().collect();
-        name.retain(|&c| c != '\u{FFFD}');
-        let name: String = name.iter().collect();
-
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let meta = AssetMeta::from_bytes(buf).map_err(payload_error)?;
         Ok(PayloadAssetMeta {
-            token_address,
-            token_chain,
-            decimals,
-            symbol,
-            name,
+            token_address: meta.token_address,
+            token_chain: meta.token_chain.into(),
+            decimals: meta.decimals,
+            symbol: meta.symbol,
+            name: meta.name,
         })
     }
 }
 
 impl SerializePayload for PayloadAssetMeta {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
-        // Payload ID
-        writer.write_u8(2)?;
-
-        writer.write(&self.token_address)?;
-        writer.write_u16::<BigEndian>(self.token_chain)?;
-
-        writer.write_u8(self.decimals)?;
-
-        let mut symbol: [u8; 32] = [0; 32];
-        for i in 0..self.symbol.len() {
-            symbol[i] = self.symbol.as_bytes()[i];
-        }
-        writer.write(&symbol)?;
-
-        let mut name: [u8; 32] = [0; 32];
-        for i in 0..self.name.len() {
-            name[i] = self.name.as_bytes()[i];
-        }
-        writer.write(&name)?;
-
+        let meta = AssetMeta {
+            token_address: self.token_address,
+            token_chain: Chain::from(self.token_chain),
+            decimals: self.decimals,
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+        };
+        writer.write_all(&meta.to_bytes())?;
         Ok(())
     }
 }
@@ -227,20 +137,10 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut v = Cursor::new(buf);
-        Self::check_governance_header(&mut v)?;
-
-        let chain = v.read_u16::<BigEndian>()?;
-        let mut endpoint_address = [0u8; 32];
-        v.read_exact(&mut endpoint_address)?;
-
-        if v.position() != v.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let action: GovernanceRegisterChain = parse_governance(buf)?;
         Ok(PayloadGovernanceRegisterChain {
-            chain,
-            endpoint_address,
+            chain: action.emitter.into(),
+            endpoint_address: action.endpoint_address,
         })
     }
 }
@@ -250,11 +150,11 @@ where
     Self: SerializeGovernancePayload,
 {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), SolitaireError> {
-        self.write_governance_header(writer)?;
-        // Payload ID
-        writer.write_u16::<BigEndian>(self.chain)?;
-        writer.write(&self.endpoint_address[..])?;
-
+        let action = GovernanceRegisterChain {
+            emitter: Chain::from(self.chain),
+            endpoint_address: self.endpoint_address,
+        };
+        writer.write_all(&action.to_bytes(Chain::Solana))?;
         Ok(())
     }
 }
@@ -267,8 +167,10 @@ pub struct GovernancePayloadUpgrade {
 
 impl SerializePayload for GovernancePayloadUpgrade {
     fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
-        self.write_governance_header(v)?;
-        v.write(&self.new_contract.to_bytes())?;
+        let action = GovernanceContractUpgrade {
+            new_contract: self.new_contract.to_bytes(),
+        };
+        v.write_all(&action.to_bytes(Chain::Solana))?;
         Ok(())
     }
 }
@@ -278,18 +180,9 @@ where
     Self: DeserializeGovernancePayload,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
-        let mut c = Cursor::new(buf);
-        Self::check_governance_header(&mut c)?;
-
-        let mut addr = [0u8; 32];
-        c.read_exact(&mut addr)?;
-
-        if c.position() != c.into_inner().len() as u64 {
-            return Err(InvalidAccountData.into());
-        }
-
+        let action: GovernanceContractUpgrade = parse_governance(buf)?;
         Ok(GovernancePayloadUpgrade {
-            new_contract: Pubkey::new(&addr[..]),
+            new_contract: Pubkey::new_from_array(action.new_contract),
         })
     }
 }
@@ -317,6 +210,17 @@ mod tests {
     use primitive_types::U256;
     use rand::RngCore;
     use solana_program::pubkey::Pubkey;
+    use wormhole_core::{
+        testing::corpus,
+        vaa::token::{
+            AssetMeta,
+            GovernanceContractUpgrade,
+            GovernanceRegisterChain,
+            Transfer,
+        },
+        Chain,
+        GovernanceAction,
+    };
 
     #[test]
     pub fn test_serde_transfer() {
@@ -386,4 +290,69 @@ mod tests {
 
         assert_eq!(original, deser);
     }
+
+    /// Check that `T` accepts exactly the byte strings wormhole-core accepts, and decodes them to
+    /// the same values. `core` parses with wormhole-core and re-serializes what it accepts.
+    fn differential<T, F>(valid: &[u8], core: F)
+    where
+        T: DeserializePayload + SerializePayload,
+        F: Fn(&[u8]) -> Option<Vec<u8>>,
+    {
+        for bytes in corpus(valid) {
+            let solana = T::deserialize(&mut bytes.as_slice()).ok();
+            let solana = solana.map(|payload| payload.try_to_vec().unwrap());
+            assert_eq!(solana, core(&bytes), "payload {:?}", bytes);
+        }
+    }
+
+    #[test]
+    pub fn test_differential_transfer() {
+        let valid = PayloadTransfer {
+            amount: U256::from(1003u64),
+            token_address: [1u8; 32],
+            token_chain: 2,
+            to: [2u8; 32],
+            to_chain: 1,
+            fee: U256::from(3u64),
+        };
+        differential::<PayloadTransfer, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            Transfer::from_bytes(bytes).ok().map(|t| t.to_bytes())
+        });
+    }
+
+    #[test]
+    pub fn test_differential_asset_meta() {
+        let valid = PayloadAssetMeta {
+            token_address: [1u8; 32],
+            token_chain: 2,
+            decimals: 8,
+            symbol: "WETH".to_string(),
+            name: "Wrapped Ether".to_string(),
+        };
+        differential::<PayloadAssetMeta, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            AssetMeta::from_bytes(bytes).ok().map(|m| m.to_bytes())
+        });
+    }
+
+    #[test]
+    pub fn test_differential_governance() {
+        let valid = PayloadGovernanceRegisterChain {
+            chain: 2,
+            endpoint_address: [1u8; 32],
+        };
+        differential::<PayloadGovernanceRegisterChain, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            GovernanceRegisterChain::from_bytes(bytes, Some(Chain::Solana))
+                .ok()
+                .map(|(_, a)| a.to_bytes(Chain::Solana))
+        });
+
+        let valid = GovernancePayloadUpgrade {
+            new_contract: Pubkey::new_unique(),
+        };
+        differential::<GovernancePayloadUpgrade, _>(&valid.try_to_vec().unwrap(), |bytes| {
+            GovernanceContractUpgrade::from_bytes(bytes, Some(Chain::Solana))
+                .ok()
+                .map(|(_, a)| a.to_bytes(Chain::Solana))
+        });
+    }
 }
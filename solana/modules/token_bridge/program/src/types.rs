@@ -29,6 +29,9 @@ pub struct Config {
 }
 
 impl Owned for Config {
+    const DISCRIMINATOR: &'static [u8] = b"cfg";
+    const ACCEPT_LEGACY: bool = true;
+
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
     }
@@ -41,6 +44,9 @@ pub struct EndpointRegistration {
 }
 
 impl Owned for EndpointRegistration {
+    const DISCRIMINATOR: &'static [u8] = b"reg";
+    const ACCEPT_LEGACY: bool = true;
+
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
     }
@@ -54,6 +60,9 @@ pub struct WrappedMeta {
 }
 
 impl Owned for WrappedMeta {
+    const DISCRIMINATOR: &'static [u8] = b"wmt";
+    const ACCEPT_LEGACY: bool = true;
+
     fn owner(&self) -> AccountOwner {
         AccountOwner::This
     }
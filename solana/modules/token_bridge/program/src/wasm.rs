@@ -11,10 +11,12 @@ use crate::{
     instructions::{
         attest,
         complete_native,
+        complete_native_sol,
         complete_wrapped,
         create_wrapped,
         register_chain,
         transfer_native,
+        transfer_native_sol,
         transfer_wrapped,
         upgrade_contract,
     },
@@ -29,10 +31,12 @@ use crate::{
         WrappedMeta,
     },
     CompleteNativeData,
+    CompleteNativeSolData,
     CompleteWrappedData,
     CreateWrappedData,
     RegisterChainData,
     TransferNativeData,
+    TransferNativeSolData,
     TransferWrappedData,
 };
 use borsh::BorshDeserialize;
@@ -45,7 +49,10 @@ use bridge::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        discriminator::deserialize_account,
+        seeded::Seeded,
+    },
     AccountState,
 };
 use std::str::FromStr;
@@ -115,6 +122,44 @@ pub fn transfer_native_ix(
     JsValue::from_serde(&ix).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn transfer_native_sol_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    message: String,
+    nonce: u32,
+    amount: u64,
+    fee: u64,
+    target_address: Vec<u8>,
+    target_chain: u16,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let message = Pubkey::from_str(message.as_str()).unwrap();
+
+    let mut target_addr = [0u8; 32];
+    target_addr.copy_from_slice(target_address.as_slice());
+
+    let ix = transfer_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        TransferNativeSolData {
+            nonce,
+            amount,
+            fee,
+            target_address: target_addr,
+            target_chain,
+        },
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn transfer_wrapped_ix(
     program_id: String,
@@ -216,6 +261,52 @@ pub fn complete_transfer_native_ix(
     JsValue::from_serde(&ix).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn complete_transfer_native_sol_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+    fee_recipient: Option<String>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = PayloadTransfer::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+
+    let ix = complete_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        Pubkey::new(&payload.to[..]),
+        fee_recipient.map(|fee_r| Pubkey::from_str(fee_r.as_str()).unwrap()),
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn complete_transfer_wrapped_ix(
     program_id: String,
@@ -440,10 +531,11 @@ pub fn wrapped_meta_address(program_id: String, mint_address: Vec<u8>) -> Vec<u8
 
 #[wasm_bindgen]
 pub fn parse_wrapped_meta(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&WrappedMeta::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&deserialize_account::<WrappedMeta>(data.as_slice()).unwrap()).unwrap()
 }
 
 #[wasm_bindgen]
 pub fn parse_endpoint_registration(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&EndpointRegistration::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&deserialize_account::<EndpointRegistration>(data.as_slice()).unwrap())
+        .unwrap()
 }
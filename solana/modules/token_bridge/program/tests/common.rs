@@ -71,7 +71,13 @@ use token_bridge::{
 };
 
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::{
+        discriminator::deserialize_account,
+        seeded::{
+            Owned,
+            Seeded,
+        },
+    },
     AccountState,
 };
 
@@ -168,14 +174,14 @@ mod helpers {
     }
 
     /// Fetch account data, the loop is there to re-attempt until data is available.
-    pub fn get_account_data<T: BorshDeserialize>(
+    pub fn get_account_data<T: BorshDeserialize + Owned>(
         client: &RpcClient,
         account: &Pubkey,
     ) -> Option<T> {
         let account = client
             .get_account_with_commitment(account, CommitmentConfig::processed())
             .unwrap();
-        T::try_from_slice(&account.value.unwrap().data).ok()
+        deserialize_account(&account.value.unwrap().data).ok()
     }
 
     pub fn initialize_bridge(
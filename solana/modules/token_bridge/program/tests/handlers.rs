@@ -0,0 +1,903 @@
+#![allow(warnings)]
+
+//! Handler tests on in-memory accounts, see `solitaire::testing`. The SPL token program and the
+//! core bridge run in-process, so a transfer can be followed from the user's token account to the
+//! message the core bridge posts.
+
+use bridge::{
+    accounts::{
+        Bridge,
+        BridgeConfig,
+        BridgeData,
+        FeeCollector,
+        MessageData,
+        PostedMessageData,
+        PostedVAAData,
+        Sequence,
+        SequenceDerivationData,
+    },
+    api::PostVAAData,
+    vaa::{
+        DeserializePayload,
+        SerializePayload,
+    },
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    instruction::Instruction,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{
+        self,
+        clock::Clock,
+    },
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    testing::{
+        Invocation,
+        TestAccount,
+        TestRuntime,
+    },
+    AccountState,
+    Result,
+    SolitaireError,
+};
+use spl_token::state::{
+    Account,
+    AccountState as SplAccountState,
+    Mint,
+};
+use std::str::FromStr;
+
+use token_bridge::{
+    accounts::{
+        AuthoritySigner,
+        BatchMessage,
+        BatchMessageDerivationData,
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        EmitterAccount,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        NativeSolAccount,
+        WrappedMetaDerivationData,
+        WrappedTokenMeta,
+    },
+    instructions::{
+        self,
+        TransferBatchAsset,
+    },
+    messages::PayloadTransfer,
+    types::{
+        Config,
+        EndpointRegistration,
+        WrappedMeta,
+    },
+    CompleteNativeData,
+    CompleteNativeSolData,
+    TokenBridgeError,
+    TransferBatchAmount,
+    TransferBatchData,
+    TransferNativeData,
+    TransferNativeSolData,
+};
+
+const FEE: u64 = 500;
+
+fn process(runtime: &mut TestRuntime, ix: &Instruction) -> Result<Vec<Invocation>> {
+    runtime.process(ix, |p, a, d| token_bridge::instruction::dispatch(p, a, d))
+}
+
+/// The core bridge this program was built against.
+fn bridge_id() -> Pubkey {
+    Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap()
+}
+
+fn config_key(program_id: &Pubkey) -> Pubkey {
+    ConfigAccount::<'_, { AccountState::Initialized }>::key(None, program_id)
+}
+
+/// A runtime with a funded payer, the sysvars, SPL token and an initialized core bridge.
+fn setup() -> (TestRuntime, Pubkey) {
+    let mut runtime = TestRuntime::new();
+    runtime.add_program(spl_token::id(), spl_token::processor::Processor::process);
+    runtime.add_program(bridge_id(), bridge::instruction::solitaire);
+
+    let payer = Pubkey::new_unique();
+    runtime.set_account(payer, TestAccount::wallet(1_000_000_000));
+    runtime.set_account(
+        sysvar::clock::id(),
+        TestAccount::with_sysvar(&Clock {
+            unix_timestamp: 1_000,
+            ..Clock::default()
+        }),
+    );
+    runtime.set_account(
+        sysvar::rent::id(),
+        TestAccount::with_sysvar(&Rent::default()),
+    );
+
+    let fee_collector = TestAccount::wallet(Rent::default().minimum_balance(0));
+    runtime.set_account(
+        Bridge::<'_, { AccountState::Initialized }>::key(None, &bridge_id()),
+        TestAccount::with_data(
+            &bridge_id(),
+            &BridgeData {
+                guardian_set_index: 0,
+                last_lamports: fee_collector.lamports,
+                config: BridgeConfig {
+                    guardian_set_expiration_time: 3600,
+                    fee: FEE,
+                },
+            },
+        ),
+    );
+    runtime.set_account(FeeCollector::key(None, &bridge_id()), fee_collector);
+
+    (runtime, payer)
+}
+
+#[test]
+fn initialize() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+
+    let ix = instructions::initialize(program_id, payer, bridge_id()).unwrap();
+    process(&mut runtime, &ix).unwrap();
+
+    let config = runtime.account(&config_key(&program_id));
+    assert_eq!(config.owner, program_id);
+    let config: Config = config.read().unwrap();
+    assert_eq!(config.wormhole_bridge, bridge_id());
+
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::AlreadyInitialized(_))
+    ));
+}
+
+/// A mint with 9 decimals and a token account holding `amount` of it, delegated to the token
+/// bridge. Returns the mint and the token account.
+fn native_token(runtime: &mut TestRuntime, program_id: &Pubkey, amount: u64) -> (Pubkey, Pubkey) {
+    let (mint, from) = (Pubkey::new_unique(), Pubkey::new_unique());
+    runtime.set_account(
+        mint,
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Mint {
+                mint_authority: COption::Some(Pubkey::new_unique()),
+                supply: amount,
+                decimals: 9,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+        ),
+    );
+    runtime.set_account(
+        from,
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Account {
+                mint,
+                owner: Pubkey::new_unique(),
+                amount,
+                delegate: COption::Some(AuthoritySigner::key(None, program_id)),
+                state: SplAccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: amount,
+                close_authority: COption::None,
+            },
+        ),
+    );
+    (mint, from)
+}
+
+#[test]
+fn transfer_native() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    let (mint, from) = native_token(&mut runtime, &program_id, 5_000_000_123);
+    let message = Pubkey::new_unique();
+
+    let ix = instructions::transfer_native(
+        program_id,
+        bridge_id(),
+        payer,
+        message,
+        from,
+        mint,
+        TransferNativeData {
+            nonce: 1,
+            amount: 5_000_000_123,
+            fee: 1_000_000_000,
+            target_address: [9u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    let invocations = process(&mut runtime, &ix).unwrap();
+    assert!(invocations
+        .iter()
+        .any(|i| i.instruction.program_id == bridge_id()));
+
+    // The amount is truncated to 8 decimals, the remainder stays with the sender.
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody: Account = runtime.account(&custody).unpack().unwrap();
+    assert_eq!(custody.amount, 5_000_000_120);
+    let from: Account = runtime.account(&from).unpack().unwrap();
+    assert_eq!(from.amount, 3);
+
+    let posted: PostedMessageData = runtime.account(&message).read().unwrap();
+    assert_eq!(posted.0.nonce, 1);
+    let payload = PayloadTransfer::deserialize(&mut posted.0.payload.as_slice()).unwrap();
+    assert_eq!(payload.amount, U256::from(500_000_012u64));
+    assert_eq!(payload.fee, U256::from(100_000_000u64));
+    assert_eq!(payload.token_address, mint.to_bytes());
+    assert_eq!(payload.token_chain, CHAIN_ID_SOLANA);
+    assert_eq!(payload.to, [9u8; 32]);
+    assert_eq!(payload.to_chain, 2);
+}
+
+#[test]
+fn transfer_native_to_solana() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    let (mint, from) = native_token(&mut runtime, &program_id, 100);
+
+    let ix = instructions::transfer_native(
+        program_id,
+        bridge_id(),
+        payer,
+        Pubkey::new_unique(),
+        from,
+        mint,
+        TransferNativeData {
+            nonce: 1,
+            amount: 100,
+            fee: 0,
+            target_address: [9u8; 32],
+            target_chain: CHAIN_ID_SOLANA,
+        },
+    )
+    .unwrap();
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::InvalidChain as u64
+    ));
+}
+
+fn native_mint(runtime: &mut TestRuntime) {
+    runtime.set_account(
+        spl_token::native_mint::id(),
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Mint {
+                mint_authority: COption::None,
+                supply: 0,
+                decimals: spl_token::native_mint::DECIMALS,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+        ),
+    );
+}
+
+#[test]
+fn transfer_native_sol() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    native_mint(&mut runtime);
+    let lamports = runtime.account(&payer).lamports;
+    let message = Pubkey::new_unique();
+
+    let ix = instructions::transfer_native_sol(
+        program_id,
+        bridge_id(),
+        payer,
+        message,
+        TransferNativeSolData {
+            nonce: 1,
+            amount: 5_000_123,
+            fee: 1_000_000,
+            target_address: [9u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    process(&mut runtime, &ix).unwrap();
+
+    // The SOL ends up in custody, truncated to 8 decimals, and the temporary account is closed.
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData {
+            mint: spl_token::native_mint::id(),
+        },
+        &program_id,
+    );
+    let custody_rent = Rent::default().minimum_balance(Account::LEN);
+    let custody_account = runtime.account(&custody);
+    assert_eq!(custody_account.lamports, custody_rent + 5_000_120);
+    let custody: Account = custody_account.unpack().unwrap();
+    assert_eq!(custody.amount, 5_000_120);
+    assert_eq!(
+        runtime
+            .account(&NativeSolAccount::key(None, &program_id))
+            .lamports,
+        0
+    );
+
+    let payer_lamports = runtime.account(&payer).lamports;
+    let message_rent = runtime.account(&message).lamports;
+    let sequence = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: EmitterAccount::key(None, &program_id),
+        },
+        &bridge_id(),
+    );
+    let sequence_rent = runtime.account(&sequence).lamports;
+    assert_eq!(
+        lamports - payer_lamports,
+        5_000_120 + custody_rent + message_rent + sequence_rent + FEE
+    );
+
+    let posted: PostedMessageData = runtime.account(&message).read().unwrap();
+    let payload = PayloadTransfer::deserialize(&mut posted.0.payload.as_slice()).unwrap();
+    assert_eq!(payload.amount, U256::from(500_012u64));
+    assert_eq!(payload.fee, U256::from(100_000u64));
+    assert_eq!(
+        payload.token_address,
+        spl_token::native_mint::id().to_bytes()
+    );
+    assert_eq!(payload.token_chain, CHAIN_ID_SOLANA);
+}
+
+#[test]
+fn transfer_native_sol_overflow() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    native_mint(&mut runtime);
+
+    // The rent of the temporary account on top of the amount doesn't fit in 64 bits.
+    let ix = instructions::transfer_native_sol(
+        program_id,
+        bridge_id(),
+        payer,
+        Pubkey::new_unique(),
+        TransferNativeSolData {
+            nonce: 1,
+            amount: u64::MAX,
+            fee: 0,
+            target_address: [9u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::AmountTooLarge as u64
+    ));
+}
+
+/// A wrapped token minted by the token bridge and a token account holding `amount` of it,
+/// delegated to the token bridge. Returns the mint and the token account.
+fn wrapped_token(
+    runtime: &mut TestRuntime,
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> (Pubkey, Pubkey) {
+    let (mint, from) = (Pubkey::new_unique(), Pubkey::new_unique());
+    runtime.set_account(
+        mint,
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Mint {
+                mint_authority: COption::Some(MintSigner::key(None, program_id)),
+                supply: amount,
+                decimals: 8,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+        ),
+    );
+    runtime.set_account(
+        WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+            &WrappedMetaDerivationData { mint_key: mint },
+            program_id,
+        ),
+        TestAccount::with_data(
+            program_id,
+            &WrappedMeta {
+                chain: 2,
+                token_address: [7u8; 32],
+                original_decimals: 18,
+            },
+        ),
+    );
+    runtime.set_account(
+        from,
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Account {
+                mint,
+                owner: *owner,
+                amount,
+                delegate: COption::Some(AuthoritySigner::key(None, program_id)),
+                state: SplAccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: amount,
+                close_authority: COption::None,
+            },
+        ),
+    );
+    (mint, from)
+}
+
+#[test]
+fn transfer_batch() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    let (native_mint, native_from) = native_token(&mut runtime, &program_id, 5_000_000_123);
+    let owner: Account = runtime.account(&native_from).unpack().unwrap();
+    let (wrapped_mint, wrapped_from) = wrapped_token(&mut runtime, &program_id, &owner.owner, 300);
+    let collected = runtime
+        .account(&FeeCollector::key(None, &bridge_id()))
+        .lamports;
+    let batch = Pubkey::new_unique();
+
+    let ix = instructions::transfer_batch(
+        program_id,
+        bridge_id(),
+        payer,
+        owner.owner,
+        batch,
+        &[
+            TransferBatchAsset {
+                from: native_from,
+                mint: native_mint,
+            },
+            TransferBatchAsset {
+                from: wrapped_from,
+                mint: wrapped_mint,
+            },
+        ],
+        TransferBatchData {
+            nonce: 7,
+            transfers: vec![
+                TransferBatchAmount {
+                    amount: 5_000_000_123,
+                    fee: 1_000_000_000,
+                },
+                TransferBatchAmount {
+                    amount: 200,
+                    fee: 10,
+                },
+            ],
+            target_address: [9u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    process(&mut runtime, &ix).unwrap();
+
+    // The native token is locked, the wrapped one burned.
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint: native_mint },
+        &program_id,
+    );
+    let custody: Account = runtime.account(&custody).unpack().unwrap();
+    assert_eq!(custody.amount, 5_000_000_120);
+    let wrapped: Account = runtime.account(&wrapped_from).unpack().unwrap();
+    assert_eq!(wrapped.amount, 100);
+
+    // Both messages share the nonce and each paid the core bridge fee.
+    assert_eq!(
+        runtime
+            .account(&FeeCollector::key(None, &bridge_id()))
+            .lamports,
+        collected + 2 * FEE
+    );
+    let message = |index| {
+        let key = BatchMessage::key(&BatchMessageDerivationData { batch, index }, &program_id);
+        let posted: PostedMessageData = runtime.account(&key).read().unwrap();
+        assert_eq!(posted.0.nonce, 7);
+        PayloadTransfer::deserialize(&mut posted.0.payload.as_slice()).unwrap()
+    };
+    let native = message(0);
+    assert_eq!(native.amount, U256::from(500_000_012u64));
+    assert_eq!(native.fee, U256::from(100_000_000u64));
+    assert_eq!(native.token_address, native_mint.to_bytes());
+    assert_eq!(native.token_chain, CHAIN_ID_SOLANA);
+    let wrapped = message(1);
+    assert_eq!(wrapped.amount, U256::from(200u64));
+    assert_eq!(wrapped.fee, U256::from(10u64));
+    assert_eq!(wrapped.token_address, [7u8; 32]);
+    assert_eq!(wrapped.token_chain, 2);
+    assert_eq!(wrapped.to, [9u8; 32]);
+}
+
+#[test]
+fn transfer_batch_mismatched_amounts() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    let (mint, from) = native_token(&mut runtime, &program_id, 100);
+    let owner: Account = runtime.account(&from).unpack().unwrap();
+
+    let ix = instructions::transfer_batch(
+        program_id,
+        bridge_id(),
+        payer,
+        owner.owner,
+        Pubkey::new_unique(),
+        &[TransferBatchAsset { from, mint }],
+        TransferBatchData {
+            nonce: 1,
+            transfers: vec![],
+            target_address: [9u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::InvalidBatch as u64
+    ));
+}
+
+#[test]
+fn transfer_batch_insufficient_fees() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    let (native_mint, native_from) = native_token(&mut runtime, &program_id, 100);
+    let owner: Account = runtime.account(&native_from).unpack().unwrap();
+    let (wrapped_mint, wrapped_from) = wrapped_token(&mut runtime, &program_id, &owner.owner, 100);
+
+    // Enough for the fee of one message, not for both.
+    runtime.set_account(payer, TestAccount::wallet(2 * FEE - 1));
+    let ix = instructions::transfer_batch(
+        program_id,
+        bridge_id(),
+        payer,
+        owner.owner,
+        Pubkey::new_unique(),
+        &[
+            TransferBatchAsset {
+                from: native_from,
+                mint: native_mint,
+            },
+            TransferBatchAsset {
+                from: wrapped_from,
+                mint: wrapped_mint,
+            },
+        ],
+        TransferBatchData {
+            nonce: 1,
+            transfers: vec![
+                TransferBatchAmount {
+                    amount: 100,
+                    fee: 0,
+                },
+                TransferBatchAmount {
+                    amount: 100,
+                    fee: 0,
+                },
+            ],
+            target_address: [9u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::InsufficientFees as u64
+    ));
+}
+
+#[test]
+fn transfer_native_dust() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    let (mint, from) = native_token(&mut runtime, &program_id, 100);
+
+    // Less than one unit at 8 decimals would send nothing.
+    let ix = instructions::transfer_native(
+        program_id,
+        bridge_id(),
+        payer,
+        Pubkey::new_unique(),
+        from,
+        mint,
+        TransferNativeData {
+            nonce: 1,
+            amount: 9,
+            fee: 0,
+            target_address: [9u8; 32],
+            target_chain: 2,
+        },
+    )
+    .unwrap();
+    assert!(matches!(
+        process(&mut runtime, &ix),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::DustAmount as u64
+    ));
+}
+
+/// A transfer VAA from the token bridge registered for chain 2, as posted by the core bridge.
+/// Returns the posted message and the VAA the instruction builders derive accounts from.
+fn transfer_vaa(
+    runtime: &mut TestRuntime,
+    program_id: &Pubkey,
+    sequence: u64,
+    payload: &PayloadTransfer,
+) -> (Pubkey, PostVAAData) {
+    let vaa = PostVAAData {
+        emitter_chain: 2,
+        emitter_address: [7u8; 32],
+        sequence,
+        payload: payload.try_to_vec().unwrap(),
+        ..PostVAAData::default()
+    };
+    runtime.set_account(
+        Endpoint::<'_, { AccountState::Initialized }>::key(
+            &EndpointDerivationData {
+                emitter_chain: vaa.emitter_chain,
+                emitter_address: vaa.emitter_address,
+            },
+            program_id,
+        ),
+        TestAccount::with_data(
+            program_id,
+            &EndpointRegistration {
+                chain: vaa.emitter_chain,
+                contract: vaa.emitter_address,
+            },
+        ),
+    );
+
+    let message = Pubkey::new_unique();
+    runtime.set_account(
+        message,
+        TestAccount::with_data(
+            &bridge_id(),
+            &PostedVAAData(MessageData {
+                vaa_version: 1,
+                sequence,
+                emitter_chain: vaa.emitter_chain,
+                emitter_address: vaa.emitter_address,
+                payload: vaa.payload.clone(),
+                ..MessageData::default()
+            }),
+        ),
+    );
+    (message, vaa)
+}
+
+#[test]
+fn complete_native() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    let (mint, from) = native_token(&mut runtime, &program_id, 5_000_000_000);
+    process(
+        &mut runtime,
+        &instructions::transfer_native(
+            program_id,
+            bridge_id(),
+            payer,
+            Pubkey::new_unique(),
+            from,
+            mint,
+            TransferNativeData {
+                nonce: 1,
+                amount: 5_000_000_000,
+                fee: 0,
+                target_address: [9u8; 32],
+                target_chain: 2,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let to = Pubkey::new_unique();
+    runtime.set_account(
+        to,
+        TestAccount::with_pack(
+            &spl_token::id(),
+            Account {
+                mint,
+                owner: Pubkey::new_unique(),
+                state: SplAccountState::Initialized,
+                ..Account::default()
+            },
+        ),
+    );
+    let payload = |amount: U256, fee: U256| PayloadTransfer {
+        amount,
+        token_address: mint.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: to.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee,
+    };
+    let complete = |runtime: &mut TestRuntime, sequence: u64, payload: &PayloadTransfer| {
+        let (message, vaa) = transfer_vaa(runtime, &program_id, sequence, payload);
+        let ix = instructions::complete_native(
+            program_id,
+            bridge_id(),
+            payer,
+            message,
+            vaa,
+            to,
+            None,
+            mint,
+            CompleteNativeData {},
+        )
+        .unwrap();
+        process(runtime, &ix)
+    };
+
+    // Amounts arrive with 8 decimals and are scaled back up to the mint's 9.
+    complete(
+        &mut runtime,
+        1,
+        &payload(U256::from(200_000_000u64), U256::from(10u64)),
+    )
+    .unwrap();
+    let account: Account = runtime.account(&to).unpack().unwrap();
+    assert_eq!(account.amount, 2_000_000_000);
+
+    // An amount that only overflows once scaled up, and one past 128 bits.
+    for (sequence, amount) in [
+        (2, U256::from(u64::MAX / 10 + 1)),
+        (3, U256::from(u128::MAX) + 1),
+    ] {
+        assert!(matches!(
+            complete(&mut runtime, sequence, &payload(amount, U256::zero())),
+            Err(SolitaireError::Custom(e)) if e == TokenBridgeError::AmountTooLarge as u64
+        ));
+    }
+
+    // A fee larger than the amount.
+    assert!(matches!(
+        complete(&mut runtime, 4, &payload(U256::from(10u64), U256::from(11u64))),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::InvalidFee as u64
+    ));
+}
+
+#[test]
+fn native_sol_prefunded() {
+    let program_id = Pubkey::new_unique();
+    let (mut runtime, payer) = setup();
+    process(
+        &mut runtime,
+        &instructions::initialize(program_id, payer, bridge_id()).unwrap(),
+    )
+    .unwrap();
+    native_mint(&mut runtime);
+
+    // Anyone can send lamports to the temporary account's address ahead of an instruction.
+    let native_sol = NativeSolAccount::key(None, &program_id);
+    runtime.set_account(native_sol, TestAccount::wallet(1));
+
+    process(
+        &mut runtime,
+        &instructions::transfer_native_sol(
+            program_id,
+            bridge_id(),
+            payer,
+            Pubkey::new_unique(),
+            TransferNativeSolData {
+                nonce: 1,
+                amount: 500_000_000,
+                fee: 0,
+                target_address: [9u8; 32],
+                target_chain: 2,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(runtime.account(&native_sol).lamports, 0);
+
+    let to = Pubkey::new_unique();
+    let fee_recipient = Pubkey::new_unique();
+    let complete = |runtime: &mut TestRuntime, sequence: u64, amount: u64, fee: u64| {
+        runtime.set_account(native_sol, TestAccount::wallet(1));
+        let payload = PayloadTransfer {
+            amount: U256::from(amount),
+            token_address: spl_token::native_mint::id().to_bytes(),
+            token_chain: CHAIN_ID_SOLANA,
+            to: to.to_bytes(),
+            to_chain: CHAIN_ID_SOLANA,
+            fee: U256::from(fee),
+        };
+        let (message, vaa) = transfer_vaa(runtime, &program_id, sequence, &payload);
+        let ix = instructions::complete_native_sol(
+            program_id,
+            bridge_id(),
+            payer,
+            message,
+            vaa,
+            to,
+            Some(fee_recipient),
+            CompleteNativeSolData {},
+        )
+        .unwrap();
+        process(runtime, &ix)
+    };
+
+    // An amount too small to fund a new wallet is refused, the same VAA goes through once the
+    // wallet holds enough SOL.
+    let rent = Rent::default().minimum_balance(0);
+    assert!(matches!(
+        complete(&mut runtime, 1, rent / 10 - 1, 0),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::RecipientNotRentExempt as u64
+    ));
+    runtime.set_account(to, TestAccount::wallet(rent));
+    complete(&mut runtime, 1, rent / 10 - 1, 0).unwrap();
+    assert_eq!(runtime.account(&to).lamports, rent + rent - 10);
+
+    // The same holds for the fee recipient.
+    assert!(matches!(
+        complete(&mut runtime, 2, 10_000_010, 10),
+        Err(SolitaireError::Custom(e)) if e == TokenBridgeError::RecipientNotRentExempt as u64
+    ));
+    complete(&mut runtime, 2, 20_000_000, 10_000_000).unwrap();
+    assert_eq!(runtime.account(&to).lamports, 2 * rent - 10 + 100_000_000);
+    assert_eq!(runtime.account(&fee_recipient).lamports, 100_000_000);
+    assert_eq!(runtime.account(&native_sol).lamports, 0);
+}
@@ -0,0 +1,786 @@
+#![allow(warnings)]
+
+//! The instruction builders in `token_bridge::instructions` are generated from the account
+//! structs of each instruction. These tests pin the generated account lists to the ones that used
+//! to be written out by hand, so a change to an account struct that breaks existing clients shows
+//! up here first.
+//!
+//! The hand-written lists for `initialize`, `register_chain` and `attest` passed the Rent sysvar
+//! and the System program as writable. The runtime demotes both to read-only anyway, the generated
+//! lists pass them read-only like every other instruction.
+
+use primitive_types::U256;
+use solana_program::{
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+    system_program,
+    sysvar,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
+
+use bridge::{
+    accounts::{
+        Bridge,
+        Claim,
+        ClaimDerivationData,
+        FeeCollector,
+        Sequence,
+        SequenceDerivationData,
+    },
+    PostVAAData,
+    CHAIN_ID_SOLANA,
+};
+use token_bridge::{
+    accounts::{
+        AuthoritySigner,
+        BatchMessage,
+        BatchMessageDerivationData,
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        NativeSolAccount,
+        SplTokenMeta,
+        SplTokenMetaDerivationData,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    },
+    instructions::{
+        self,
+        TransferBatchAsset,
+    },
+    messages::{
+        PayloadAssetMeta,
+        PayloadGovernanceRegisterChain,
+        PayloadTransfer,
+    },
+    CompleteNativeData,
+    CompleteNativeSolData,
+    CompleteWrappedData,
+    CreateWrappedData,
+    RegisterChainData,
+    TransferBatchData,
+    TransferNativeData,
+    TransferNativeSolData,
+    TransferWrappedData,
+};
+
+fn config_key(program_id: &Pubkey) -> Pubkey {
+    ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, program_id)
+}
+
+fn vaa() -> PostVAAData {
+    PostVAAData {
+        version: 1,
+        guardian_set_index: 0,
+        timestamp: 1,
+        nonce: 2,
+        emitter_chain: 2,
+        emitter_address: [4u8; 32],
+        sequence: 5,
+        consistency_level: 1,
+        payload: vec![],
+    }
+}
+
+fn claim_key(program_id: &Pubkey, vaa: &PostVAAData) -> Pubkey {
+    Claim::<'_, { AccountState::Initialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        program_id,
+    )
+}
+
+fn endpoint_key(program_id: &Pubkey, emitter_chain: u16, emitter_address: [u8; 32]) -> Pubkey {
+    Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain,
+            emitter_address,
+        },
+        program_id,
+    )
+}
+
+fn wrapped_keys(
+    program_id: &Pubkey,
+    token_chain: u16,
+    token_address: [u8; 32],
+) -> (Pubkey, Pubkey) {
+    let mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedDerivationData {
+            token_chain,
+            token_address,
+        },
+        program_id,
+    );
+    let meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key },
+        program_id,
+    );
+    (mint_key, meta_key)
+}
+
+/// Core bridge accounts used to post a message: bridge config, sequence and fee collector.
+fn bridge_keys(program_id: &Pubkey, bridge_id: &Pubkey) -> (Pubkey, Pubkey, Pubkey) {
+    let emitter_key = EmitterAccount::key(None, program_id);
+    (
+        Bridge::<'_, { AccountState::Uninitialized }>::key(None, bridge_id),
+        Sequence::key(&SequenceDerivationData { emitter_key }, bridge_id),
+        FeeCollector::key(None, bridge_id),
+    )
+}
+
+#[test]
+fn initialize() {
+    let (program_id, payer, bridge) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix = instructions::initialize(program_id, payer, bridge).unwrap();
+
+    assert_eq!(ix.program_id, program_id);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key(&program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn complete_native() {
+    let (program_id, bridge_id, payer, message, to, fee_recipient, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let vaa = vaa();
+    let ix = instructions::complete_native(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        vaa.clone(),
+        to,
+        Some(fee_recipient),
+        mint,
+        CompleteNativeData {},
+    )
+    .unwrap();
+
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim_key(&program_id, &vaa), false),
+            AccountMeta::new_readonly(
+                endpoint_key(&program_id, vaa.emitter_chain, vaa.emitter_address),
+                false
+            ),
+            AccountMeta::new(to, false),
+            AccountMeta::new(fee_recipient, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(CustodySigner::key(None, &program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn complete_native_sol() {
+    let (program_id, bridge_id, payer, message, to) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let vaa = vaa();
+    let ix = instructions::complete_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        vaa.clone(),
+        to,
+        None,
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+
+    let mint = spl_token::native_mint::id();
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim_key(&program_id, &vaa), false),
+            AccountMeta::new_readonly(
+                endpoint_key(&program_id, vaa.emitter_chain, vaa.emitter_address),
+                false
+            ),
+            AccountMeta::new(to, false),
+            AccountMeta::new(to, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(CustodySigner::key(None, &program_id), false),
+            AccountMeta::new(NativeSolAccount::key(None, &program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn complete_wrapped() {
+    let (program_id, bridge_id, payer, message, to) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let vaa = vaa();
+    let payload = PayloadTransfer {
+        amount: U256::from(100),
+        token_address: [7u8; 32],
+        token_chain: 2,
+        to: to.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0),
+    };
+    let ix = instructions::complete_wrapped(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        vaa.clone(),
+        payload,
+        to,
+        None,
+        CompleteWrappedData {},
+    )
+    .unwrap();
+
+    let (mint_key, meta_key) = wrapped_keys(&program_id, 2, [7u8; 32]);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim_key(&program_id, &vaa), false),
+            AccountMeta::new_readonly(
+                endpoint_key(&program_id, vaa.emitter_chain, vaa.emitter_address),
+                false
+            ),
+            AccountMeta::new(to, false),
+            AccountMeta::new(to, false),
+            AccountMeta::new(mint_key, false),
+            AccountMeta::new_readonly(meta_key, false),
+            AccountMeta::new_readonly(MintSigner::key(None, &program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn create_wrapped() {
+    let (program_id, bridge_id, payer, message) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let vaa = vaa();
+    let payload = PayloadAssetMeta {
+        token_address: [7u8; 32],
+        token_chain: 2,
+        decimals: 8,
+        symbol: "".to_string(),
+        name: "".to_string(),
+    };
+    let ix = instructions::create_wrapped(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        vaa.clone(),
+        payload,
+        CreateWrappedData {},
+    )
+    .unwrap();
+
+    let (mint_key, meta_key) = wrapped_keys(&program_id, 2, [7u8; 32]);
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint: mint_key },
+        &spl_token_metadata::id(),
+    );
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new_readonly(
+                endpoint_key(&program_id, vaa.emitter_chain, vaa.emitter_address),
+                false
+            ),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim_key(&program_id, &vaa), false),
+            AccountMeta::new(mint_key, false),
+            AccountMeta::new(meta_key, false),
+            AccountMeta::new(spl_metadata, false),
+            AccountMeta::new_readonly(MintSigner::key(None, &program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn register_chain() {
+    let (program_id, bridge_id, payer, message) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let vaa = vaa();
+    let payload = PayloadGovernanceRegisterChain {
+        chain: 2,
+        endpoint_address: [8u8; 32],
+    };
+    let ix = instructions::register_chain(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        vaa.clone(),
+        payload,
+        RegisterChainData {},
+    )
+    .unwrap();
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new(endpoint_key(&program_id, 2, [8u8; 32]), false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim_key(&program_id, &vaa), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+        ]
+    );
+}
+
+#[test]
+fn transfer_native() {
+    let (program_id, bridge_id, payer, message, from, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix = instructions::transfer_native(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        from,
+        mint,
+        TransferNativeData::default(),
+    )
+    .unwrap();
+
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let (bridge_config, sequence_key, fee_collector_key) = bridge_keys(&program_id, &bridge_id);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new(from, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(AuthoritySigner::key(None, &program_id), false),
+            AccountMeta::new_readonly(CustodySigner::key(None, &program_id), false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(EmitterAccount::key(None, &program_id), false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn transfer_native_sol() {
+    let (program_id, bridge_id, payer, message) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix = instructions::transfer_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        TransferNativeSolData::default(),
+    )
+    .unwrap();
+
+    let mint = spl_token::native_mint::id();
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let (bridge_config, sequence_key, fee_collector_key) = bridge_keys(&program_id, &bridge_id);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new(NativeSolAccount::key(None, &program_id), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(CustodySigner::key(None, &program_id), false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(EmitterAccount::key(None, &program_id), false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn transfer_batch() {
+    let (program_id, bridge_id, payer, from_owner, batch, from, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix = instructions::transfer_batch(
+        program_id,
+        bridge_id,
+        payer,
+        from_owner,
+        batch,
+        &[TransferBatchAsset { from, mint }],
+        TransferBatchData::default(),
+    )
+    .unwrap();
+
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let meta_key = WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+        &WrappedMetaDerivationData { mint_key: mint },
+        &program_id,
+    );
+    let message_key =
+        BatchMessage::key(&BatchMessageDerivationData { batch, index: 0 }, &program_id);
+    let (bridge_config, sequence_key, fee_collector_key) = bridge_keys(&program_id, &bridge_id);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new_readonly(from_owner, true),
+            AccountMeta::new_readonly(AuthoritySigner::key(None, &program_id), false),
+            AccountMeta::new_readonly(CustodySigner::key(None, &program_id), false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new_readonly(batch, true),
+            AccountMeta::new_readonly(EmitterAccount::key(None, &program_id), false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(from, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(meta_key, false),
+            AccountMeta::new(message_key, false),
+        ]
+    );
+}
+
+#[test]
+fn transfer_wrapped() {
+    let (program_id, bridge_id, payer, message, from, from_owner) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix = instructions::transfer_wrapped(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        from,
+        from_owner,
+        2,
+        [7u8; 32],
+        TransferWrappedData::default(),
+    )
+    .unwrap();
+
+    let (mint_key, meta_key) = wrapped_keys(&program_id, 2, [7u8; 32]);
+    let (bridge_config, sequence_key, fee_collector_key) = bridge_keys(&program_id, &bridge_id);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key(&program_id), false),
+            AccountMeta::new(from, false),
+            AccountMeta::new_readonly(from_owner, true),
+            AccountMeta::new(mint_key, false),
+            AccountMeta::new_readonly(meta_key, false),
+            AccountMeta::new_readonly(AuthoritySigner::key(None, &program_id), false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(EmitterAccount::key(None, &program_id), false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn attest() {
+    let (program_id, bridge_id, payer, message, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix = instructions::attest(program_id, bridge_id, payer, message, mint, 0).unwrap();
+
+    let spl_metadata = SplTokenMeta::key(
+        &SplTokenMetaDerivationData { mint },
+        &spl_token_metadata::id(),
+    );
+    let mint_meta = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key: mint },
+        &program_id,
+    );
+    let (bridge_config, sequence_key, fee_collector_key) = bridge_keys(&program_id, &bridge_id);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key(&program_id), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(mint_meta, false),
+            AccountMeta::new_readonly(spl_metadata, false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(EmitterAccount::key(None, &program_id), false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+        ]
+    );
+}
+
+#[test]
+fn upgrade_contract() {
+    let program_id = Pubkey::new_unique();
+    let (payer, message, emitter, new_contract, spill) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix =
+        instructions::upgrade_contract(program_id, payer, message, emitter, new_contract, spill, 7);
+
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_SOLANA,
+            sequence: 7,
+        },
+        &program_id,
+    );
+    let (upgrade_authority, _) = Pubkey::find_program_address(&[b"upgrade"], &program_id);
+    let (program_data, _) = Pubkey::find_program_address(
+        &[program_id.as_ref()],
+        &solana_program::bpf_loader_upgradeable::id(),
+    );
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(upgrade_authority, false),
+            AccountMeta::new(spill, false),
+            AccountMeta::new(new_contract, false),
+            AccountMeta::new(program_data, false),
+            AccountMeta::new(program_id, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn migrate_config() {
+    let (program_id, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instructions::migrate_config(program_id, payer);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key(&program_id), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn migrate_wrapped_meta() {
+    let (program_id, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instructions::migrate_wrapped_meta(program_id, payer, 2, [1u8; 32]);
+
+    let mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedDerivationData {
+            token_chain: 2,
+            token_address: [1u8; 32],
+        },
+        &program_id,
+    );
+    let meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key },
+        &program_id,
+    );
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(meta_key, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+}
+
+#[test]
+fn migrate_endpoint() {
+    let (program_id, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instructions::migrate_endpoint(program_id, payer, 2, [1u8; 32]);
+
+    let endpoint = Endpoint::<'_, { AccountState::Uninitialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: 2,
+            emitter_address: [1u8; 32],
+        },
+        &program_id,
+    );
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(endpoint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+}
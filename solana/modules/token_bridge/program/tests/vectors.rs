@@ -0,0 +1,137 @@
+#![allow(warnings)]
+
+//! Runs the shared wire format vectors in `sdk/vectors` through the token bridge payload types.
+//! wormhole-core and the Terra contracts load the same file.
+
+use primitive_types::U256;
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+
+use bridge::{
+    DeserializePayload,
+    SerializePayload,
+};
+use token_bridge::messages::{
+    GovernancePayloadUpgrade,
+    PayloadAssetMeta,
+    PayloadGovernanceRegisterChain,
+    PayloadTransfer,
+};
+
+const VECTORS: &str = include_str!("../../../../../sdk/vectors/v1.json");
+
+/// The vectors of the given type as (name, bytes, valid, fields, canonical encoding).
+fn vectors(kind: &str) -> Vec<(String, Vec<u8>, bool, Value, Vec<u8>)> {
+    let file: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(file["version"], 1);
+    file["vectors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["type"] == kind)
+        .map(|v| {
+            let bytes = hex::decode(v["hex"].as_str().unwrap()).unwrap();
+            let canonical = match v["canonical"].as_str() {
+                Some(canonical) => hex::decode(canonical).unwrap(),
+                None => bytes.clone(),
+            };
+            let name = v["name"].as_str().unwrap().to_string();
+            (name, bytes, v["valid"].as_bool().unwrap(), v["fields"].clone(), canonical)
+        })
+        .collect()
+}
+
+fn bytes32(fields: &Value, key: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hex::decode(fields[key].as_str().unwrap()).unwrap());
+    out
+}
+
+fn int(fields: &Value, key: &str) -> u64 {
+    fields[key].as_u64().unwrap()
+}
+
+fn u256(fields: &Value, key: &str) -> U256 {
+    U256::from_big_endian(&bytes32(fields, key))
+}
+
+fn string(fields: &Value, key: &str) -> String {
+    fields[key].as_str().unwrap().to_string()
+}
+
+/// Check a payload type against the vectors of `kind`, `expected` builds the payload from the
+/// fields of valid vectors.
+fn check<T, F>(kind: &str, expected: F)
+where
+    T: DeserializePayload + SerializePayload + PartialEq + std::fmt::Debug,
+    F: Fn(&Value) -> T,
+{
+    check_encoding(kind, expected, |encoded, canonical| encoded == canonical);
+}
+
+/// Like `check`, for governance payloads. These are always serialized addressed to Solana, so the
+/// target chain in the header is not compared.
+fn check_governance<T, F>(kind: &str, expected: F)
+where
+    T: DeserializePayload + SerializePayload + PartialEq + std::fmt::Debug,
+    F: Fn(&Value) -> T,
+{
+    check_encoding(kind, expected, |encoded, canonical| {
+        encoded[..33] == canonical[..33] && encoded[35..] == canonical[35..]
+    });
+}
+
+fn check_encoding<T, F, E>(kind: &str, expected: F, same_encoding: E)
+where
+    T: DeserializePayload + SerializePayload + PartialEq + std::fmt::Debug,
+    F: Fn(&Value) -> T,
+    E: Fn(&[u8], &[u8]) -> bool,
+{
+    let vectors = vectors(kind);
+    assert!(!vectors.is_empty(), "no vectors for {}", kind);
+    for (name, data, valid, f, canonical) in vectors {
+        match T::deserialize(&mut data.as_slice()) {
+            Ok(payload) => {
+                assert!(valid, "{}", name);
+                assert_eq!(payload, expected(&f), "{}", name);
+                let encoded = payload.try_to_vec().unwrap();
+                assert!(same_encoding(&encoded, &canonical), "{}", name);
+            }
+            Err(_) => assert!(!valid, "{}", name),
+        }
+    }
+}
+
+#[test]
+fn test_transfer_vectors() {
+    check("token_transfer", |f| PayloadTransfer {
+        amount: u256(f, "amount"),
+        token_address: bytes32(f, "token_address"),
+        token_chain: int(f, "token_chain") as u16,
+        to: bytes32(f, "to"),
+        to_chain: int(f, "to_chain") as u16,
+        fee: u256(f, "fee"),
+    });
+}
+
+#[test]
+fn test_asset_meta_vectors() {
+    check("token_asset_meta", |f| PayloadAssetMeta {
+        token_address: bytes32(f, "token_address"),
+        token_chain: int(f, "token_chain") as u16,
+        decimals: int(f, "decimals") as u8,
+        symbol: string(f, "symbol"),
+        name: string(f, "name"),
+    });
+}
+
+#[test]
+fn test_governance_vectors() {
+    check_governance("token_register_chain", |f| PayloadGovernanceRegisterChain {
+        chain: int(f, "emitter_chain") as u16,
+        endpoint_address: bytes32(f, "endpoint_address"),
+    });
+    check_governance("token_contract_upgrade", |f| GovernancePayloadUpgrade {
+        new_contract: Pubkey::new_from_array(bytes32(f, "new_contract")),
+    });
+}
@@ -0,0 +1,271 @@
+//! Client-side instruction building.
+//!
+//! Every account struct deriving `FromAccounts` also gets a `<Name>Accounts` struct holding one
+//! `AccEntry` per account, in the order they are peeled. Wrapping that struct walks the same
+//! layers the program checks on-chain, so an entry that could never pass `Peel` (a read-only key
+//! for a `Mut<T>` account, a plain key where a `Signer<T>` is expected) is rejected before a
+//! transaction is ever sent. Constant derivations such as `Derive<T, Seed>` are computed from the
+//! program id, accounts annotated with `#[seeds(Type)]` take their derivation data instead of an
+//! entry and are derived from the program id the instruction is built for, and the dependencies
+//! reported by `Peel::deps` are appended at the end, unless the struct ends with `Remaining`
+//! accounts, in which case they must already be listed.
+
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    pubkey::Pubkey,
+    sysvar::Sysvar as SolanaSysvar,
+};
+
+use crate::{
+    processors::seeded::{
+        Owned,
+        Seeded,
+    },
+    types::*,
+    Result,
+    SolitaireError,
+};
+
+/// The sum type for clearly specifying the accounts required on client side.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccEntry {
+    /// Least privileged account.
+    Unprivileged(Pubkey),
+    /// Least privileged account, read-only.
+    UnprivilegedRO(Pubkey),
+
+    /// Accounts that need to sign a Solana call
+    Signer(Pubkey),
+    /// Accounts that need to sign a Solana call, read-only.
+    SignerRO(Pubkey),
+
+    /// Program addresses for cross calls
+    CPIProgram(Pubkey),
+
+    /// Key decided from SPL constants
+    Sysvar(Pubkey),
+
+    /// Key derived from constants and the program address passed in
+    Derived(Pubkey),
+    /// Key derived from constants and the program address passed in, read-only.
+    DerivedRO(Pubkey),
+
+    /// Empty value for nullables
+    Empty,
+}
+
+impl AccEntry {
+    /// Entry for an account derived from the id of another program, such as the core bridge's
+    /// `Sequence` of an emitter. Accounts derived from the program's own id are annotated with
+    /// `#[seeds]` and derived by the generated client.
+    pub fn seeded<T: Seeded<I>, I>(accs: I, program_id: &Pubkey) -> Self {
+        AccEntry::Unprivileged(T::key(accs, program_id))
+    }
+
+    /// Read-only variant of `AccEntry::seeded`.
+    pub fn seeded_ro<T: Seeded<I>, I>(accs: I, program_id: &Pubkey) -> Self {
+        AccEntry::UnprivilegedRO(T::key(accs, program_id))
+    }
+}
+
+/// Types implementing Wrap are those that can be turned into a partial account vector for a
+/// program call. Layers take a single `AccEntry`, account structs take their generated
+/// `<Name>Accounts` struct. The program id is the one the instruction is built for, accounts
+/// derived from it are derived on the fly.
+pub trait Wrap {
+    type Entry;
+
+    fn wrap(_: &Self::Entry, program_id: &Pubkey) -> Result<Vec<AccountMeta>>;
+}
+
+/// Trait used on client side to turn a filled in `<Name>Accounts` struct into an instruction.
+pub trait ToInstruction {
+    /// All accounts of the instruction, followed by the dependencies not already present.
+    fn to_account_metas(&self, program_id: &Pubkey) -> Result<Vec<AccountMeta>>;
+
+    fn to_ix(&self, program_id: Pubkey, ix_data: &[u8]) -> Result<Instruction> {
+        Ok(Instruction::new_with_bytes(
+            program_id,
+            ix_data,
+            self.to_account_metas(&program_id)?,
+        ))
+    }
+}
+
+/// Append dependencies to a list of accounts. Dependencies are emitted once, in the order they
+/// were first reported, and skipped when the instruction already lists them as a regular account.
+pub fn append_deps(account_metas: &mut Vec<AccountMeta>, deps: Vec<Pubkey>) {
+    for dep in deps {
+        if !account_metas.iter().any(|m| m.pubkey == dep) {
+            account_metas.push(AccountMeta::new_readonly(dep, false));
+        }
+    }
+}
+
+/// Check that every dependency is already listed as a regular account. Instructions ending with
+/// `Remaining` accounts take every account left, so nothing can be appended after them.
+pub fn require_deps(account_metas: &[AccountMeta], deps: Vec<Pubkey>) -> Result<()> {
+    match deps
+        .into_iter()
+        .find(|dep| !account_metas.iter().any(|m| m.pubkey == *dep))
+    {
+        Some(dep) => Err(SolitaireError::InvalidAccountEntry(format!(
+            "{} must be listed as an account before the remaining accounts",
+            dep
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn invalid_entry<T>(expected: &str, found: &AccEntry) -> SolitaireError {
+    SolitaireError::InvalidAccountEntry(format!(
+        "{} must be passed as {}, found {:?}",
+        std::any::type_name::<T>(),
+        expected,
+        found
+    ))
+}
+
+impl<T: Wrap<Entry = AccEntry>> Wrap for Option<T> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        match a {
+            AccEntry::Empty => Ok(vec![AccountMeta::new_readonly(
+                Pubkey::new_from_array([0u8; 32]),
+                false,
+            )]),
+            other => T::wrap(other, program_id),
+        }
+    }
+}
+
+impl<T> Wrap for Signer<T> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, _program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        match a {
+            AccEntry::Signer(k) => Ok(vec![AccountMeta::new(*k, true)]),
+            AccEntry::SignerRO(k) => Ok(vec![AccountMeta::new_readonly(*k, true)]),
+            other => Err(invalid_entry::<Self>("Signer or SignerRO", other)),
+        }
+    }
+}
+
+impl<T, const Seed: &'static str> Wrap for Derive<T, Seed> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, _program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        match a {
+            AccEntry::Derived(program_id) => {
+                Ok(vec![AccountMeta::new(Self::key(None, program_id), false)])
+            }
+            AccEntry::DerivedRO(program_id) => Ok(vec![AccountMeta::new_readonly(
+                Self::key(None, program_id),
+                false,
+            )]),
+            other => Err(invalid_entry::<Self>("Derived or DerivedRO", other)),
+        }
+    }
+}
+
+impl<'b, T: Owned + Default, const IsInitialized: AccountState> Wrap
+    for Data<'b, T, IsInitialized>
+{
+    type Entry = AccEntry;
+
+    // Write access is checked by the Mut layer, on-chain an account not wrapped in Mut must be
+    // passed read-only regardless of its state.
+    fn wrap(a: &AccEntry, _program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        match a {
+            AccEntry::Unprivileged(k) => Ok(vec![AccountMeta::new(*k, false)]),
+            AccEntry::UnprivilegedRO(k) => Ok(vec![AccountMeta::new_readonly(*k, false)]),
+            AccEntry::Signer(k) => Ok(vec![AccountMeta::new(*k, true)]),
+            AccEntry::SignerRO(k) => Ok(vec![AccountMeta::new_readonly(*k, true)]),
+            other => Err(invalid_entry::<Self>(
+                "Unprivileged, Signer or the respective read-only variant",
+                other,
+            )),
+        }
+    }
+}
+
+impl<'b, Var: SolanaSysvar> Wrap for Sysvar<'b, Var> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, _program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        match a {
+            AccEntry::Sysvar(k) if Var::check_id(k) => {
+                Ok(vec![AccountMeta::new_readonly(*k, false)])
+            }
+            other => Err(invalid_entry::<Self>(
+                "Sysvar pointing at its own id",
+                other,
+            )),
+        }
+    }
+}
+
+impl<'b> Wrap for Info<'b> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, _program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        match a {
+            AccEntry::Unprivileged(k) => Ok(vec![AccountMeta::new(*k, false)]),
+            AccEntry::UnprivilegedRO(k) | AccEntry::CPIProgram(k) => {
+                Ok(vec![AccountMeta::new_readonly(*k, false)])
+            }
+            other => Err(invalid_entry::<Self>(
+                "Unprivileged, UnprivilegedRO or CPIProgram",
+                other,
+            )),
+        }
+    }
+}
+
+impl<T: Wrap<Entry = AccEntry>> Wrap for Mut<T> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        match a {
+            AccEntry::Unprivileged(_) | AccEntry::Signer(_) | AccEntry::Derived(_) => {
+                T::wrap(a, program_id)
+            }
+            other => Err(invalid_entry::<Self>(
+                "Unprivileged, Signer or Derived (must be mutable on-chain)",
+                other,
+            )),
+        }
+    }
+}
+
+impl<T: Wrap<Entry = AccEntry>> Wrap for MaybeMut<T> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        T::wrap(a, program_id)
+    }
+}
+
+impl<T: Wrap<Entry = AccEntry>> Wrap for System<T> {
+    type Entry = AccEntry;
+
+    fn wrap(a: &AccEntry, program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        T::wrap(a, program_id)
+    }
+}
+
+impl<T: Wrap> Wrap for Remaining<T> {
+    type Entry = Vec<T::Entry>;
+
+    fn wrap(entries: &Vec<T::Entry>, program_id: &Pubkey) -> Result<Vec<AccountMeta>> {
+        let mut account_metas = Vec::new();
+        for entry in entries {
+            account_metas.append(&mut T::wrap(entry, program_id)?);
+        }
+        Ok(account_metas)
+    }
+}
@@ -43,12 +43,18 @@ pub enum SolitaireError {
     /// Owner of the account is ambiguous
     AmbiguousOwner,
 
+    /// The account data does not start with the discriminator of the expected type.
+    InvalidDiscriminator(Pubkey),
+
     /// Account has already been initialized
     AlreadyInitialized(Pubkey),
 
     /// An instruction that wasn't recognised was sent.
     UnknownInstruction(u8),
 
+    /// A client-side account entry does not fit the account type it was passed for.
+    InvalidAccountEntry(String),
+
     Custom(u64),
 }
 
@@ -0,0 +1,355 @@
+//! IDL.
+//!
+//! Programs built with Solitaire describe their accounts entirely through the type system, which
+//! means the account layout of each instruction can be recovered without hand-copying it into
+//! clients. This module walks the same layers that `Peel` does and produces a JSON-serializable
+//! description of every instruction: its discriminator, the Borsh schema of its data and the
+//! accounts it expects, in order.
+
+use borsh::{
+    schema::{
+        Definition,
+        Fields,
+    },
+    BorshSchema,
+};
+use serde::Serialize;
+use solana_program::{
+    pubkey::Pubkey,
+    sysvar::Sysvar as SolanaSysvar,
+};
+use std::collections::BTreeMap;
+
+use crate::{
+    processors::seeded::{
+        AccountOwner,
+        Owned,
+    },
+    types::*,
+    ExecutionContext,
+    Result,
+};
+
+/// Top level description of a Solitaire program.
+#[derive(Debug, Serialize)]
+pub struct Idl {
+    pub name: String,
+    pub version: String,
+    pub instructions: Vec<IdlInstruction>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IdlInstruction {
+    /// Name of the variant in the generated `Instruction` enum.
+    pub name: String,
+
+    /// First byte of the instruction data, used by `dispatch` to select a handler.
+    pub discriminator: u8,
+
+    /// Borsh schema of the data following the discriminator.
+    pub data: IdlSchema,
+
+    /// Accounts in the order `FromAccounts` peels them.
+    pub accounts: Vec<IdlAccount>,
+
+    /// Accounts appended after the named accounts, such as the System program and Rent sysvar
+    /// required to create accounts. Sorted by key, each listed once.
+    pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct IdlAccount {
+    pub name: String,
+    pub is_mut: bool,
+    pub is_signer: bool,
+
+    /// Set for accounts peeled as `Option<T>`, the zero key is passed to signal `None`.
+    pub is_optional: bool,
+
+    /// Set for accounts peeled as `Remaining<T>`, any number of them are passed at the end of the
+    /// account list. Dependencies of the instruction are listed before them, never after.
+    pub is_variadic: bool,
+
+    /// Seeds of accounts derived from the program's id, either constants or fields of the
+    /// derivation data the program checks the account against. Accounts derived from the ids of
+    /// other programs are not described here.
+    pub seeds: Option<Vec<IdlSeed>>,
+
+    /// Expected owner of the account data, if the account holds any.
+    pub owner: Option<IdlOwner>,
+
+    /// Expected initialization state of the account data.
+    pub state: Option<IdlAccountState>,
+
+    /// Rust type of the data held by the account.
+    pub data_type: Option<String>,
+
+    /// Bytes the account data starts with, for types that set a discriminator.
+    pub discriminator: Option<Vec<u8>>,
+
+    /// Fixed address of the account, used for sysvars.
+    pub address: Option<String>,
+}
+
+/// One seed of a program derived address, in the order they are hashed.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IdlSeed {
+    /// Constant bytes, such as the prefix naming the kind of account.
+    Const { value: String },
+
+    /// A field of the derivation data. Integers are encoded big endian, keys and byte arrays as
+    /// they are.
+    Field { name: String, ty: String },
+}
+
+impl IdlSeed {
+    pub fn constant(value: &str) -> Self {
+        IdlSeed::Const {
+            value: value.to_string(),
+        }
+    }
+
+    pub fn field<T>(name: &str) -> Self {
+        IdlSeed::Field {
+            name: name.to_string(),
+            ty: std::any::type_name::<T>().to_string(),
+        }
+    }
+}
+
+/// Describes the seeds a derivation data type produces, implemented alongside `Seeded` for the
+/// data of each kind of program derived account. Fields of account structs annotated with
+/// `#[seeds(Type)]` are described with the seeds of `Type`.
+pub trait IdlSeeds {
+    fn idl_seeds() -> Vec<IdlSeed>;
+}
+
+/// Attach the seeds of `D` to the accounts described by a field annotated with `#[seeds(D)]`.
+pub fn with_seeds<D: IdlSeeds>(mut accounts: Vec<IdlAccount>) -> Vec<IdlAccount> {
+    for account in accounts.iter_mut() {
+        account.seeds = Some(D::idl_seeds());
+    }
+    accounts
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdlOwner {
+    /// Owned by the program the IDL describes.
+    Program,
+    Other(String),
+    Any,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdlAccountState {
+    Initialized,
+    Uninitialized,
+    MaybeInitialized,
+}
+
+/// A Borsh schema with deterministic ordering of definitions.
+#[derive(Debug, Serialize)]
+pub struct IdlSchema {
+    pub declaration: String,
+    pub definitions: BTreeMap<String, IdlDefinition>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IdlDefinition {
+    Array { length: u32, elements: String },
+    Sequence { elements: String },
+    Tuple { elements: Vec<String> },
+    Enum { variants: Vec<IdlField> },
+    Struct { fields: Vec<IdlField> },
+}
+
+#[derive(Debug, Serialize)]
+pub struct IdlField {
+    pub name: Option<String>,
+    pub declaration: String,
+}
+
+impl IdlSchema {
+    pub fn of<T: BorshSchema>() -> Self {
+        let container = T::schema_container();
+        IdlSchema {
+            declaration: container.declaration,
+            definitions: container
+                .definitions
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<Definition> for IdlDefinition {
+    fn from(d: Definition) -> Self {
+        let named = |(name, declaration)| IdlField {
+            name: Some(name),
+            declaration,
+        };
+        match d {
+            Definition::Array { length, elements } => IdlDefinition::Array { length, elements },
+            Definition::Sequence { elements } => IdlDefinition::Sequence { elements },
+            Definition::Tuple { elements } => IdlDefinition::Tuple { elements },
+            Definition::Enum { variants } => IdlDefinition::Enum {
+                variants: variants.into_iter().map(named).collect(),
+            },
+            Definition::Struct { fields } => IdlDefinition::Struct {
+                fields: match fields {
+                    Fields::NamedFields(v) => v.into_iter().map(named).collect(),
+                    Fields::UnnamedFields(v) => v
+                        .into_iter()
+                        .map(|declaration| IdlField {
+                            name: None,
+                            declaration,
+                        })
+                        .collect(),
+                    Fields::Empty => vec![],
+                },
+            },
+        }
+    }
+}
+
+/// Describes the accounts a Peel-able type consumes. Implemented for every layer Solitaire
+/// provides, and derived alongside `FromAccounts` for account structs.
+pub trait IdlAccounts {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount>;
+
+    /// Dependencies of an account struct, mirrors `Peel::deps`.
+    fn idl_deps() -> Vec<Pubkey> {
+        vec![]
+    }
+}
+
+/// Build the IDL entry for a single instruction. The handler is only taken to let the compiler
+/// infer the accounts and data types used by the `solitaire!` macro.
+pub fn instruction<'r, D: BorshSchema, A: IdlAccounts>(
+    name: &str,
+    discriminator: u8,
+    _handler: fn(&ExecutionContext<'r, 'r>, &mut A, D) -> Result<()>,
+) -> IdlInstruction {
+    let mut dependencies = A::idl_deps();
+    dependencies.sort();
+    dependencies.dedup();
+
+    IdlInstruction {
+        name: name.to_string(),
+        discriminator,
+        data: IdlSchema::of::<D>(),
+        accounts: A::idl_accounts(""),
+        dependencies: dependencies.iter().map(|k| k.to_string()).collect(),
+    }
+}
+
+/// Name of an account within a nested account struct. Top level structs are described with an
+/// empty name so their fields keep their own names.
+pub fn nested_name(parent: &str, field: &str) -> String {
+    if parent.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", parent, field)
+    }
+}
+
+/// Apply a modification to each account described by a layer, layers wrapping a single account
+/// produce exactly one entry.
+fn map_accounts<T: IdlAccounts, F: Fn(&mut IdlAccount)>(name: &str, f: F) -> Vec<IdlAccount> {
+    let mut accounts = T::idl_accounts(name);
+    accounts.iter_mut().for_each(f);
+    accounts
+}
+
+impl<T: IdlAccounts> IdlAccounts for Option<T> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        map_accounts::<T, _>(name, |a| a.is_optional = true)
+    }
+}
+
+impl<T: IdlAccounts> IdlAccounts for Remaining<T> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        map_accounts::<T, _>(name, |a| a.is_variadic = true)
+    }
+}
+
+impl<T: IdlAccounts, const Seed: &'static str> IdlAccounts for Derive<T, Seed> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        map_accounts::<T, _>(name, |a| a.seeds = Some(vec![IdlSeed::constant(Seed)]))
+    }
+}
+
+impl<T: IdlAccounts> IdlAccounts for Mut<T> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        map_accounts::<T, _>(name, |a| a.is_mut = true)
+    }
+}
+
+impl<T: IdlAccounts> IdlAccounts for MaybeMut<T> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        T::idl_accounts(name)
+    }
+}
+
+impl<T: IdlAccounts> IdlAccounts for Signer<T> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        map_accounts::<T, _>(name, |a| a.is_signer = true)
+    }
+}
+
+impl<T: IdlAccounts> IdlAccounts for System<T> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        T::idl_accounts(name)
+    }
+}
+
+impl<'b, Var: SolanaSysvar> IdlAccounts for Sysvar<'b, Var> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        vec![IdlAccount {
+            name: name.to_string(),
+            address: Some(Var::id().to_string()),
+            ..IdlAccount::default()
+        }]
+    }
+}
+
+impl<'b> IdlAccounts for Info<'b> {
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        vec![IdlAccount {
+            name: name.to_string(),
+            ..IdlAccount::default()
+        }]
+    }
+}
+
+impl<'b, T: Owned + Default, const IsInitialized: AccountState> IdlAccounts
+    for Data<'b, T, IsInitialized>
+{
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        let owner = match T::default().owner() {
+            AccountOwner::This => IdlOwner::Program,
+            AccountOwner::Other(v) => IdlOwner::Other(v.to_string()),
+            AccountOwner::Any => IdlOwner::Any,
+        };
+
+        let state = match IsInitialized {
+            AccountState::Initialized => IdlAccountState::Initialized,
+            AccountState::Uninitialized => IdlAccountState::Uninitialized,
+            AccountState::MaybeInitialized => IdlAccountState::MaybeInitialized,
+        };
+
+        vec![IdlAccount {
+            name: name.to_string(),
+            owner: Some(owner),
+            state: Some(state),
+            data_type: Some(std::any::type_name::<T>().to_string()),
+            discriminator: Some(T::DISCRIMINATOR.to_vec()).filter(|d| !d.is_empty()),
+            ..IdlAccount::default()
+        }]
+    }
+}
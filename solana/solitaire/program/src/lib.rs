@@ -7,7 +7,6 @@ pub use rocksalt::*;
 // Lacking:
 //
 // - Error is a lacking as its just a basic enum, maybe use errorcode.
-// - Client generation incomplete.
 
 // We need a few Solana things in scope in order to properly abstract Solana.
 use solana_program::{
@@ -50,13 +49,20 @@ use std::{
 
 pub use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 
 // Expose all submodules for consumption.
+#[cfg(feature = "client")]
+pub mod client;
 pub mod error;
+#[cfg(feature = "idl")]
+pub mod idl;
 pub mod macros;
 pub mod processors;
+#[cfg(all(feature = "testing", not(target_arch = "bpf")))]
+pub mod testing;
 pub mod types;
 
 // We can also re-export a set of types at module scope, this defines the intended API we expect
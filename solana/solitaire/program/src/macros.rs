@@ -29,6 +29,7 @@ macro_rules! trace_impl {
 /// - A set of functions which take as arguments the enum fields.
 /// - A Dispatcher that deserializes bytes into the enum and dispatches the function call.
 /// - A set of client calls scoped to the module `api` that can generate instructions.
+/// - With the `idl` feature, an `idl()` function describing the program's instructions.
 #[macro_export]
 macro_rules! solitaire {
     { $($row:ident($kind:ty) => $fn:ident),+ $(,)* } => {
@@ -98,6 +99,26 @@ macro_rules! solitaire {
                 }
             }
 
+            /// Generated:
+            /// Describes every instruction above along with its accounts and data layout, so
+            /// clients can be generated rather than mirroring the account order by hand.
+            #[cfg(feature = "idl")]
+            pub fn idl() -> solitaire::idl::Idl {
+                solitaire::idl::Idl {
+                    name: env!("CARGO_PKG_NAME").to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    instructions: vec![
+                        $(
+                            solitaire::idl::instruction::<$kind, _>(
+                                stringify!($row),
+                                Instruction::$row as u8,
+                                $fn,
+                            ),
+                        )*
+                    ],
+                }
+            }
+
             pub fn solitaire(p: &Pubkey, a: &[AccountInfo], d: &[u8]) -> ProgramResult {
                 trace!("{} {} built with {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), solitaire::PKG_NAME_VERSION);
                 if let Err(err) = dispatch(p, a, d) {
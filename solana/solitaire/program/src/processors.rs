@@ -1,3 +1,4 @@
+pub mod discriminator;
 pub mod keyed;
 pub mod peel;
 pub mod persist;
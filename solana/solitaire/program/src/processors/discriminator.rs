@@ -0,0 +1,122 @@
+//! Account discriminators.
+//!
+//! Accounts of a type that sets `Owned::DISCRIMINATOR` start with those bytes, followed by the
+//! Borsh encoding of the data. `Data` checks them when peeling and writes them when persisting,
+//! so an account of one type cannot be passed where another type with the same owner is expected.
+//! Types that leave the discriminator empty are stored as plain Borsh, as before.
+//!
+//! Adding a discriminator to a type with live accounts is rolled out in three steps:
+//!
+//! 1. Upgrade the program with the discriminator set and `Owned::ACCEPT_LEGACY` on. Accounts
+//!    without the discriminator are still read as plain Borsh, and keep that layout when written
+//!    back unless the discriminator fits.
+//! 2. Run the type's migration instruction, see `migrate_account`, over every live account.
+//! 3. Upgrade again with `ACCEPT_LEGACY` off, from then on only tagged accounts are accepted.
+//!
+//! Until the last step type-confused legacy accounts are only told apart as they were before,
+//! by owner, derivation and whether they decode.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    program::invoke,
+    system_instruction,
+};
+use std::io::{
+    Error,
+    ErrorKind,
+    Write,
+};
+
+use crate::{
+    processors::seeded::Owned,
+    CreationLamports,
+    ExecutionContext,
+    Info,
+    Result,
+    SolitaireError,
+};
+
+/// Remove the discriminator of `T` from the front of the account data, None if the data does not
+/// start with it.
+pub fn strip_discriminator<T: Owned>(data: &[u8]) -> Option<&[u8]> {
+    data.strip_prefix(T::DISCRIMINATOR)
+}
+
+/// Deserialize account data written by `Data`, for clients that fetch accounts as raw bytes.
+/// Legacy accounts are read as plain Borsh while `T` accepts them.
+pub fn deserialize_account<T: Owned + BorshDeserialize>(data: &[u8]) -> std::io::Result<T> {
+    match strip_discriminator::<T>(data) {
+        Some(mut data) => T::deserialize(&mut data),
+        None if T::ACCEPT_LEGACY => T::deserialize(&mut &data[..]),
+        None => Err(Error::new(
+            ErrorKind::InvalidData,
+            "Account data does not start with the expected discriminator",
+        )),
+    }
+}
+
+/// Serialize account data, discriminator first.
+pub fn serialize_account<T: Owned + BorshSerialize>(
+    value: &T,
+    mut data: &mut [u8],
+) -> std::io::Result<()> {
+    data.write_all(T::DISCRIMINATOR)?;
+    value.serialize(&mut data)
+}
+
+/// Size of the account holding `value`, including its discriminator.
+pub fn account_size<T: Owned + BorshSerialize>(value: &T) -> usize {
+    T::DISCRIMINATOR.len() + value.try_to_vec().unwrap().len()
+}
+
+/// Prefix the data of an account created before `T` set a discriminator.
+///
+/// The decoded legacy data is handed to `verify` before anything is written. It must establish
+/// that the account really holds a `T`, usually by checking the account's derivation against the
+/// data: owner and Borsh layout alone do not rule out another account type that happens to decode.
+/// Accounts that already carry the discriminator are left untouched, so migrations can be retried.
+/// The account grows by the size of the discriminator, `payer` tops up its rent.
+pub fn migrate_account<T, F>(
+    ctx: &ExecutionContext,
+    account: &Info,
+    payer: &Info,
+    verify: F,
+) -> Result<()>
+where
+    T: Owned + BorshSerialize + BorshDeserialize,
+    F: FnOnce(&T) -> Result<()>,
+{
+    if account.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*account.owner));
+    }
+
+    let legacy = {
+        let data = account.data.borrow();
+        // Accounts may be allocated larger than their data, trailing bytes are not part of it.
+        let migrated = strip_discriminator::<T>(&data)
+            .map(|mut data| T::deserialize(&mut data).is_ok())
+            .unwrap_or(false);
+        if migrated {
+            return Ok(());
+        }
+        T::deserialize(&mut &data[..])?
+    };
+    verify(&legacy)?;
+
+    let size = account_size(&legacy);
+    let rent = CreationLamports::Exempt.amount(size);
+    let lamports = account.lamports();
+    if rent > lamports {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, rent - lamports),
+            ctx.accounts,
+        )?;
+    }
+
+    account.realloc(size, false)?;
+    serialize_account(&legacy, &mut account.data.borrow_mut())?;
+    Ok(())
+}
@@ -17,11 +17,18 @@ use solana_program::{
 use std::marker::PhantomData;
 
 use crate::{
-    trace,
-    processors::seeded::{
-        AccountOwner,
-        Owned,
+    processors::{
+        discriminator::{
+            account_size,
+            serialize_account,
+            strip_discriminator,
+        },
+        seeded::{
+            AccountOwner,
+            Owned,
+        },
     },
+    trace,
     types::*,
     AccountState::MaybeInitialized,
     Context,
@@ -45,26 +52,32 @@ pub trait Peel<'a, 'b: 'a, 'c> {
 /// Peel a nullable value (0-account means None)
 impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Option<T> {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
-	// Check for 0-account
-	if ctx.info().key == &Pubkey::new_from_array([0u8; 32]) {
-	    trace!(&format!("Peeled {} is None, returning", std::any::type_name::<Option<T>>()));
-	    Ok(None)
-	} else {
-	    Ok(Some(T::peel(ctx)?))
-	}
+        // Check for 0-account
+        if ctx.info().key == &Pubkey::new_from_array([0u8; 32]) {
+            trace!(&format!(
+                "Peeled {} is None, returning",
+                std::any::type_name::<Option<T>>()
+            ));
+            Ok(None)
+        } else {
+            Ok(Some(T::peel(ctx)?))
+        }
     }
 
     fn deps() -> Vec<Pubkey> {
-	T::deps()
+        T::deps()
     }
 
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
-	if let Some(s) = self.as_ref() {
+        if let Some(s) = self.as_ref() {
             T::persist(s, program_id)
-	} else {
-	    trace!(&format!("Peeled {} is None, not persisting", std::any::type_name::<Option<T>>()));
-	    Ok(())
-	}
+        } else {
+            trace!(&format!(
+                "Peeled {} is None, not persisting",
+                std::any::type_name::<Option<T>>()
+            ));
+            Ok(())
+        }
     }
 }
 
@@ -162,6 +175,38 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for System<T> {
     }
 }
 
+/// Peel every account left in the instruction, so `Remaining` must be the last field of its
+/// account struct and programs or sysvars the instruction needs are listed before it. Each item is
+/// peeled with a fresh context, so layers such as `Mut` apply per item.
+impl<'a, 'b: 'a, 'c, T> Peel<'a, 'b, 'c> for Remaining<T>
+where
+    T: for<'d> Peel<'a, 'b, 'd>,
+{
+    fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
+        let mut items = Vec::new();
+        while !ctx.iter.as_slice().is_empty() {
+            items.push(T::peel(&mut Context::new(ctx.this, ctx.iter, ctx.data))?);
+        }
+        trace!(&format!(
+            "Peeled {} remaining accounts as {}",
+            items.len(),
+            std::any::type_name::<T>()
+        ));
+        Ok(Remaining(items))
+    }
+
+    fn deps() -> Vec<Pubkey> {
+        T::deps()
+    }
+
+    fn persist(&self, program_id: &Pubkey) -> Result<()> {
+        for item in self.iter() {
+            item.persist(program_id)?;
+        }
+        Ok(())
+    }
+}
+
 /// Peel a Sysvar
 impl<'a, 'b: 'a, 'c, Var> Peel<'a, 'b, 'c> for Sysvar<'b, Var>
 where
@@ -231,14 +276,12 @@ impl<
                 }
                 (false, T::default())
             }
-            AccountState::Initialized => {
-                (true, T::try_from_slice(&mut *ctx.info().data.borrow_mut())?)
-            }
+            AccountState::Initialized => (true, deserialize_data(ctx.info())?),
             AccountState::MaybeInitialized => {
                 if **ctx.info().lamports.borrow() == 0 {
                     (false, T::default())
                 } else {
-                    (true, T::try_from_slice(&mut *ctx.info().data.borrow_mut())?)
+                    (true, deserialize_data(ctx.info())?)
                 }
             }
         };
@@ -281,8 +324,30 @@ impl<
             return Ok(());
         }
 
-        self.1.serialize(&mut *self.0.data.borrow_mut())?;
+        // Legacy accounts keep their layout until migrated, unless the discriminator fits.
+        let mut data = self.0.data.borrow_mut();
+        if T::ACCEPT_LEGACY
+            && strip_discriminator::<T>(&data).is_none()
+            && data.len() < account_size(&self.1)
+        {
+            self.1.serialize(&mut &mut data[..])?;
+        } else {
+            serialize_account(&self.1, &mut data)?;
+        }
 
         Ok(())
     }
 }
+
+/// Deserialize the data of an initialized account, which must start with the discriminator of
+/// `T` unless `T` still accepts legacy accounts.
+fn deserialize_data<T: BorshDeserialize + Owned>(info: &Info) -> Result<T> {
+    let data = info.data.borrow();
+    match strip_discriminator::<T>(&data) {
+        Some(data) => Ok(T::try_from_slice(data)?),
+        None if T::ACCEPT_LEGACY => {
+            T::try_from_slice(&data).map_err(|_| SolitaireError::InvalidDiscriminator(*info.key))
+        }
+        None => Err(SolitaireError::InvalidDiscriminator(*info.key)),
+    }
+}
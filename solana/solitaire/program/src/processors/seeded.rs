@@ -1,4 +1,7 @@
-use super::keyed::Keyed;
+use super::{
+    discriminator::account_size,
+    keyed::Keyed,
+};
 use crate::{
     system_instruction,
     AccountInfo,
@@ -40,6 +43,15 @@ pub enum AccountOwner {
 }
 
 pub trait Owned {
+    /// Bytes written in front of the data of every account of this type, see the
+    /// `discriminator` module. Empty unless a type opts in.
+    const DISCRIMINATOR: &'static [u8] = &[];
+
+    /// Whether accounts written before this type set its discriminator are still read, as plain
+    /// Borsh. Types that add a discriminator to live accounts keep this on until all of them have
+    /// been migrated.
+    const ACCEPT_LEGACY: bool = false;
+
     fn owner(&self) -> AccountOwner;
 
     fn owner_pubkey(&self, program_id: &Pubkey) -> Result<Pubkey> {
@@ -54,6 +66,9 @@ pub trait Owned {
 impl<'a, T: Owned + Default, const IsInitialized: AccountState> Owned
     for Data<'a, T, IsInitialized>
 {
+    const DISCRIMINATOR: &'static [u8] = T::DISCRIMINATOR;
+    const ACCEPT_LEGACY: bool = T::ACCEPT_LEGACY;
+
     fn owner(&self) -> AccountOwner {
         self.1.owner()
     }
@@ -120,7 +135,7 @@ impl<T: BorshSerialize + Owned + Default, const IsInitialized: AccountState> Acc
     for Data<'_, T, IsInitialized>
 {
     fn size(&self) -> usize {
-        self.1.try_to_vec().unwrap().len()
+        account_size(&self.1)
     }
 }
 
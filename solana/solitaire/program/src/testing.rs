@@ -0,0 +1,510 @@
+//! Host-side unit tests for Solitaire programs.
+//!
+//! `TestRuntime` holds a set of accounts and runs instructions against them without a validator.
+//! Accounts are serialized exactly as the runtime passes them to a program, so `realloc`, `assign`
+//! and duplicate accounts behave as they do on-chain. The program's `dispatch` then peels them with
+//! `FromAccounts`, runs the handler and persists the result, and the accounts are read back.
+//!
+//! Cross-program invocations made by the handler are recorded. The System program is executed
+//! in-process, other programs only if registered with `TestRuntime::add_program`, and are
+//! otherwise treated as succeeding without side-effects.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{
+        deserialize,
+        ProcessInstruction,
+        ProgramResult,
+        BPF_ALIGN_OF_U128,
+        MAX_PERMITTED_DATA_INCREASE,
+    },
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    msg,
+    program_error::ProgramError,
+    program_pack::{
+        IsInitialized,
+        Pack,
+    },
+    program_stubs::{
+        set_syscall_stubs,
+        SyscallStubs,
+    },
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemError,
+    system_program,
+    sysvar::{
+        self,
+        Sysvar as SolanaSysvar,
+    },
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::Once,
+};
+
+use crate::{
+    processors::{
+        discriminator::{
+            deserialize_account,
+            serialize_account,
+        },
+        seeded::{
+            AccountOwner,
+            Owned,
+        },
+    },
+    Result,
+    SolitaireError,
+};
+
+/// State of an account outside of a transaction.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TestAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    /// An account without data owned by the System program, such as a funded wallet.
+    pub fn wallet(lamports: u64) -> Self {
+        TestAccount {
+            lamports,
+            owner: system_program::id(),
+            ..TestAccount::default()
+        }
+    }
+
+    /// A rent-exempt account holding `value` the way `Data` persists it. Accounts owned by the
+    /// program itself, or by any program, are owned by `program_id`.
+    pub fn with_data<T: Owned + BorshSerialize>(program_id: &Pubkey, value: &T) -> Self {
+        let mut data = vec![0u8; T::DISCRIMINATOR.len() + value.try_to_vec().unwrap().len()];
+        serialize_account(value, &mut data).unwrap();
+        let owner = match value.owner() {
+            AccountOwner::Other(owner) => owner,
+            AccountOwner::This | AccountOwner::Any => *program_id,
+        };
+        Self::rent_exempt(data, owner)
+    }
+
+    /// A rent-exempt account holding a `Pack` type, such as SPL token mints and accounts.
+    pub fn with_pack<T: Pack>(owner: &Pubkey, value: T) -> Self {
+        let mut data = vec![0u8; T::LEN];
+        T::pack(value, &mut data).unwrap();
+        Self::rent_exempt(data, *owner)
+    }
+
+    /// A sysvar account, to be stored under `Var::id()`.
+    pub fn with_sysvar<Var: SolanaSysvar>(value: &Var) -> Self {
+        let (key, mut lamports, mut data) = (Var::id(), 0, vec![0u8; Var::size_of()]);
+        let owner = sysvar::id();
+        let mut info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        value.to_account_info(&mut info).unwrap();
+        Self::rent_exempt(data, owner)
+    }
+
+    fn rent_exempt(data: Vec<u8>, owner: Pubkey) -> Self {
+        TestAccount {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner,
+            executable: false,
+        }
+    }
+
+    /// Read back data written by `Data`.
+    pub fn read<T: Owned + BorshDeserialize>(&self) -> std::io::Result<T> {
+        deserialize_account(&self.data)
+    }
+
+    /// Read back a `Pack` type.
+    pub fn unpack<T: Pack + IsInitialized>(&self) -> std::result::Result<T, ProgramError> {
+        T::unpack(&self.data)
+    }
+}
+
+/// A cross-program invocation made while processing an instruction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invocation {
+    pub instruction: Instruction,
+
+    /// Seeds the calling program signed with, one entry per program derived signer.
+    pub signers_seeds: Vec<Vec<Vec<u8>>>,
+}
+
+/// Accounts and programs that instructions are run against.
+pub struct TestRuntime {
+    accounts: HashMap<Pubkey, TestAccount>,
+    programs: HashMap<Pubkey, ProcessInstruction>,
+}
+
+impl Default for TestRuntime {
+    fn default() -> Self {
+        let mut programs: HashMap<Pubkey, ProcessInstruction> = HashMap::new();
+        programs.insert(system_program::id(), process_system_instruction);
+        TestRuntime {
+            accounts: HashMap::new(),
+            programs,
+        }
+    }
+}
+
+impl TestRuntime {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store an account, replacing any previous state.
+    pub fn set_account(&mut self, key: Pubkey, account: TestAccount) {
+        self.accounts.insert(key, account);
+    }
+
+    /// State of an account, accounts never stored or written are empty.
+    pub fn account(&self, key: &Pubkey) -> TestAccount {
+        self.accounts.get(key).cloned().unwrap_or_default()
+    }
+
+    /// Execute invocations of `program_id` with `processor`, such as
+    /// `spl_token::processor::Processor::process`, instead of only recording them.
+    pub fn add_program(&mut self, program_id: Pubkey, processor: ProcessInstruction) {
+        self.programs.insert(program_id, processor);
+    }
+
+    /// Run `ix` through `dispatch`, usually the `instruction::dispatch` generated by `solitaire!`,
+    /// passed as a closure: `|p, a, d| instruction::dispatch(p, a, d)`.
+    ///
+    /// Account state is only written back if the instruction succeeds. The invocations it made
+    /// are returned in the order they were made, nested invocations after their caller.
+    pub fn process<F>(&mut self, ix: &Instruction, dispatch: F) -> Result<Vec<Invocation>>
+    where
+        F: FnOnce(&Pubkey, &[AccountInfo], &[u8]) -> Result<()>,
+    {
+        install_stubs();
+
+        let mut input = Input::serialize(ix, &self.accounts);
+        let result = {
+            let (program_id, accounts, data) =
+                unsafe { deserialize(input.buffer.as_mut_ptr() as *mut u8) };
+            STATE.with(|s| {
+                *s.borrow_mut() = Some(State {
+                    programs: self.programs.clone(),
+                    callers: vec![*program_id],
+                    invocations: vec![],
+                })
+            });
+            dispatch(program_id, &accounts, data)
+        };
+        let invocations = STATE.with(|s| s.borrow_mut().take().unwrap().invocations);
+        result?;
+
+        let accounts = input.read();
+        for (meta, account) in &accounts {
+            if !meta.is_writable && *account != self.account(&meta.pubkey) {
+                return Err(SolitaireError::NonWriteableAccount(meta.pubkey));
+            }
+        }
+        for (meta, account) in accounts {
+            self.accounts.insert(meta.pubkey, account);
+        }
+
+        Ok(invocations)
+    }
+}
+
+/// The serialized input of a program, as laid out by the BPF loader.
+struct Input {
+    /// Backed by u64s to keep the 8 byte alignment the loader guarantees.
+    buffer: Vec<u64>,
+
+    /// Each account passed once with its merged privileges, and the offset of its key.
+    accounts: Vec<(AccountMeta, usize)>,
+}
+
+impl Input {
+    fn serialize(ix: &Instruction, accounts: &HashMap<Pubkey, TestAccount>) -> Self {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut unique: Vec<(AccountMeta, usize)> = Vec::new();
+
+        bytes.extend_from_slice(&(ix.accounts.len() as u64).to_le_bytes());
+        for meta in &ix.accounts {
+            if let Some(index) = unique.iter().position(|(m, _)| m.pubkey == meta.pubkey) {
+                bytes.push(index as u8);
+                bytes.extend_from_slice(&[0u8; 7]);
+                continue;
+            }
+
+            // The runtime passes an account with the union of the privileges it is given.
+            let meta = AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: ix
+                    .accounts
+                    .iter()
+                    .any(|m| m.pubkey == meta.pubkey && m.is_signer),
+                is_writable: ix
+                    .accounts
+                    .iter()
+                    .any(|m| m.pubkey == meta.pubkey && m.is_writable),
+            };
+            let account = accounts.get(&meta.pubkey).cloned().unwrap_or_default();
+
+            bytes.push(u8::MAX);
+            bytes.push(meta.is_signer as u8);
+            bytes.push(meta.is_writable as u8);
+            bytes.push(account.executable as u8);
+            bytes.extend_from_slice(&[0u8; 4]);
+            unique.push((meta.clone(), bytes.len()));
+            bytes.extend_from_slice(meta.pubkey.as_ref());
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(align(bytes.len()), 0);
+            bytes.extend_from_slice(&0u64.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(ix.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&ix.data);
+        bytes.extend_from_slice(ix.program_id.as_ref());
+
+        let mut buffer = vec![0u64; align(bytes.len()) / 8];
+        for (word, chunk) in buffer.iter_mut().zip(bytes.chunks(8)) {
+            let mut le = [0u8; 8];
+            le[..chunk.len()].copy_from_slice(chunk);
+            *word = u64::from_le_bytes(le);
+        }
+
+        Input {
+            buffer,
+            accounts: unique,
+        }
+    }
+
+    /// Read the accounts back after the program has run.
+    fn read(&self) -> Vec<(AccountMeta, TestAccount)> {
+        let bytes: Vec<u8> = self.buffer.iter().flat_map(|w| w.to_le_bytes()).collect();
+        let u64_at = |offset: usize| {
+            let mut le = [0u8; 8];
+            le.copy_from_slice(&bytes[offset..offset + 8]);
+            u64::from_le_bytes(le)
+        };
+
+        self.accounts
+            .iter()
+            .map(|(meta, offset)| {
+                let executable = bytes[offset - 5] != 0;
+                let owner = Pubkey::new(&bytes[offset + 32..offset + 64]);
+                let lamports = u64_at(offset + 64);
+                let len = u64_at(offset + 72) as usize;
+                let data = bytes[offset + 80..offset + 80 + len].to_vec();
+                (
+                    meta.clone(),
+                    TestAccount {
+                        lamports,
+                        data,
+                        owner,
+                        executable,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+fn align(offset: usize) -> usize {
+    (offset + BPF_ALIGN_OF_U128 - 1) / BPF_ALIGN_OF_U128 * BPF_ALIGN_OF_U128
+}
+
+/// Per-thread state of the instruction being processed, tests run on separate threads.
+struct State {
+    programs: HashMap<Pubkey, ProcessInstruction>,
+
+    /// Program that is currently executing, last.
+    callers: Vec<Pubkey>,
+
+    invocations: Vec<Invocation>,
+}
+
+thread_local! {
+    static STATE: RefCell<Option<State>> = RefCell::new(None);
+}
+
+static INSTALL_STUBS: Once = Once::new();
+
+fn install_stubs() {
+    INSTALL_STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(TestStubs));
+    });
+}
+
+struct TestStubs;
+
+impl SyscallStubs for TestStubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let (caller, processor) = STATE.with(|s| {
+            let mut s = s.borrow_mut();
+            let s = s
+                .as_mut()
+                .expect("invoke called outside of TestRuntime::process");
+            s.invocations.push(Invocation {
+                instruction: instruction.clone(),
+                signers_seeds: signers_seeds
+                    .iter()
+                    .map(|seeds| seeds.iter().map(|seed| seed.to_vec()).collect())
+                    .collect(),
+            });
+            (
+                *s.callers.last().unwrap(),
+                s.programs.get(&instruction.program_id).copied(),
+            )
+        });
+
+        // Accounts are passed to the callee in instruction order, with the privileges the
+        // instruction asks for. Those can only be granted if the caller holds them, or for
+        // signers, if the caller derives the account from one of its signer seeds.
+        let signers: Vec<Pubkey> = signers_seeds
+            .iter()
+            .filter_map(|seeds| Pubkey::create_program_address(seeds, &caller).ok())
+            .collect();
+        let mut accounts = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let mut info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?
+                .clone();
+            if meta.is_signer && !info.is_signer && !signers.contains(info.key) {
+                msg!("{} must sign the invocation", info.key);
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !info.is_writable {
+                msg!("{} is not writable in the caller", info.key);
+                return Err(ProgramError::InvalidArgument);
+            }
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            accounts.push(info);
+        }
+
+        let processor = match processor {
+            Some(processor) => processor,
+            None => return Ok(()),
+        };
+        STATE.with(|s| {
+            s.borrow_mut()
+                .as_mut()
+                .unwrap()
+                .callers
+                .push(instruction.program_id)
+        });
+        let result = processor(&instruction.program_id, &accounts, &instruction.data);
+        STATE.with(|s| s.borrow_mut().as_mut().unwrap().callers.pop());
+        result
+    }
+}
+
+/// The subset of the System program Solitaire programs use: creating, funding and assigning
+/// accounts.
+fn process_system_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // System instructions are bincode encoded, a u32 variant followed by its fields.
+    let u64_at = |offset: usize| -> std::result::Result<u64, ProgramError> {
+        let mut le = [0u8; 8];
+        le.copy_from_slice(
+            data.get(offset..offset + 8)
+                .ok_or(ProgramError::InvalidInstructionData)?,
+        );
+        Ok(u64::from_le_bytes(le))
+    };
+    let pubkey_at = |offset: usize| -> std::result::Result<Pubkey, ProgramError> {
+        Ok(Pubkey::new(
+            data.get(offset..offset + 32)
+                .ok_or(ProgramError::InvalidInstructionData)?,
+        ))
+    };
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+
+    match u64_at(0)? as u32 {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            let (from, to) = (account(0)?, account(1)?);
+            if to.lamports() != 0 || to.data_len() != 0 || *to.owner != system_program::id() {
+                return Err(ProgramError::Custom(
+                    SystemError::AccountAlreadyInUse as u32,
+                ));
+            }
+            transfer(from, to, u64_at(4)?)?;
+            allocate(to, u64_at(12)? as usize)?;
+            to.assign(&pubkey_at(20)?);
+        }
+        // Assign { owner }
+        1 => account(0)?.assign(&pubkey_at(4)?),
+        // Transfer { lamports }
+        2 => transfer(account(0)?, account(1)?, u64_at(4)?)?,
+        // Allocate { space }
+        8 => allocate(account(0)?, u64_at(4)? as usize)?,
+        _ => {
+            msg!("System instruction not supported by TestRuntime");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+    Ok(())
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if from.lamports() < lamports {
+        return Err(ProgramError::Custom(
+            SystemError::ResultWithNegativeLamports as u32,
+        ));
+    }
+    **from.lamports.borrow_mut() -= lamports;
+    **to.lamports.borrow_mut() += lamports;
+    Ok(())
+}
+
+fn allocate(account: &AccountInfo, space: usize) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    // Accounts are laid out with room for MAX_PERMITTED_DATA_INCREASE bytes to grow, larger
+    // accounts cannot be created in tests.
+    if account.data_len() != 0 || space > MAX_PERMITTED_DATA_INCREASE {
+        return Err(ProgramError::Custom(
+            SystemError::InvalidAccountDataLength as u32,
+        ));
+    }
+    account.realloc(space, true)
+}
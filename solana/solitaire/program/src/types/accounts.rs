@@ -18,7 +18,10 @@ use std::ops::{
 };
 
 use crate::{
-    processors::seeded::Owned,
+    processors::{
+        discriminator::account_size,
+        seeded::Owned,
+    },
     CreationLamports,
     Derive,
     ExecutionContext,
@@ -114,7 +117,7 @@ impl<const Seed: &'static str, T: BorshSerialize + Owned + Default>
         lamports: CreationLamports,
     ) -> Result<()> {
         // Get serialized struct size
-        let size = self.0.try_to_vec().unwrap().len();
+        let size = account_size(&self.0 .1);
         let ix = system_instruction::create_account(
             payer,
             self.0 .0.key,
@@ -38,6 +38,11 @@ pub struct System<Next>(pub Next);
 #[repr(transparent)]
 pub struct Derive<Next, const Seed: &'static str>(pub Next);
 
+/// Takes every account left in the instruction, each one peeled as `Next`. Must be the last field
+/// of an account struct.
+#[repr(transparent)]
+pub struct Remaining<Next>(pub Vec<Next>);
+
 // Several traits are required for types defined here, they cannot be defined in another file due
 // to orphan instance limitations.
 
@@ -105,3 +110,16 @@ impl<T, const Seed: &'static str> DerefMut for Derive<T, Seed> {
         unsafe { std::mem::transmute(&mut self.0) }
     }
 }
+
+impl<T> Deref for Remaining<T> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Remaining<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,63 @@
+//! Derive macro logic for IdlAccounts
+
+use crate::seeds_type;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data,
+    DataStruct,
+    Fields,
+};
+
+/// Generate an IdlAccounts implementation for a product of accounts. Each field is described by
+/// the IdlAccounts instance of its type, in the same order FromAccounts peels them, with the seeds
+/// of its `#[seeds(Type)]` annotation if it has one.
+pub fn generate_idl_accounts(
+    name: &syn::Ident,
+    impl_generics: &syn::ImplGenerics,
+    type_generics: &syn::TypeGenerics,
+    data: &Data,
+) -> TokenStream2 {
+    match *data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(ref fields),
+            ..
+        }) => {
+            let expanded_appends = fields.named.iter().map(|field| {
+                let field_name = &field.ident;
+                let ty = &field.ty;
+
+                let field_accounts = quote! {
+                    <#ty as solitaire::idl::IdlAccounts>::idl_accounts(
+                        &solitaire::idl::nested_name(name, stringify!(#field_name)),
+                    )
+                };
+                match seeds_type(field) {
+                    Some(seeds) => quote! {
+                        accounts.append(&mut solitaire::idl::with_seeds::<#seeds>(#field_accounts));
+                    },
+                    None => quote! {
+                        accounts.append(&mut #field_accounts);
+                    },
+                }
+            });
+
+            quote! {
+                /// Macro generated implementation of IdlAccounts by Solitaire.
+                #[cfg(feature = "idl")]
+                impl #impl_generics solitaire::idl::IdlAccounts for #name #type_generics {
+                    fn idl_accounts(name: &str) -> Vec<solitaire::idl::IdlAccount> {
+                        let mut accounts = Vec::new();
+                        #(#expanded_appends;)*
+                        accounts
+                    }
+
+                    fn idl_deps() -> Vec<solana_program::pubkey::Pubkey> {
+                        <Self as solitaire::Peel>::deps()
+                    }
+                }
+            }
+        }
+        _ => unimplemented!(),
+    }
+}
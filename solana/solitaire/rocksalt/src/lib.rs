@@ -1,7 +1,9 @@
 #![allow(warnings)]
 
+mod idl;
 mod to_instruction;
 
+use idl::*;
 use to_instruction::*;
 
 use solana_program::{
@@ -34,46 +36,17 @@ use syn::{
     Index,
 };
 
-#[proc_macro_derive(ToInstruction)]
-pub fn derive_to_instruction(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
-
-    // Type params of the instruction context account
-    let type_params: Vec<GenericParam> = input
-        .generics
-        .type_params()
-        .map(|v| GenericParam::Type(v.clone()))
-        .collect();
-
-    // Generics lifetimes of the peel type
-    let mut peel_g = input.generics.clone();
-    peel_g.params = parse_quote!('a, 'b: 'a, 'c);
-    let (_, peel_type_g, _) = peel_g.split_for_impl();
-
-    // Params of the instruction context
-    let mut type_generics = input.generics.clone();
-    type_generics.params = parse_quote!('b);
-    for x in &type_params {
-        type_generics.params.push(x.clone());
-    }
-    let (type_impl_g, type_g, _) = type_generics.split_for_impl();
-
-    // Combined lifetimes of peel and the instruction context
-    let mut combined_generics = Generics::default();
-    combined_generics.params = peel_g.params.clone();
-    for x in &type_params {
-        combined_generics.params.push(x.clone());
-    }
-    let (combined_impl_g, _, _) = combined_generics.split_for_impl();
-
-    let expanded = generate_to_instruction(&name, &combined_impl_g, &input.data);
-    TokenStream::from(expanded)
-}
-
 /// Generate a FromAccounts implementation for a product of accounts. Each field is constructed by
 /// a call to the Verify::verify instance of its type.
-#[proc_macro_derive(FromAccounts)]
+///
+/// Fields holding program derived accounts are annotated with `#[seeds(Type)]`, naming the
+/// derivation data their `Seeded` implementation takes. The annotation does not change how the
+/// account is checked on-chain, it describes the account to the IDL and lets the generated client
+/// derive the key from that data.
+///
+/// Structs generic over types cannot have their client generated, they are marked with
+/// `#[client(manual)]` and implement `solitaire::client::Wrap` by hand.
+#[proc_macro_derive(FromAccounts, attributes(seeds, client))]
 pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
@@ -109,6 +82,8 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
     let from_method = generate_fields(&name, &input.data);
     let persist_method = generate_persist(&name, &input.data);
     let deps_method = generate_deps_fields(&name, &input.data);
+    let idl_impl = generate_idl_accounts(&name, &type_impl_g, &type_g, &input.data);
+    let client_impl = generate_to_instruction(&name, &input.attrs, &input.generics, &input.data);
     let expanded = quote! {
         /// Macro generated implementation of FromAccounts by Solitaire.
         impl #combined_impl_g solitaire::FromAccounts #peel_type_g for #name #type_g {
@@ -138,6 +113,10 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
                 #persist_method
             }
         }
+
+        #idl_impl
+
+        #client_impl
     };
 
     // Hand the output tokens back to the compiler
@@ -153,6 +132,18 @@ fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
             match data.fields {
                 // For now, we only care about struct { a: T } forms, not struct(T);
                 Fields::Named(ref fields) => {
+                    // `Remaining` takes every account left, nothing can be peeled after it.
+                    let last = fields.named.len().saturating_sub(1);
+                    if let Some(field) =
+                        fields.named.iter().take(last).find(|f| is_remaining(&f.ty))
+                    {
+                        return syn::Error::new(
+                            field.span(),
+                            "`Remaining` takes every account left, it must be the last field",
+                        )
+                        .to_compile_error();
+                    }
+
                     // For each field, generate an expression that parses an account info field
                     // from the Solana accounts list. This relies on Verify::verify to do most of
                     // the work.
@@ -284,3 +275,34 @@ fn generate_persist(name: &syn::Ident, data: &Data) -> TokenStream2 {
         Data::Enum(_) | Data::Union(_) => unimplemented!(),
     }
 }
+
+/// The derivation data type of a field annotated with `#[seeds(Type)]`.
+fn seeds_type(field: &syn::Field) -> Option<syn::Type> {
+    field
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("seeds"))
+        .map(|attr| attr.parse_args().expect("expected #[seeds(Type)]"))
+}
+
+/// Whether a field is peeled as `Remaining<T>`.
+fn is_remaining(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Remaining"),
+        _ => false,
+    }
+}
+
+/// Whether the struct opted out of client generation with `#[client(manual)]`.
+fn client_manual(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path.is_ident("client")
+            && attr
+                .parse_args::<syn::Ident>()
+                .map_or(false, |arg| arg == "manual")
+    })
+}
@@ -1,118 +1,207 @@
 //! Derive macro logic for ToInstruction
 
-use proc_macro::TokenStream;
+use crate::{
+    client_manual,
+    is_remaining,
+    seeds_type,
+};
 use proc_macro2::{
+    Group,
     Span,
     TokenStream as TokenStream2,
+    TokenTree,
 };
 use quote::{
     quote,
-    quote_spanned,
+    ToTokens,
 };
 use syn::{
-    parse_macro_input,
-    parse_quote,
-    spanned::Spanned,
     Data,
     DataStruct,
-    DeriveInput,
     Fields,
     GenericParam,
     Generics,
-    Index,
 };
 
+/// Generate the client-side `<Name>Accounts` struct for a product of accounts, along with the Wrap
+/// and ToInstruction implementations that turn it into account metas. Account structs borrow
+/// their accounts, the client-side struct does not, so lifetimes are replaced with `'static` when
+/// naming the on-chain types.
+///
+/// Fields annotated with `#[seeds(Type)]` take their derivation data on the client and the key is
+/// derived from it and the program id the instruction is built for. Other fields take the entry of
+/// their type, which is an `AccEntry` for single accounts.
+///
+/// Structs generic over anything but lifetimes (such as `ClaimableVAA<'b, T>`) cannot name the
+/// entries of their fields without their parameters, those are marked `#[client(manual)]` and
+/// implement Wrap by hand.
 pub fn generate_to_instruction(
     name: &syn::Ident,
-    impl_generics: &syn::ImplGenerics,
+    attrs: &[syn::Attribute],
+    generics: &Generics,
     data: &Data,
 ) -> TokenStream2 {
+    if generics
+        .params
+        .iter()
+        .any(|p| !matches!(p, GenericParam::Lifetime(_)))
+    {
+        if client_manual(attrs) {
+            return quote!();
+        }
+        return syn::Error::new(
+            name.span(),
+            "cannot generate the client of a struct generic over types, mark it \
+             #[client(manual)] and implement solitaire::client::Wrap by hand",
+        )
+        .to_compile_error();
+    }
+
     match *data {
         Data::Struct(DataStruct {
             fields: Fields::Named(ref fields),
             ..
         }) => {
-            let expanded_appends = fields.named.iter().map(|field| {
-                let name = &field.ident;
-                let ty = &field.ty;
+            let lifetimes: Vec<syn::Ident> = generics
+                .lifetimes()
+                .map(|l| l.lifetime.ident.clone())
+                .collect();
+
+            // The account struct itself, with every lifetime set to 'static.
+            let static_lifetimes = lifetimes.iter().map(|_| quote!('static));
+            let static_name = quote!(#name<#(#static_lifetimes),*>);
 
-                quote! {
-                    deps.append(&mut <#ty as solitaire::Peel>::deps());
-                    account_metas.append(&mut <#ty as solitaire_client::Wrap>::wrap(&self.#name)?);
-			if let Some(pair) = <#ty as solitaire_client::Wrap>::keypair(self.#name) {
-			    signers.push(pair);
-			}
-                }
-            });
             let client_struct_name =
                 syn::Ident::new(&format!("{}Accounts", name.to_string()), Span::call_site());
 
-            let client_struct_decl = generate_clientside_struct(&name, &client_struct_name, &data);
+            let client_fields = fields.named.iter().map(|field| {
+                let field_name = &field.ident;
+                match seeds_type(field) {
+                    Some(seeds) => quote! {
+                        pub #field_name: #seeds
+                    },
+                    None => {
+                        let ty = make_static(field.ty.to_token_stream(), &lifetimes);
+                        quote! {
+                            pub #field_name: <#ty as solitaire::client::Wrap>::Entry
+                        }
+                    }
+                }
+            });
 
-            quote! {
-            /// Solitaire-generated client-side #name representation
-            #[cfg(feature = "client")]
-            #client_struct_decl
+            let expanded_appends = fields.named.iter().map(|field| {
+                let field_name = &field.ident;
+                match seeds_type(field) {
+                    Some(seeds) => {
+                        let (inner, writable) = seeded_account(&field.ty);
+                        let inner = make_static(inner.to_token_stream(), &lifetimes);
+                        let meta = if writable {
+                            quote!(new)
+                        } else {
+                            quote!(new_readonly)
+                        };
+                        quote! {
+                            account_metas.push(solana_program::instruction::AccountMeta::#meta(
+                                <#inner as solitaire::processors::seeded::Seeded<&#seeds>>::key(&accs.#field_name, program_id),
+                                false,
+                            ));
+                        }
+                    }
+                    None => {
+                        let ty = make_static(field.ty.to_token_stream(), &lifetimes);
+                        quote! {
+                            account_metas.append(&mut <#ty as solitaire::client::Wrap>::wrap(&accs.#field_name, program_id)?);
+                        }
+                    }
+                }
+            });
 
-                /// Solitaire-generatied ToInstruction implementation
-            #[cfg(feature = "client")]
-                impl #impl_generics  solitaire_client::ToInstruction for #client_struct_name {
-                    fn to_ix(
-                self,
-                program_id: solana_program::pubkey::Pubkey,
-                ix_data: &[u8]) -> std::result::Result<
-                (solitaire_client::Instruction, Vec<solitaire_client::Keypair>),
-                            solitaire::ErrBox
-                > {
-            use solana_program::{pubkey::Pubkey, instruction::Instruction};
-            let mut account_metas = Vec::new();
-            let mut signers = Vec::new();
-            let mut deps = Vec::new();
+            // Dependencies cannot follow `Remaining` accounts, they must already be listed.
+            let deps = if fields.named.last().map_or(false, |f| is_remaining(&f.ty)) {
+                quote!(solitaire::client::require_deps(&account_metas, <#static_name as solitaire::Peel>::deps())?;)
+            } else {
+                quote!(solitaire::client::append_deps(&mut account_metas, <#static_name as solitaire::Peel>::deps());)
+            };
 
-            #(#expanded_appends;)*
+            let doc = format!(
+                "Solitaire-generated client-side representation of the accounts of `{}`.",
+                name
+            );
 
-            // Add dependencies
-            deps.dedup();
-            let mut dep_ams = deps.iter().map(|v| solana_program::instruction::AccountMeta::new_readonly(*v, false)).collect();
-            account_metas.append(&mut dep_ams);
+            quote! {
+                #[doc = #doc]
+                #[cfg(feature = "client")]
+                pub struct #client_struct_name {
+                    #(#client_fields,)*
+                }
 
-            Ok((solana_program::instruction::Instruction::new_with_bytes(program_id,
-                                             ix_data,
-                                             account_metas), signers))
+                /// Solitaire-generated Wrap implementation
+                #[cfg(feature = "client")]
+                impl solitaire::client::Wrap for #static_name {
+                    type Entry = #client_struct_name;
 
+                    fn wrap(accs: &Self::Entry, program_id: &solana_program::pubkey::Pubkey) -> solitaire::Result<Vec<solana_program::instruction::AccountMeta>> {
+                        let mut account_metas = Vec::new();
+                        #(#expanded_appends)*
+                        Ok(account_metas)
                     }
-
                 }
+
+                /// Solitaire-generated ToInstruction implementation
+                #[cfg(feature = "client")]
+                impl solitaire::client::ToInstruction for #client_struct_name {
+                    fn to_account_metas(&self, program_id: &solana_program::pubkey::Pubkey) -> solitaire::Result<Vec<solana_program::instruction::AccountMeta>> {
+                        let mut account_metas = <#static_name as solitaire::client::Wrap>::wrap(self, program_id)?;
+                        #deps
+                        Ok(account_metas)
+                    }
                 }
+            }
         }
         _ => unimplemented!(),
     }
 }
 
-pub fn generate_clientside_struct(
-    name: &syn::Ident,
-    client_struct_name: &syn::Ident,
-    data: &Data,
-) -> TokenStream2 {
-    match *data {
-        Data::Struct(DataStruct {
-            fields: Fields::Named(ref fields),
-            ..
-        }) => {
-            let expanded_fields = fields.named.iter().map(|field| {
-                let field_name = &field.ident;
-
-                quote! {
-                    #field_name: solitaire_client::AccEntry
+/// The account type a `#[seeds]` field is derived as, with its `Mut` or `MaybeMut` layer peeled,
+/// and whether the account is passed writable.
+fn seeded_account(ty: &syn::Type) -> (syn::Type, bool) {
+    if let syn::Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            let writable = segment.ident == "Mut";
+            if writable || segment.ident == "MaybeMut" {
+                if let syn::PathArguments::AngleBracketed(ref args) = segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return (inner.clone(), writable);
+                    }
                 }
-            });
+            }
+        }
+    }
+    (ty.clone(), false)
+}
 
-            quote! {
-                        pub struct #client_struct_name {
-                #(pub #expanded_fields,)*
+/// Replace the given lifetimes with 'static throughout a type.
+fn make_static(tokens: TokenStream2, lifetimes: &[syn::Ident]) -> TokenStream2 {
+    let mut out = Vec::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Punct(ref p) if p.as_char() == '\'' => {
+                let is_ours =
+                    matches!(iter.peek(), Some(TokenTree::Ident(i)) if lifetimes.contains(i));
+                out.push(tt.clone());
+                if is_ours {
+                    let span = iter.next().unwrap().span();
+                    out.push(TokenTree::Ident(syn::Ident::new("static", span)));
+                }
             }
+            TokenTree::Group(g) => {
+                let mut group = Group::new(g.delimiter(), make_static(g.stream(), lifetimes));
+                group.set_span(g.span());
+                out.push(TokenTree::Group(group));
             }
+            other => out.push(other),
         }
-        _ => unimplemented!(),
     }
+    out.into_iter().collect()
 }
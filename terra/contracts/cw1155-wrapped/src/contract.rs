@@ -0,0 +1,638 @@
+use cosmwasm_std::{
+    entry_point,
+    to_binary,
+    Binary,
+    CosmosMsg,
+    Deps,
+    DepsMut,
+    Empty,
+    Env,
+    MessageInfo,
+    Response,
+    StdError,
+    StdResult,
+    Uint128,
+    WasmMsg,
+};
+
+use cw2::set_contract_version;
+
+use crate::{
+    error::ContractError,
+    msg::{
+        BalanceResponse,
+        ContractInfoResponse,
+        Cw1155ReceiveMsg,
+        ExecuteMsg,
+        InstantiateMsg,
+        IsApprovedForAllResponse,
+        MigrateMsg,
+        QueryMsg,
+        ReceiverExecuteMsg,
+        TokenInfoResponse,
+        WrappedAssetInfoResponse,
+    },
+    state::{
+        approvals,
+        approvals_read,
+        balances,
+        balances_read,
+        contract_info,
+        contract_info_read,
+        token_uris,
+        token_uris_read,
+        wrapped_asset_info,
+        wrapped_asset_info_read,
+        ContractInfo,
+        WrappedAssetInfo,
+    },
+};
+use std::string::String;
+
+type HumanAddr = String;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw1155-wrapped";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    contract_info(deps.storage).save(&ContractInfo {
+        name: msg.name,
+        symbol: msg.symbol,
+    })?;
+
+    // save wrapped asset info, the creator is the bridge
+    let data = WrappedAssetInfo {
+        asset_chain: msg.asset_chain,
+        asset_address: msg.asset_address,
+        bridge: deps.api.addr_canonicalize(&info.sender.as_str())?,
+    };
+    wrapped_asset_info(deps.storage).save(&data)?;
+
+    if let Some(mint) = msg.mint {
+        execute_mint(
+            deps,
+            env,
+            info,
+            mint.to,
+            mint.token_id,
+            mint.value,
+            mint.token_uri,
+        )
+        .map_err(|e| StdError::generic_err(format!("{}", e)))?;
+    }
+
+    if let Some(hook) = msg.init_hook {
+        Ok(
+            Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: hook.contract_addr,
+                msg: hook.msg,
+                funds: vec![],
+            })),
+        )
+    } else {
+        Ok(Response::default())
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SendFrom {
+            from,
+            to,
+            token_id,
+            value,
+            msg,
+        } => execute_send_from(deps, env, info, from, to, token_id, value, msg),
+        ExecuteMsg::Mint {
+            to,
+            token_id,
+            value,
+            token_uri,
+        } => execute_mint(deps, env, info, to, token_id, value, token_uri),
+        ExecuteMsg::BurnFrom {
+            from,
+            token_id,
+            value,
+        } => execute_burn_from(deps, env, info, from, token_id, value),
+        ExecuteMsg::ApproveAll { operator } => execute_approve_all(deps, env, info, operator),
+        ExecuteMsg::RevokeAll { operator } => execute_revoke_all(deps, env, info, operator),
+    }
+}
+
+/// Fails unless `sender` is `owner` or one of its operators.
+fn check_can_send(deps: Deps, sender: &HumanAddr, owner: &HumanAddr) -> Result<(), ContractError> {
+    if sender == owner {
+        return Ok(());
+    }
+    let owner = deps.api.addr_canonicalize(owner)?;
+    let sender = deps.api.addr_canonicalize(sender)?;
+    if approvals_read(deps.storage, &owner)
+        .may_load(sender.as_slice())?
+        .is_some()
+    {
+        Ok(())
+    } else {
+        Err(ContractError::Unauthorized {})
+    }
+}
+
+/// Move `value` copies of a token between balances. Minting has no `from`, burning no `to`.
+fn transfer_balance(
+    deps: DepsMut,
+    from: Option<&HumanAddr>,
+    to: Option<&HumanAddr>,
+    token_id: &str,
+    value: Uint128,
+) -> Result<(), ContractError> {
+    if value.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    if let Some(from) = from {
+        let from = deps.api.addr_canonicalize(from)?;
+        let mut bucket = balances(deps.storage, token_id);
+        let balance = bucket.may_load(from.as_slice())?.unwrap_or_default();
+        bucket.save(from.as_slice(), &balance.checked_sub(value)?)?;
+    }
+
+    if let Some(to) = to {
+        let to = deps.api.addr_canonicalize(to)?;
+        let mut bucket = balances(deps.storage, token_id);
+        let balance = bucket.may_load(to.as_slice())?.unwrap_or_default();
+        bucket.save(to.as_slice(), &balance.checked_add(value)?)?;
+    }
+
+    Ok(())
+}
+
+pub fn execute_send_from(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    from: HumanAddr,
+    to: HumanAddr,
+    token_id: String,
+    value: Uint128,
+    msg: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let from = deps.api.addr_validate(&from)?.into_string();
+    let to = deps.api.addr_validate(&to)?.into_string();
+    check_can_send(deps.as_ref(), &info.sender.to_string(), &from)?;
+
+    transfer_balance(deps, Some(&from), Some(&to), &token_id, value)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "transfer")
+        .add_attribute("token_id", token_id.clone())
+        .add_attribute("amount", value)
+        .add_attribute("from", from.clone())
+        .add_attribute("to", to.clone());
+
+    if let Some(msg) = msg {
+        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: to,
+            msg: to_binary(&ReceiverExecuteMsg::Receive(Cw1155ReceiveMsg {
+                operator: info.sender.to_string(),
+                from: Some(from),
+                token_id,
+                amount: value,
+                msg,
+            }))?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(response)
+}
+
+pub fn execute_mint(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    to: HumanAddr,
+    token_id: String,
+    value: Uint128,
+    token_uri: Option<String>,
+) -> Result<Response, ContractError> {
+    // Only bridge can mint
+    let wrapped_info = wrapped_asset_info_read(deps.storage).load()?;
+    if wrapped_info.bridge != deps.api.addr_canonicalize(&info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let to = deps.api.addr_validate(&to)?.into_string();
+
+    if let Some(token_uri) = token_uri {
+        if token_uris_read(deps.storage)
+            .may_load(token_id.as_bytes())?
+            .is_none()
+        {
+            token_uris(deps.storage).save(token_id.as_bytes(), &token_uri)?;
+        }
+    }
+
+    transfer_balance(deps, None, Some(&to), &token_id, value)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("token_id", token_id)
+        .add_attribute("amount", value)
+        .add_attribute("to", to))
+}
+
+pub fn execute_burn_from(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    from: HumanAddr,
+    token_id: String,
+    value: Uint128,
+) -> Result<Response, ContractError> {
+    let from = deps.api.addr_validate(&from)?.into_string();
+    check_can_send(deps.as_ref(), &info.sender.to_string(), &from)?;
+
+    transfer_balance(deps, Some(&from), None, &token_id, value)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("token_id", token_id)
+        .add_attribute("amount", value)
+        .add_attribute("from", from))
+}
+
+pub fn execute_approve_all(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operator: HumanAddr,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_canonicalize(&info.sender.as_str())?;
+    let operator_canonical = deps.api.addr_canonicalize(&operator)?;
+    approvals(deps.storage, &owner).save(operator_canonical.as_slice(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_all")
+        .add_attribute("sender", info.sender)
+        .add_attribute("operator", operator))
+}
+
+pub fn execute_revoke_all(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operator: HumanAddr,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_canonicalize(&info.sender.as_str())?;
+    let operator_canonical = deps.api.addr_canonicalize(&operator)?;
+    approvals(deps.storage, &owner).remove(operator_canonical.as_slice());
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_all")
+        .add_attribute("sender", info.sender)
+        .add_attribute("operator", operator))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::WrappedAssetInfo {} => to_binary(&query_wrapped_asset_info(deps)?),
+        QueryMsg::Balance { owner, token_id } => to_binary(&query_balance(deps, owner, token_id)?),
+        QueryMsg::IsApprovedForAll { owner, operator } => {
+            to_binary(&query_is_approved_for_all(deps, owner, operator)?)
+        }
+        QueryMsg::TokenInfo { token_id } => to_binary(&query_token_info(deps, token_id)?),
+        QueryMsg::ContractInfo {} => to_binary(&query_contract_info(deps)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+pub fn query_balance(deps: Deps, owner: HumanAddr, token_id: String) -> StdResult<BalanceResponse> {
+    let owner = deps.api.addr_canonicalize(&owner)?;
+    let balance = balances_read(deps.storage, &token_id)
+        .may_load(owner.as_slice())?
+        .unwrap_or_default();
+    Ok(BalanceResponse { balance })
+}
+
+pub fn query_is_approved_for_all(
+    deps: Deps,
+    owner: HumanAddr,
+    operator: HumanAddr,
+) -> StdResult<IsApprovedForAllResponse> {
+    let owner = deps.api.addr_canonicalize(&owner)?;
+    let operator = deps.api.addr_canonicalize(&operator)?;
+    let approved = approvals_read(deps.storage, &owner)
+        .may_load(operator.as_slice())?
+        .is_some();
+    Ok(IsApprovedForAllResponse { approved })
+}
+
+pub fn query_token_info(deps: Deps, token_id: String) -> StdResult<TokenInfoResponse> {
+    let url = token_uris_read(deps.storage).load(token_id.as_bytes())?;
+    Ok(TokenInfoResponse { url })
+}
+
+pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
+    let info = contract_info_read(deps.storage).load()?;
+    Ok(ContractInfoResponse {
+        name: info.name,
+        symbol: info.symbol,
+    })
+}
+
+pub fn query_wrapped_asset_info(deps: Deps) -> StdResult<WrappedAssetInfoResponse> {
+    let info = wrapped_asset_info_read(deps.storage).load()?;
+    Ok(WrappedAssetInfoResponse {
+        asset_chain: info.asset_chain,
+        asset_address: info.asset_address,
+        bridge: deps.api.addr_humanize(&info.bridge)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{
+        testing::{
+            mock_dependencies,
+            mock_env,
+            mock_info,
+        },
+        Addr,
+    };
+
+    fn get_balance(deps: Deps, owner: &str, token_id: &str) -> Uint128 {
+        query_balance(deps, owner.to_string(), token_id.to_string())
+            .unwrap()
+            .balance
+    }
+
+    fn do_init(deps: DepsMut, creator: &str) {
+        let init_msg = InstantiateMsg {
+            name: "Integers".to_string(),
+            symbol: "INT".to_string(),
+            asset_chain: 2,
+            asset_address: vec![1; 32].into(),
+            mint: None,
+            init_hook: None,
+        };
+        let info = mock_info(creator, &[]);
+        let res = instantiate(deps, mock_env(), info, init_msg).unwrap();
+        assert_eq!(0, res.messages.len());
+    }
+
+    fn do_mint(
+        deps: DepsMut,
+        sender: &str,
+        to: &str,
+        value: u128,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::Mint {
+            to: to.to_string(),
+            token_id: "42".to_string(),
+            value: Uint128::new(value),
+            token_uri: Some("https://example.com/42".to_string()),
+        };
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)
+    }
+
+    #[test]
+    fn can_mint_by_bridge() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        do_mint(deps.as_mut(), "bridge", "owner", 1000).unwrap();
+        do_mint(deps.as_mut(), "bridge", "owner", 500).unwrap();
+
+        assert_eq!(
+            get_balance(deps.as_ref(), "owner", "42"),
+            Uint128::new(1500)
+        );
+        assert_eq!(
+            query_token_info(deps.as_ref(), "42".to_string())
+                .unwrap()
+                .url,
+            "https://example.com/42"
+        );
+        assert_eq!(
+            query_wrapped_asset_info(deps.as_ref()).unwrap(),
+            WrappedAssetInfoResponse {
+                asset_chain: 2,
+                asset_address: vec![1; 32].into(),
+                bridge: Addr::unchecked("bridge"),
+            }
+        );
+    }
+
+    #[test]
+    fn others_cannot_mint() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        let res = do_mint(deps.as_mut(), "other", "owner", 1000);
+        assert_eq!(
+            format!("{}", res.unwrap_err()),
+            format!("{}", ContractError::Unauthorized {})
+        );
+    }
+
+    #[test]
+    fn burn_needs_approval() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        do_mint(deps.as_mut(), "bridge", "owner", 1000).unwrap();
+
+        let burn = ExecuteMsg::BurnFrom {
+            from: "owner".to_string(),
+            token_id: "42".to_string(),
+            value: Uint128::new(400),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bridge", &[]),
+            burn.clone(),
+        );
+        assert_eq!(
+            format!("{}", res.unwrap_err()),
+            format!("{}", ContractError::Unauthorized {})
+        );
+
+        let approve = ExecuteMsg::ApproveAll {
+            operator: "bridge".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), approve).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("bridge", &[]), burn).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "owner", "42"), Uint128::new(600));
+    }
+
+    #[test]
+    fn send_balance_not_enough() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        do_mint(deps.as_mut(), "bridge", "owner", 1000).unwrap();
+
+        let send = |value| ExecuteMsg::SendFrom {
+            from: "owner".to_string(),
+            to: "recipient".to_string(),
+            token_id: "42".to_string(),
+            value: Uint128::new(value),
+            msg: None,
+        };
+        let info = mock_info("owner", &[]);
+        assert!(execute(deps.as_mut(), mock_env(), info.clone(), send(1001)).is_err());
+        execute(deps.as_mut(), mock_env(), info, send(1000)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "owner", "42"), Uint128::zero());
+        assert_eq!(
+            get_balance(deps.as_ref(), "recipient", "42"),
+            Uint128::new(1000)
+        );
+    }
+
+    #[test]
+    fn mint_overflow() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        do_mint(deps.as_mut(), "bridge", "owner", u128::MAX).unwrap();
+        assert!(matches!(
+            do_mint(deps.as_mut(), "bridge", "owner", 1),
+            Err(ContractError::Overflow(_))
+        ));
+        assert_eq!(
+            get_balance(deps.as_ref(), "owner", "42"),
+            Uint128::new(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn owner_and_operators_can_burn() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        do_mint(deps.as_mut(), "bridge", "owner", 1000).unwrap();
+
+        let burn = |value| ExecuteMsg::BurnFrom {
+            from: "owner".to_string(),
+            token_id: "42".to_string(),
+            value: Uint128::new(value),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            burn(100),
+        )
+        .unwrap();
+
+        // Approval is per owner, and ends when revoked.
+        let approve = ExecuteMsg::ApproveAll {
+            operator: "bridge".to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("other", &[]),
+            approve.clone(),
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bridge", &[]),
+            burn(100),
+        );
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), approve).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bridge", &[]),
+            burn(100),
+        )
+        .unwrap();
+
+        let revoke = ExecuteMsg::RevokeAll {
+            operator: "bridge".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), revoke).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bridge", &[]),
+            burn(100),
+        );
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+        assert_eq!(get_balance(deps.as_ref(), "owner", "42"), Uint128::new(800));
+    }
+
+    #[test]
+    fn burn_balance_not_enough() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        do_mint(deps.as_mut(), "bridge", "owner", 1000).unwrap();
+
+        let burn = |from: &str, value| ExecuteMsg::BurnFrom {
+            from: from.to_string(),
+            token_id: "42".to_string(),
+            value: Uint128::new(value),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            burn("owner", 1001),
+        );
+        assert!(matches!(res, Err(ContractError::Overflow(_))));
+        assert_eq!(
+            get_balance(deps.as_ref(), "owner", "42"),
+            Uint128::new(1000)
+        );
+
+        // An account that never held the token has nothing to burn.
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("other", &[]),
+            burn("other", 1),
+        );
+        assert!(matches!(res, Err(ContractError::Overflow(_))));
+    }
+
+    #[test]
+    fn others_cannot_send() {
+        let mut deps = mock_dependencies(&[]);
+        do_init(deps.as_mut(), "bridge");
+        do_mint(deps.as_mut(), "bridge", "owner", 1000).unwrap();
+
+        let send = ExecuteMsg::SendFrom {
+            from: "owner".to_string(),
+            to: "other".to_string(),
+            token_id: "42".to_string(),
+            value: Uint128::new(1),
+            msg: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("other", &[]), send);
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+        assert_eq!(
+            get_balance(deps.as_ref(), "owner", "42"),
+            Uint128::new(1000)
+        );
+    }
+}
@@ -0,0 +1,7 @@
+mod error;
+
+pub mod contract;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,154 @@
+use schemars::JsonSchema;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use cosmwasm_std::{
+    Addr,
+    Binary,
+    Uint128,
+};
+
+type HumanAddr = String;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Name of the token contract
+    pub name: String,
+    /// Symbol of the token contract
+    pub symbol: String,
+
+    /// Native chain of the token
+    pub asset_chain: u16,
+
+    /// Native address of the token
+    pub asset_address: Binary,
+
+    /// Mint a first token upon creation
+    pub mint: Option<InitMint>,
+
+    /// Generic callback - used to register the newly instantiated asset
+    pub init_hook: Option<InitHook>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InitHook {
+    pub msg: Binary,
+    pub contract_addr: HumanAddr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InitMint {
+    pub to: HumanAddr,
+    pub token_id: String,
+    pub value: Uint128,
+    pub token_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Implements CW1155. Moves `value` copies of a token from `from` to `to`, if `env.sender` is
+    /// `from` or an operator of it. If `msg` is set, `to` is a contract and is sent a
+    /// `Cw1155ReceiveMsg`.
+    SendFrom {
+        from: HumanAddr,
+        to: HumanAddr,
+        token_id: String,
+        value: Uint128,
+        msg: Option<Binary>,
+    },
+    /// Creates `value` new copies of a token for `to`. Only the bridge can mint, the URI is kept
+    /// from the first mint of the token.
+    Mint {
+        to: HumanAddr,
+        token_id: String,
+        value: Uint128,
+        token_uri: Option<String>,
+    },
+    /// Implements CW1155. Destroys `value` copies of a token of `from`, if `env.sender` is `from`
+    /// or an operator of it.
+    BurnFrom {
+        from: HumanAddr,
+        token_id: String,
+        value: Uint128,
+    },
+    /// Implements CW1155. Allows `operator` to move all tokens of `env.sender`. Approvals here do
+    /// not expire.
+    ApproveAll { operator: HumanAddr },
+    /// Implements CW1155. Removes an approval given by `ApproveAll`.
+    RevokeAll { operator: HumanAddr },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    // Generic information about the wrapped asset
+    WrappedAssetInfo {},
+    /// Implements CW1155. Returns the number of copies of a token `owner` holds, 0 if unset.
+    Balance {
+        owner: HumanAddr,
+        token_id: String,
+    },
+    /// Implements CW1155. Returns whether `operator` may move all tokens of `owner`.
+    IsApprovedForAll {
+        owner: HumanAddr,
+        operator: HumanAddr,
+    },
+    /// Implements CW1155. Returns the URI of a token.
+    TokenInfo {
+        token_id: String,
+    },
+    /// Returns the name and symbol of the contract.
+    ContractInfo {},
+}
+
+/// Message sent to a contract receiving tokens through `SendFrom` with a `msg`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw1155ReceiveMsg {
+    pub operator: HumanAddr,
+    pub from: Option<HumanAddr>,
+    pub token_id: String,
+    pub amount: Uint128,
+    pub msg: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiverExecuteMsg {
+    Receive(Cw1155ReceiveMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BalanceResponse {
+    pub balance: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsApprovedForAllResponse {
+    pub approved: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenInfoResponse {
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WrappedAssetInfoResponse {
+    pub asset_chain: u16,      // Asset chain id
+    pub asset_address: Binary, // Asset smart contract address in the original chain
+    pub bridge: Addr,          // Bridge address, authorized to mint and burn wrapped tokens
+}
@@ -0,0 +1,89 @@
+use schemars::JsonSchema;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use cosmwasm_std::{
+    Binary,
+    CanonicalAddr,
+    Empty,
+    Storage,
+    Uint128,
+};
+use cosmwasm_storage::{
+    bucket,
+    bucket_read,
+    singleton,
+    singleton_read,
+    Bucket,
+    ReadonlyBucket,
+    ReadonlySingleton,
+    Singleton,
+};
+
+pub const KEY_WRAPPED_ASSET: &[u8] = b"wrappedAsset";
+pub const KEY_CONTRACT_INFO: &[u8] = b"contractInfo";
+pub const KEY_BALANCES: &[u8] = b"balances";
+pub const KEY_TOKEN_URIS: &[u8] = b"tokenUris";
+pub const KEY_APPROVALS: &[u8] = b"approvals";
+
+// Created at initialization and reference original asset and bridge address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WrappedAssetInfo {
+    pub asset_chain: u16,      // Asset chain id
+    pub asset_address: Binary, // Asset smart contract address on the original chain
+    pub bridge: CanonicalAddr, // Bridge address, authorized to mint and burn wrapped tokens
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractInfo {
+    pub name: String,
+    pub symbol: String,
+}
+
+pub fn wrapped_asset_info(storage: &mut dyn Storage) -> Singleton<WrappedAssetInfo> {
+    singleton(storage, KEY_WRAPPED_ASSET)
+}
+
+pub fn wrapped_asset_info_read(storage: &dyn Storage) -> ReadonlySingleton<WrappedAssetInfo> {
+    singleton_read(storage, KEY_WRAPPED_ASSET)
+}
+
+pub fn contract_info(storage: &mut dyn Storage) -> Singleton<ContractInfo> {
+    singleton(storage, KEY_CONTRACT_INFO)
+}
+
+pub fn contract_info_read(storage: &dyn Storage) -> ReadonlySingleton<ContractInfo> {
+    singleton_read(storage, KEY_CONTRACT_INFO)
+}
+
+// Balances of one token, keyed by canonical owner address
+pub fn balances<'a>(storage: &'a mut dyn Storage, token_id: &str) -> Bucket<'a, Uint128> {
+    Bucket::multilevel(storage, &[KEY_BALANCES, token_id.as_bytes()])
+}
+
+pub fn balances_read<'a>(storage: &'a dyn Storage, token_id: &str) -> ReadonlyBucket<'a, Uint128> {
+    ReadonlyBucket::multilevel(storage, &[KEY_BALANCES, token_id.as_bytes()])
+}
+
+// URI of each token, set when the token is first minted
+pub fn token_uris(storage: &mut dyn Storage) -> Bucket<String> {
+    bucket(storage, KEY_TOKEN_URIS)
+}
+
+pub fn token_uris_read(storage: &dyn Storage) -> ReadonlyBucket<String> {
+    bucket_read(storage, KEY_TOKEN_URIS)
+}
+
+// Operators allowed to move all tokens of an owner, keyed by canonical operator address
+pub fn approvals<'a>(storage: &'a mut dyn Storage, owner: &CanonicalAddr) -> Bucket<'a, Empty> {
+    Bucket::multilevel(storage, &[KEY_APPROVALS, owner.as_slice()])
+}
+
+pub fn approvals_read<'a>(
+    storage: &'a dyn Storage,
+    owner: &CanonicalAddr,
+) -> ReadonlyBucket<'a, Empty> {
+    ReadonlyBucket::multilevel(storage, &[KEY_APPROVALS, owner.as_slice()])
+}
@@ -1,10 +1,10 @@
 use crate::{
     msg::WrappedRegistryResponse,
     state::{
+        pending_mints,
         spl_cache,
         spl_cache_read,
         wrapped_asset,
-        BoundedVec,
         SplCacheItem,
     },
     token_id::{
@@ -16,6 +16,7 @@ use crate::{
 use cosmwasm_std::{
     entry_point,
     to_binary,
+    Addr,
     Binary,
     CanonicalAddr,
     CosmosMsg,
@@ -28,6 +29,7 @@ use cosmwasm_std::{
     Response,
     StdError,
     StdResult,
+    Uint128,
     WasmMsg,
     WasmQuery, Order,
 };
@@ -48,8 +50,11 @@ use crate::{
         wrapped_asset_address_read,
         wrapped_asset_read,
         Action,
+        BatchTokenInfo,
+        BatchTransferInfo,
         ConfigInfo,
         RegisterChain,
+        SemiFungibleTransferInfo,
         TokenBridgeMessage,
         TransferInfo,
         UpgradeContract,
@@ -60,6 +65,7 @@ use wormhole::{
         extend_address_to_32,
         extend_address_to_32_array,
         get_string_from_32,
+        get_valid_string_from_32,
         string_to_array,
         ByteUtils,
     },
@@ -82,11 +88,22 @@ use sha3::{
     Digest,
     Keccak256,
 };
+use wormhole_core::{
+    truncate_utf8,
+    vaa::nft::PayloadKind,
+    Chain,
+};
 
 type HumanAddr = String;
 
 const WRAPPED_ASSET_UPDATING: &str = "updating";
 
+// Longest URI the metadata program on Solana accepts
+const SOLANA_MAX_URI_LENGTH: usize = 200;
+
+// Most tokens a batch sent from Terra can hold, so that redeeming it stays within gas limits
+const MAX_BATCH_SIZE: usize = 32;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
     Ok(Response::new())
@@ -105,6 +122,7 @@ pub fn instantiate(
         gov_address: msg.gov_address.as_slice().to_vec(),
         wormhole_contract: msg.wormhole_contract,
         wrapped_asset_code_id: msg.wrapped_asset_code_id,
+        wrapped_semi_fungible_code_id: msg.wrapped_semi_fungible_code_id,
     };
     config(deps.storage).save(&state)?;
 
@@ -142,6 +160,40 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             recipient.to_array()?,
             nonce,
         ),
+        ExecuteMsg::InitiateBatchTransfer {
+            contract_addr,
+            token_ids,
+            recipient_chain,
+            recipient,
+            nonce,
+        } => handle_initiate_batch_transfer(
+            deps,
+            env,
+            info,
+            contract_addr,
+            token_ids,
+            recipient_chain,
+            recipient.to_array()?,
+            nonce,
+        ),
+        ExecuteMsg::InitiateSemiFungibleTransfer {
+            contract_addr,
+            token_id,
+            amount,
+            recipient_chain,
+            recipient,
+            nonce,
+        } => handle_initiate_semi_fungible_transfer(
+            deps,
+            env,
+            info,
+            contract_addr,
+            token_id,
+            amount,
+            recipient_chain,
+            recipient.to_array()?,
+            nonce,
+        ),
         ExecuteMsg::SubmitVaa { data } => submit_vaa(deps, env, info, &data),
         ExecuteMsg::RegisterAssetHook { asset_id } => {
             handle_register_asset(deps, env, info, &asset_id.as_slice())
@@ -173,13 +225,29 @@ fn submit_vaa(
     let message = TokenBridgeMessage::deserialize(&data)?;
 
     match message.action {
-        Action::TRANSFER => handle_complete_transfer(
+        Action::TRANSFER | Action::TRANSFER_V2 => handle_complete_transfer(
+            deps,
+            env,
+            info,
+            vaa.emitter_chain,
+            vaa.emitter_address,
+            TransferInfo::deserialize(&data)?,
+        ),
+        Action::BATCH_TRANSFER => handle_complete_batch_transfer(
+            deps,
+            env,
+            info,
+            vaa.emitter_chain,
+            vaa.emitter_address,
+            BatchTransferInfo::deserialize(&data)?,
+        ),
+        Action::SEMI_FUNGIBLE_TRANSFER => handle_complete_semi_fungible_transfer(
             deps,
             env,
             info,
             vaa.emitter_chain,
             vaa.emitter_address,
-            TransferInfo::deserialize(&message.payload)?,
+            SemiFungibleTransferInfo::deserialize(&data)?,
         ),
         _ => ContractError::InvalidVAAAction.std_err(),
     }
@@ -249,172 +317,396 @@ fn handle_register_chain(
         .add_attribute("chain_address", hex::encode(chain_address)))
 }
 
-fn handle_complete_transfer(
-    deps: DepsMut,
-    env: Env,
-    _info: MessageInfo,
+/// Check that a transfer was sent by a registered NFT bridge and is directed at this chain.
+fn verify_transfer(
+    deps: Deps,
     emitter_chain: u16,
-    emitter_address: Vec<u8>,
-    transfer_info: TransferInfo,
-) -> StdResult<Response> {
-    let cfg = config_read(deps.storage).load()?;
-
+    emitter_address: &Vec<u8>,
+    recipient_chain: u16,
+) -> StdResult<()> {
     let expected_contract =
         bridge_contracts_read(deps.storage).load(&emitter_chain.to_be_bytes())?;
 
     // must be sent by a registered token bridge contract
-    if expected_contract != emitter_address {
+    if expected_contract != *emitter_address {
         return Err(StdError::generic_err("invalid emitter"));
     }
 
-    if transfer_info.recipient_chain != CHAIN_ID {
+    if recipient_chain != CHAIN_ID {
         return Err(StdError::generic_err(
             "this transfer is not directed at this chain",
         ));
     }
 
-    let token_chain = transfer_info.nft_chain;
+    Ok(())
+}
+
+fn handle_complete_transfer(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    emitter_chain: u16,
+    emitter_address: Vec<u8>,
+    transfer_info: TransferInfo,
+) -> StdResult<Response> {
+    verify_transfer(
+        deps.as_ref(),
+        emitter_chain,
+        &emitter_address,
+        transfer_info.recipient_chain,
+    )?;
+
     let target_address = &(&transfer_info.recipient[..]).get_address(0);
 
-    let mut messages = vec![];
+    let recipient = deps
+        .api
+        .addr_humanize(&target_address)
+        .or_else(|_| ContractError::WrongTargetAddressFormat.std_err())?;
+
+    let (messages, contract_addr) = complete_tokens(
+        deps,
+        env,
+        transfer_info.nft_chain,
+        transfer_info.nft_address,
+        transfer_info.name,
+        transfer_info.symbol,
+        vec![(transfer_info.token_id, transfer_info.uri)],
+        &recipient,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "complete_transfer")
+        .add_attribute("recipient", recipient)
+        .add_attribute("contract", contract_addr))
+}
+
+fn handle_complete_batch_transfer(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    emitter_chain: u16,
+    emitter_address: Vec<u8>,
+    batch_info: BatchTransferInfo,
+) -> StdResult<Response> {
+    verify_transfer(
+        deps.as_ref(),
+        emitter_chain,
+        &emitter_address,
+        batch_info.recipient_chain,
+    )?;
+
+    if batch_info.tokens.is_empty() {
+        return Err(StdError::generic_err("batch transfer without tokens"));
+    }
+
+    let target_address = &(&batch_info.recipient[..]).get_address(0);
 
     let recipient = deps
         .api
         .addr_humanize(&target_address)
         .or_else(|_| ContractError::WrongTargetAddressFormat.std_err())?;
 
-    let contract_addr;
+    let token_count = batch_info.tokens.len();
+    let (messages, contract_addr) = complete_tokens(
+        deps,
+        env,
+        batch_info.nft_chain,
+        batch_info.nft_address,
+        batch_info.name,
+        batch_info.symbol,
+        batch_info
+            .tokens
+            .into_iter()
+            .map(|t| (t.token_id, t.uri))
+            .collect(),
+        &recipient,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "complete_batch_transfer")
+        .add_attribute("recipient", recipient)
+        .add_attribute("contract", contract_addr)
+        .add_attribute("token_count", token_count.to_string()))
+}
+
+fn handle_complete_semi_fungible_transfer(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    emitter_chain: u16,
+    emitter_address: Vec<u8>,
+    transfer_info: SemiFungibleTransferInfo,
+) -> StdResult<Response> {
+    verify_transfer(
+        deps.as_ref(),
+        emitter_chain,
+        &emitter_address,
+        transfer_info.recipient_chain,
+    )?;
+
+    // cw1155 balances are 128 bit
+    let amount = &transfer_info.amount[..];
+    if amount.get_u128_be(0) != 0 {
+        return ContractError::AmountTooHigh.std_err();
+    }
+    let amount = Uint128::new(amount.get_u128_be(16));
+    if amount.is_zero() {
+        return ContractError::AmountTooLow.std_err();
+    }
 
+    let target_address = &(&transfer_info.recipient[..]).get_address(0);
+
+    let recipient = deps
+        .api
+        .addr_humanize(&target_address)
+        .or_else(|_| ContractError::WrongTargetAddressFormat.std_err())?;
+
+    let token_chain = transfer_info.nft_chain;
+    let nft_address = transfer_info.nft_address;
     let token_id = from_external_token_id(
         deps.storage,
         token_chain,
-        &transfer_info.nft_address,
+        &nft_address,
         &transfer_info.token_id,
     )?;
 
-    if token_chain != CHAIN_ID {
-        // NFT is not native to this chain, so we need a wrapper
-        let asset_address = transfer_info.nft_address;
-        let asset_id = build_asset_id(token_chain, &asset_address);
+    let (message, contract_addr) = if token_chain == CHAIN_ID {
+        // Native token, transfer from custody
+        let token_address = (&nft_address[..]).get_address(0);
+        let contract_addr = deps.api.addr_humanize(&token_address)?.to_string();
 
-        let token_uri = String::from_utf8(transfer_info.uri.to_vec())
-            .or_else(|_| Err(StdError::generic_err("could not parse uri string")))?;
+        let message = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&cw1155_wrapped::msg::ExecuteMsg::SendFrom {
+                from: env.contract.address.to_string(),
+                to: recipient.to_string(),
+                token_id,
+                value: amount,
+                msg: None,
+            })?,
+            funds: vec![],
+        });
+        (message, contract_addr)
+    } else {
+        // Token is not native to this chain, so we need a wrapper
+        if token_chain == 1 {
+            // Solana tokens share one wrapped contract like Solana NFTs, see `complete_tokens`
+            let spl_cache_item = SplCacheItem {
+                name: string_to_array(truncate_utf8(&transfer_info.name, 32)),
+                symbol: string_to_array(truncate_utf8(&transfer_info.symbol, 32)),
+            };
+            spl_cache(deps.storage).save(&transfer_info.token_id, &spl_cache_item)?;
+        }
 
-        let mint_msg = cw721_base::msg::MintMsg {
+        let mint = cw1155_wrapped::msg::InitMint {
+            to: recipient.to_string(),
             token_id,
-            owner: recipient.to_string(),
-            token_uri: Some(token_uri),
-            extension: None,
+            value: amount,
+            token_uri: Some(transfer_info.uri),
         };
 
+        let asset_id = build_semi_fungible_asset_id(token_chain, &nft_address);
+
         // Check if this asset is already deployed
         if let Some(wrapped_addr) = wrapped_asset_read(deps.storage).load(&asset_id).ok() {
-            contract_addr = wrapped_addr;
             // Asset already deployed, just mint
-
-            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: contract_addr.clone(),
-                msg: to_binary(&cw721_base::msg::ExecuteMsg::Mint(mint_msg))?,
+            let message = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: wrapped_addr.clone(),
+                msg: to_binary(&cw1155_wrapped::msg::ExecuteMsg::Mint {
+                    to: mint.to,
+                    token_id: mint.token_id,
+                    value: mint.value,
+                    token_uri: mint.token_uri,
+                })?,
                 funds: vec![],
-            }));
+            });
+            (message, wrapped_addr)
         } else {
-            contract_addr = env.contract.address.clone().into_string();
+            let code_id = config_read(deps.storage)
+                .load()?
+                .wrapped_semi_fungible_code_id
+                .ok_or_else(|| StdError::generic_err("semi-fungible tokens are not enabled"))?;
+
+            let contract_addr = env.contract.address.clone().into_string();
             wrapped_asset(deps.storage)
                 .save(&asset_id, &HumanAddr::from(WRAPPED_ASSET_UPDATING))?;
 
             let (name, symbol) = if token_chain == 1 {
-                let spl_cache_item = SplCacheItem {
-                    name: transfer_info.name,
-                    symbol: transfer_info.symbol,
-                };
-                spl_cache(deps.storage).save(&transfer_info.token_id, &spl_cache_item)?;
-                // Solana NFTs all use the same NFT contract, so unify the name
+                // Solana tokens all use the same contract, so unify the name
                 (
-                    "Wormhole Bridged Solana-NFT".to_string(),
-                    "WORMSPLNFT".to_string(),
+                    "Wormhole Bridged Solana-SFT".to_string(),
+                    "WORMSPLSFT".to_string(),
                 )
             } else {
-                (
-                    get_string_from_32(&transfer_info.name.to_vec()),
-                    get_string_from_32(&transfer_info.symbol.to_vec()),
-                )
+                (transfer_info.name, transfer_info.symbol)
             };
-            messages.push(CosmosMsg::Wasm(WasmMsg::Instantiate {
+            let message = CosmosMsg::Wasm(WasmMsg::Instantiate {
                 admin: Some(contract_addr.clone()),
-                code_id: cfg.wrapped_asset_code_id,
-                msg: to_binary(&cw721_wrapped::msg::InstantiateMsg {
+                code_id,
+                msg: to_binary(&cw1155_wrapped::msg::InstantiateMsg {
                     name,
                     symbol,
                     asset_chain: token_chain,
-                    asset_address: (&transfer_info.nft_address[..]).into(),
-                    minter: env.contract.address.into_string(),
-                    mint: Some(mint_msg),
-                    init_hook: Some(cw721_wrapped::msg::InitHook {
-                        msg: cw721_wrapped::to_binary(&ExecuteMsg::RegisterAssetHook {
+                    asset_address: (&nft_address[..]).into(),
+                    mint: Some(mint),
+                    init_hook: Some(cw1155_wrapped::msg::InitHook {
+                        msg: to_binary(&ExecuteMsg::RegisterAssetHook {
                             asset_id: asset_id.to_vec().into(),
-                        })
-                        .map_err(|_| StdError::generic_err("couldn't convert to binary"))?,
+                        })?,
                         contract_addr: contract_addr.clone(),
                     }),
                 })?,
                 funds: vec![],
                 label: String::new(),
-            }));
+            });
+            (message, contract_addr)
         }
-    } else {
-        // Native NFT, transfer from custody
-        let token_address = (&transfer_info.nft_address[..]).get_address(0);
-
-        contract_addr = deps.api.addr_humanize(&token_address)?.to_string();
+    };
 
-        messages.push(CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
-            contract_addr: contract_addr.clone(),
-            msg: to_binary(&cw721_base::msg::ExecuteMsg::<Option<Empty>>::TransferNft {
-                recipient: recipient.to_string(),
-                token_id,
-            })?,
-            funds: vec![],
-        }));
-    }
     Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "complete_transfer")
+        .add_message(message)
+        .add_attribute("action", "complete_semi_fungible_transfer")
         .add_attribute("recipient", recipient)
-        .add_attribute("contract", contract_addr))
+        .add_attribute("contract", contract_addr)
+        .add_attribute("amount", amount))
 }
 
-fn handle_initiate_transfer(
+/// Release from custody or mint the given (external token id, uri) tokens of one NFT contract,
+/// deploying the wrapped contract if this is the first time the contract comes through. Returns
+/// the messages doing so and the address of the contract holding the tokens.
+fn complete_tokens(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
-    asset: HumanAddr,
-    token_id: String,
-    recipient_chain: u16,
-    recipient: [u8; 32],
-    nonce: u32,
-) -> StdResult<Response> {
-    if recipient_chain == CHAIN_ID {
-        return ContractError::SameSourceAndTarget.std_err();
+    token_chain: u16,
+    nft_address: [u8; 32],
+    name: String,
+    symbol: String,
+    tokens: Vec<([u8; 32], String)>,
+    recipient: &Addr,
+) -> StdResult<(Vec<CosmosMsg>, HumanAddr)> {
+    let mut messages = vec![];
+
+    if token_chain == CHAIN_ID {
+        // Native NFT, transfer from custody
+        let token_address = (&nft_address[..]).get_address(0);
+
+        let contract_addr = deps.api.addr_humanize(&token_address)?.to_string();
+
+        for (external_token_id, _) in tokens {
+            let token_id = from_external_token_id(
+                deps.storage,
+                token_chain,
+                &nft_address,
+                &external_token_id,
+            )?;
+            messages.push(CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_binary(&cw721_base::msg::ExecuteMsg::<Option<Empty>>::TransferNft {
+                    recipient: recipient.to_string(),
+                    token_id,
+                })?,
+                funds: vec![],
+            }));
+        }
+        return Ok((messages, contract_addr));
     }
 
-    let asset_chain: u16;
-    let asset_address: [u8; 32];
+    // NFT is not native to this chain, so we need a wrapper
+    let mut mint_msgs = vec![];
+    for (external_token_id, uri) in tokens {
+        if token_chain == 1 {
+            // Solana NFTs all share one wrapped contract, so their name and symbol are kept per
+            // token for the way back. The cache only holds 32 bytes, cut on a character boundary
+            // so it stays valid.
+            let spl_cache_item = SplCacheItem {
+                name: string_to_array(truncate_utf8(&name, 32)),
+                symbol: string_to_array(truncate_utf8(&symbol, 32)),
+            };
+            spl_cache(deps.storage).save(&external_token_id, &spl_cache_item)?;
+        }
 
-    let cfg: ConfigInfo = config_read(deps.storage).load()?;
-    let asset_canonical: CanonicalAddr = deps.api.addr_canonicalize(&asset)?;
+        mint_msgs.push(cw721_base::msg::MintMsg {
+            token_id: from_external_token_id(
+                deps.storage,
+                token_chain,
+                &nft_address,
+                &external_token_id,
+            )?,
+            owner: recipient.to_string(),
+            token_uri: Some(uri),
+            extension: None,
+        });
+    }
 
-    let mut messages: Vec<CosmosMsg> = vec![];
+    let asset_id = build_asset_id(token_chain, &nft_address);
 
-    if let Ok(_) = wrapped_asset_address_read(deps.storage).load(asset_canonical.as_slice()) {
-        // This is a deployed wrapped asset, burn it
-        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: asset.clone(),
-            msg: to_binary(&cw721_wrapped::msg::ExecuteMsg::Burn::<Option<Empty>> {
-                token_id: token_id.clone(),
-            })?,
-            funds: vec![],
-        }));
+    // Check if this asset is already deployed
+    if let Some(wrapped_addr) = wrapped_asset_read(deps.storage).load(&asset_id).ok() {
+        // Asset already deployed, just mint
+        for mint_msg in mint_msgs {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: wrapped_addr.clone(),
+                msg: to_binary(&cw721_base::msg::ExecuteMsg::Mint(mint_msg))?,
+                funds: vec![],
+            }));
+        }
+        return Ok((messages, wrapped_addr));
+    }
+
+    let contract_addr = env.contract.address.clone().into_string();
+    wrapped_asset(deps.storage).save(&asset_id, &HumanAddr::from(WRAPPED_ASSET_UPDATING))?;
+
+    // The wrapped contract is instantiated with the first token, the rest are minted once it
+    // registers itself in `handle_register_asset`.
+    let mut mint_msgs = mint_msgs.into_iter();
+    let mint_msg = mint_msgs.next();
+    let pending: Vec<_> = mint_msgs.collect();
+    if !pending.is_empty() {
+        pending_mints(deps.storage).save(&asset_id, &pending)?;
+    }
 
+    let (name, symbol) = if token_chain == 1 {
+        // Solana NFTs all use the same NFT contract, so unify the name
+        (
+            "Wormhole Bridged Solana-NFT".to_string(),
+            "WORMSPLNFT".to_string(),
+        )
+    } else {
+        (name, symbol)
+    };
+    messages.push(CosmosMsg::Wasm(WasmMsg::Instantiate {
+        admin: Some(contract_addr.clone()),
+        code_id: config_read(deps.storage).load()?.wrapped_asset_code_id,
+        msg: to_binary(&cw721_wrapped::msg::InstantiateMsg {
+            name,
+            symbol,
+            asset_chain: token_chain,
+            asset_address: (&nft_address[..]).into(),
+            minter: env.contract.address.into_string(),
+            mint: mint_msg,
+            init_hook: Some(cw721_wrapped::msg::InitHook {
+                msg: cw721_wrapped::to_binary(&ExecuteMsg::RegisterAssetHook {
+                    asset_id: asset_id.to_vec().into(),
+                })
+                .map_err(|_| StdError::generic_err("couldn't convert to binary"))?,
+                contract_addr: contract_addr.clone(),
+            }),
+        })?,
+        funds: vec![],
+        label: String::new(),
+    }));
+
+    Ok((messages, contract_addr))
+}
+
+/// The chain and address an NFT contract originates from, and whether it is a wrapped asset
+/// deployed by this bridge.
+fn query_asset_origin(deps: Deps, asset: &HumanAddr) -> StdResult<(u16, [u8; 32], bool)> {
+    let asset_canonical: CanonicalAddr = deps.api.addr_canonicalize(asset)?;
+
+    if let Ok(_) = wrapped_asset_address_read(deps.storage).load(asset_canonical.as_slice()) {
         let wrapped_token_info: cw721_wrapped::msg::WrappedAssetInfoResponse = deps
             .querier
             .custom_query(&QueryRequest::<Empty>::Wasm(WasmQuery::Smart {
@@ -422,36 +714,62 @@ fn handle_initiate_transfer(
                 msg: to_binary(&cw721_wrapped::msg::QueryMsg::WrappedAssetInfo {})?,
             }))?;
 
-        asset_address = wrapped_token_info.asset_address.to_array()?;
-        asset_chain = wrapped_token_info.asset_chain;
+        Ok((
+            wrapped_token_info.asset_chain,
+            wrapped_token_info.asset_address.to_array()?,
+            true,
+        ))
     } else {
-        // Native NFT, lock it up
-        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: asset.clone(),
-            msg: to_binary(&cw721_base::msg::ExecuteMsg::<Option<Empty>>::TransferNft {
-                recipient: env.contract.address.to_string(),
-                token_id: token_id.clone(),
-            })?,
-            funds: vec![],
-        }));
+        Ok((
+            CHAIN_ID,
+            extend_address_to_32_array(&asset_canonical),
+            false,
+        ))
+    }
+}
 
-        asset_chain = CHAIN_ID;
-        asset_address = extend_address_to_32_array(&asset_canonical);
+/// Burn a wrapped token, or lock a native one up in custody.
+fn escrow_token(
+    env: &Env,
+    asset: &HumanAddr,
+    token_id: &String,
+    wrapped: bool,
+) -> StdResult<CosmosMsg> {
+    let msg = if wrapped {
+        to_binary(&cw721_wrapped::msg::ExecuteMsg::Burn::<Option<Empty>> {
+            token_id: token_id.clone(),
+        })?
+    } else {
+        to_binary(&cw721_base::msg::ExecuteMsg::<Option<Empty>>::TransferNft {
+            recipient: env.contract.address.to_string(),
+            token_id: token_id.clone(),
+        })?
     };
 
-    let external_token_id =
-        to_external_token_id(deps.storage, asset_chain, &asset_address, token_id.clone())?;
-
-    let symbol: [u8; 32];
-    let name: [u8; 32];
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: asset.clone(),
+        msg,
+        funds: vec![],
+    }))
+}
 
+/// Name and symbol of an NFT contract. Wrapped Solana NFTs share a single contract, theirs are
+/// cached per token when they arrive.
+fn query_name_symbol(
+    deps: Deps,
+    asset: &HumanAddr,
+    asset_chain: u16,
+    external_token_id: &[u8; 32],
+) -> StdResult<(String, String)> {
     if asset_chain == 1 {
         let SplCacheItem {
             name: cached_name,
             symbol: cached_symbol,
-        } = spl_cache_read(deps.storage).load(&external_token_id)?;
-        symbol = cached_symbol;
-        name = cached_name;
+        } = spl_cache_read(deps.storage).load(external_token_id)?;
+        Ok((
+            get_valid_string_from_32(&cached_name),
+            get_valid_string_from_32(&cached_symbol),
+        ))
     } else {
         let response: cw721::ContractInfoResponse =
             deps.querier
@@ -459,10 +777,17 @@ fn handle_initiate_transfer(
                     contract_addr: asset.clone(),
                     msg: to_binary(&cw721_base::msg::QueryMsg::ContractInfo {})?,
                 }))?;
-        name = string_to_array(&response.name);
-        symbol = string_to_array(&response.symbol);
+        Ok((response.name, response.symbol))
     }
+}
 
+/// URI of a token, refused if it is too long for the chain it is sent to.
+fn query_token_uri(
+    deps: Deps,
+    asset: &HumanAddr,
+    token_id: &String,
+    recipient_chain: u16,
+) -> StdResult<String> {
     let cw721::NftInfoResponse::<Option<Empty>> { token_uri, .. } =
         deps.querier
             .custom_query(&QueryRequest::<Empty>::Wasm(WasmQuery::Smart {
@@ -472,26 +797,290 @@ fn handle_initiate_transfer(
                 })?,
             }))?;
 
+    // The metadata program on Solana stores at most 200 bytes, longer URIs would be cut off.
+    let uri = token_uri.unwrap_or_default();
+    if recipient_chain == 1 && uri.len() > SOLANA_MAX_URI_LENGTH {
+        return Err(StdError::generic_err(
+            "uri too long to be redeemed on Solana",
+        ));
+    }
+
+    Ok(uri)
+}
+
+fn handle_initiate_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: HumanAddr,
+    token_id: String,
+    recipient_chain: u16,
+    recipient: [u8; 32],
+    nonce: u32,
+) -> StdResult<Response> {
+    if recipient_chain == CHAIN_ID {
+        return ContractError::SameSourceAndTarget.std_err();
+    }
+
+    let cfg: ConfigInfo = config_read(deps.storage).load()?;
+
+    let (asset_chain, asset_address, wrapped) = query_asset_origin(deps.as_ref(), &asset)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![escrow_token(&env, &asset, &token_id, wrapped)?];
+
+    let external_token_id =
+        to_external_token_id(deps.storage, asset_chain, &asset_address, token_id.clone())?;
+
+    let (name, symbol) = query_name_symbol(deps.as_ref(), &asset, asset_chain, &external_token_id)?;
+
+    let uri = query_token_uri(deps.as_ref(), &asset, &token_id, recipient_chain)?;
+
+    // Collections, royalties and creators are not tracked by cw721, the NFT contract itself is the
+    // collection.
     let transfer_info = TransferInfo {
         nft_address: asset_address,
         nft_chain: asset_chain,
         symbol,
         name,
         token_id: external_token_id,
-        uri: BoundedVec::new(token_uri.unwrap().into())?,
+        uri,
+        collection: [0u8; 32],
+        seller_fee_basis_points: 0,
+        creators: vec![],
         recipient,
         recipient_chain,
     };
 
-    let token_bridge_message = TokenBridgeMessage {
-        action: Action::TRANSFER,
-        payload: transfer_info.serialize(),
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: cfg.wormhole_contract,
+        msg: to_binary(&WormholeExecuteMsg::PostMessage {
+            message: Binary::from(transfer_info.serialize()),
+            nonce,
+        })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("transfer.token_chain", asset_chain.to_string())
+        .add_attribute("transfer.token", hex::encode(asset_address))
+        .add_attribute("transfer.token_id", token_id)
+        .add_attribute("transfer.external_token_id", hex::encode(external_token_id))
+        .add_attribute(
+            "transfer.sender",
+            hex::encode(extend_address_to_32(
+                &deps.api.addr_canonicalize(&info.sender.as_str())?,
+            )),
+        )
+        .add_attribute("transfer.recipient_chain", recipient_chain.to_string())
+        .add_attribute("transfer.recipient", hex::encode(recipient))
+        .add_attribute("transfer.nonce", nonce.to_string())
+        .add_attribute("transfer.block_time", env.block.time.seconds().to_string()))
+}
+
+fn handle_initiate_batch_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: HumanAddr,
+    token_ids: Vec<String>,
+    recipient_chain: u16,
+    recipient: [u8; 32],
+    nonce: u32,
+) -> StdResult<Response> {
+    if recipient_chain == CHAIN_ID {
+        return ContractError::SameSourceAndTarget.std_err();
+    }
+    if !PayloadKind::BatchTransfer.is_redeemable_on(&Chain::from(recipient_chain)) {
+        return Err(StdError::generic_err(format!(
+            "chain {} does not redeem batch transfers",
+            recipient_chain
+        )));
+    }
+
+    if token_ids.is_empty() || token_ids.len() > MAX_BATCH_SIZE {
+        return Err(StdError::generic_err(format!(
+            "a batch holds between 1 and {} tokens",
+            MAX_BATCH_SIZE
+        )));
+    }
+
+    // Locking a native token the bridge already holds succeeds, so a repeated id would be sent
+    // twice.
+    let mut unique = token_ids.clone();
+    unique.sort();
+    unique.dedup();
+    if unique.len() != token_ids.len() {
+        return Err(StdError::generic_err("duplicate token id in batch"));
+    }
+
+    let cfg: ConfigInfo = config_read(deps.storage).load()?;
+
+    let (asset_chain, asset_address, wrapped) = query_asset_origin(deps.as_ref(), &asset)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut tokens = vec![];
+    for token_id in token_ids.iter() {
+        messages.push(escrow_token(&env, &asset, token_id, wrapped)?);
+
+        tokens.push(BatchTokenInfo {
+            token_id: to_external_token_id(
+                deps.storage,
+                asset_chain,
+                &asset_address,
+                token_id.clone(),
+            )?,
+            uri: query_token_uri(deps.as_ref(), &asset, token_id, recipient_chain)?,
+        });
+    }
+
+    // The name and symbol of the batch are those of its first token
+    let (name, symbol) =
+        query_name_symbol(deps.as_ref(), &asset, asset_chain, &tokens[0].token_id)?;
+
+    let external_token_ids: Vec<_> = tokens.iter().map(|t| hex::encode(t.token_id)).collect();
+
+    let batch_info = BatchTransferInfo {
+        nft_address: asset_address,
+        nft_chain: asset_chain,
+        symbol,
+        name,
+        tokens,
+        recipient,
+        recipient_chain,
+    };
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: cfg.wormhole_contract,
+        msg: to_binary(&WormholeExecuteMsg::PostMessage {
+            message: Binary::from(batch_info.serialize()),
+            nonce,
+        })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("transfer.token_chain", asset_chain.to_string())
+        .add_attribute("transfer.token", hex::encode(asset_address))
+        .add_attribute("transfer.token_ids", token_ids.join(","))
+        .add_attribute("transfer.external_token_ids", external_token_ids.join(","))
+        .add_attribute(
+            "transfer.sender",
+            hex::encode(extend_address_to_32(
+                &deps.api.addr_canonicalize(&info.sender.as_str())?,
+            )),
+        )
+        .add_attribute("transfer.recipient_chain", recipient_chain.to_string())
+        .add_attribute("transfer.recipient", hex::encode(recipient))
+        .add_attribute("transfer.nonce", nonce.to_string())
+        .add_attribute("transfer.block_time", env.block.time.seconds().to_string()))
+}
+
+fn handle_initiate_semi_fungible_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: HumanAddr,
+    token_id: String,
+    amount: Uint128,
+    recipient_chain: u16,
+    recipient: [u8; 32],
+    nonce: u32,
+) -> StdResult<Response> {
+    if recipient_chain == CHAIN_ID {
+        return ContractError::SameSourceAndTarget.std_err();
+    }
+    if !PayloadKind::SemiFungibleTransfer.is_redeemable_on(&Chain::from(recipient_chain)) {
+        return Err(StdError::generic_err(format!(
+            "chain {} does not redeem semi-fungible transfers",
+            recipient_chain
+        )));
+    }
+
+    if amount.is_zero() {
+        return ContractError::AmountTooLow.std_err();
+    }
+
+    // SPL amounts are 64 bit, more copies could not be redeemed on Solana
+    if recipient_chain == 1 && amount.u128() > u64::MAX as u128 {
+        return ContractError::AmountTooHigh.std_err();
+    }
+
+    let cfg: ConfigInfo = config_read(deps.storage).load()?;
+
+    let (asset_chain, asset_address, wrapped) = query_asset_origin(deps.as_ref(), &asset)?;
+
+    // Wrapped tokens are burnt, native ones locked up in custody. Either way the bridge must be
+    // an operator of the sender.
+    let escrow_msg = if wrapped {
+        cw1155_wrapped::msg::ExecuteMsg::BurnFrom {
+            from: info.sender.to_string(),
+            token_id: token_id.clone(),
+            value: amount,
+        }
+    } else {
+        cw1155_wrapped::msg::ExecuteMsg::SendFrom {
+            from: info.sender.to_string(),
+            to: env.contract.address.to_string(),
+            token_id: token_id.clone(),
+            value: amount,
+            msg: None,
+        }
+    };
+    let mut messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: asset.clone(),
+        msg: to_binary(&escrow_msg)?,
+        funds: vec![],
+    })];
+
+    let external_token_id =
+        to_external_token_id(deps.storage, asset_chain, &asset_address, token_id.clone())?;
+
+    // cw1155 has no name or symbol, native contracts that don't expose them like cw721 does are
+    // sent without.
+    let (name, symbol) =
+        match query_name_symbol(deps.as_ref(), &asset, asset_chain, &external_token_id) {
+            Ok(name_symbol) => name_symbol,
+            Err(_) if !wrapped => (String::new(), String::new()),
+            Err(e) => return Err(e),
+        };
+
+    let cw1155_wrapped::msg::TokenInfoResponse { url: uri } =
+        deps.querier
+            .custom_query(&QueryRequest::<Empty>::Wasm(WasmQuery::Smart {
+                contract_addr: asset.clone(),
+                msg: to_binary(&cw1155_wrapped::msg::QueryMsg::TokenInfo {
+                    token_id: token_id.clone(),
+                })?,
+            }))?;
+
+    // The metadata program on Solana stores at most 200 bytes, longer URIs would be cut off.
+    if recipient_chain == 1 && uri.len() > SOLANA_MAX_URI_LENGTH {
+        return Err(StdError::generic_err(
+            "uri too long to be redeemed on Solana",
+        ));
+    }
+
+    let mut amount_bytes = [0u8; 32];
+    amount_bytes[16..].copy_from_slice(&amount.u128().to_be_bytes());
+
+    let transfer_info = SemiFungibleTransferInfo {
+        nft_address: asset_address,
+        nft_chain: asset_chain,
+        symbol,
+        name,
+        token_id: external_token_id,
+        amount: amount_bytes,
+        uri,
+        recipient,
+        recipient_chain,
     };
 
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: cfg.wormhole_contract,
         msg: to_binary(&WormholeExecuteMsg::PostMessage {
-            message: Binary::from(token_bridge_message.serialize()),
+            message: Binary::from(transfer_info.serialize()),
             nonce,
         })?,
         funds: vec![],
@@ -503,6 +1092,7 @@ fn handle_initiate_transfer(
         .add_attribute("transfer.token", hex::encode(asset_address))
         .add_attribute("transfer.token_id", token_id)
         .add_attribute("transfer.external_token_id", hex::encode(external_token_id))
+        .add_attribute("transfer.amount", amount)
         .add_attribute(
             "transfer.sender",
             hex::encode(extend_address_to_32(
@@ -515,12 +1105,16 @@ fn handle_initiate_transfer(
         .add_attribute("transfer.block_time", env.block.time.seconds().to_string()))
 }
 
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::WrappedRegistry { chain, address } => {
             to_binary(&query_wrapped_registry(deps, chain, address.as_slice())?)
         }
+        QueryMsg::WrappedSemiFungibleRegistry { chain, address } => to_binary(
+            &query_wrapped_semi_fungible_registry(deps, chain, address.as_slice())?,
+        ),
         QueryMsg::AllWrappedAssets {  } => {
             to_binary(&query_all_wrapped_assets(deps)?)
         }
@@ -547,7 +1141,21 @@ fn handle_register_asset(
     let contract_address: CanonicalAddr = deps.api.addr_canonicalize(&info.sender.as_str())?;
     wrapped_asset_address(deps.storage).save(contract_address.as_slice(), &asset_id.to_vec())?;
 
+    // Mint the rest of the batch that deployed the contract
+    let mut messages = vec![];
+    if let Some(pending) = pending_mints(deps.storage).may_load(asset_id)? {
+        pending_mints(deps.storage).remove(asset_id);
+        for mint_msg in pending {
+            messages.push(CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
+                contract_addr: info.sender.to_string(),
+                msg: to_binary(&cw721_base::msg::ExecuteMsg::Mint(mint_msg))?,
+                funds: vec![],
+            }));
+        }
+    }
+
     Ok(Response::new()
+        .add_messages(messages)
         .add_attribute("action", "register_asset")
         .add_attribute("asset_id", format!("{:?}", asset_id))
         .add_attribute("contract_addr", info.sender))
@@ -566,6 +1174,18 @@ pub fn query_wrapped_registry(
     }
 }
 
+pub fn query_wrapped_semi_fungible_registry(
+    deps: Deps,
+    chain: u16,
+    address: &[u8],
+) -> StdResult<WrappedRegistryResponse> {
+    let asset_id = build_semi_fungible_asset_id(chain, address);
+    match wrapped_asset_read(deps.storage).load(&asset_id) {
+        Ok(address) => Ok(WrappedRegistryResponse { address }),
+        Err(_) => ContractError::AssetNotFound.std_err(),
+    }
+}
+
 fn query_all_wrapped_assets(deps: Deps) -> StdResult<Vec<String>> {
     let bucket = wrapped_asset_address_read(deps.storage);
     let mut result = vec![];
@@ -586,3 +1206,68 @@ fn build_asset_id(chain: u16, address: &[u8]) -> Vec<u8> {
     hasher.update(asset_id);
     hasher.finalize().to_vec()
 }
+
+// Semi-fungible tokens get a wrapped contract of their own, apart from the NFTs of the same
+// origin, so their asset IDs are kept apart too.
+fn build_semi_fungible_asset_id(chain: u16, address: &[u8]) -> Vec<u8> {
+    let mut asset_id: Vec<u8> = vec![];
+    asset_id.extend_from_slice(&chain.to_be_bytes());
+    asset_id.extend_from_slice(address);
+    asset_id.extend_from_slice(b"semi_fungible");
+
+    let mut hasher = Keccak256::new();
+    hasher.update(asset_id);
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        testing::{
+            mock_dependencies,
+            mock_env,
+            mock_info,
+        },
+        StdError,
+        Uint128,
+    };
+
+    use super::execute;
+    use crate::msg::ExecuteMsg;
+
+    #[test]
+    fn test_batch_transfer_chain() {
+        // Only Solana redeems batches besides Terra itself, the EVM bridges would reject them.
+        let mut deps = mock_dependencies(&[]);
+        let msg = ExecuteMsg::InitiateBatchTransfer {
+            contract_addr: "nft".to_string(),
+            token_ids: vec!["1".to_string()],
+            recipient_chain: 2,
+            recipient: [1u8; 32].to_vec().into(),
+            nonce: 0,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("sender", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("chain 2 does not redeem batch transfers")
+        );
+    }
+
+    #[test]
+    fn test_semi_fungible_transfer_chain() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = ExecuteMsg::InitiateSemiFungibleTransfer {
+            contract_addr: "sft".to_string(),
+            token_id: "1".to_string(),
+            amount: Uint128::new(1),
+            recipient_chain: 2,
+            recipient: [1u8; 32].to_vec().into(),
+            nonce: 0,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("sender", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("chain 2 does not redeem semi-fungible transfers")
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use cosmwasm_std::Binary;
+use cosmwasm_std::{
+    Binary,
+    Uint128,
+};
 use schemars::JsonSchema;
 use serde::{
     Deserialize,
@@ -15,6 +18,7 @@ pub struct InstantiateMsg {
 
     pub wormhole_contract: HumanAddr,
     pub wrapped_asset_code_id: u64,
+    pub wrapped_semi_fungible_code_id: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -32,6 +36,23 @@ pub enum ExecuteMsg {
         nonce: u32,
     },
 
+    InitiateBatchTransfer {
+        contract_addr: String,
+        token_ids: Vec<String>,
+        recipient_chain: u16,
+        recipient: Binary,
+        nonce: u32,
+    },
+
+    InitiateSemiFungibleTransfer {
+        contract_addr: String,
+        token_id: String,
+        amount: Uint128,
+        recipient_chain: u16,
+        recipient: Binary,
+        nonce: u32,
+    },
+
     SubmitVaa {
         data: Binary,
     },
@@ -45,6 +66,7 @@ pub struct MigrateMsg {}
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     WrappedRegistry { chain: u16, address: Binary },
+    WrappedSemiFungibleRegistry { chain: u16, address: Binary },
     AllWrappedAssets { },
 }
 
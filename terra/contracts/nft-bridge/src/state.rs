@@ -1,5 +1,3 @@
-use std::convert::TryInto;
-
 use schemars::JsonSchema;
 use serde::{
     Deserialize,
@@ -7,6 +5,7 @@ use serde::{
 };
 
 use cosmwasm_std::{
+    Empty,
     StdError,
     StdResult,
     Storage,
@@ -22,7 +21,16 @@ use cosmwasm_storage::{
     Singleton,
 };
 
-use wormhole::byte_utils::ByteUtils;
+use cw721_base::msg::MintMsg;
+use primitive_types::U256;
+use wormhole::{
+    byte_utils::ByteUtils,
+    state::parse_governance_action,
+};
+use wormhole_core::{
+    vaa::nft,
+    Chain,
+};
 
 type HumanAddr = String;
 
@@ -32,6 +40,7 @@ pub static WRAPPED_ASSET_ADDRESS_KEY: &[u8] = b"wrapped_asset_address";
 pub static BRIDGE_CONTRACTS_KEY: &[u8] = b"bridge_contracts";
 pub static TOKEN_ID_HASHES_KEY: &[u8] = b"token_id_hashes";
 pub static SPL_CACHE_KEY: &[u8] = b"spl_cache";
+pub static PENDING_MINTS_KEY: &[u8] = b"pending_mints";
 
 // Guardian set information
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -42,6 +51,10 @@ pub struct ConfigInfo {
 
     pub wormhole_contract: HumanAddr,
     pub wrapped_asset_code_id: u64,
+
+    // Code of the cw1155-wrapped contract, semi-fungible transfers are refused until it is set
+    #[serde(default)]
+    pub wrapped_semi_fungible_code_id: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -90,6 +103,12 @@ pub fn spl_cache_read(storage: &dyn Storage) -> ReadonlyBucket<SplCacheItem> {
     bucket_read(storage, SPL_CACHE_KEY)
 }
 
+// A wrapped contract is instantiated with a single token, the rest of a batch creating it waits
+// here, under the asset ID, until the contract registers itself.
+pub fn pending_mints(storage: &mut dyn Storage) -> Bucket<Vec<MintMsg<Option<Empty>>>> {
+    bucket(storage, PENDING_MINTS_KEY)
+}
+
 pub fn token_id_hashes(storage: &mut dyn Storage, chain: u16, address: [u8; 32]) -> Bucket<String> {
     Bucket::multilevel(
         storage,
@@ -112,6 +131,9 @@ pub struct Action;
 
 impl Action {
     pub const TRANSFER: u8 = 1;
+    pub const TRANSFER_V2: u8 = 2;
+    pub const BATCH_TRANSFER: u8 = 3;
+    pub const SEMI_FUNGIBLE_TRANSFER: u8 = 4;
 }
 
 // 0 u8 action
@@ -139,104 +161,203 @@ impl TokenBridgeMessage {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[repr(transparent)]
-pub struct BoundedVec<T, const N: usize> {
-    vec: Vec<T>,
+pub struct CreatorInfo {
+    pub address: [u8; 32],
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferInfo {
+    pub nft_address: [u8; 32],
+    pub nft_chain: u16,
+    pub symbol: String,
+    pub name: String,
+    pub token_id: [u8; 32],
+    pub uri: String,
+    pub collection: [u8; 32], // zero if the NFT belongs to no collection
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<CreatorInfo>,
+    pub recipient: [u8; 32],
+    pub recipient_chain: u16,
 }
 
-impl<T, const N: usize> BoundedVec<T, N> {
-    pub fn new(vec: Vec<T>) -> StdResult<Self> {
-        if vec.len() > N {
-            return Result::Err(StdError::GenericErr {
-                msg: format!("vector length exceeds {}", N),
-            });
+// The wire format is defined by wormhole-core. Both versions of the transfer are accepted, their
+// payload ID doubles as the action, so `deserialize` and `serialize` work on the whole payload
+// including the leading action byte. Transfers version 1 can carry are sent as version 1.
+impl TransferInfo {
+    pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
+        let transfer = nft::TransferV2::from_versioned_bytes(data)
+            .map_err(|_| StdError::generic_err("invalid transfer payload"))?;
+
+        let mut token_id = [0u8; 32];
+        transfer.token_id.to_big_endian(&mut token_id);
+
+        Ok(TransferInfo {
+            nft_address: transfer.nft_address,
+            nft_chain: transfer.nft_chain.into(),
+            symbol: transfer.symbol,
+            name: transfer.name,
+            token_id,
+            uri: transfer.uri,
+            collection: transfer.collection,
+            seller_fee_basis_points: transfer.seller_fee_basis_points,
+            creators: transfer
+                .creators
+                .into_iter()
+                .map(|c| CreatorInfo {
+                    address: c.address,
+                    verified: c.verified,
+                    share: c.share,
+                })
+                .collect(),
+            recipient: transfer.to,
+            recipient_chain: transfer.to_chain.into(),
+        })
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let transfer = nft::TransferV2 {
+            nft_address: self.nft_address,
+            nft_chain: Chain::from(self.nft_chain),
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            token_id: U256::from_big_endian(&self.token_id),
+            uri: self.uri.clone(),
+            collection: self.collection,
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            creators: self
+                .creators
+                .iter()
+                .map(|c| nft::Creator {
+                    address: c.address,
+                    verified: c.verified,
+                    share: c.share,
+                })
+                .collect(),
+            to: self.recipient,
+            to_chain: Chain::from(self.recipient_chain),
         };
-        Ok(Self { vec })
+        transfer.to_versioned_bytes()
     }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchTokenInfo {
+    pub token_id: [u8; 32],
+    pub uri: String,
+}
 
-    #[inline]
-    pub fn to_vec(&self) -> Vec<T>
-    where
-        T: Clone,
-    {
-        self.vec.clone()
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchTransferInfo {
+    pub nft_address: [u8; 32],
+    pub nft_chain: u16,
+    pub symbol: String,
+    pub name: String,
+    pub tokens: Vec<BatchTokenInfo>,
+    pub recipient: [u8; 32],
+    pub recipient_chain: u16,
+}
+
+// Like `TransferInfo`, this works on the whole payload including the action byte.
+impl BatchTransferInfo {
+    pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
+        let transfer = nft::BatchTransfer::from_bytes(data)
+            .map_err(|_| StdError::generic_err("invalid batch transfer payload"))?;
+
+        Ok(BatchTransferInfo {
+            nft_address: transfer.nft_address,
+            nft_chain: transfer.nft_chain.into(),
+            symbol: transfer.symbol,
+            name: transfer.name,
+            tokens: transfer
+                .tokens
+                .into_iter()
+                .map(|t| {
+                    let mut token_id = [0u8; 32];
+                    t.token_id.to_big_endian(&mut token_id);
+                    BatchTokenInfo {
+                        token_id,
+                        uri: t.uri,
+                    }
+                })
+                .collect(),
+            recipient: transfer.to,
+            recipient_chain: transfer.to_chain.into(),
+        })
     }
 
-    #[inline]
-    pub fn len(&self) -> usize {
-        self.vec.len()
+    pub fn serialize(&self) -> Vec<u8> {
+        let transfer = nft::BatchTransfer {
+            nft_address: self.nft_address,
+            nft_chain: Chain::from(self.nft_chain),
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            tokens: self
+                .tokens
+                .iter()
+                .map(|t| nft::BatchToken {
+                    token_id: U256::from_big_endian(&t.token_id),
+                    uri: t.uri.clone(),
+                })
+                .collect(),
+            to: self.recipient,
+            to_chain: Chain::from(self.recipient_chain),
+        };
+        transfer.to_bytes()
     }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct TransferInfo {
+pub struct SemiFungibleTransferInfo {
     pub nft_address: [u8; 32],
     pub nft_chain: u16,
-    pub symbol: [u8; 32],
-    pub name: [u8; 32],
+    pub symbol: String,
+    pub name: String,
     pub token_id: [u8; 32],
-    pub uri: BoundedVec<u8, 200>, // max 200 bytes due to Solana
+    pub amount: [u8; 32],
+    pub uri: String,
     pub recipient: [u8; 32],
     pub recipient_chain: u16,
 }
 
-impl TransferInfo {
+// Like `TransferInfo`, this works on the whole payload including the action byte.
+impl SemiFungibleTransferInfo {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let mut offset: usize = 0; // offset into data in bytes
-        let nft_address = data.get_const_bytes::<32>(offset);
-        offset += 32;
-        let nft_chain = data.get_u16(offset);
-        offset += 2;
-        let symbol = data.get_const_bytes::<32>(offset);
-        offset += 32;
-        let name = data.get_const_bytes::<32>(offset);
-        offset += 32;
-        let token_id = data.get_const_bytes::<32>(offset);
-        offset += 32;
-        let uri_length: usize = data.get_u8(offset).into();
-        offset += 1;
-        let uri = data.get_bytes(offset, uri_length).to_vec();
-        offset += uri_length;
-        let recipient = data.get_const_bytes::<32>(offset);
-        offset += 32;
-        let recipient_chain = data.get_u16(offset);
-        offset += 2;
-
-        if data.len() != offset {
-            return Result::Err(StdError::GenericErr {
-                msg: format!(
-                    "Invalid transfer length, expected {}, but got {}",
-                    offset,
-                    data.len()
-                ),
-            });
-        }
-
-        Ok(TransferInfo {
-            nft_address,
-            nft_chain,
-            symbol,
-            name,
+        let transfer = nft::SemiFungibleTransfer::from_bytes(data)
+            .map_err(|_| StdError::generic_err("invalid semi-fungible transfer payload"))?;
+
+        let mut token_id = [0u8; 32];
+        transfer.token_id.to_big_endian(&mut token_id);
+        let mut amount = [0u8; 32];
+        transfer.amount.to_big_endian(&mut amount);
+
+        Ok(SemiFungibleTransferInfo {
+            nft_address: transfer.nft_address,
+            nft_chain: transfer.nft_chain.into(),
+            symbol: transfer.symbol,
+            name: transfer.name,
             token_id,
-            uri: BoundedVec::new(uri.to_vec())?,
-            recipient,
-            recipient_chain,
+            amount,
+            uri: transfer.uri,
+            recipient: transfer.to,
+            recipient_chain: transfer.to_chain.into(),
         })
     }
+
     pub fn serialize(&self) -> Vec<u8> {
-        [
-            self.nft_address.to_vec(),
-            self.nft_chain.to_be_bytes().to_vec(),
-            self.symbol.to_vec(),
-            self.name.to_vec(),
-            self.token_id.to_vec(),
-            vec![self.uri.len().try_into().unwrap()], // won't panic, because uri.len() is less than 200
-            self.uri.to_vec(),
-            self.recipient.to_vec(),
-            self.recipient_chain.to_be_bytes().to_vec(),
-        ]
-        .concat()
+        let transfer = nft::SemiFungibleTransfer {
+            nft_address: self.nft_address,
+            nft_chain: Chain::from(self.nft_chain),
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            token_id: U256::from_big_endian(&self.token_id),
+            amount: U256::from_big_endian(&self.amount),
+            uri: self.uri.clone(),
+            to: self.recipient,
+            to_chain: Chain::from(self.recipient_chain),
+        };
+        transfer.to_bytes()
     }
 }
 
@@ -251,21 +372,282 @@ pub struct RegisterChain {
 
 impl UpgradeContract {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let new_contract = data.get_u64(24);
+        let action: nft::GovernanceContractUpgrade = parse_governance_action(data)?;
+        // The code ID takes up the last 8 bytes of the address.
+        let new_contract = (&action.new_contract[..]).get_u64(24);
         Ok(UpgradeContract { new_contract })
     }
 }
 
 impl RegisterChain {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let chain_id = data.get_u16(0);
-        let chain_address = data[2..].to_vec();
+        let action: nft::GovernanceRegisterChain = parse_governance_action(data)?;
 
         Ok(RegisterChain {
-            chain_id,
-            chain_address,
+            chain_id: action.emitter.into(),
+            chain_address: action.endpoint_address.to_vec(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wormhole::{
+        byte_utils::get_string_from_32,
+        state::GovernancePacket,
+    };
+    use wormhole_core::testing::corpus;
+
+    const VECTORS: &str = include_str!("../../../../sdk/vectors/v1.json");
+
+    /// The shared wire format vectors of the given type as (name, bytes, valid, fields,
+    /// canonical encoding). wormhole-core and the Solana programs load the same file.
+    fn vectors(kind: &str) -> Vec<(String, Vec<u8>, bool, serde_json::Value, Vec<u8>)> {
+        let file: serde_json::Value = serde_json::from_str(VECTORS).unwrap();
+        assert_eq!(file["version"], 1);
+        let vectors: Vec<_> = file["vectors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|v| v["type"] == kind)
+            .map(|v| {
+                let bytes = hex::decode(v["hex"].as_str().unwrap()).unwrap();
+                let canonical = match v["canonical"].as_str() {
+                    Some(canonical) => hex::decode(canonical).unwrap(),
+                    None => bytes.clone(),
+                };
+                let name = v["name"].as_str().unwrap().to_string();
+                (name, bytes, v["valid"].as_bool().unwrap(), v["fields"].clone(), canonical)
+            })
+            .collect();
+        assert!(!vectors.is_empty(), "no vectors for {}", kind);
+        vectors
+    }
+
+    fn field_bytes(fields: &serde_json::Value, key: &str) -> Vec<u8> {
+        hex::decode(fields[key].as_str().unwrap()).unwrap()
+    }
+
+    fn field_int(fields: &serde_json::Value, key: &str) -> u64 {
+        fields[key].as_u64().unwrap()
+    }
+
+    /// The action payload of a governance packet, checked the way the contract checks it before
+    /// dispatching on the action.
+    fn governance_payload(data: &Vec<u8>, module: &str, action: u8) -> StdResult<Vec<u8>> {
+        let packet = GovernancePacket::deserialize(data)?;
+        let module_ok = get_string_from_32(&packet.module) == module;
+        let chain_ok = packet.chain == 0 || packet.chain == 3;
+        if !module_ok || !chain_ok || packet.action != action {
+            return Err(StdError::generic_err("governance packet rejected"));
+        }
+        Ok(packet.payload)
+    }
+
+    fn transfer(uri: &str) -> TransferInfo {
+        TransferInfo {
+            nft_address: [1u8; 32],
+            nft_chain: 2,
+            symbol: "TEST".to_string(),
+            name: "Token Token".to_string(),
+            token_id: [3u8; 32],
+            uri: uri.to_string(),
+            collection: [0u8; 32],
+            seller_fee_basis_points: 0,
+            creators: vec![],
+            recipient: [4u8; 32],
+            recipient_chain: 3,
+        }
+    }
+
+    fn core_transfer(bytes: &[u8]) -> Option<nft::TransferV2> {
+        nft::TransferV2::from_versioned_bytes(bytes).ok()
+    }
+
+    // Terra must accept exactly the payloads wormhole-core accepts, and decode them to the same
+    // values.
+
+    #[test]
+    fn test_differential_transfer() {
+        let v1 = transfer("https://abc.abc.abc.com/é");
+        let v2 = TransferInfo {
+            collection: [5u8; 32],
+            seller_fee_basis_points: 500,
+            creators: vec![CreatorInfo {
+                address: [6u8; 32],
+                verified: true,
+                share: 100,
+            }],
+            ..v1.clone()
+        };
+        for valid in [v1.serialize(), v2.serialize()].iter() {
+            for bytes in corpus(valid) {
+                let terra = TransferInfo::deserialize(&bytes)
+                    .ok()
+                    .map(|t| t.serialize());
+                let core = core_transfer(&bytes).map(|t| t.to_versioned_bytes());
+                assert_eq!(terra, core, "payload {:?}", bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_uri_length() {
+        // URIs past 200 bytes used to be rejected for Solana's sake, which now truncates them
+        // itself. Version 1 carries up to 255 bytes, longer ones switch to version 2.
+        let mut uri = transfer("").serialize();
+        uri[131] = 201;
+        uri.splice(132..132, vec![b'a'; 201]);
+        assert!(core_transfer(&uri).is_some());
+        assert_eq!(
+            TransferInfo::deserialize(&uri).unwrap().uri,
+            "a".repeat(201)
+        );
+
+        let long = transfer(&"a".repeat(1000));
+        let bytes = long.serialize();
+        assert_eq!(bytes[0], Action::TRANSFER_V2);
+        assert_eq!(TransferInfo::deserialize(&bytes).unwrap(), long);
+
+        // The EVM bridges only redeem version 1, so transfers there are truncated instead.
+        let to_ethereum = TransferInfo {
+            recipient_chain: 2,
+            ..long
+        };
+        let bytes = to_ethereum.serialize();
+        assert_eq!(bytes[0], Action::TRANSFER);
+        assert_eq!(
+            TransferInfo::deserialize(&bytes).unwrap().uri,
+            "a".repeat(255)
+        );
+    }
+
+    fn field_bytes32(fields: &serde_json::Value, key: &str) -> [u8; 32] {
+        field_bytes(fields, key).as_slice().get_const_bytes::<32>(0)
+    }
+
+    fn field_creators(fields: &serde_json::Value) -> Vec<CreatorInfo> {
+        fields["creators"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| CreatorInfo {
+                address: field_bytes32(c, "address"),
+                verified: c["verified"].as_bool().unwrap(),
+                share: field_int(c, "share") as u8,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_transfer_vectors() {
+        for kind in ["nft_transfer", "nft_transfer_v2"].iter() {
+            for (name, data, valid, f, canonical) in vectors(kind) {
+                let info = TransferInfo::deserialize(&data).ok();
+                assert_eq!(info.is_some(), valid, "{}", name);
+                if let Some(info) = info {
+                    let v2 = *kind == "nft_transfer_v2";
+                    let expected = TransferInfo {
+                        nft_address: field_bytes32(&f, "nft_address"),
+                        nft_chain: field_int(&f, "nft_chain") as u16,
+                        symbol: f["symbol"].as_str().unwrap().to_string(),
+                        name: f["name"].as_str().unwrap().to_string(),
+                        token_id: field_bytes32(&f, "token_id"),
+                        uri: f["uri"].as_str().unwrap().to_string(),
+                        collection: if v2 {
+                            field_bytes32(&f, "collection")
+                        } else {
+                            [0u8; 32]
+                        },
+                        seller_fee_basis_points: if v2 {
+                            field_int(&f, "seller_fee_basis_points") as u16
+                        } else {
+                            0
+                        },
+                        creators: if v2 { field_creators(&f) } else { vec![] },
+                        recipient: field_bytes32(&f, "to"),
+                        recipient_chain: field_int(&f, "to_chain") as u16,
+                    };
+                    assert_eq!(info, expected, "{}", name);
+                    assert_eq!(info.serialize(), canonical, "{}", name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_transfer_vectors() {
+        for (name, data, valid, f, canonical) in vectors("nft_batch_transfer") {
+            let info = BatchTransferInfo::deserialize(&data).ok();
+            assert_eq!(info.is_some(), valid, "{}", name);
+            if let Some(info) = info {
+                let expected = BatchTransferInfo {
+                    nft_address: field_bytes32(&f, "nft_address"),
+                    nft_chain: field_int(&f, "nft_chain") as u16,
+                    symbol: f["symbol"].as_str().unwrap().to_string(),
+                    name: f["name"].as_str().unwrap().to_string(),
+                    tokens: f["tokens"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|t| BatchTokenInfo {
+                            token_id: field_bytes32(t, "token_id"),
+                            uri: t["uri"].as_str().unwrap().to_string(),
+                        })
+                        .collect(),
+                    recipient: field_bytes32(&f, "to"),
+                    recipient_chain: field_int(&f, "to_chain") as u16,
+                };
+                assert_eq!(info, expected, "{}", name);
+                assert_eq!(info.serialize(), canonical, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_semi_fungible_transfer_vectors() {
+        for (name, data, valid, f, canonical) in vectors("nft_semi_fungible_transfer") {
+            let info = SemiFungibleTransferInfo::deserialize(&data).ok();
+            assert_eq!(info.is_some(), valid, "{}", name);
+            if let Some(info) = info {
+                let expected = SemiFungibleTransferInfo {
+                    nft_address: field_bytes32(&f, "nft_address"),
+                    nft_chain: field_int(&f, "nft_chain") as u16,
+                    symbol: f["symbol"].as_str().unwrap().to_string(),
+                    name: f["name"].as_str().unwrap().to_string(),
+                    token_id: field_bytes32(&f, "token_id"),
+                    amount: field_bytes32(&f, "amount"),
+                    uri: f["uri"].as_str().unwrap().to_string(),
+                    recipient: field_bytes32(&f, "to"),
+                    recipient_chain: field_int(&f, "to_chain") as u16,
+                };
+                assert_eq!(info, expected, "{}", name);
+                assert_eq!(info.serialize(), canonical, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_governance_vectors() {
+        for (name, data, valid, f, _) in vectors("nft_register_chain") {
+            let action = governance_payload(&data, "NFTBridge", 1)
+                .and_then(|payload| RegisterChain::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                assert_eq!(action.chain_id as u64, field_int(&f, "emitter_chain"), "{}", name);
+                assert_eq!(action.chain_address, field_bytes(&f, "endpoint_address"), "{}", name);
+            }
+        }
+
+        for (name, data, valid, f, _) in vectors("nft_contract_upgrade") {
+            let action = governance_payload(&data, "NFTBridge", 2)
+                .and_then(|payload| UpgradeContract::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                let code_id = field_bytes(&f, "new_contract").as_slice().get_u64(24);
+                assert_eq!(action.new_contract, code_id, "{}", name);
+            }
+        }
+    }
+}
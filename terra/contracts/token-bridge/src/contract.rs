@@ -2,6 +2,7 @@ use crate::msg::WrappedRegistryResponse;
 use cosmwasm_std::{
     coin,
     entry_point,
+    has_coins,
     to_binary,
     BankMsg,
     Binary,
@@ -27,17 +28,20 @@ use cosmwasm_std::{
 
 use crate::{
     msg::{
+        BatchTransfer,
         ExecuteMsg,
         InstantiateMsg,
         MigrateMsg,
         QueryMsg,
     },
     state::{
+        batch_transfer_tmp,
         bridge_contracts,
         bridge_contracts_read,
         bridge_deposit,
         config,
         config_read,
+        pair_to_u256,
         receive_native,
         send_native,
         wrapped_asset,
@@ -49,6 +53,7 @@ use crate::{
         wrapped_transfer_tmp,
         Action,
         AssetMeta,
+        BatchTransferState,
         ConfigInfo,
         RegisterChain,
         TokenBridgeMessage,
@@ -67,6 +72,16 @@ use wormhole::{
     error::ContractError,
 };
 
+use wormhole_core::{
+    decimal_factor,
+    denormalize_amount,
+    normalize_amount,
+    normalize_fee,
+    NormalizedAmount,
+    WormholeError,
+    MAX_DECIMALS,
+};
+
 use cw20_base::msg::{
     ExecuteMsg as TokenMsg,
     QueryMsg as TokenQuery,
@@ -74,6 +89,7 @@ use cw20_base::msg::{
 
 use wormhole::msg::{
     ExecuteMsg as WormholeExecuteMsg,
+    GetStateResponse,
     QueryMsg as WormholeQueryMsg,
 };
 
@@ -106,10 +122,7 @@ use sha3::{
     Keccak256,
 };
 use std::{
-    cmp::{
-        max,
-        min,
-    },
+    cmp::min,
     str::FromStr,
 };
 
@@ -118,8 +131,15 @@ type HumanAddr = String;
 // Chain ID of Terra
 const CHAIN_ID: u16 = 3;
 
+// Decimals of native denoms, their amounts are sent over the wormhole as is
+const NATIVE_DENOM_DECIMALS: u8 = 6;
+
 const WRAPPED_ASSET_UPDATING: &str = "updating";
 
+// Replies to the CW20 transfers of `InitiateTransfer` and `InitiateTransferBatch`.
+const TRANSFER_REPLY_ID: u64 = 1;
+const BATCH_TRANSFER_REPLY_ID: u64 = 2;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
     Ok(Response::new())
@@ -148,13 +168,29 @@ pub fn instantiate(
 // of the bridge. This is to handle fee tokens where the amount expected to be transferred may be
 // less due to burns, fees, etc.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, env: Env, _msg: Reply) -> StdResult<Response> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
     let cfg = config_read(deps.storage).load()?;
 
-    let state = wrapped_transfer_tmp(deps.storage).load()?;
-    // NOTE: Reentrancy protection. See note in `handle_initiate_transfer_token`
-    // for why this is necessary.
-    wrapped_transfer_tmp(deps.storage).remove();
+    let (state, funds) = if msg.id == BATCH_TRANSFER_REPLY_ID {
+        // Batched transfers reply in the order they were queued.
+        let mut queue = batch_transfer_tmp(deps.storage).load()?;
+        if queue.is_empty() {
+            return Err(StdError::generic_err("no batched transfer in progress"));
+        }
+        let next = queue.remove(0);
+        if queue.is_empty() {
+            batch_transfer_tmp(deps.storage).remove();
+        } else {
+            batch_transfer_tmp(deps.storage).save(&queue)?;
+        }
+        (next.transfer, next.funds)
+    } else {
+        let state = wrapped_transfer_tmp(deps.storage).load()?;
+        // NOTE: Reentrancy protection. See note in `handle_initiate_transfer_token`
+        // for why this is necessary.
+        wrapped_transfer_tmp(deps.storage).remove();
+        (state, vec![])
+    };
 
     let mut info = TransferInfo::deserialize(&state.message)?;
 
@@ -182,13 +218,13 @@ pub fn reply(deps: DepsMut, env: Env, _msg: Reply) -> StdResult<Response> {
 
     let token_bridge_message = TokenBridgeMessage {
         action: Action::TRANSFER,
-        payload: info.serialize(),
+        payload: info.serialize()?,
     };
 
     // Post Wormhole Message
     let message = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: cfg.wormhole_contract,
-        funds: vec![],
+        funds,
         msg: to_binary(&WormholeExecuteMsg::PostMessage {
             message: Binary::from(token_bridge_message.serialize()),
             nonce: state.nonce,
@@ -249,6 +285,20 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             fee,
             nonce,
         ),
+        ExecuteMsg::InitiateTransferBatch {
+            transfers,
+            recipient_chain,
+            recipient,
+            nonce,
+        } => handle_initiate_transfer_batch(
+            deps,
+            env,
+            info,
+            transfers,
+            recipient_chain,
+            recipient.as_slice().to_vec(),
+            nonce,
+        ),
         ExecuteMsg::DepositTokens {} => deposit_tokens(deps, env, info),
         ExecuteMsg::WithdrawTokens { asset } => withdraw_tokens(deps, env, info, asset),
         ExecuteMsg::SubmitVaa { data } => submit_vaa(deps, env, info, &data),
@@ -443,7 +493,7 @@ fn handle_create_asset_meta_token(
 
     let token_bridge_message = TokenBridgeMessage {
         action: Action::ATTEST_META,
-        payload: meta.serialize().to_vec(),
+        payload: meta.serialize()?,
     };
 
     Ok(Response::new()
@@ -476,13 +526,13 @@ fn handle_create_asset_meta_native_token(
     let meta: AssetMeta = AssetMeta {
         token_chain: CHAIN_ID,
         token_address: asset_id.clone(),
-        decimals: 6,
+        decimals: NATIVE_DENOM_DECIMALS,
         symbol: extend_string_to_32(&symbol),
         name: extend_string_to_32(&symbol),
     };
     let token_bridge_message = TokenBridgeMessage {
         action: Action::ATTEST_META,
-        payload: meta.serialize().to_vec(),
+        payload: meta.serialize()?,
     };
     Ok(Response::new()
         .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -647,15 +697,8 @@ fn handle_complete_transfer_token(
     let token_chain = transfer_info.token_chain;
     let target_address = (&transfer_info.recipient.as_slice()).get_address(0);
 
-    let (not_supported_amount, mut amount) = transfer_info.amount;
-    let (not_supported_fee, mut fee) = transfer_info.fee;
-
-    amount = amount.checked_sub(fee).unwrap();
-
-    // Check high 128 bit of amount value to be empty
-    if not_supported_amount != 0 || not_supported_fee != 0 {
-        return ContractError::AmountTooHigh.std_err();
-    }
+    // Wrapped assets have at most 8 decimals, so they are minted in the amounts of the payload
+    let (amount, fee) = denormalize_transfer(&transfer_info, MAX_DECIMALS)?;
 
     if token_chain != CHAIN_ID {
         let asset_address = transfer_info.token_address;
@@ -716,11 +759,7 @@ fn handle_complete_transfer_token(
                 contract_addr: contract_addr.to_string(),
                 msg: to_binary(&TokenQuery::TokenInfo {})?,
             }))?;
-
-        let decimals = token_info.decimals;
-        let multiplier = 10u128.pow((max(decimals, 8u8) - 8u8) as u32);
-        amount = amount.checked_mul(multiplier).unwrap();
-        fee = fee.checked_mul(multiplier).unwrap();
+        let (amount, fee) = denormalize_transfer(&transfer_info, token_info.decimals)?;
 
         let mut messages = vec![CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: contract_addr.to_string(),
@@ -776,16 +815,7 @@ fn handle_complete_transfer_token_native(
     }
 
     let target_address = (&transfer_info.recipient.as_slice()).get_address(0);
-
-    let (not_supported_amount, mut amount) = transfer_info.amount;
-    let (not_supported_fee, fee) = transfer_info.fee;
-
-    amount = amount.checked_sub(fee).unwrap();
-
-    // Check high 128 bit of amount value to be empty
-    if not_supported_amount != 0 || not_supported_fee != 0 {
-        return ContractError::AmountTooHigh.std_err();
-    }
+    let (amount, fee) = denormalize_transfer(&transfer_info, NATIVE_DENOM_DECIMALS)?;
 
     // Wipe the native byte marker and extract the serialized denom.
     let mut token_address = transfer_info.token_address.clone();
@@ -822,8 +852,16 @@ fn handle_complete_transfer_token_native(
         .add_attribute("amount", amount.to_string()))
 }
 
+/// The messages locking or burning one asset and posting its transfer, with what was transferred.
+struct InitiatedTransfer {
+    messages: Vec<SubMsg>,
+    token_chain: u16,
+    token_address: Vec<u8>,
+    amount: Uint128,
+}
+
 fn handle_initiate_transfer(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     asset: Asset,
@@ -832,43 +870,173 @@ fn handle_initiate_transfer(
     fee: Uint128,
     nonce: u32,
 ) -> StdResult<Response> {
-    match asset.info {
-        AssetInfo::Token { contract_addr } => handle_initiate_transfer_token(
-            deps,
-            env,
-            info,
+    // forward coins sent to this message
+    let funds = coins_after_tax(deps.branch(), info.funds.clone())?;
+    let transfer = match asset.info {
+        AssetInfo::Token { contract_addr } => initiate_transfer_token(
+            deps.branch(),
+            &env,
+            &info,
             contract_addr,
             asset.amount,
             recipient_chain,
-            recipient,
+            &recipient,
             fee,
             nonce,
-        ),
-        AssetInfo::NativeToken { ref denom } => handle_initiate_transfer_native_token(
-            deps,
-            env,
-            info,
+            funds,
+            false,
+        )?,
+        AssetInfo::NativeToken { ref denom } => initiate_transfer_native_token(
+            deps.branch(),
+            &info,
             denom.clone(),
             asset.amount,
             recipient_chain,
-            recipient,
+            &recipient,
             fee,
             nonce,
-        ),
-    }
+            funds,
+        )?,
+    };
+
+    add_transfer_attributes(
+        deps.as_ref(),
+        &env,
+        &info,
+        Response::new(),
+        transfer,
+        recipient_chain,
+        &recipient,
+        nonce,
+    )
 }
 
-fn handle_initiate_transfer_token(
+fn handle_initiate_transfer_batch(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    transfers: Vec<BatchTransfer>,
+    recipient_chain: u16,
+    recipient: Vec<u8>,
+    nonce: u32,
+) -> StdResult<Response> {
+    if transfers.is_empty() {
+        return Err(StdError::generic_err("batch contains no transfers"));
+    }
+
+    // NOTE: Reentrancy protection, see `handle_initiate_transfer_token`. The CW20 transfers of a
+    // batch are queued all at once, so no other batch may be in flight.
+    assert!(batch_transfer_tmp(deps.storage).load().is_err());
+
+    // The core bridge fee of every message is paid in one go and split between the messages.
+    let cfg: ConfigInfo = config_read(deps.storage).load()?;
+    let state: GetStateResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: cfg.wormhole_contract,
+        msg: to_binary(&WormholeQueryMsg::GetState {})?,
+    }))?;
+    let funds = if state.fee.amount.is_zero() {
+        vec![]
+    } else {
+        let total = coin(
+            state.fee.amount.u128() * transfers.len() as u128,
+            state.fee.denom.clone(),
+        );
+        if !has_coins(info.funds.as_ref(), &total) {
+            return Err(StdError::generic_err(format!(
+                "batch of {} transfers requires a fee of {}{}",
+                transfers.len(),
+                total.amount,
+                total.denom
+            )));
+        }
+        coins_after_tax(deps.branch(), vec![state.fee])?
+    };
+
+    let mut response = Response::new();
+    for transfer in transfers {
+        let transfer = match transfer.asset.info {
+            AssetInfo::Token { contract_addr } => initiate_transfer_token(
+                deps.branch(),
+                &env,
+                &info,
+                contract_addr,
+                transfer.asset.amount,
+                recipient_chain,
+                &recipient,
+                transfer.fee,
+                nonce,
+                funds.clone(),
+                true,
+            )?,
+            AssetInfo::NativeToken { denom } => initiate_transfer_native_token(
+                deps.branch(),
+                &info,
+                denom,
+                transfer.asset.amount,
+                recipient_chain,
+                &recipient,
+                transfer.fee,
+                nonce,
+                funds.clone(),
+            )?,
+        };
+        response = add_transfer_attributes(
+            deps.as_ref(),
+            &env,
+            &info,
+            response,
+            transfer,
+            recipient_chain,
+            &recipient,
+            nonce,
+        )?;
+    }
+    Ok(response)
+}
+
+fn add_transfer_attributes(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    response: Response,
+    transfer: InitiatedTransfer,
+    recipient_chain: u16,
+    recipient: &[u8],
+    nonce: u32,
+) -> StdResult<Response> {
+    Ok(response
+        .add_submessages(transfer.messages)
+        .add_attribute("transfer.token_chain", transfer.token_chain.to_string())
+        .add_attribute("transfer.token", hex::encode(transfer.token_address))
+        .add_attribute(
+            "transfer.sender",
+            hex::encode(extend_address_to_32(
+                &deps.api.addr_canonicalize(&info.sender.as_str())?,
+            )),
+        )
+        .add_attribute("transfer.recipient_chain", recipient_chain.to_string())
+        .add_attribute("transfer.recipient", hex::encode(recipient))
+        .add_attribute("transfer.amount", transfer.amount.to_string())
+        .add_attribute("transfer.nonce", nonce.to_string())
+        .add_attribute("transfer.block_time", env.block.time.seconds().to_string()))
+}
+
+/// Burn a wrapped CW20 token or lock a native one. `funds` are forwarded to the core bridge with
+/// the message. Native CW20 tokens post their message from the reply handler, batched ones are
+/// queued behind the other CW20 transfers of their batch.
+fn initiate_transfer_token(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
     asset: HumanAddr,
     mut amount: Uint128,
     recipient_chain: u16,
-    recipient: Vec<u8>,
+    recipient: &[u8],
     mut fee: Uint128,
     nonce: u32,
-) -> StdResult<Response> {
+    funds: Vec<Coin>,
+    batch: bool,
+) -> StdResult<InitiatedTransfer> {
     if recipient_chain == CHAIN_ID {
         return ContractError::SameSourceAndTarget.std_err();
     }
@@ -882,8 +1050,7 @@ fn handle_initiate_transfer_token(
     let cfg: ConfigInfo = config_read(deps.storage).load()?;
     let asset_canonical: CanonicalAddr = deps.api.addr_canonicalize(&asset)?;
 
-    let mut messages: Vec<CosmosMsg> = vec![];
-    let mut submessages: Vec<SubMsg> = vec![];
+    let mut messages: Vec<SubMsg> = vec![];
 
     match wrapped_asset_address_read(deps.storage).load(asset_canonical.as_slice()) {
         Ok(_) => {
@@ -893,14 +1060,14 @@ fn handle_initiate_transfer_token(
             }
 
             // This is a deployed wrapped asset, burn it
-            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: asset.clone(),
                 msg: to_binary(&WrappedMsg::Burn {
                     account: info.sender.to_string(),
                     amount,
                 })?,
                 funds: vec![],
-            }));
+            })));
             let request = QueryRequest::<Empty>::Wasm(WasmQuery::Smart {
                 contract_addr: asset,
                 msg: to_binary(&WrappedQuery::WrappedAssetInfo {})?,
@@ -915,24 +1082,23 @@ fn handle_initiate_transfer_token(
                 token_address: asset_address.clone(),
                 amount: (0, amount.u128()),
                 recipient_chain,
-                recipient: recipient.clone(),
+                recipient: recipient.to_vec(),
                 fee: (0, fee.u128()),
             };
 
             let token_bridge_message = TokenBridgeMessage {
                 action: Action::TRANSFER,
-                payload: transfer_info.serialize(),
+                payload: transfer_info.serialize()?,
             };
 
-            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: cfg.wormhole_contract,
                 msg: to_binary(&WormholeExecuteMsg::PostMessage {
                     message: Binary::from(token_bridge_message.serialize()),
                     nonce,
                 })?,
-                // forward coins sent to this message
-                funds: coins_after_tax(deps.branch(), info.funds.clone())?,
-            }));
+                funds,
+            })));
         }
         Err(_) => {
             // normalize amount to 8 decimals when it sent over the wormhole
@@ -943,24 +1109,15 @@ fn handle_initiate_transfer_token(
                 }))?;
 
             let decimals = token_info.decimals;
-            let multiplier = 10u128.pow((max(decimals, 8u8) - 8u8) as u32);
+            let multiplier = decimal_factor(decimals).map_err(amount_error)?;
+            let (normalized, normalized_fee) =
+                normalize_transfer(amount.u128(), fee.u128(), decimals)?;
 
             // chop off dust
-            amount = Uint128::new(
-                amount
-                    .u128()
-                    .checked_sub(amount.u128().checked_rem(multiplier).unwrap())
-                    .unwrap(),
-            );
-
-            fee = Uint128::new(
-                fee.u128()
-                    .checked_sub(fee.u128().checked_rem(multiplier).unwrap())
-                    .unwrap(),
-            );
+            amount = Uint128::new(normalized.truncated);
 
             // This is a regular asset, transfer its balance
-            submessages.push(SubMsg::reply_on_success(
+            messages.push(SubMsg::reply_on_success(
                 CosmosMsg::Wasm(WasmMsg::Execute {
                     contract_addr: asset.clone(),
                     msg: to_binary(&TokenMsg::TransferFrom {
@@ -970,22 +1127,26 @@ fn handle_initiate_transfer_token(
                     })?,
                     funds: vec![],
                 }),
-                1,
+                if batch {
+                    BATCH_TRANSFER_REPLY_ID
+                } else {
+                    TRANSFER_REPLY_ID
+                },
             ));
 
             asset_address = extend_address_to_32(&asset_canonical);
             asset_chain = CHAIN_ID;
 
             // convert to normalized amounts before recording & posting vaa
-            amount = Uint128::new(amount.u128().checked_div(multiplier).unwrap());
-            fee = Uint128::new(fee.u128().checked_div(multiplier).unwrap());
+            amount = Uint128::new(normalized.normalized);
+            fee = Uint128::new(normalized_fee);
 
             let transfer_info = TransferInfo {
                 token_chain: asset_chain,
                 token_address: asset_address.clone(),
                 amount: (0, amount.u128()),
                 recipient_chain,
-                recipient: recipient.clone(),
+                recipient: recipient.to_vec(),
                 fee: (0, fee.u128()),
             };
 
@@ -998,44 +1159,86 @@ fn handle_initiate_transfer_token(
                     })?,
                 }))?;
 
-            // NOTE: Reentrancy protection. It is crucial that there's no
-            // ongoing transfer in progress here, otherwise we would override
-            // its state.  This could happen if the asset's TransferFrom handler
-            // sends us an InitiateTransfer message, which would be executed
-            // before the reply handler due the the depth-first semantics of
-            // message execution.  A simple protection mechanism is to require
-            // that there's no execution in progress. The reply handler takes
-            // care of clearing out this temporary storage when done.
-            assert!(wrapped_transfer_tmp(deps.storage).load().is_err());
-            // Wrap up state to be captured by the submessage reply.
-            wrapped_transfer_tmp(deps.storage).save(&TransferState {
+            let state = TransferState {
                 previous_balance: balance.balance.to_string(),
                 account: info.sender.to_string(),
                 token_address: asset,
                 token_canonical: asset_canonical.clone(),
-                message: transfer_info.serialize(),
+                message: transfer_info.serialize()?,
                 multiplier: Uint128::new(multiplier).to_string(),
                 nonce,
-            })?;
+            };
+
+            if batch {
+                // The balance before the transfer is only right if no earlier transfer of the
+                // batch moves the same token.
+                let mut queue = batch_transfer_tmp(deps.storage).load().unwrap_or_default();
+                if queue
+                    .iter()
+                    .any(|queued| queued.transfer.token_canonical == asset_canonical)
+                {
+                    return Err(StdError::generic_err("token transferred twice in batch"));
+                }
+                queue.push(BatchTransferState {
+                    transfer: state,
+                    funds,
+                });
+                batch_transfer_tmp(deps.storage).save(&queue)?;
+            } else {
+                // NOTE: Reentrancy protection. It is crucial that there's no
+                // ongoing transfer in progress here, otherwise we would override
+                // its state.  This could happen if the asset's TransferFrom handler
+                // sends us an InitiateTransfer message, which would be executed
+                // before the reply handler due the the depth-first semantics of
+                // message execution.  A simple protection mechanism is to require
+                // that there's no execution in progress. The reply handler takes
+                // care of clearing out this temporary storage when done.
+                assert!(wrapped_transfer_tmp(deps.storage).load().is_err());
+                // Wrap up state to be captured by the submessage reply.
+                wrapped_transfer_tmp(deps.storage).save(&state)?;
+            }
         }
     };
 
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_submessages(submessages)
-        .add_attribute("transfer.token_chain", asset_chain.to_string())
-        .add_attribute("transfer.token", hex::encode(asset_address))
-        .add_attribute(
-            "transfer.sender",
-            hex::encode(extend_address_to_32(
-                &deps.api.addr_canonicalize(&info.sender.as_str())?,
-            )),
-        )
-        .add_attribute("transfer.recipient_chain", recipient_chain.to_string())
-        .add_attribute("transfer.recipient", hex::encode(recipient))
-        .add_attribute("transfer.amount", amount.to_string())
-        .add_attribute("transfer.nonce", nonce.to_string())
-        .add_attribute("transfer.block_time", env.block.time.seconds().to_string()))
+    Ok(InitiatedTransfer {
+        messages,
+        token_chain: asset_chain,
+        token_address: asset_address,
+        amount,
+    })
+}
+
+/// Map a wormhole-core amount error to the errors transfers have always been rejected with.
+fn amount_error(e: WormholeError) -> StdError {
+    match e {
+        WormholeError::DustAmount => ContractError::AmountTooLow.std(),
+        _ => ContractError::AmountTooHigh.std(),
+    }
+}
+
+/// Normalize the amount and fee of an outgoing transfer of a token with `decimals`, returning the
+/// amount split as `normalize_amount` does and the normalized fee.
+fn normalize_transfer(
+    amount: u128,
+    fee: u128,
+    decimals: u8,
+) -> StdResult<(NormalizedAmount, u128)> {
+    let normalized = normalize_amount(amount, decimals).map_err(amount_error)?;
+    let fee = normalize_fee(fee, decimals).map_err(amount_error)?;
+    Ok((normalized, fee))
+}
+
+/// Denormalize the amount and fee of an incoming transfer to a token with `decimals`, returning
+/// the amount for the recipient and the fee.
+fn denormalize_transfer(transfer_info: &TransferInfo, decimals: u8) -> StdResult<(u128, u128)> {
+    let amount =
+        denormalize_amount(pair_to_u256(transfer_info.amount), decimals).map_err(amount_error)?;
+    let fee =
+        denormalize_amount(pair_to_u256(transfer_info.fee), decimals).map_err(amount_error)?;
+    let amount = amount
+        .checked_sub(fee)
+        .ok_or_else(|| StdError::generic_err("fee greater than sent amount"))?;
+    Ok((amount, fee))
 }
 
 /// All ISO-4217 currency codes are 3 letters, so we can safely slice anything that is not ULUNA.
@@ -1048,17 +1251,19 @@ fn format_native_denom_symbol(denom: &str) -> String {
     denom.to_uppercase()[1..3].to_string() + "T"
 }
 
-fn handle_initiate_transfer_native_token(
+/// Lock a native denom deposited with `DepositTokens`. `funds` are forwarded to the core bridge
+/// with the message.
+fn initiate_transfer_native_token(
     deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
+    info: &MessageInfo,
     denom: String,
     amount: Uint128,
     recipient_chain: u16,
-    recipient: Vec<u8>,
+    recipient: &[u8],
     fee: Uint128,
     nonce: u32,
-) -> StdResult<Response> {
+    funds: Vec<Coin>,
+) -> StdResult<InitiatedTransfer> {
     if recipient_chain == CHAIN_ID {
         return ContractError::SameSourceAndTarget.std_err();
     }
@@ -1078,7 +1283,6 @@ fn handle_initiate_transfer_native_token(
     })?;
 
     let cfg: ConfigInfo = config_read(deps.storage).load()?;
-    let mut messages: Vec<CosmosMsg> = vec![];
 
     let asset_chain: u16 = CHAIN_ID;
     let mut asset_address: Vec<u8> = build_native_id(&denom);
@@ -1094,38 +1298,30 @@ fn handle_initiate_transfer_native_token(
         token_address: asset_address.to_vec(),
         amount: (0, amount.u128()),
         recipient_chain,
-        recipient: recipient.clone(),
+        recipient: recipient.to_vec(),
         fee: (0, fee.u128()),
     };
 
     let token_bridge_message = TokenBridgeMessage {
         action: Action::TRANSFER,
-        payload: transfer_info.serialize(),
+        payload: transfer_info.serialize()?,
     };
 
-    let sender = deps.api.addr_canonicalize(&info.sender.as_str())?;
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+    let messages = vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: cfg.wormhole_contract,
         msg: to_binary(&WormholeExecuteMsg::PostMessage {
             message: Binary::from(token_bridge_message.serialize()),
             nonce,
         })?,
-        funds: coins_after_tax(deps, info.funds.clone())?,
-    }));
+        funds,
+    }))];
 
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("transfer.token_chain", asset_chain.to_string())
-        .add_attribute("transfer.token", hex::encode(asset_address))
-        .add_attribute(
-            "transfer.sender",
-            hex::encode(extend_address_to_32(&sender)),
-        )
-        .add_attribute("transfer.recipient_chain", recipient_chain.to_string())
-        .add_attribute("transfer.recipient", hex::encode(recipient))
-        .add_attribute("transfer.amount", amount.to_string())
-        .add_attribute("transfer.nonce", nonce.to_string())
-        .add_attribute("transfer.block_time", env.block.time.seconds().to_string()))
+    Ok(InitiatedTransfer {
+        messages,
+        token_chain: asset_chain,
+        token_address: asset_address,
+        amount,
+    })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -1177,6 +1373,62 @@ mod tests {
         Binary,
         StdResult,
     };
+    use wormhole::error::ContractError;
+
+    use super::{
+        denormalize_transfer,
+        normalize_transfer,
+    };
+    use crate::state::TransferInfo;
+
+    fn transfer(amount: u128, fee: u128) -> TransferInfo {
+        TransferInfo {
+            amount: (0, amount),
+            token_address: vec![0; 32],
+            token_chain: 2,
+            recipient: vec![0; 32],
+            recipient_chain: 3,
+            fee: (0, fee),
+        }
+    }
+
+    #[test]
+    fn test_normalize_transfer() {
+        // Dust past 8 decimals stays with the sender.
+        let (normalized, fee) = normalize_transfer(1_234_567_891, 5_678, 10).unwrap();
+        assert_eq!(normalized.normalized, 12_345_678);
+        assert_eq!(normalized.truncated, 1_234_567_800);
+        assert_eq!(fee, 56);
+
+        let (normalized, _) = normalize_transfer(1_234, 0, 6).unwrap();
+        assert_eq!(normalized.normalized, 1_234);
+        assert_eq!(normalized.truncated, 1_234);
+
+        assert_eq!(
+            normalize_transfer(99, 0, 10).unwrap_err(),
+            ContractError::AmountTooLow.std()
+        );
+    }
+
+    #[test]
+    fn test_denormalize_transfer() {
+        assert_eq!(
+            denormalize_transfer(&transfer(12_345_678, 56), 10).unwrap(),
+            (1_234_562_200, 5_600)
+        );
+        assert_eq!(denormalize_transfer(&transfer(12, 0), 6).unwrap(), (12, 0));
+
+        // Scaling up must not wrap, and the fee can't exceed the amount.
+        assert_eq!(
+            denormalize_transfer(&transfer(u128::MAX / 10, 0), 18).unwrap_err(),
+            ContractError::AmountTooHigh.std()
+        );
+        assert_eq!(
+            denormalize_transfer(&transfer(0, u128::MAX), 9).unwrap_err(),
+            ContractError::AmountTooHigh.std()
+        );
+        assert!(denormalize_transfer(&transfer(1, 2), 8).is_err());
+    }
 
     #[test]
     fn test_me() -> StdResult<()> {
@@ -44,6 +44,13 @@ pub enum ExecuteMsg {
         nonce: u32,
     },
 
+    InitiateTransferBatch {
+        transfers: Vec<BatchTransfer>,
+        recipient_chain: u16,
+        recipient: Binary,
+        nonce: u32,
+    },
+
     SubmitVaa {
         data: Binary,
     },
@@ -54,6 +61,14 @@ pub enum ExecuteMsg {
     },
 }
 
+/// One asset of an `InitiateTransferBatch`, with the fee paid to whoever redeems its transfer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BatchTransfer {
+    pub asset: Asset,
+    pub fee: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct MigrateMsg {}
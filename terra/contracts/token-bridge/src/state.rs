@@ -6,6 +6,7 @@ use serde::{
 
 use cosmwasm_std::{
     CanonicalAddr,
+    Coin,
     StdError,
     StdResult,
     Storage,
@@ -22,12 +23,26 @@ use cosmwasm_storage::{
     Singleton,
 };
 
-use wormhole::byte_utils::ByteUtils;
+use primitive_types::U256;
+use std::convert::TryInto;
+use wormhole::{
+    byte_utils::{
+        extend_string_to_32,
+        get_valid_string_from_32,
+        ByteUtils,
+    },
+    state::parse_governance_action,
+};
+use wormhole_core::{
+    vaa::token,
+    Chain,
+};
 
 type HumanAddr = String;
 
 pub static CONFIG_KEY: &[u8] = b"config";
 pub static TRANSFER_TMP_KEY: &[u8] = b"transfer_tmp";
+pub static BATCH_TRANSFER_TMP_KEY: &[u8] = b"batch_transfer_tmp";
 pub static WRAPPED_ASSET_KEY: &[u8] = b"wrapped_asset";
 pub static WRAPPED_ASSET_SEQ_KEY: &[u8] = b"wrapped_seq_asset";
 pub static WRAPPED_ASSET_ADDRESS_KEY: &[u8] = b"wrapped_asset_address";
@@ -113,6 +128,19 @@ pub fn wrapped_transfer_tmp(storage: &mut dyn Storage) -> Singleton<TransferStat
     singleton(storage, TRANSFER_TMP_KEY)
 }
 
+/// A CW20 transfer of a batch waiting for its reply, along with the core bridge fee its message is
+/// posted with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchTransferState {
+    pub transfer: TransferState,
+    pub funds: Vec<Coin>,
+}
+
+/// The CW20 transfers of a batch in flight, in the order their replies arrive.
+pub fn batch_transfer_tmp(storage: &mut dyn Storage) -> Singleton<Vec<BatchTransferState>> {
+    singleton(storage, BATCH_TRANSFER_TMP_KEY)
+}
+
 pub fn send_native(
     storage: &mut dyn Storage,
     asset_address: &CanonicalAddr,
@@ -174,6 +202,10 @@ impl TokenBridgeMessage {
     }
 }
 
+// The wire formats of the payloads below are defined by wormhole-core. Their `deserialize` and
+// `serialize` work on the payload without the leading action byte, which `TokenBridgeMessage`
+// splits off.
+
 //     0   u256     amount
 //     32  [u8; 32] token_address
 //     64  u16      token_chain
@@ -193,38 +225,51 @@ pub struct TransferInfo {
 
 impl TransferInfo {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let amount = data.get_u256(0);
-        let token_address = data.get_bytes32(32).to_vec();
-        let token_chain = data.get_u16(64);
-        let recipient = data.get_bytes32(66).to_vec();
-        let recipient_chain = data.get_u16(98);
-        let fee = data.get_u256(100);
+        let payload = [&[Action::TRANSFER][..], data.as_slice()].concat();
+        let transfer = token::Transfer::from_bytes(payload)
+            .map_err(|_| StdError::generic_err("invalid transfer payload"))?;
 
         Ok(TransferInfo {
-            amount,
-            token_address,
-            token_chain,
-            recipient,
-            recipient_chain,
-            fee,
+            amount: u256_to_pair(transfer.amount),
+            token_address: transfer.token_address.to_vec(),
+            token_chain: transfer.token_chain.into(),
+            recipient: transfer.to.to_vec(),
+            recipient_chain: transfer.to_chain.into(),
+            fee: u256_to_pair(transfer.fee),
         })
     }
-    pub fn serialize(&self) -> Vec<u8> {
-        [
-            self.amount.0.to_be_bytes().to_vec(),
-            self.amount.1.to_be_bytes().to_vec(),
-            self.token_address.clone(),
-            self.token_chain.to_be_bytes().to_vec(),
-            self.recipient.to_vec(),
-            self.recipient_chain.to_be_bytes().to_vec(),
-            self.fee.0.to_be_bytes().to_vec(),
-            self.fee.1.to_be_bytes().to_vec(),
-        ]
-        .concat()
+
+    /// Fails if the token or recipient address is not 32 bytes long.
+    pub fn serialize(&self) -> StdResult<Vec<u8>> {
+        let transfer = token::Transfer {
+            amount: pair_to_u256(self.amount),
+            token_address: to_bytes32(&self.token_address, "token address")?,
+            token_chain: Chain::from(self.token_chain),
+            to: to_bytes32(&self.recipient, "recipient")?,
+            to_chain: Chain::from(self.recipient_chain),
+            fee: pair_to_u256(self.fee),
+        };
+        Ok(transfer.to_bytes()[1..].to_vec())
     }
 }
 
+/// Split a uint256 into its high and low 128 bits.
+fn u256_to_pair(v: U256) -> (u128, u128) {
+    let mut bytes = [0u8; 32];
+    v.to_big_endian(&mut bytes);
+    (bytes.as_slice().get_u128_be(0), bytes.as_slice().get_u128_be(16))
+}
+
+/// Join the high and low 128 bits of a uint256.
+pub fn pair_to_u256(v: (u128, u128)) -> U256 {
+    U256::from_big_endian(&[v.0.to_be_bytes(), v.1.to_be_bytes()].concat())
+}
+
+fn to_bytes32(v: &[u8], field: &str) -> StdResult<[u8; 32]> {
+    v.try_into()
+        .map_err(|_| StdError::generic_err(format!("{} must be 32 bytes", field)))
+}
+
 // 0  [32]uint8  TokenAddress
 // 32 uint16     TokenChain
 // 34 uint8      Decimals
@@ -241,31 +286,29 @@ pub struct AssetMeta {
 
 impl AssetMeta {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let token_address = data.get_bytes32(0).to_vec();
-        let token_chain = data.get_u16(32);
-        let decimals = data.get_u8(34);
-        let symbol = data.get_bytes32(35).to_vec();
-        let name = data.get_bytes32(67).to_vec();
+        let payload = [&[Action::ATTEST_META][..], data.as_slice()].concat();
+        let meta = token::AssetMeta::from_bytes(payload)
+            .map_err(|_| StdError::generic_err("invalid asset meta payload"))?;
 
         Ok(AssetMeta {
-            token_chain,
-            token_address,
-            decimals,
-            symbol,
-            name,
+            token_chain: meta.token_chain.into(),
+            token_address: meta.token_address.to_vec(),
+            decimals: meta.decimals,
+            symbol: extend_string_to_32(&meta.symbol),
+            name: extend_string_to_32(&meta.name),
         })
     }
 
-    pub fn serialize(&self) -> Vec<u8> {
-        [
-            self.token_address.clone(),
-            self.token_chain.to_be_bytes().to_vec(),
-            self.decimals.to_be_bytes().to_vec(),
-            self.symbol.clone(),
-            self.name.clone(),
-        ]
-        .concat()
+    /// Fails if the token address is not 32 bytes long.
+    pub fn serialize(&self) -> StdResult<Vec<u8>> {
+        let meta = token::AssetMeta {
+            token_address: to_bytes32(&self.token_address, "token address")?,
+            token_chain: Chain::from(self.token_chain),
+            decimals: self.decimals,
+            symbol: get_valid_string_from_32(&self.symbol),
+            name: get_valid_string_from_32(&self.name),
+        };
+        Ok(meta.to_bytes()[1..].to_vec())
     }
 }
 
@@ -280,21 +323,257 @@ pub struct RegisterChain {
 
 impl UpgradeContract {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let new_contract = data.get_u64(24);
+        let action: token::GovernanceContractUpgrade = parse_governance_action(data)?;
+        // The code ID takes up the last 8 bytes of the address.
+        let new_contract = (&action.new_contract[..]).get_u64(24);
         Ok(UpgradeContract { new_contract })
     }
 }
 
 impl RegisterChain {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let chain_id = data.get_u16(0);
-        let chain_address = data[2..].to_vec();
+        let action: token::GovernanceRegisterChain = parse_governance_action(data)?;
 
         Ok(RegisterChain {
-            chain_id,
-            chain_address,
+            chain_id: action.emitter.into(),
+            chain_address: action.endpoint_address.to_vec(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wormhole::{
+        byte_utils::get_string_from_32,
+        state::GovernancePacket,
+    };
+    use wormhole_core::testing::corpus;
+
+    const VECTORS: &str = include_str!("../../../../sdk/vectors/v1.json");
+
+    /// The shared wire format vectors of the given type as (name, bytes, valid, fields,
+    /// canonical encoding). wormhole-core and the Solana programs load the same file.
+    fn vectors(kind: &str) -> Vec<(String, Vec<u8>, bool, serde_json::Value, Vec<u8>)> {
+        let file: serde_json::Value = serde_json::from_str(VECTORS).unwrap();
+        assert_eq!(file["version"], 1);
+        let vectors: Vec<_> = file["vectors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|v| v["type"] == kind)
+            .map(|v| {
+                let bytes = hex::decode(v["hex"].as_str().unwrap()).unwrap();
+                let canonical = match v["canonical"].as_str() {
+                    Some(canonical) => hex::decode(canonical).unwrap(),
+                    None => bytes.clone(),
+                };
+                let name = v["name"].as_str().unwrap().to_string();
+                (name, bytes, v["valid"].as_bool().unwrap(), v["fields"].clone(), canonical)
+            })
+            .collect();
+        assert!(!vectors.is_empty(), "no vectors for {}", kind);
+        vectors
+    }
+
+    fn field_bytes(fields: &serde_json::Value, key: &str) -> Vec<u8> {
+        hex::decode(fields[key].as_str().unwrap()).unwrap()
+    }
+
+    fn field_int(fields: &serde_json::Value, key: &str) -> u64 {
+        fields[key].as_u64().unwrap()
+    }
+
+    /// The action payload of a governance packet, checked the way the contract checks it before
+    /// dispatching on the action.
+    fn governance_payload(data: &Vec<u8>, module: &str, action: u8) -> StdResult<Vec<u8>> {
+        let packet = GovernancePacket::deserialize(data)?;
+        let module_ok = get_string_from_32(&packet.module) == module;
+        let chain_ok = packet.chain == 0 || packet.chain == 3;
+        if !module_ok || !chain_ok || packet.action != action {
+            return Err(StdError::generic_err("governance packet rejected"));
+        }
+        Ok(packet.payload)
+    }
+
+    fn transfer() -> TransferInfo {
+        TransferInfo {
+            amount: (1, 1003),
+            token_address: vec![1u8; 32],
+            token_chain: 2,
+            recipient: vec![2u8; 32],
+            recipient_chain: 3,
+            fee: (0, 3),
+        }
+    }
+
+    // Terra must accept exactly the payloads wormhole-core accepts, and decode them to the same
+    // values.
+
+    #[test]
+    fn test_differential_transfer() {
+        let valid = transfer().serialize().unwrap();
+        for bytes in corpus(&valid) {
+            let terra = TransferInfo::deserialize(&bytes)
+                .ok()
+                .map(|t| t.serialize().unwrap());
+            let payload = [&[Action::TRANSFER][..], bytes.as_slice()].concat();
+            let core = token::Transfer::from_bytes(payload)
+                .ok()
+                .map(|t| t.to_bytes()[1..].to_vec());
+            assert_eq!(terra, core, "payload {:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn test_differential_asset_meta() {
+        let valid = AssetMeta {
+            token_address: vec![1u8; 32],
+            token_chain: 2,
+            decimals: 8,
+            symbol: extend_string_to_32("WETH"),
+            name: extend_string_to_32("Wrapped Ether"),
+        }
+        .serialize()
+        .unwrap();
+        for bytes in corpus(&valid) {
+            let terra = AssetMeta::deserialize(&bytes)
+                .ok()
+                .map(|m| m.serialize().unwrap());
+            let payload = [&[Action::ATTEST_META][..], bytes.as_slice()].concat();
+            let core = token::AssetMeta::from_bytes(payload)
+                .ok()
+                .map(|m| m.to_bytes()[1..].to_vec());
+            assert_eq!(terra, core, "payload {:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn test_serialize_address_length() {
+        let mut info = transfer();
+        info.recipient = vec![2u8; 20];
+        assert!(info.serialize().is_err());
+    }
+
+    /// Split a uint256 vector field into its high and low 128 bits.
+    fn field_pair(fields: &serde_json::Value, key: &str) -> (u128, u128) {
+        let bytes = field_bytes(fields, key);
+        (bytes.as_slice().get_u128_be(0), bytes.as_slice().get_u128_be(16))
+    }
+
+    /// The payload of a token bridge message with the given action, as the contract dispatches it.
+    fn message_payload(data: &Vec<u8>, action: u8) -> Option<Vec<u8>> {
+        let message = TokenBridgeMessage::deserialize(data).ok()?;
+        (message.action == action).then(|| message.payload)
+    }
+
+    #[test]
+    fn test_transfer_vectors() {
+        for (name, data, valid, f, canonical) in vectors("token_transfer") {
+            let info = message_payload(&data, Action::TRANSFER)
+                .and_then(|payload| TransferInfo::deserialize(&payload).ok());
+            assert_eq!(info.is_some(), valid, "{}", name);
+            if let Some(info) = info {
+                let expected = TransferInfo {
+                    amount: field_pair(&f, "amount"),
+                    token_address: field_bytes(&f, "token_address"),
+                    token_chain: field_int(&f, "token_chain") as u16,
+                    recipient: field_bytes(&f, "to"),
+                    recipient_chain: field_int(&f, "to_chain") as u16,
+                    fee: field_pair(&f, "fee"),
+                };
+                assert_eq!(info, expected, "{}", name);
+                let encoded = [vec![Action::TRANSFER], info.serialize().unwrap()].concat();
+                assert_eq!(encoded, canonical, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_asset_meta_vectors() {
+        for (name, data, valid, f, canonical) in vectors("token_asset_meta") {
+            let meta = message_payload(&data, Action::ATTEST_META)
+                .and_then(|payload| AssetMeta::deserialize(&payload).ok());
+            assert_eq!(meta.is_some(), valid, "{}", name);
+            if let Some(meta) = meta {
+                assert_eq!(meta.token_address, field_bytes(&f, "token_address"), "{}", name);
+                assert_eq!(meta.token_chain as u64, field_int(&f, "token_chain"), "{}", name);
+                assert_eq!(meta.decimals as u64, field_int(&f, "decimals"), "{}", name);
+                let symbol = extend_string_to_32(f["symbol"].as_str().unwrap());
+                let name_field = extend_string_to_32(f["name"].as_str().unwrap());
+                assert_eq!(meta.symbol, symbol, "{}", name);
+                assert_eq!(meta.name, name_field, "{}", name);
+                let encoded = [vec![Action::ATTEST_META], meta.serialize().unwrap()].concat();
+                assert_eq!(encoded, canonical, "{}", name);
+            }
+        }
+    }
+
+    // The decoders used before payloads were parsed with wormhole-core read fixed offsets, ignored
+    // anything after the last field and kept symbols and names as raw bytes. The tests below pin
+    // down how VAAs that were already signed are handled now.
+
+    fn vector(kind: &str, name: &str) -> Vec<u8> {
+        let vector = vectors(kind).into_iter().find(|v| v.0 == name).unwrap();
+        message_payload(&vector.1, vector.1[0]).unwrap()
+    }
+
+    #[test]
+    fn test_legacy_trailing_bytes() {
+        // Transfers with trailing bytes used to be redeemed as if the bytes were not there, they
+        // are now rejected.
+        let data = vector("token_transfer", "token_transfer_trailing");
+        assert!(TransferInfo::deserialize(&data).is_err());
+
+        let data = data.as_slice();
+        let legacy = TransferInfo {
+            amount: data.get_u256(0),
+            token_address: data.get_bytes32(32).to_vec(),
+            token_chain: data.get_u16(64),
+            recipient: data.get_bytes32(66).to_vec(),
+            recipient_chain: data.get_u16(98),
+            fee: data.get_u256(100),
+        };
+        let expected =
+            TransferInfo::deserialize(&vector("token_transfer", "token_transfer")).unwrap();
+        assert_eq!(legacy.serialize().unwrap(), expected.serialize().unwrap());
+    }
+
+    #[test]
+    fn test_legacy_asset_meta_encoding() {
+        // Symbols and names that are not valid UTF-8 are still accepted, but the invalid bytes are
+        // dropped where wrapped assets used to show a replacement character for them.
+        let data = vector("token_asset_meta", "token_asset_meta_invalid_utf8");
+        let meta = AssetMeta::deserialize(&data).unwrap();
+
+        let legacy_symbol = get_string_from_32(&data.as_slice().get_bytes32(35).to_vec());
+        let legacy_name = get_string_from_32(&data.as_slice().get_bytes32(67).to_vec());
+        assert_eq!(legacy_symbol, "AB\u{FFFD}C");
+        assert_eq!(legacy_name, "N\u{e4}me \u{FFFD}");
+        assert_eq!(get_string_from_32(&meta.symbol), "ABC");
+        assert_eq!(get_string_from_32(&meta.name), "N\u{e4}me ");
+    }
+
+    #[test]
+    fn test_governance_vectors() {
+        for (name, data, valid, f, _) in vectors("token_register_chain") {
+            let action = governance_payload(&data, "TokenBridge", 1)
+                .and_then(|payload| RegisterChain::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                assert_eq!(action.chain_id as u64, field_int(&f, "emitter_chain"), "{}", name);
+                assert_eq!(action.chain_address, field_bytes(&f, "endpoint_address"), "{}", name);
+            }
+        }
+
+        for (name, data, valid, f, _) in vectors("token_contract_upgrade") {
+            let action = governance_payload(&data, "TokenBridge", 2)
+                .and_then(|payload| UpgradeContract::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                let code_id = field_bytes(&f, "new_contract").as_slice().get_u64(24);
+                assert_eq!(action.new_contract, code_id, "{}", name);
+            }
+        }
+    }
+}
@@ -95,3 +95,11 @@ pub fn get_string_from_32(v: &Vec<u8>) -> String {
     let s = String::from_utf8_lossy(v);
     s.chars().filter(|c| c != &'\0').collect()
 }
+
+/// Like `get_string_from_32`, but drops the characters that are not valid UTF-8 instead of
+/// replacing them. This is how wormhole-core decodes fixed length strings, so a string built with
+/// `string_to_array` and cut in the middle of a character still round-trips through the wire.
+pub fn get_valid_string_from_32(v: &[u8]) -> String {
+    let s = String::from_utf8_lossy(v);
+    s.chars().filter(|c| c != &'\0' && c != &'\u{FFFD}').collect()
+}
@@ -20,6 +20,7 @@ use cosmwasm_std::{
 use crate::{
     byte_utils::{
         extend_address_to_32,
+        get_string_from_32,
         ByteUtils,
     },
     error::ContractError,
@@ -150,8 +151,7 @@ fn handle_submit_vaa(
 fn handle_governance_payload(deps: DepsMut, env: Env, data: &Vec<u8>) -> StdResult<Response> {
     let gov_packet = GovernancePacket::deserialize(&data)?;
 
-    let module = String::from_utf8(gov_packet.module).unwrap();
-    let module: String = module.chars().filter(|c| c != &'\0').collect();
+    let module = get_string_from_32(&gov_packet.module);
 
     if module != "Core" {
         return Err(StdError::generic_err("this is not a valid module"));
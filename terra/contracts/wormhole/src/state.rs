@@ -35,6 +35,17 @@ use sha3::{
     Keccak256,
 };
 
+use wormhole_core::{
+    vaa::core::{
+        GovernanceContractUpgrade,
+        GovernanceGuardianSetChange,
+        GovernanceSetMessageFee,
+        GovernanceTransferFees,
+    },
+    GovHeader,
+    GovernanceAction,
+};
+
 type HumanAddr = String;
 
 pub static CONFIG_KEY: &[u8] = b"config";
@@ -121,6 +132,9 @@ impl ParsedVAA {
     pub const SIG_RECOVERY_POS: usize = Self::SIG_DATA_POS + Self::SIG_DATA_LEN;
 
     pub fn deserialize(data: &[u8]) -> StdResult<Self> {
+        if data.len() < Self::HEADER_LEN {
+            return ContractError::InvalidVAA.std_err();
+        }
         let version = data.get_u8(0);
 
         // Load 4 bytes starting from index 1
@@ -284,21 +298,29 @@ pub struct GovernancePacket {
 
 impl GovernancePacket {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let module = data.get_bytes32(0).to_vec();
-        let action = data.get_u8(32);
-        let chain = data.get_u16(33);
-        let payload = data[35..].to_vec();
+        let (header, payload) = match GovHeader::from_bytes(data) {
+            Ok(parsed) => parsed,
+            Err(_) => return ContractError::InvalidVAA.std_err(),
+        };
 
         Ok(GovernancePacket {
-            module,
-            action,
-            chain,
-            payload,
+            module: header.module.to_vec(),
+            action: header.action,
+            chain: header.chains.into(),
+            payload: payload.to_vec(),
         })
     }
 }
 
+/// Parse the action that follows a governance header with wormhole-core. The action has to take
+/// up the rest of the payload.
+pub fn parse_governance_action<A: GovernanceAction>(payload: &[u8]) -> StdResult<A> {
+    match A::parse(payload) {
+        Ok((rest, action)) if rest.is_empty() => Ok(action),
+        _ => ContractError::InvalidVAA.std_err(),
+    }
+}
+
 // action 1
 pub struct ContractUpgrade  {
     pub new_contract: u64,
@@ -312,8 +334,9 @@ pub struct GuardianSetUpgrade {
 
 impl ContractUpgrade {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let new_contract = data.get_u64(24);
+        let action: GovernanceContractUpgrade = parse_governance_action(data)?;
+        // The code ID takes up the last 8 bytes of the address.
+        let new_contract = (&action.new_contract[..]).get_u64(24);
         Ok(ContractUpgrade {
             new_contract,
         })
@@ -322,25 +345,15 @@ impl ContractUpgrade {
 
 impl GuardianSetUpgrade {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        const ADDRESS_LEN: usize = 20;
-
-        let data = data.as_slice();
-        let new_guardian_set_index = data.get_u32(0);
-
-        let n_guardians = data.get_u8(4);
-
-        let mut addresses = vec![];
-
-        for i in 0..n_guardians {
-            let pos = 5 + (i as usize) * ADDRESS_LEN;
-            if pos + ADDRESS_LEN > data.len() {
-                return ContractError::InvalidVAA.std_err();
-            }
+        let action: GovernanceGuardianSetChange = parse_governance_action(data)?;
 
-            addresses.push(GuardianAddress {
-                bytes: data[pos..pos + ADDRESS_LEN].to_vec().into(),
-            });
-        }
+        let addresses = action
+            .new_guardian_set
+            .iter()
+            .map(|key| GuardianAddress {
+                bytes: key.to_vec().into(),
+            })
+            .collect();
 
         let new_guardian_set = GuardianSetInfo {
             addresses,
@@ -348,7 +361,7 @@ impl GuardianSetUpgrade {
         };
 
         return Ok(GuardianSetUpgrade {
-            new_guardian_set_index,
+            new_guardian_set_index: action.new_guardian_set_index,
             new_guardian_set,
         });
     }
@@ -361,12 +374,12 @@ pub struct SetFee {
 
 impl SetFee {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
+        let action: GovernanceSetMessageFee = parse_governance_action(data)?;
 
-        let (_, amount) = data.get_u256(0);
+        // Only the low 128 bits of the fee are used.
         let fee = Coin {
             denom: String::from(FEE_DENOMINATION),
-            amount: Uint128::new(amount),
+            amount: Uint128::new(action.fee.low_u128()),
         };
         Ok(SetFee { fee })
     }
@@ -380,13 +393,13 @@ pub struct TransferFee {
 
 impl TransferFee {
     pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
-        let data = data.as_slice();
-        let recipient = data.get_address(0);
+        let action: GovernanceTransferFees = parse_governance_action(data)?;
+        let recipient = (&action.to[..]).get_address(0);
 
-        let (_, amount) = data.get_u256(32);
+        // Only the low 128 bits of the amount are used.
         let amount = Coin {
             denom: String::from(FEE_DENOMINATION),
-            amount: Uint128::new(amount),
+            amount: Uint128::new(action.amount.low_u128()),
         };
         Ok(TransferFee { amount, recipient })
     }
@@ -395,6 +408,54 @@ impl TransferFee {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::byte_utils::get_string_from_32;
+    use cosmwasm_std::StdError;
+
+    const VECTORS: &str = include_str!("../../../../sdk/vectors/v1.json");
+
+    /// The shared wire format vectors of the given type as (name, bytes, valid, fields,
+    /// canonical encoding). wormhole-core and the Solana programs load the same file.
+    fn vectors(kind: &str) -> Vec<(String, Vec<u8>, bool, serde_json::Value, Vec<u8>)> {
+        let file: serde_json::Value = serde_json::from_str(VECTORS).unwrap();
+        assert_eq!(file["version"], 1);
+        let vectors: Vec<_> = file["vectors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|v| v["type"] == kind)
+            .map(|v| {
+                let bytes = hex::decode(v["hex"].as_str().unwrap()).unwrap();
+                let canonical = match v["canonical"].as_str() {
+                    Some(canonical) => hex::decode(canonical).unwrap(),
+                    None => bytes.clone(),
+                };
+                let name = v["name"].as_str().unwrap().to_string();
+                (name, bytes, v["valid"].as_bool().unwrap(), v["fields"].clone(), canonical)
+            })
+            .collect();
+        assert!(!vectors.is_empty(), "no vectors for {}", kind);
+        vectors
+    }
+
+    fn field_bytes(fields: &serde_json::Value, key: &str) -> Vec<u8> {
+        hex::decode(fields[key].as_str().unwrap()).unwrap()
+    }
+
+    fn field_int(fields: &serde_json::Value, key: &str) -> u64 {
+        fields[key].as_u64().unwrap()
+    }
+
+    /// The action payload of a governance packet, checked the way the contract checks it before
+    /// dispatching on the action.
+    fn governance_payload(data: &Vec<u8>, module: &str, action: u8) -> StdResult<Vec<u8>> {
+        let packet = GovernancePacket::deserialize(data)?;
+        let module_ok = get_string_from_32(&packet.module) == module;
+        let chain_ok = packet.chain == 0 || packet.chain == 3;
+        if !module_ok || !chain_ok || packet.action != action {
+            return Err(StdError::generic_err("governance packet rejected"));
+        }
+        Ok(packet.payload)
+    }
 
     fn build_guardian_set(length: usize) -> GuardianSetInfo {
         let mut addresses: Vec<GuardianAddress> = Vec::with_capacity(length);
@@ -429,6 +490,24 @@ mod tests {
         assert_eq!(build_guardian_set(100).quorum(), 67);
     }
 
+    #[test]
+    fn test_deserialize_governance_packet() {
+        let mut data = wormhole_core::governance_module(b"Core").to_vec();
+        data.extend_from_slice(&[2, 0, 3, 0xaa, 0xbb]);
+
+        let packet = GovernancePacket::deserialize(&data).unwrap();
+        assert_eq!(crate::byte_utils::get_string_from_32(&packet.module), "Core");
+        assert_eq!(packet.action, 2);
+        assert_eq!(packet.chain, 3);
+        assert_eq!(packet.payload, vec![0xaa, 0xbb]);
+
+        // Truncated headers are rejected like wormhole-core rejects them, instead of panicking.
+        for n in 0..35 {
+            assert!(GovernancePacket::deserialize(&data[..n].to_vec()).is_err());
+            assert!(GovHeader::from_bytes(&data[..n]).is_err());
+        }
+    }
+
     #[test]
     fn test_deserialize() {
         let x = hex::decode("080000000901007bfa71192f886ab6819fa4862e34b4d178962958d9b2e3d9437338c9e5fde1443b809d2886eaa69e0f0158ea517675d96243c9209c3fe1d94d5b19866654c6980000000b150000000500020001020304000000000000000000000000000000000000000000000000000000000000000000000a0261626364").unwrap();
@@ -456,4 +535,96 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_deserialize_truncated() {
+        let x = hex::decode("080000000901007bfa71192f886ab6819fa4862e34b4d178962958d9b2e3d9437338c9e5fde1443b809d2886eaa69e0f0158ea517675d96243c9209c3fe1d94d5b19866654c6980000000b150000000500020001020304000000000000000000000000000000000000000000000000000000000000000000000a02").unwrap();
+        assert!(ParsedVAA::deserialize(x.as_slice()).is_ok());
+        for n in 0..x.len() {
+            assert!(ParsedVAA::deserialize(&x[..n]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_vaa_vectors() {
+        for (name, data, valid, f, _) in vectors("vaa") {
+            let vaa = ParsedVAA::deserialize(&data);
+            assert_eq!(vaa.is_ok(), valid, "{}", name);
+            if let Ok(vaa) = vaa {
+                let signatures = f["signatures"].as_array().unwrap().len();
+                assert_eq!(vaa.version as u64, field_int(&f, "version"), "{}", name);
+                assert_eq!(vaa.guardian_set_index as u64, field_int(&f, "guardian_set_index"));
+                assert_eq!(vaa.len_signers as usize, signatures, "{}", name);
+                assert_eq!(vaa.timestamp as u64, field_int(&f, "timestamp"), "{}", name);
+                assert_eq!(vaa.nonce as u64, field_int(&f, "nonce"), "{}", name);
+                assert_eq!(vaa.emitter_chain as u64, field_int(&f, "emitter_chain"), "{}", name);
+                assert_eq!(vaa.emitter_address, field_bytes(&f, "emitter_address"), "{}", name);
+                assert_eq!(vaa.sequence, field_int(&f, "sequence"), "{}", name);
+                assert_eq!(vaa.consistency_level as u64, field_int(&f, "consistency_level"));
+                assert_eq!(vaa.payload, field_bytes(&f, "payload"), "{}", name);
+
+                // The contract identifies VAAs by the hash of the body hash.
+                let hash = Keccak256::digest(&field_bytes(&f, "hash")).to_vec();
+                assert_eq!(vaa.hash, hash, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_governance_vectors() {
+        for (name, data, valid, f, _) in vectors("core_contract_upgrade") {
+            let action = governance_payload(&data, "Core", 1)
+                .and_then(|payload| ContractUpgrade::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                let code_id = field_bytes(&f, "new_contract").as_slice().get_u64(24);
+                assert_eq!(action.new_contract, code_id, "{}", name);
+            }
+        }
+
+        for (name, data, valid, f, _) in vectors("core_guardian_set_change") {
+            let action = governance_payload(&data, "Core", 2)
+                .and_then(|payload| GuardianSetUpgrade::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                let keys: Vec<Vec<u8>> = f["new_guardian_set"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|key| hex::decode(key.as_str().unwrap()).unwrap())
+                    .collect();
+                let addresses: Vec<Vec<u8>> = action
+                    .new_guardian_set
+                    .addresses
+                    .iter()
+                    .map(|address| address.bytes.to_vec())
+                    .collect();
+                let index = field_int(&f, "new_guardian_set_index");
+                assert_eq!(action.new_guardian_set_index as u64, index, "{}", name);
+                assert_eq!(addresses, keys, "{}", name);
+            }
+        }
+
+        for (name, data, valid, f, _) in vectors("core_set_message_fee") {
+            let action = governance_payload(&data, "Core", 3)
+                .and_then(|payload| SetFee::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                let fee = field_bytes(&f, "fee").as_slice().get_u128_be(16);
+                assert_eq!(action.fee.amount, Uint128::new(fee), "{}", name);
+            }
+        }
+
+        for (name, data, valid, f, _) in vectors("core_transfer_fees") {
+            let action = governance_payload(&data, "Core", 4)
+                .and_then(|payload| TransferFee::deserialize(&payload));
+            assert_eq!(action.is_ok(), valid, "{}", name);
+            if let Ok(action) = action {
+                let amount = field_bytes(&f, "amount").as_slice().get_u128_be(16);
+                let recipient = field_bytes(&f, "to").as_slice().get_address(0);
+                assert_eq!(action.amount.amount, Uint128::new(amount), "{}", name);
+                assert_eq!(action.recipient, recipient, "{}", name);
+            }
+        }
+    }
 }
@@ -22,6 +22,7 @@ const artifacts = [
   "nft_bridge.wasm",
   "cw721_wrapped.wasm",
   "cw721_base.wasm",
+  "cw1155_wrapped.wasm",
 ];
 
 /* Check that the artifact folder contains all the wasm files we expect and nothing else */
@@ -188,6 +189,7 @@ addresses["nft_bridge.wasm"] = await instantiate("nft_bridge.wasm", {
   gov_address: Buffer.from(govAddress, "hex").toString("base64"),
   wormhole_contract: addresses["wormhole.wasm"],
   wrapped_asset_code_id: codeIds["cw721_wrapped.wasm"],
+  wrapped_semi_fungible_code_id: codeIds["cw1155_wrapped.wasm"],
 });
 
 addresses["cw721_base.wasm"] = await instantiate("cw721_base.wasm", {